@@ -0,0 +1,1087 @@
+//! Application runtime tying the component tree, layout, events and renderer together
+//!
+//! [`App`] owns everything needed to drive a UI and advances it one frame at
+//! a time: queued input is dispatched, pending state updates are flushed,
+//! components whose state changed re-render, layout is recomputed and the
+//! tree is drawn. [`App::run`] hands those frames to the platform's event
+//! loop.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::component::{
+    AxTree, Callback, Component, ComponentId, ComponentInstance, ComponentTree, Context, Node,
+    PortalTarget, TreeError,
+};
+use crate::events::{
+    Event, EventDelegate, EventPropagation, EventSystem, KeyEvent, PointerInput, ShortcutRegistry,
+    Ticker,
+};
+use crate::layout::{
+    Dimension, LayoutEngine, LayoutNode, LayoutStats, LayoutStyle, PositionType, Size,
+};
+use crate::platform::{LoopControl, PlatformEvent, WindowConfig};
+use crate::renderer::{
+    create_renderer, AnimationDriver, ImageCache, LoadListener, RenderContext, RenderStats,
+    Renderer, RendererType,
+};
+use crate::Error;
+
+/// Input waiting for the next frame
+#[derive(Debug, Clone)]
+enum QueuedInput {
+    Pointer(PointerInput),
+    Key(KeyEvent),
+}
+
+/// What happened during one frame, for profiling and devtools overlays
+///
+/// The stats structs are snapshots of the renderer's and layout engine's
+/// statistics after the frame, so their counters are totals since startup
+/// while the other fields cover this frame alone.
+#[derive(Debug, Clone, Default)]
+pub struct FrameReport {
+    /// Number of frames run so far, including this one
+    pub frame: u64,
+    /// Time spent in [`App::frame`]
+    pub frame_time: Duration,
+    /// Components whose `render` ran this frame
+    pub components_rendered: u32,
+    /// Whether layout was recomputed
+    pub layout_updated: bool,
+    /// Whether anything was drawn
+    pub drawn: bool,
+    /// Renderer statistics, where `draw_calls` and `component_count` cover
+    /// the last frame drawn
+    pub render: RenderStats,
+    /// Layout engine statistics
+    pub layout: LayoutStats,
+}
+
+impl FrameReport {
+    /// Fraction of layout lookups answered from the cache, from 0 to 1
+    pub fn layout_cache_hit_rate(&self) -> f32 {
+        hit_rate(self.layout.cache_hits, self.layout.cache_misses)
+    }
+}
+
+fn hit_rate(hits: u64, misses: u64) -> f32 {
+    match hits + misses {
+        0 => 0.0,
+        total => hits as f32 / total as f32,
+    }
+}
+
+/// A running Orbit application
+///
+/// ```ignore
+/// App::new::<Counter>(CounterProps::default()).run()?;
+/// ```
+///
+/// The root component is created with the app's [`Context`], so reactive
+/// state it creates through that context re-renders it on change.
+pub struct App {
+    context: Context,
+    tree: ComponentTree,
+    root: ComponentId,
+    root_instance: ComponentInstance,
+    /// Created on the first frame unless one was provided
+    renderer: Option<Box<dyn Renderer>>,
+    layout: LayoutEngine,
+    events: EventSystem,
+    /// Shortcuts matched against key presses before the focused component sees them
+    shortcuts: ShortcutRegistry,
+    /// Event delegates of components with handlers registered through [`App::on`]
+    delegates: HashMap<ComponentId, Arc<Mutex<EventDelegate>>>,
+    window: WindowConfig,
+    /// Nodes from the last render, rooted at the root component; `None` until mounted
+    nodes: Option<Node>,
+    layout_root: LayoutNode,
+    /// Components whose state changed since they last rendered
+    dirty: Arc<Mutex<HashSet<ComponentId>>>,
+    input: VecDeque<QueuedInput>,
+    needs_layout: bool,
+    /// Whether the next frame draws everything rather than only dirty components
+    full_render: bool,
+    /// Set when an image finishes loading, so the next frame draws it
+    images_loaded: Arc<AtomicBool>,
+    /// Keeps the image cache listener that sets `images_loaded` registered
+    _image_listener: Arc<LoadListener>,
+    animations: AnimationDriver,
+    /// Advanced at the start of every frame
+    tickers: Vec<Box<dyn Ticker>>,
+    /// Components rendered so far in the current frame
+    components_rendered: u32,
+    frame_report: Option<FrameReport>,
+    on_frame: Option<Callback<FrameReport>>,
+}
+
+impl App {
+    /// Create an app with `C` as its root component
+    pub fn new<C: Component + 'static>(props: C::Props) -> Self {
+        let context = Context::new();
+        let root_instance =
+            ComponentInstance::new(C::create(props.clone(), context.clone()), props);
+        let root = root_instance.id();
+
+        let tree = ComponentTree::new(context.clone());
+        // A fresh tree can't already hold the root, so neither call can fail
+        let _ = tree.add_component(root_instance.clone());
+        let _ = tree.set_root(root);
+
+        let dirty = Arc::new(Mutex::new(HashSet::new()));
+        let sink = Arc::clone(&dirty);
+        context.set_update_handler(move |id| {
+            if let Ok(mut dirty) = sink.lock() {
+                dirty.insert(id);
+            }
+        });
+
+        let images_loaded = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&images_loaded);
+        let image_listener: Arc<LoadListener> = Arc::new(move || {
+            flag.store(true, Ordering::Release);
+        });
+        ImageCache::global().on_load(&image_listener);
+
+        Self {
+            context,
+            tree,
+            root,
+            root_instance,
+            renderer: None,
+            layout: LayoutEngine::new(),
+            events: EventSystem::new(),
+            shortcuts: ShortcutRegistry::new(),
+            delegates: HashMap::new(),
+            window: WindowConfig::default(),
+            nodes: None,
+            layout_root: LayoutNode::new(root, LayoutStyle::default()),
+            dirty,
+            input: VecDeque::new(),
+            needs_layout: true,
+            full_render: true,
+            images_loaded,
+            _image_listener: image_listener,
+            animations: AnimationDriver::new(),
+            tickers: Vec::new(),
+            components_rendered: 0,
+            frame_report: None,
+            on_frame: None,
+        }
+    }
+
+    /// Use a specific renderer instead of the best one for the platform
+    pub fn with_renderer(mut self, renderer: Box<dyn Renderer>) -> Self {
+        self.set_renderer(renderer);
+        self
+    }
+
+    /// Set the window the app opens in, which also sets the viewport size
+    pub fn with_window(mut self, window: WindowConfig) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// ID of the root component
+    pub fn root_id(&self) -> ComponentId {
+        self.root
+    }
+
+    /// Context shared by the app and its root component
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Component tree holding every mounted component
+    pub fn tree(&self) -> &ComponentTree {
+        &self.tree
+    }
+
+    /// Event system, for focus and pointer capture
+    pub fn events_mut(&mut self) -> &mut EventSystem {
+        &mut self.events
+    }
+
+    /// Keyboard shortcuts, scoped by the component tree
+    pub fn shortcuts_mut(&mut self) -> &mut ShortcutRegistry {
+        &mut self.shortcuts
+    }
+
+    /// Renderer, once it has been provided or created
+    pub fn renderer(&self) -> Option<&dyn Renderer> {
+        self.renderer.as_deref()
+    }
+
+    /// Nodes from the last render, with their layout applied
+    pub fn nodes(&self) -> Option<&Node> {
+        self.nodes.as_ref()
+    }
+
+    /// Layout tree from the last frame
+    ///
+    /// It mirrors the nodes, except that portal content sits under the
+    /// portal's target.
+    pub fn layout_root(&self) -> &LayoutNode {
+        &self.layout_root
+    }
+
+    /// Report for the most recent frame, or `None` before the first
+    pub fn last_frame_report(&self) -> Option<&FrameReport> {
+        self.frame_report.as_ref()
+    }
+
+    /// Call `callback` with the report at the end of every frame
+    pub fn on_frame(&mut self, callback: Callback<FrameReport>) {
+        self.on_frame = Some(callback);
+    }
+
+    /// Animations advanced at the start of every frame
+    pub fn animations_mut(&mut self) -> &mut AnimationDriver {
+        &mut self.animations
+    }
+
+    /// Advance `ticker` at the start of every frame
+    ///
+    /// Frames keep running while it is active.
+    pub fn add_ticker(&mut self, ticker: impl Ticker + 'static) {
+        self.tickers.push(Box::new(ticker));
+    }
+
+    /// Accessibility tree for the current frame, with bounds from layout
+    pub fn accessibility_snapshot(&self) -> Result<AxTree, Error> {
+        let mut snapshot = self.tree.accessibility_snapshot().map_err(tree_error)?;
+        snapshot.apply_layout(&self.layout_root);
+        Ok(snapshot)
+    }
+
+    /// Register a handler for events of type `E` delivered to a component
+    pub fn on<E: Event + 'static>(
+        &mut self,
+        component: ComponentId,
+        handler: impl Fn(&E, &EventPropagation) + Send + Sync + 'static,
+    ) {
+        if let Ok(delegate) = self.delegate_for(component).lock() {
+            delegate.on(handler);
+        }
+    }
+
+    /// Queue pointer input for the next frame
+    pub fn pointer_input(&mut self, input: PointerInput) {
+        self.input.push_back(QueuedInput::Pointer(input));
+    }
+
+    /// Queue key input for the next frame, delivered to the focused component
+    pub fn key_input(&mut self, event: KeyEvent) {
+        self.input.push_back(QueuedInput::Key(event));
+    }
+
+    /// Change the viewport size, relaying out on the next frame
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.window.width = width;
+        self.window.height = height;
+        // Fixed nodes are placed against the viewport
+        self.layout.clear_cache();
+        self.needs_layout = true;
+        self.full_render = true;
+    }
+
+    /// Feed an event from the platform's event loop
+    pub fn handle_event(&mut self, event: PlatformEvent) {
+        match event {
+            PlatformEvent::Resized { width, height, .. } => self.resize(width, height),
+            PlatformEvent::RedrawRequested(_) => self.full_render = true,
+            PlatformEvent::CloseRequested(_) => {}
+            PlatformEvent::Pointer(_, input) => self.pointer_input(input),
+            PlatformEvent::Key(_, event) => self.key_input(event),
+        }
+    }
+
+    /// Whether the next [`App::frame`] has any work to do
+    pub fn needs_frame(&self) -> bool {
+        self.nodes.is_none()
+            || !self.input.is_empty()
+            || self.needs_layout
+            || self.full_render
+            || self.images_loaded.load(Ordering::Acquire)
+            || !self.animations.is_empty()
+            || self.tickers.iter().any(|ticker| ticker.is_active())
+            || self.dirty.lock().is_ok_and(|dirty| !dirty.is_empty())
+    }
+
+    /// Run one frame
+    ///
+    /// The first frame mounts the root component. Each frame dispatches
+    /// queued input, advances tickers, flushes pending state updates,
+    /// re-renders the components
+    /// they touched, advances animations, recomputes layout if anything
+    /// changed and draws. When only some components re-rendered or animated,
+    /// only they are redrawn. A [`FrameReport`] is recorded for every frame
+    /// that completes.
+    pub fn frame(&mut self) -> Result<(), Error> {
+        self.frame_at(Instant::now())
+    }
+
+    /// Run one frame with tickers and animations advanced to `now`
+    ///
+    /// [`App::frame`] passes the current time; passing a time of your own
+    /// steps timers and animations without waiting for them.
+    pub fn frame_at(&mut self, now: Instant) -> Result<(), Error> {
+        let start = Instant::now();
+        self.components_rendered = 0;
+        // Images drawn as placeholders can be drawn for real now
+        if self.images_loaded.swap(false, Ordering::AcqRel) {
+            self.full_render = true;
+        }
+        if self.nodes.is_none() {
+            self.mount()?;
+        }
+
+        // Updates from one frame's handlers re-render each component once
+        let context = self.context.clone();
+        context.batch(|| {
+            while let Some(input) = self.input.pop_front() {
+                self.dispatch(input);
+            }
+            for ticker in &self.tickers {
+                ticker.tick(now);
+            }
+        });
+        context.flush_updates();
+
+        let mut dirty: Vec<ComponentId> = match self.dirty.lock() {
+            Ok(mut dirty) => dirty.drain().collect(),
+            Err(_) => Vec::new(),
+        };
+        for &id in &dirty {
+            self.rerender(id)?;
+        }
+        if !dirty.is_empty() {
+            self.needs_layout = true;
+        }
+
+        // Animated values are written after re-rendering so they aren't lost
+        if !self.animations.is_empty() {
+            if let Some(nodes) = self.nodes.as_mut() {
+                let mut context = RenderContext::new(self.window.width, self.window.height);
+                self.animations.tick(now, nodes, &mut context);
+                for id in context.get_dirty_components() {
+                    if !dirty.contains(&id) {
+                        dirty.push(id);
+                    }
+                }
+            }
+        }
+
+        // Layout measures text through the renderer, so create it first
+        if self.renderer.is_none() {
+            let mut renderer = create_renderer(RendererType::Auto)?;
+            renderer.init()?;
+            self.set_renderer(renderer);
+        }
+
+        let layout_updated = self.needs_layout;
+        if layout_updated {
+            self.update_layout()?;
+        }
+        let drawn = self.full_render || !dirty.is_empty();
+        if drawn {
+            self.draw(&dirty)?;
+        }
+
+        let report = FrameReport {
+            frame: self.frame_report.as_ref().map_or(0, |report| report.frame) + 1,
+            frame_time: start.elapsed(),
+            components_rendered: self.components_rendered,
+            layout_updated,
+            drawn,
+            render: self
+                .renderer
+                .as_ref()
+                .map(|renderer| renderer.get_stats())
+                .unwrap_or_default(),
+            layout: self.layout.get_stats().clone(),
+        };
+        if let Some(on_frame) = &self.on_frame {
+            on_frame.call(report.clone());
+        }
+        self.frame_report = Some(report);
+        Ok(())
+    }
+
+    /// Open a window and run frames from the platform's event loop
+    ///
+    /// On native targets this returns when the window is closed; on the web
+    /// it returns right away and the browser keeps the app running.
+    pub fn run(mut self) -> Result<(), Error> {
+        let mut platform = crate::init()?;
+        let window = platform.create_window(self.window.clone())?;
+
+        platform.run_event_loop(Box::new(move |event| {
+            match event {
+                PlatformEvent::CloseRequested(_) => return LoopControl::Exit,
+                PlatformEvent::RedrawRequested(_) => {
+                    self.handle_event(event);
+                    if let Err(e) = self.frame() {
+                        crate::trace::error_event!("Frame failed: {e}");
+                    }
+                }
+                event => self.handle_event(event),
+            }
+
+            if self.needs_frame() {
+                LoopControl::Redraw(window)
+            } else {
+                LoopControl::Continue
+            }
+        }))
+    }
+
+    /// Draw with `renderer`, and measure text for layout through it
+    fn set_renderer(&mut self, renderer: Box<dyn Renderer>) {
+        if let Some(measurer) = renderer.text_measurer() {
+            self.layout.set_text_measurer(measurer);
+            self.layout.clear_cache();
+        }
+        self.renderer = Some(renderer);
+    }
+
+    /// Mount the root component and render the whole tree
+    fn mount(&mut self) -> Result<(), Error> {
+        self.tree
+            .mount_component_tree(self.root)
+            .map_err(tree_error)?;
+
+        let mut root = Node::new(Some(self.root_instance.clone()));
+        self.render_component(self.root, &mut root)?;
+        self.components_rendered += count_components(&root);
+        self.nodes = Some(root);
+        self.needs_layout = true;
+        self.full_render = true;
+        Ok(())
+    }
+
+    /// Re-render a component in place, if it is part of the rendered tree
+    ///
+    /// The component's `handle_updates` hook runs first.
+    fn rerender(&mut self, id: ComponentId) -> Result<(), Error> {
+        let Some(mut nodes) = self.nodes.take() else {
+            return Ok(());
+        };
+        let result = match nodes.find_component_mut(id) {
+            Some(node) => {
+                let result = self
+                    .tree
+                    .handle_updates(id)
+                    .map_err(tree_error)
+                    .and_then(|()| self.render_component(id, node));
+                self.components_rendered += count_components(node);
+                result
+            }
+            None => Ok(()),
+        };
+        self.nodes = Some(nodes);
+        result
+    }
+
+    /// Render a component into `node`'s children, rendering nested components too
+    fn render_component(&self, id: ComponentId, node: &mut Node) -> Result<(), Error> {
+        let mut children = self.tree.render_component(id).map_err(tree_error)?;
+
+        // Child components are added, mounted and removed to match the render
+        let mut child_components = Vec::new();
+        collect_component_nodes(&children, &mut child_components);
+        self.tree
+            .reconcile_children(id, &child_components)
+            .map_err(tree_error)?;
+
+        self.render_nested(&mut children)?;
+        *node.children_mut() = children;
+        Ok(())
+    }
+
+    /// Render the components found among `nodes` and their descendants
+    fn render_nested(&self, nodes: &mut [Node]) -> Result<(), Error> {
+        for node in nodes {
+            let component = node.component().map(|component| component.id());
+            match component {
+                Some(id) if self.tree.has_component(id) => self.render_component(id, node)?,
+                _ => self.render_nested(node.children_mut())?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Lay the rendered nodes out in the viewport
+    fn update_layout(&mut self) -> Result<(), Error> {
+        let Some(nodes) = self.nodes.as_mut() else {
+            return Ok(());
+        };
+
+        let viewport = Size::new(self.window.width as f32, self.window.height as f32);
+        let mut portals = Vec::new();
+        let mut layout_root = layout_tree(nodes, &mut portals);
+        // The root fills the viewport unless it asks for a size
+        fill_if_auto(&mut layout_root.style, viewport);
+        place_portals(&mut layout_root, portals, viewport);
+
+        // A node's size can depend on any other, so the cache is only
+        // trusted when the whole tree is unchanged
+        if same_layout_input(&layout_root, &self.layout_root) {
+            reuse_layout(&mut layout_root, &self.layout_root);
+        }
+        self.layout
+            .calculate_layout(&mut layout_root, viewport)
+            .map_err(|e| Error::Render(format!("Layout failed: {e}")))?;
+        self.layout.retain_cache(&layout_root);
+        apply_layout(nodes, &layout_root, &layout_root);
+
+        self.layout_root = layout_root;
+        self.needs_layout = false;
+        Ok(())
+    }
+
+    /// Draw the whole tree, or only `dirty` components when nothing else changed
+    fn draw(&mut self, dirty: &[ComponentId]) -> Result<(), Error> {
+        let Some(nodes) = self.nodes.as_ref() else {
+            return Ok(());
+        };
+        let Some(renderer) = self.renderer.as_mut() else {
+            return Ok(());
+        };
+
+        let mut context = RenderContext::new(self.window.width, self.window.height);
+        if self.full_render {
+            renderer.render(nodes, &mut context)?;
+        } else {
+            renderer.render_selective(nodes, &mut context, dirty)?;
+        }
+        renderer.flush()?;
+
+        self.full_render = false;
+        Ok(())
+    }
+
+    /// Deliver queued input through the event system
+    fn dispatch(&mut self, input: QueuedInput) {
+        let result = match input {
+            QueuedInput::Pointer(input) => self
+                .events
+                .process_pointer_input(input, &self.layout_root)
+                .map(|_| ()),
+            QueuedInput::Key(event) => {
+                let focused = self.events.focus_manager().focused();
+                if self.shortcuts.handle(&event, &self.focus_path(focused)) {
+                    return;
+                }
+                self.events.process_key_event(event, focused).map(|_| ())
+            }
+        };
+        if let Err(e) = result {
+            crate::trace::debug_event!("Dropped input: {e}");
+        }
+    }
+
+    /// The focused component followed by its ancestors
+    fn focus_path(&self, focused: Option<ComponentId>) -> Vec<ComponentId> {
+        let Some(focused) = focused else {
+            return Vec::new();
+        };
+        self.tree
+            .with_snapshot(|view| view.ancestors(focused).collect())
+            .unwrap_or_else(|_| vec![focused])
+    }
+
+    /// The event delegate for a component, created under its parent's on first use
+    fn delegate_for(&mut self, id: ComponentId) -> Arc<Mutex<EventDelegate>> {
+        if let Some(delegate) = self.delegates.get(&id) {
+            return delegate.clone();
+        }
+
+        let delegate = Arc::new(Mutex::new(EventDelegate::new(id.delegate_key())));
+        match self.tree.get_parent(id).ok().flatten() {
+            Some(parent) => {
+                if let Ok(mut parent) = self.delegate_for(parent).lock() {
+                    parent.add_child(delegate.clone());
+                }
+            }
+            None => self.events.delegator_mut().add_child(delegate.clone()),
+        }
+        self.delegates.insert(id, delegate.clone());
+        delegate
+    }
+}
+
+impl std::fmt::Debug for App {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("App")
+            .field("root", &self.root)
+            .field("window", &self.window)
+            .field("renderer", &self.renderer.as_ref().map(|r| r.name()))
+            .field("mounted", &self.nodes.is_some())
+            .finish()
+    }
+}
+
+fn tree_error(error: TreeError) -> Error {
+    Error::Component(error.to_string())
+}
+
+/// Count the nodes with a component in a subtree, including its root
+fn count_components(node: &Node) -> u32 {
+    u32::from(node.component().is_some())
+        + node.children().iter().map(count_components).sum::<u32>()
+}
+
+/// Collect the outermost component nodes among `nodes`
+fn collect_component_nodes(nodes: &[Node], found: &mut Vec<Node>) {
+    for node in nodes {
+        if node.component().is_some() {
+            found.push(node.clone());
+        } else {
+            collect_component_nodes(node.children(), found);
+        }
+    }
+}
+
+/// Build a layout tree mirroring the node tree
+///
+/// Component nodes keep their component's ID so hit testing can route events
+/// to them; other nodes get an ID derived from their parent's and their
+/// place under it, so they keep it from one render to the next. Portal
+/// subtrees are left out and collected into `portals` with their targets,
+/// innermost first.
+pub(crate) fn layout_tree(
+    node: &Node,
+    portals: &mut Vec<(PortalTarget, LayoutNode)>,
+) -> LayoutNode {
+    let id = node
+        .component()
+        .map(|component| component.id())
+        .unwrap_or_default();
+    layout_subtree(node, id, portals)
+}
+
+fn layout_subtree(
+    node: &Node,
+    id: ComponentId,
+    portals: &mut Vec<(PortalTarget, LayoutNode)>,
+) -> LayoutNode {
+    let style = node
+        .style()
+        .and_then(|style| style.layout_style.clone())
+        .unwrap_or_default();
+
+    let mut layout = LayoutNode::new(id, style);
+    layout.text = node.text().map(str::to_string);
+    for (index, child) in node.children().iter().enumerate() {
+        let child_id = child.component().map_or_else(
+            || ComponentId::child_of(id, index),
+            |component| component.id(),
+        );
+        let child_layout = layout_subtree(child, child_id, portals);
+        match child.portal_target() {
+            Some(target) => portals.push((target, child_layout)),
+            None => layout.add_child(child_layout),
+        }
+    }
+    layout
+}
+
+/// Whether two layout trees have the same nodes, styles and text
+fn same_layout_input(a: &LayoutNode, b: &LayoutNode) -> bool {
+    a.id == b.id
+        && a.style == b.style
+        && a.text == b.text
+        && a.focusable == b.focusable
+        && a.children.len() == b.children.len()
+        && a.children
+            .iter()
+            .zip(&b.children)
+            .all(|(a, b)| same_layout_input(a, b))
+}
+
+/// Give every node of `node` the layout of its counterpart in `previous`
+///
+/// The trees must have the same shape, as checked by [`same_layout_input`].
+fn reuse_layout(node: &mut LayoutNode, previous: &LayoutNode) {
+    node.layout = previous.layout.clone();
+    for (child, previous) in node.children.iter_mut().zip(&previous.children) {
+        reuse_layout(child, previous);
+    }
+}
+
+/// Give a style the viewport's size along any axis it leaves to layout
+fn fill_if_auto(style: &mut LayoutStyle, viewport: Size) {
+    if matches!(style.width, Dimension::Auto) {
+        style.width = Dimension::Points(viewport.width);
+    }
+    if matches!(style.height, Dimension::Auto) {
+        style.height = Dimension::Points(viewport.height);
+    }
+}
+
+/// Attach portal subtrees under their targets
+///
+/// Overlay portals become layers over the whole viewport, added after the
+/// root's own children. A portal targeting a component inside another
+/// portal is placed once that portal is, and one whose target isn't in the
+/// tree goes to the overlay.
+fn place_portals(
+    root: &mut LayoutNode,
+    mut portals: Vec<(PortalTarget, LayoutNode)>,
+    viewport: Size,
+) {
+    let mut overlay = Vec::new();
+    while !portals.is_empty() {
+        let count = portals.len();
+        let mut waiting = Vec::new();
+        for (target, portal) in portals {
+            match target {
+                PortalTarget::Overlay => overlay.push(portal),
+                PortalTarget::Component(id) => match find_layout_node_mut(root, id) {
+                    Some(parent) => parent.add_child(portal),
+                    None => waiting.push((target, portal)),
+                },
+            }
+        }
+        if waiting.len() == count {
+            overlay.extend(waiting.into_iter().map(|(_, portal)| portal));
+            break;
+        }
+        portals = waiting;
+    }
+
+    for mut layer in overlay {
+        layer.style.position_type = PositionType::Absolute;
+        fill_if_auto(&mut layer.style, viewport);
+        root.add_child(layer);
+    }
+}
+
+fn find_layout_node_mut(node: &mut LayoutNode, id: ComponentId) -> Option<&mut LayoutNode> {
+    if node.id == id {
+        return Some(node);
+    }
+    node.children
+        .iter_mut()
+        .find_map(|child| find_layout_node_mut(child, id))
+}
+
+fn find_layout_node(node: &LayoutNode, id: ComponentId) -> Option<&LayoutNode> {
+    if node.id == id {
+        return Some(node);
+    }
+    node.children
+        .iter()
+        .find_map(|child| find_layout_node(child, id))
+}
+
+/// Copy computed rectangles from a layout tree onto the nodes it mirrors
+///
+/// Portal nodes are looked up by their component's ID from `root`, since
+/// their layout lives under their target.
+fn apply_layout(node: &mut Node, layout: &LayoutNode, root: &LayoutNode) {
+    node.set_layout_rect(layout.layout.rect);
+    node.set_content_rect(layout.layout.content_rect);
+
+    let mut in_place = layout.children.iter();
+    for child in node.children_mut() {
+        let child_layout = match child.portal_target() {
+            Some(_) => child
+                .component()
+                .and_then(|component| find_layout_node(root, component.id())),
+            None => in_place.next(),
+        };
+        if let Some(child_layout) = child_layout {
+            apply_layout(child, child_layout, root);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{ComponentBase, ComponentError, Portal, PortalProps};
+    use crate::events::Click;
+    use crate::layout::Point;
+    use crate::renderer::NullRenderer;
+    use crate::style::Style;
+
+    fn frames_drawn(app: &App) -> u64 {
+        app.renderer().unwrap().get_stats().frame_count
+    }
+
+    /// Component that renders a fixed list of nodes
+    #[derive(Debug)]
+    struct Fixed {
+        id: ComponentId,
+        children: Vec<Node>,
+    }
+
+    impl Component for Fixed {
+        type Props = Vec<Node>;
+
+        fn component_id(&self) -> ComponentId {
+            self.id
+        }
+
+        fn create(children: Vec<Node>, _context: Context) -> Self {
+            Self {
+                id: ComponentId::new(),
+                children,
+            }
+        }
+
+        fn update(&mut self, children: Vec<Node>) -> Result<(), ComponentError> {
+            self.children = children;
+            Ok(())
+        }
+
+        fn render(&self) -> Result<Vec<Node>, ComponentError> {
+            Ok(self.children.clone())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    fn fixed_node(children: Vec<Node>, layout_style: LayoutStyle) -> (Node, ComponentId) {
+        let component = Fixed::create(children.clone(), Context::new());
+        let id = component.id;
+        let mut node = Node::new(Some(ComponentInstance::new(component, children)));
+        node.set_style(Style {
+            layout_style: Some(layout_style),
+            ..Default::default()
+        });
+        (node, id)
+    }
+
+    #[test]
+    fn test_click_that_changes_state_rerenders() {
+        let mut app = App::new::<ComponentBase>(())
+            .with_renderer(Box::new(NullRenderer::new()))
+            .with_window(WindowConfig::default().with_size(200, 100));
+        app.frame().unwrap();
+        assert_eq!(frames_drawn(&app), 1);
+
+        let root = app.root_id();
+        let clicks = Arc::new(app.context().create_reactive_state(0, root));
+        let counter = Arc::clone(&clicks);
+        app.on::<Click>(root, move |_, _| counter.set(counter.get() + 1));
+
+        // Nothing changed, so nothing is drawn
+        app.frame().unwrap();
+        assert_eq!(frames_drawn(&app), 1);
+
+        app.pointer_input(PointerInput::Down(Point::new(50.0, 50.0)));
+        app.pointer_input(PointerInput::Up(Point::new(50.0, 50.0)));
+        assert!(app.needs_frame());
+        app.frame().unwrap();
+
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(frames_drawn(&app), 2);
+        let stats = app.renderer().unwrap().get_stats();
+        assert_eq!(stats.component_count, 1);
+        assert!(!app.needs_frame());
+    }
+
+    #[test]
+    fn test_portal_is_laid_out_in_the_overlay_but_bubbles_to_its_parent() {
+        // root > anchor > portal > tooltip, with the tooltip placed well
+        // outside the anchor's box
+        let (tooltip, tooltip_id) = fixed_node(
+            Vec::new(),
+            LayoutStyle {
+                position_type: PositionType::Absolute,
+                left: Dimension::Points(100.0),
+                top: Dimension::Points(60.0),
+                width: Dimension::Points(40.0),
+                height: Dimension::Points(20.0),
+                ..Default::default()
+            },
+        );
+        let portal = Portal::node(
+            PortalProps {
+                target: PortalTarget::Overlay,
+                children: vec![tooltip],
+            },
+            Context::new(),
+        );
+        let portal_id = portal.component().unwrap().id();
+        let (anchor, anchor_id) = fixed_node(
+            vec![portal],
+            LayoutStyle {
+                width: Dimension::Points(20.0),
+                height: Dimension::Points(20.0),
+                ..Default::default()
+            },
+        );
+
+        let mut app = App::new::<Fixed>(vec![anchor])
+            .with_renderer(Box::new(NullRenderer::new()))
+            .with_window(WindowConfig::default().with_size(200, 100));
+        app.frame().unwrap();
+
+        // The portal is laid out as a layer of the root, not inside the anchor
+        let root_layout = app.layout_root();
+        let layer = root_layout.children.last().unwrap();
+        assert_eq!(layer.id, portal_id);
+        assert_eq!(layer.children[0].id, tooltip_id);
+        assert!(root_layout.children[0].children.is_empty());
+
+        // The tooltip node is still the anchor's logical descendant
+        let anchor_node = &app.nodes().unwrap().children()[0];
+        let tooltip_node = &anchor_node.children()[0].children()[0];
+        let rect = tooltip_node.layout_rect().unwrap();
+        assert_eq!((rect.x(), rect.y()), (100.0, 60.0));
+        assert_eq!(app.tree().get_parent(portal_id).unwrap(), Some(anchor_id));
+
+        // A click on the tooltip bubbles through the anchor
+        let bubbled = Arc::new(Mutex::new(Vec::new()));
+        app.on::<Click>(tooltip_id, |_, _| {});
+        let log = Arc::clone(&bubbled);
+        if let Ok(delegate) = app.delegate_for(anchor_id).lock() {
+            delegate.bubble(move |_: &Click, propagation| {
+                log.lock().unwrap().push(propagation.target_id);
+            });
+        }
+
+        app.pointer_input(PointerInput::Down(Point::new(110.0, 70.0)));
+        app.pointer_input(PointerInput::Up(Point::new(110.0, 70.0)));
+        app.frame().unwrap();
+        assert_eq!(
+            *bubbled.lock().unwrap(),
+            vec![Some(tooltip_id.id() as usize)]
+        );
+    }
+
+    #[test]
+    fn test_frame_report_counts_rendered_components() {
+        let (first, _) = fixed_node(Vec::new(), LayoutStyle::default());
+        let (second, _) = fixed_node(Vec::new(), LayoutStyle::default());
+        let mut app = App::new::<Fixed>(vec![first, second])
+            .with_renderer(Box::new(NullRenderer::new()))
+            .with_window(WindowConfig::default().with_size(200, 100));
+        assert!(app.last_frame_report().is_none());
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        app.on_frame(crate::component::callback(move |report: FrameReport| {
+            sink.lock().unwrap().push(report.frame);
+        }));
+
+        // Mounting renders the root and both children, and draws all three
+        app.frame().unwrap();
+        let report = app.last_frame_report().unwrap();
+        assert_eq!(report.frame, 1);
+        assert_eq!(report.components_rendered, 3);
+        assert!(report.layout_updated && report.drawn);
+        assert_eq!(report.render.component_count, 3);
+        assert_eq!(report.render.frame_count, 1);
+        assert!(report.layout.layout_calculations > 0);
+
+        // An idle frame renders nothing
+        app.frame().unwrap();
+        let report = app.last_frame_report().unwrap();
+        assert_eq!(report.frame, 2);
+        assert_eq!(report.components_rendered, 0);
+        assert!(!report.layout_updated && !report.drawn);
+        assert_eq!(*reported.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_unchanged_relayout_is_answered_from_the_layout_cache() {
+        let (child, _) = fixed_node(Vec::new(), LayoutStyle::default());
+        let mut app = App::new::<Fixed>(vec![child, Node::default()])
+            .with_renderer(Box::new(NullRenderer::new()))
+            .with_window(WindowConfig::default().with_size(200, 100));
+        app.frame().unwrap();
+        assert_eq!(
+            app.last_frame_report().unwrap().layout_cache_hit_rate(),
+            0.0
+        );
+
+        // Re-rendering the same nodes lays them out again from the cache
+        app.context().schedule_update(app.root_id());
+        app.frame().unwrap();
+        let report = app.last_frame_report().unwrap();
+        assert!(report.layout_updated);
+        assert!(report.layout_cache_hit_rate() > 0.0);
+    }
+
+    #[test]
+    fn test_relayout_keeps_the_layout_cache_the_same_size() {
+        let mut text = Node::default();
+        text.set_text("label");
+        let mut row = Node::default();
+        row.add_child(text);
+        row.add_child(Node::default());
+        let mut app = App::new::<Fixed>(vec![row])
+            .with_renderer(Box::new(NullRenderer::new()))
+            .with_window(WindowConfig::default().with_size(200, 100));
+        app.frame().unwrap();
+        let cached = app.layout.cache_size();
+        assert_eq!(cached, 4);
+
+        let root = app.root_id();
+        for _ in 0..3 {
+            app.context().schedule_update(root);
+            app.frame().unwrap();
+            assert!(app.last_frame_report().unwrap().layout_updated);
+            assert_eq!(app.layout.cache_size(), cached);
+        }
+    }
+
+    #[test]
+    fn test_image_finishing_loading_requests_a_redraw() {
+        let mut app = App::new::<ComponentBase>(())
+            .with_renderer(Box::new(NullRenderer::new()))
+            .with_window(WindowConfig::default().with_size(200, 100));
+        app.frame().unwrap();
+        assert!(!app.needs_frame());
+
+        // A private cache, so other tests' apps aren't woken
+        let images = ImageCache::new();
+        images.on_load(&app._image_listener);
+        let handle = images.load_bytes("placeholder.png", vec![0; 4]);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !app.needs_frame() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(handle.is_settled());
+        assert!(app.needs_frame());
+
+        app.frame().unwrap();
+        assert!(app.last_frame_report().unwrap().drawn);
+        assert_eq!(frames_drawn(&app), 2);
+        assert!(!app.needs_frame());
+    }
+
+    #[test]
+    fn test_root_fills_the_viewport() {
+        let mut app = App::new::<ComponentBase>(())
+            .with_renderer(Box::new(NullRenderer::new()))
+            .with_window(WindowConfig::default().with_size(320, 240));
+        app.frame().unwrap();
+
+        let rect = app.nodes().unwrap().layout_rect().unwrap();
+        assert_eq!((rect.width(), rect.height()), (320.0, 240.0));
+
+        app.resize(640, 480);
+        app.frame().unwrap();
+        let rect = app.nodes().unwrap().layout_rect().unwrap();
+        assert_eq!((rect.width(), rect.height()), (640.0, 480.0));
+
+        // The accessibility tree reports the same bounds
+        let snapshot = app.accessibility_snapshot().unwrap();
+        assert_eq!(snapshot.root.unwrap().bounds, Some(rect));
+    }
+}
@@ -0,0 +1,176 @@
+//! Accessibility tree built from the component tree
+//!
+//! Each component describes itself with `Component::accessibility`, and
+//! `ComponentTree::accessibility_snapshot` collects those descriptions into an
+//! `AxTree` that platform layers can hand to the OS accessibility APIs.
+
+use std::collections::HashMap;
+
+use crate::component::ComponentId;
+use crate::layout::{LayoutNode, Rect};
+
+/// What kind of control a node is to assistive technology
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AxRole {
+    /// A node with no semantics of its own, such as a layout container
+    #[default]
+    Generic,
+    /// A control that performs an action when activated
+    Button,
+    /// A control that toggles between checked and unchecked
+    Checkbox,
+    /// An editable text field
+    TextBox,
+    /// An indicator of how far a task has got
+    ProgressBar,
+}
+
+impl AxRole {
+    /// Role name as used by ARIA
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AxRole::Generic => "generic",
+            AxRole::Button => "button",
+            AxRole::Checkbox => "checkbox",
+            AxRole::TextBox => "textbox",
+            AxRole::ProgressBar => "progressbar",
+        }
+    }
+}
+
+/// Dynamic state of an accessible node
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AxState {
+    /// Whether a checkable control is checked, or `None` if not checkable
+    pub checked: Option<bool>,
+    /// Whether the control ignores input
+    pub disabled: bool,
+    /// Whether an expandable control is expanded, or `None` if not expandable
+    pub expanded: Option<bool>,
+}
+
+/// How a component presents itself to assistive technology
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AxProperties {
+    /// Kind of control
+    pub role: AxRole,
+    /// Accessible name, usually the visible label
+    pub name: Option<String>,
+    /// Current value read out for a range, such as `40%` for a progress bar
+    pub value: Option<String>,
+    /// Dynamic state
+    pub state: AxState,
+}
+
+impl AxProperties {
+    /// Create properties with a role
+    pub fn new(role: AxRole) -> Self {
+        Self {
+            role,
+            ..Default::default()
+        }
+    }
+
+    /// Set the accessible name, ignoring empty names
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.name = (!name.is_empty()).then_some(name);
+        self
+    }
+
+    /// Set the value read out for a range
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Set the dynamic state
+    pub fn with_state(mut self, state: AxState) -> Self {
+        self.state = state;
+        self
+    }
+}
+
+/// A node in the accessibility tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxNode {
+    /// Component the node describes
+    pub id: ComponentId,
+    /// Kind of control
+    pub role: AxRole,
+    /// Accessible name
+    pub name: Option<String>,
+    /// Current value of a range
+    pub value: Option<String>,
+    /// Dynamic state
+    pub state: AxState,
+    /// Bounding rectangle in viewport pixels, once layout has run
+    pub bounds: Option<Rect>,
+    /// Child nodes in tree order
+    pub children: Vec<AxNode>,
+}
+
+impl AxNode {
+    /// Create a node for a component from its properties
+    pub fn new(id: ComponentId, properties: AxProperties) -> Self {
+        Self {
+            id,
+            role: properties.role,
+            name: properties.name,
+            value: properties.value,
+            state: properties.state,
+            bounds: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn apply_bounds(&mut self, bounds: &HashMap<ComponentId, Rect>) {
+        if let Some(rect) = bounds.get(&self.id) {
+            self.bounds = Some(*rect);
+        }
+        for child in &mut self.children {
+            child.apply_bounds(bounds);
+        }
+    }
+}
+
+/// Snapshot of the accessibility tree
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AxTree {
+    /// Node for the root component, if the tree has one
+    pub root: Option<AxNode>,
+}
+
+impl AxTree {
+    /// All nodes, depth first in tree order
+    pub fn nodes(&self) -> Vec<&AxNode> {
+        let mut nodes = Vec::new();
+        let mut stack: Vec<&AxNode> = self.root.iter().collect();
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            stack.extend(node.children.iter().rev());
+        }
+        nodes
+    }
+
+    /// Find the node for a component
+    pub fn find(&self, id: ComponentId) -> Option<&AxNode> {
+        self.nodes().into_iter().find(|node| node.id == id)
+    }
+
+    /// Fill in bounding rectangles from a computed layout tree
+    pub fn apply_layout(&mut self, layout: &LayoutNode) {
+        let mut bounds = HashMap::new();
+        collect_bounds(layout, &mut bounds);
+        if let Some(root) = &mut self.root {
+            root.apply_bounds(&bounds);
+        }
+    }
+}
+
+fn collect_bounds(layout: &LayoutNode, bounds: &mut HashMap<ComponentId, Rect>) {
+    bounds.insert(layout.id, layout.layout.rect);
+    for child in &layout.children {
+        collect_bounds(child, bounds);
+    }
+}
@@ -97,6 +97,27 @@ where
     }
 }
 
+/// Name of the slot receiving children that don't name one
+pub const DEFAULT_SLOT: &str = "default";
+
+/// Group projected children by the slot each one targets
+///
+/// `slot_name` gives the slot a child asks for, usually from its `slot`
+/// attribute; children without one go to [`DEFAULT_SLOT`]. Children keep
+/// their original order within a slot. Shared by [`SlottedProps`] and
+/// template slot projection so both resolve slots the same way.
+pub fn assign_slots<T>(
+    children: Vec<T>,
+    mut slot_name: impl FnMut(&mut T) -> Option<String>,
+) -> HashMap<String, Vec<T>> {
+    let mut slots: HashMap<String, Vec<T>> = HashMap::new();
+    for mut child in children {
+        let name = slot_name(&mut child).unwrap_or_else(|| DEFAULT_SLOT.to_string());
+        slots.entry(name).or_default().push(child);
+    }
+    slots
+}
+
 /// Slot-based composition system
 #[derive(Debug, Clone)]
 pub struct Slot {
@@ -152,6 +173,27 @@ impl SlottedProps {
             .map(|slot| slot.nodes.clone())
             .unwrap_or_default()
     }
+
+    /// Sort projected children into slots
+    ///
+    /// Children with a `slot` attribute go to the slot it names; the rest go
+    /// to [`DEFAULT_SLOT`], in their original order.
+    pub fn from_children(children: Vec<Node>) -> Self {
+        let slots = assign_slots(children, |child| child.attributes().get("slot").cloned());
+        slots.into_iter().fold(Self::new(), |props, (name, nodes)| {
+            props.with_slot(Slot::new(name).with_nodes(nodes))
+        })
+    }
+
+    /// Nodes to render where a slot appears
+    ///
+    /// Returns the nodes projected into the slot, or `fallback` if none were.
+    pub fn render_slot(&self, name: &str, fallback: Vec<Node>) -> Vec<Node> {
+        match self.slots.get(name) {
+            Some(slot) if !slot.nodes.is_empty() => slot.nodes.clone(),
+            _ => fallback,
+        }
+    }
 }
 
 impl Default for SlottedProps {
@@ -416,10 +458,12 @@ mod tests {
     use super::*;
     use crate::component::ComponentBase;
 
+    #[allow(dead_code)]
     struct TestComponent {
         base: ComponentBase,
     }
 
+    #[allow(dead_code)]
     #[derive(Clone)]
     struct TestProps;
     // Using the blanket implementation of Props instead of implementing it manually
@@ -473,6 +517,39 @@ mod tests {
         assert!(props.get_slot("footer").is_none());
     }
 
+    fn text_node(text: &str, slot: Option<&str>) -> Node {
+        let mut node = Node::default();
+        node.set_text(text);
+        if let Some(slot) = slot {
+            node.add_attribute("slot".to_string(), slot.to_string());
+        }
+        node
+    }
+
+    #[test]
+    fn test_children_project_into_slots() {
+        let body = text_node("body", None);
+        let title = text_node("title", Some("header"));
+        let more = text_node("more", None);
+        let (body_id, title_id, more_id) = (body.id_value(), title.id_value(), more.id_value());
+        let props = SlottedProps::from_children(vec![body, title, more]);
+
+        let ids = |nodes: Vec<Node>| nodes.iter().map(Node::id_value).collect::<Vec<_>>();
+        assert_eq!(
+            ids(props.render_slot(DEFAULT_SLOT, vec![])),
+            vec![body_id, more_id]
+        );
+        assert_eq!(ids(props.render_slot("header", vec![])), vec![title_id]);
+
+        // Unfilled slots render their fallback
+        let fallback = text_node("No footer", None);
+        let fallback_id = fallback.id_value();
+        assert_eq!(
+            ids(props.render_slot("footer", vec![fallback])),
+            vec![fallback_id]
+        );
+    }
+
     #[test]
     fn test_slotted_component() {
         let context = Context::new();
@@ -168,6 +168,12 @@ impl<Args: 'static, Ret: 'static> Clone for Callback<Args, Ret> {
     }
 }
 
+impl<Args, Ret> Debug for Callback<Args, Ret> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callback").finish_non_exhaustive()
+    }
+}
+
 impl<Args, Ret> Callback<Args, Ret> {
     /// Create a new callback
     pub fn new<F>(func: F) -> Self
@@ -0,0 +1,490 @@
+//! Diffing between two rendered node trees
+//!
+//! `diff` compares the previously rendered tree with a new one and returns
+//! the patches that turn the first into the second, so a renderer can update
+//! only what changed instead of repainting everything.
+//!
+//! Children are matched by their `key` attribute where present and by
+//! position among unkeyed siblings otherwise. Matched children whose order
+//! changed are moved, except for the longest run that is already in order,
+//! which keeps the number of moves minimal.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::component::{ComponentError, Node};
+use crate::style::Style;
+
+/// Child indices leading from the root to a node
+pub type NodePath = Vec<usize>;
+
+/// A single change to apply to a node tree
+///
+/// Paths and indices refer to the tree as it is when the patch is applied,
+/// so patches must be applied in the order `diff` returns them.
+#[derive(Debug, Clone)]
+pub enum Patch {
+    /// Insert `node` as child `index` of the node at `parent`
+    InsertNode {
+        parent: NodePath,
+        index: usize,
+        node: Box<Node>,
+    },
+    /// Remove child `index` of the node at `parent`
+    RemoveNode { parent: NodePath, index: usize },
+    /// Set and remove attributes on the node at `path`
+    UpdateAttributes {
+        path: NodePath,
+        /// Attributes that were added or changed, sorted by name
+        set: Vec<(String, String)>,
+        /// Names of attributes that were removed, sorted
+        removed: Vec<String>,
+    },
+    /// Replace the text content of the node at `path`
+    UpdateText {
+        path: NodePath,
+        text: Option<String>,
+    },
+    /// Replace the resolved style of the node at `path`
+    UpdateStyle {
+        path: NodePath,
+        style: Option<Box<Style>>,
+    },
+    /// Replace the image source of the node at `path`
+    UpdateImage {
+        path: NodePath,
+        source: Option<String>,
+    },
+    /// Move a child of the node at `parent` from index `from` to `to`
+    ///
+    /// `to` is the index after the child has been taken out of the list.
+    MoveNode {
+        parent: NodePath,
+        from: usize,
+        to: usize,
+    },
+}
+
+/// Compute the patches that turn `old` into `new`
+///
+/// The roots are always patched in place. Children whose component types
+/// differ are never matched, so they are removed and inserted afresh.
+pub fn diff(old: &Node, new: &Node) -> Vec<Patch> {
+    let mut patches = Vec::new();
+    diff_node(old, new, &mut Vec::new(), &mut patches);
+    patches
+}
+
+/// Apply patches from `diff` to a tree, in order
+pub fn apply_patches(root: &mut Node, patches: &[Patch]) -> Result<(), ComponentError> {
+    for patch in patches {
+        match patch {
+            Patch::InsertNode {
+                parent,
+                index,
+                node,
+            } => {
+                let children = node_at(root, parent)?.children_mut();
+                if *index > children.len() {
+                    return Err(out_of_bounds(parent, *index));
+                }
+                children.insert(*index, Node::clone(node));
+            }
+            Patch::RemoveNode { parent, index } => {
+                let children = node_at(root, parent)?.children_mut();
+                if *index >= children.len() {
+                    return Err(out_of_bounds(parent, *index));
+                }
+                children.remove(*index);
+            }
+            Patch::UpdateAttributes { path, set, removed } => {
+                let node = node_at(root, path)?;
+                for name in removed {
+                    node.remove_attribute(name);
+                }
+                for (name, value) in set {
+                    node.add_attribute(name.clone(), value.clone());
+                }
+            }
+            Patch::UpdateText { path, text } => {
+                let node = node_at(root, path)?;
+                match text {
+                    Some(text) => node.set_text(text.clone()),
+                    None => node.clear_text(),
+                }
+            }
+            Patch::UpdateStyle { path, style } => {
+                let node = node_at(root, path)?;
+                match style {
+                    Some(style) => node.set_style(Style::clone(style)),
+                    None => node.clear_style(),
+                }
+            }
+            Patch::UpdateImage { path, source } => {
+                let node = node_at(root, path)?;
+                match source {
+                    Some(source) => node.set_image_source(source.clone()),
+                    None => node.clear_image_source(),
+                }
+            }
+            Patch::MoveNode { parent, from, to } => {
+                let children = node_at(root, parent)?.children_mut();
+                if *from >= children.len() || *to >= children.len() {
+                    return Err(out_of_bounds(parent, (*from).max(*to)));
+                }
+                let child = children.remove(*from);
+                children.insert(*to, child);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn node_at<'a>(root: &'a mut Node, path: &[usize]) -> Result<&'a mut Node, ComponentError> {
+    path.iter().try_fold(root, |node, &index| {
+        node.children_mut()
+            .get_mut(index)
+            .ok_or_else(|| ComponentError::UpdateError(format!("No node at path {path:?}")))
+    })
+}
+
+fn out_of_bounds(parent: &[usize], index: usize) -> ComponentError {
+    ComponentError::UpdateError(format!(
+        "Child index {index} out of bounds under {parent:?}"
+    ))
+}
+
+fn diff_node(old: &Node, new: &Node, path: &mut NodePath, patches: &mut Vec<Patch>) {
+    let mut set: Vec<_> = new
+        .attributes()
+        .iter()
+        .filter(|(name, value)| old.attributes().get(*name) != Some(*value))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    let mut removed: Vec<_> = old
+        .attributes()
+        .keys()
+        .filter(|name| !new.attributes().contains_key(*name))
+        .cloned()
+        .collect();
+    if !set.is_empty() || !removed.is_empty() {
+        set.sort();
+        removed.sort();
+        patches.push(Patch::UpdateAttributes {
+            path: path.clone(),
+            set,
+            removed,
+        });
+    }
+
+    if old.text() != new.text() {
+        patches.push(Patch::UpdateText {
+            path: path.clone(),
+            text: new.text().map(str::to_string),
+        });
+    }
+
+    if old.style() != new.style() {
+        patches.push(Patch::UpdateStyle {
+            path: path.clone(),
+            style: new.style().cloned().map(Box::new),
+        });
+    }
+
+    if old.image_source() != new.image_source() {
+        patches.push(Patch::UpdateImage {
+            path: path.clone(),
+            source: new.image_source().map(str::to_string),
+        });
+    }
+
+    diff_children(old.children(), new.children(), path, patches);
+}
+
+/// Diff matched children, then remove, move and insert children of `path`
+///
+/// Patches inside matched children come first and use the old child
+/// indices, since this parent's child list hasn't been reordered yet.
+fn diff_children(old: &[Node], new: &[Node], path: &mut NodePath, patches: &mut Vec<Patch>) {
+    let matches = match_children(old, new);
+
+    for (new_child, old_index) in new.iter().zip(&matches) {
+        if let Some(old_index) = *old_index {
+            path.push(old_index);
+            diff_node(&old[old_index], new_child, path, patches);
+            path.pop();
+        }
+    }
+
+    // Remove from the back so earlier indices stay valid
+    let mut kept = vec![false; old.len()];
+    for old_index in matches.iter().flatten() {
+        kept[*old_index] = true;
+    }
+    for (old_index, _) in kept.iter().enumerate().rev().filter(|(_, kept)| !**kept) {
+        patches.push(Patch::RemoveNode {
+            parent: path.clone(),
+            index: old_index,
+        });
+    }
+
+    // Matched children that are already in order stay where they are
+    let reused: Vec<(usize, usize)> = matches
+        .iter()
+        .enumerate()
+        .filter_map(|(new_index, old_index)| old_index.map(|old_index| (old_index, new_index)))
+        .collect();
+    let mut stable = vec![false; new.len()];
+    let old_order: Vec<usize> = reused.iter().map(|(old_index, _)| *old_index).collect();
+    for position in longest_increasing_run(&old_order) {
+        stable[reused[position].1] = true;
+    }
+
+    // Children by their new index, in their current order
+    let mut current: Vec<usize> = {
+        let mut by_old: Vec<_> = reused.clone();
+        by_old.sort_unstable();
+        by_old.into_iter().map(|(_, new_index)| new_index).collect()
+    };
+
+    // Place each child just before its next sibling, working backwards so
+    // that sibling is always in its final position
+    for new_index in (0..new.len()).rev() {
+        let anchor = current
+            .iter()
+            .position(|&index| index == new_index + 1)
+            .unwrap_or(current.len());
+
+        match matches[new_index] {
+            None => {
+                patches.push(Patch::InsertNode {
+                    parent: path.clone(),
+                    index: anchor,
+                    node: Box::new(new[new_index].clone()),
+                });
+                current.insert(anchor, new_index);
+            }
+            Some(_) if !stable[new_index] => {
+                let from = current
+                    .iter()
+                    .position(|&index| index == new_index)
+                    .unwrap_or_default();
+                let to = if from < anchor { anchor - 1 } else { anchor };
+                current.remove(from);
+                current.insert(to, new_index);
+                if from != to {
+                    patches.push(Patch::MoveNode {
+                        parent: path.clone(),
+                        from,
+                        to,
+                    });
+                }
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// For each new child, the index of the old child it updates, if any
+///
+/// Keyed children match the old child with the same key, and unkeyed ones
+/// pair up with unkeyed old children in order.
+fn match_children(old: &[Node], new: &[Node]) -> Vec<Option<usize>> {
+    let mut keyed: HashMap<&str, usize> = HashMap::new();
+    let mut unkeyed = VecDeque::new();
+    for (index, child) in old.iter().enumerate() {
+        match key(child) {
+            Some(key) => {
+                keyed.insert(key, index);
+            }
+            None => unkeyed.push_back(index),
+        }
+    }
+
+    new.iter()
+        .map(|child| {
+            let old_index = match key(child) {
+                Some(key) => keyed.remove(key),
+                None => unkeyed.pop_front(),
+            }?;
+            same_kind(&old[old_index], child).then_some(old_index)
+        })
+        .collect()
+}
+
+fn key(node: &Node) -> Option<&str> {
+    node.attributes().get("key").map(String::as_str)
+}
+
+/// Whether two nodes render the same kind of component
+fn same_kind(old: &Node, new: &Node) -> bool {
+    old.component().map(|c| c.type_id) == new.component().map(|c| c.type_id)
+}
+
+/// Positions in `indices` of the longest strictly increasing run
+pub(super) fn longest_increasing_run(indices: &[usize]) -> Vec<usize> {
+    // tails[len] = position of the smallest tail of an increasing run of length len + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; indices.len()];
+
+    for (position, &index) in indices.iter().enumerate() {
+        let len = tails.partition_point(|&tail| indices[tail] < index);
+        if len > 0 {
+            previous[position] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(position);
+        } else {
+            tails[len] = position;
+        }
+    }
+
+    let mut run = Vec::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(position) = current {
+        run.push(position);
+        current = previous[position];
+    }
+    run.reverse();
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_node(text: &str) -> Node {
+        let mut node = Node::default();
+        node.set_text(text);
+        node
+    }
+
+    fn keyed(key: &str) -> Node {
+        let mut node = text_node(key);
+        node.add_attribute("key".to_string(), key.to_string());
+        node
+    }
+
+    fn list(children: Vec<Node>) -> Node {
+        let mut root = Node::default();
+        for child in children {
+            root.add_child(child);
+        }
+        root
+    }
+
+    fn texts(root: &Node) -> Vec<&str> {
+        root.children()
+            .iter()
+            .map(|child| child.text().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_changed_text_is_a_single_patch() {
+        let old = list(vec![text_node("Title"), text_node("Count: 1")]);
+        let new = list(vec![text_node("Title"), text_node("Count: 2")]);
+
+        let patches = diff(&old, &new);
+        assert_eq!(patches.len(), 1);
+        assert!(matches!(
+            &patches[0],
+            Patch::UpdateText { path, text } if path == &vec![1] && text.as_deref() == Some("Count: 2")
+        ));
+    }
+
+    #[test]
+    fn test_changed_style_is_patched() {
+        let styled = |opacity: f32| {
+            let mut node = text_node("Card");
+            node.set_style(Style {
+                opacity: Some(opacity),
+                ..Default::default()
+            });
+            node
+        };
+        let old = list(vec![styled(1.0), styled(1.0)]);
+        let new = list(vec![styled(1.0), styled(0.5)]);
+
+        let patches = diff(&old, &new);
+        assert_eq!(patches.len(), 1);
+        assert!(matches!(
+            &patches[0],
+            Patch::UpdateStyle { path, style: Some(style) }
+                if path == &vec![1] && style.opacity == Some(0.5)
+        ));
+
+        // Dropping the style entirely clears it
+        let unstyled = list(vec![styled(1.0), text_node("Card")]);
+        let mut patched = old.clone();
+        apply_patches(&mut patched, &diff(&old, &unstyled)).unwrap();
+        assert!(patched.children()[1].style().is_none());
+        apply_patches(&mut patched, &diff(&unstyled, &new)).unwrap();
+        assert_eq!(patched.children()[1].style(), new.children()[1].style());
+    }
+
+    #[test]
+    fn test_changed_image_source_is_patched() {
+        let image = |source: &str| {
+            let mut node = Node::default();
+            node.set_image_source(source);
+            node
+        };
+        let old = list(vec![image("avatar.png"), image("logo.png")]);
+        let new = list(vec![image("avatar@2x.png"), Node::default()]);
+
+        let patches = diff(&old, &new);
+        assert_eq!(patches.len(), 2);
+        assert!(matches!(
+            &patches[0],
+            Patch::UpdateImage { path, source }
+                if path == &vec![0] && source.as_deref() == Some("avatar@2x.png")
+        ));
+
+        let mut patched = old.clone();
+        apply_patches(&mut patched, &patches).unwrap();
+        assert_eq!(patched.children()[0].image_source(), Some("avatar@2x.png"));
+        assert_eq!(patched.children()[1].image_source(), None);
+    }
+
+    #[test]
+    fn test_keyed_reorder_moves_the_fewest_children() {
+        let old = list(["a", "b", "c", "d"].map(keyed).into());
+        let new = list(["d", "a", "e", "b", "c"].map(keyed).into());
+
+        let patches = diff(&old, &new);
+        let moves = patches
+            .iter()
+            .filter(|patch| matches!(patch, Patch::MoveNode { .. }))
+            .count();
+        let inserts = patches
+            .iter()
+            .filter(|patch| matches!(patch, Patch::InsertNode { .. }))
+            .count();
+        assert_eq!((moves, inserts, patches.len()), (1, 1, 2));
+
+        let mut patched = old.clone();
+        apply_patches(&mut patched, &patches).unwrap();
+        assert_eq!(texts(&patched), vec!["d", "a", "e", "b", "c"]);
+    }
+
+    #[test]
+    fn test_patches_rebuild_the_new_tree() {
+        let mut old_item = keyed("x");
+        old_item.add_attribute("class".to_string(), "item".to_string());
+        old_item.add_child(text_node("old"));
+        let old = list(vec![keyed("a"), old_item, text_node("gone"), keyed("b")]);
+
+        let mut new_item = keyed("x");
+        new_item.add_attribute("class".to_string(), "item active".to_string());
+        new_item.add_child(text_node("new"));
+        let new = list(vec![keyed("b"), new_item, keyed("a")]);
+
+        let mut patched = old.clone();
+        apply_patches(&mut patched, &diff(&old, &new)).unwrap();
+
+        assert_eq!(texts(&patched), vec!["b", "x", "a"]);
+        let item = &patched.children()[1];
+        assert_eq!(item.attributes()["class"], "item active");
+        assert_eq!(item.children()[0].text(), Some("new"));
+    }
+}
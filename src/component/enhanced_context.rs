@@ -111,7 +111,7 @@ impl Context {
             .unwrap_or_else(|_| {
                 // If we can't read the lock, default to Created
                 // This should never happen in normal operation
-                eprintln!("Error reading lifecycle phase, defaulting to Created");
+                crate::trace::warn_event!("Error reading lifecycle phase, defaulting to Created");
                 LifecyclePhase::Created
             })
     }
@@ -211,7 +211,9 @@ impl std::fmt::Debug for Context {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::component::{ComponentError, ComponentId, LifecyclePhase, Props};
+    use crate::component::{
+        AxProperties, ComponentError, ComponentId, LifecyclePhase, Node, Props, StateValue,
+    };
 
     // Mock component for testing
     struct TestComponent {
@@ -249,7 +251,7 @@ mod tests {
         fn request_update(&mut self) -> Result<(), ComponentError> {
             self.context
                 .request_update(self.id)
-                .map_err(|e| ComponentError::UpdateError(e))
+                .map_err(ComponentError::UpdateError)
         }
 
         fn as_any(&self) -> &dyn std::any::Any {
@@ -279,6 +281,26 @@ mod tests {
             Ok(())
         }
 
+        fn any_validate_props(&self, _props: &dyn Props) -> Result<(), ComponentError> {
+            Ok(())
+        }
+
+        fn any_should_update(&self, _props: &dyn Props) -> bool {
+            true
+        }
+
+        fn any_render(&self) -> Result<Vec<Node>, ComponentError> {
+            Ok(vec![])
+        }
+
+        fn any_accessibility(&self) -> AxProperties {
+            AxProperties::default()
+        }
+
+        fn any_tracked_state(&self) -> HashMap<String, StateValue> {
+            HashMap::new()
+        }
+
         fn any_update(&mut self, _props: Box<dyn Props>) -> Result<(), ComponentError> {
             self.lifecycle_events.push("update".to_string());
             Ok(())
@@ -289,6 +311,10 @@ mod tests {
             Ok(())
         }
 
+        fn any_handle_updates(&mut self) -> Result<(), ComponentError> {
+            Ok(())
+        }
+
         fn any_before_unmount(&mut self) -> Result<(), ComponentError> {
             self.lifecycle_events.push("before_unmount".to_string());
             Ok(())
@@ -4,6 +4,7 @@ use std::any::TypeId;
 use std::error::Error;
 use std::fmt;
 
+use crate::component::props::PropValidationError;
 use crate::component::LifecyclePhase;
 
 /// Errors that can occur during component operations
@@ -21,6 +22,9 @@ pub enum ComponentError {
     /// Invalid props content or validation failed
     InvalidProps(String),
 
+    /// Props rejected by the component's `validate_props`
+    PropValidation(PropValidationError),
+
     /// Error downcasting props or component
     DowncastError,
 
@@ -67,11 +71,25 @@ impl fmt::Display for ComponentError {
             Self::UnmountError(msg) => write!(f, "Error unmounting component: {msg}"),
             Self::ReactiveSystemError(msg) => write!(f, "Reactive system error: {msg}"),
             Self::InvalidProps(msg) => write!(f, "Invalid props: {msg}"),
+            Self::PropValidation(error) => write!(f, "Prop validation failed: {error}"),
+        }
+    }
+}
+
+impl Error for ComponentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::PropValidation(error) => Some(error),
+            _ => None,
         }
     }
 }
 
-impl Error for ComponentError {}
+impl From<PropValidationError> for ComponentError {
+    fn from(error: PropValidationError) -> Self {
+        ComponentError::PropValidation(error)
+    }
+}
 
 // Conversion from SignalError to ComponentError
 impl From<crate::state::SignalError> for ComponentError {
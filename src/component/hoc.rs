@@ -182,6 +182,10 @@ where
         self.wrapped_component.after_update()
     }
 
+    fn handle_updates(&mut self) -> Result<(), ComponentError> {
+        self.wrapped_component.handle_updates()
+    }
+
     fn before_unmount(&mut self) -> Result<(), ComponentError> {
         self.wrapped_component.before_unmount()
     }
@@ -248,10 +252,7 @@ impl<T: Component> HigherOrderComponent<T> for WithLogging {
         _context: &MountContext,
         _hoc_props: &Self::HOCProps,
     ) -> Result<(), ComponentError> {
-        println!(
-            "🔄 Component {} mounted",
-            Component::component_id(component)
-        );
+        crate::trace::info_event!("Component {} mounted", Component::component_id(component));
         Ok(())
     }
 
@@ -260,8 +261,8 @@ impl<T: Component> HigherOrderComponent<T> for WithLogging {
         changes: &StateChanges,
         _hoc_props: &Self::HOCProps,
     ) -> Result<(), ComponentError> {
-        println!(
-            "🔄 Component {} updated with {} changes",
+        crate::trace::info_event!(
+            "Component {} updated with {} changes",
             Component::component_id(component),
             changes.changes.len()
         );
@@ -272,10 +273,7 @@ impl<T: Component> HigherOrderComponent<T> for WithLogging {
         _context: &UnmountContext,
         _hoc_props: &Self::HOCProps,
     ) -> Result<(), ComponentError> {
-        println!(
-            "🔄 Component {} unmounted",
-            Component::component_id(component)
-        );
+        crate::trace::info_event!("Component {} unmounted", Component::component_id(component));
         Ok(())
     }
 }
@@ -297,8 +295,8 @@ impl<T: Component> HigherOrderComponent<T> for WithPerformanceMonitoring {
         let start = std::time::Instant::now();
         let result = component.mount();
         let duration = start.elapsed();
-        println!(
-            "⚡ Component {} mount took {:?}",
+        crate::trace::info_event!(
+            "Component {} mount took {:?}",
             Component::component_id(component),
             duration
         );
@@ -313,8 +311,8 @@ impl<T: Component> HigherOrderComponent<T> for WithPerformanceMonitoring {
         let start = std::time::Instant::now();
         let result = component.on_update(changes);
         let duration = start.elapsed();
-        println!(
-            "⚡ Component {} update took {:?}",
+        crate::trace::info_event!(
+            "Component {} update took {:?}",
             Component::component_id(component),
             duration
         );
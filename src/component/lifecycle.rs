@@ -1,21 +1,62 @@
 //! Component lifecycle management for Orbit UI framework
 
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
+use crate::component::performance::{ErasedMemoKey, PerformanceMonitor};
 use crate::component::{
-    ComponentError, ComponentInstance, Context, LifecyclePhase, UnmountContext, UnmountReason,
+    ComponentError, ComponentId, ComponentInstance, Context, LifecyclePhase, Node, StateChanges,
+    UnmountContext, UnmountReason,
 };
+use crate::trace::{self, Phase};
+
+/// A component moving from one lifecycle phase to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifecycleEvent {
+    /// Component whose phase changed
+    pub component_id: ComponentId,
+    /// Phase before the transition
+    pub from: LifecyclePhase,
+    /// Phase after the transition
+    pub to: LifecyclePhase,
+    /// When the transition happened
+    pub at: Instant,
+}
+
+/// Callback that receives lifecycle events
+pub type LifecycleListener = Arc<dyn Fn(&LifecycleEvent) + Send + Sync>;
+
+/// Nodes from a render, and whether they came from the memo cache
+#[derive(Debug, Clone)]
+pub struct MemoizedRender {
+    /// Rendered nodes
+    pub nodes: Vec<Node>,
+    /// Whether `render` was skipped because the memo key was unchanged
+    pub cache_hit: bool,
+}
 
 /// Manages the lifecycle of components
 pub struct LifecycleManager {
     /// Current phase of the component
     phase: LifecyclePhase,
 
+    /// ID of the managed component
+    component_id: ComponentId,
+
     /// Component instance being managed
     component: Arc<Mutex<ComponentInstance>>,
 
     /// Context for the component
     context: Context,
+
+    /// Callback notified of every phase transition
+    listener: Option<LifecycleListener>,
+
+    /// Memo key and nodes of the last render, for memoized components
+    render_cache: Mutex<Option<(ErasedMemoKey, Vec<Node>)>>,
+
+    /// Monitor that receives mount and update timings
+    monitor: Option<Arc<PerformanceMonitor>>,
 }
 
 impl LifecycleManager {
@@ -23,8 +64,41 @@ impl LifecycleManager {
     pub fn new(component: ComponentInstance, context: Context) -> Self {
         Self {
             phase: LifecyclePhase::Created,
+            component_id: component.id(),
             component: Arc::new(Mutex::new(component)),
             context: context.clone(),
+            listener: None,
+            render_cache: Mutex::new(None),
+            monitor: None,
+        }
+    }
+
+    /// Set the callback notified of every phase transition
+    ///
+    /// `initialize` reports a `Created` to `Created` event, since it runs
+    /// without leaving the created phase.
+    pub fn set_listener(&mut self, listener: Option<LifecycleListener>) {
+        self.listener = listener;
+    }
+
+    /// Set the monitor that receives mount and update timings
+    pub fn set_performance_monitor(&mut self, monitor: Option<Arc<PerformanceMonitor>>) {
+        self.monitor = monitor;
+    }
+
+    /// Move to a new phase and report the transition
+    fn transition(&mut self, to: LifecyclePhase) {
+        let from = self.phase;
+        self.phase = to;
+        self.context.set_lifecycle_phase(to);
+
+        if let Some(listener) = &self.listener {
+            listener(&LifecycleEvent {
+                component_id: self.component_id,
+                from,
+                to,
+                at: Instant::now(),
+            });
         }
     }
 
@@ -62,7 +136,7 @@ impl LifecycleManager {
 
         if result.is_ok() {
             // Update context phase
-            self.context.set_lifecycle_phase(LifecyclePhase::Created);
+            self.transition(LifecyclePhase::Created);
         }
 
         result
@@ -75,6 +149,9 @@ impl LifecycleManager {
                 "mount".to_string(),
             ));
         }
+        let _span = trace::span(Phase::Mount, self.component_id);
+
+        let started = Instant::now();
 
         // Create mount context
         let mount_context = crate::component::MountContext::new(
@@ -113,8 +190,7 @@ impl LifecycleManager {
         before_mount_result?;
 
         // Set mounting phase
-        self.phase = LifecyclePhase::Mounting;
-        self.context.set_lifecycle_phase(LifecyclePhase::Mounting);
+        self.transition(LifecyclePhase::Mounting);
 
         // Execute enhanced mount with context
         let result = {
@@ -140,8 +216,15 @@ impl LifecycleManager {
 
         // Set mounted phase after successful mount
         if result.is_ok() {
-            self.phase = LifecyclePhase::Mounted;
-            self.context.set_lifecycle_phase(LifecyclePhase::Mounted);
+            if let Some(monitor) = &self.monitor {
+                monitor.record_mount_time(self.component_id, started.elapsed());
+            }
+            self.transition(LifecyclePhase::Mounted);
+            trace::debug_event!("Component mounted");
+
+            if let Ok(component_instance) = self.component.lock() {
+                component_instance.attach_refs();
+            }
 
             // Call after_mount hook
             let after_mount_result = {
@@ -162,14 +245,16 @@ impl LifecycleManager {
 
             if let Err(e) = after_mount_result {
                 // If after_mount fails, we still consider the component mounted but log the error
-                eprintln!("Warning: after_mount failed for component: {e}");
+                trace::warn_event!(
+                    "after_mount failed for component {}: {e}",
+                    self.component_id
+                );
             }
         }
 
         if result.is_err() {
             // Reset phase on error
-            self.phase = LifecyclePhase::Created;
-            self.context.set_lifecycle_phase(LifecyclePhase::Created);
+            self.transition(LifecyclePhase::Created);
         }
 
         result
@@ -186,11 +271,34 @@ impl LifecycleManager {
                 "update".to_string(),
             ));
         }
+        let _span = trace::span(Phase::Update, self.component_id);
+
+        let started = Instant::now();
+
+        // Reject invalid props before any hook runs or state changes, and
+        // skip the update entirely when the component says it is unneeded
+        {
+            let mut component = self.component.lock().map_err(|_| {
+                ComponentError::LockError("Failed to lock component for validation".to_string())
+            })?;
+            let should_update = {
+                let instance = component.instance.lock().map_err(|_| {
+                    ComponentError::LockError(
+                        "Failed to lock component instance for validation".to_string(),
+                    )
+                })?;
+                instance.any_validate_props(&*props)?;
+                instance.any_should_update(&*props)
+            };
+
+            if !should_update {
+                component.props = props;
+                return Ok(());
+            }
+        }
 
         // Before update phase
-        self.phase = LifecyclePhase::BeforeUpdate;
-        self.context
-            .set_lifecycle_phase(LifecyclePhase::BeforeUpdate);
+        self.transition(LifecyclePhase::BeforeUpdate);
 
         // Execute before update hooks and call component's before_update
         let result = {
@@ -244,9 +352,11 @@ impl LifecycleManager {
         };
 
         if result.is_ok() {
+            if let Some(monitor) = &self.monitor {
+                monitor.record_update_time(self.component_id, started.elapsed());
+            }
             // Update phase
-            self.phase = LifecyclePhase::Mounted;
-            self.context.set_lifecycle_phase(LifecyclePhase::Mounted);
+            self.transition(LifecyclePhase::Mounted);
         }
 
         result
@@ -260,14 +370,16 @@ impl LifecycleManager {
                 "unmount".to_string(),
             ));
         }
+        let _span = trace::span(Phase::Unmount, self.component_id);
 
         // Before unmount phase
-        self.phase = LifecyclePhase::BeforeUnmount;
-        self.context
-            .set_lifecycle_phase(LifecyclePhase::BeforeUnmount);
+        self.transition(LifecyclePhase::BeforeUnmount);
 
         // Execute before unmount hooks and call component's before_unmount
         if let Ok(component_instance) = self.component.lock() {
+            // Refs stop resolving as soon as unmounting starts
+            component_instance.detach_refs();
+
             let mut inner_component = component_instance.instance.lock().map_err(|_| {
                 ComponentError::LockError(
                     "Failed to lock inner component for before_unmount".to_string(),
@@ -286,8 +398,7 @@ impl LifecycleManager {
         }
 
         // Unmounting phase
-        self.phase = LifecyclePhase::Unmounting;
-        self.context.set_lifecycle_phase(LifecyclePhase::Unmounting);
+        self.transition(LifecyclePhase::Unmounting);
 
         // Create unmount context
         let unmount_context = UnmountContext::new(
@@ -331,8 +442,12 @@ impl LifecycleManager {
 
         if unmount_result.is_ok() {
             // Update phase after successful unmount
-            self.phase = LifecyclePhase::Unmounted;
-            self.context.set_lifecycle_phase(LifecyclePhase::Unmounted);
+            self.transition(LifecyclePhase::Unmounted);
+            trace::debug_event!("Component unmounted");
+
+            if let Ok(mut cache) = self.render_cache.lock() {
+                cache.take();
+            }
 
             // Call after_unmount hook
             let after_unmount_result = if let Ok(component_instance) = self.component.lock() {
@@ -351,14 +466,17 @@ impl LifecycleManager {
 
             if let Err(e) = after_unmount_result {
                 // If after_unmount fails, we still consider the component unmounted but log the error
-                eprintln!("Warning: after_unmount failed for component: {e}");
+                trace::warn_event!(
+                    "after_unmount failed for component {}: {e}",
+                    self.component_id
+                );
             }
         }
 
         unmount_result
     }
 
-    /// Handle updates to the component
+    /// Deliver a flushed scheduled update to the component's `handle_updates`
     pub fn handle_updates(&mut self) -> Result<(), ComponentError> {
         if self.phase != LifecyclePhase::Mounted {
             return Err(ComponentError::InvalidLifecycleTransition(
@@ -366,34 +484,106 @@ impl LifecycleManager {
                 "handle_updates".to_string(),
             ));
         }
+        let _span = trace::span(Phase::Update, self.component_id);
+
+        let component_instance = self.component.lock().map_err(|_| {
+            ComponentError::LockError("Failed to lock component for handle_updates".to_string())
+        })?;
+        let mut inner_component = component_instance.instance.lock().map_err(|_| {
+            ComponentError::LockError(
+                "Failed to lock inner component for handle_updates".to_string(),
+            )
+        })?;
+        inner_component.any_handle_updates()
+    }
+
+    /// Deliver batched state changes to the component's `on_update`
+    pub fn on_update(&mut self, changes: &StateChanges) -> Result<(), ComponentError> {
+        if self.phase != LifecyclePhase::Mounted {
+            return Err(ComponentError::InvalidLifecycleTransition(
+                self.phase,
+                "on_update".to_string(),
+            ));
+        }
+        let _span = trace::span(Phase::Update, self.component_id);
+
+        let started = Instant::now();
+        {
+            let component_instance = self.component.lock().map_err(|_| {
+                ComponentError::LockError("Failed to lock component for on_update".to_string())
+            })?;
+            let mut inner_component = component_instance.instance.lock().map_err(|_| {
+                ComponentError::LockError(
+                    "Failed to lock inner component for on_update".to_string(),
+                )
+            })?;
+            inner_component.any_on_update(changes)?;
+        }
 
-        // In a real implementation, this would check dirty state and update as needed
-        // For now, this is a placeholder that does nothing
+        if let Some(monitor) = &self.monitor {
+            monitor.record_update_time(self.component_id, started.elapsed());
+        }
         Ok(())
     }
 
     /// Render the component
-    pub fn render(&self) -> Result<Vec<crate::component::Node>, ComponentError> {
+    ///
+    /// Memoized components reuse their previous nodes while their memo key
+    /// is unchanged; see [`Self::render_with_memo`].
+    pub fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        self.render_with_memo().map(|render| render.nodes)
+    }
+
+    /// Render the component, reporting whether the memo cache was used
+    ///
+    /// Components created with [`ComponentInstance::new_memoized`] are only
+    /// re-rendered when `should_memo_update` reports a changed memo key.
+    /// Other components always render.
+    pub fn render_with_memo(&self) -> Result<MemoizedRender, ComponentError> {
         if self.phase != LifecyclePhase::Mounted {
             return Err(ComponentError::InvalidLifecycleTransition(
                 self.phase,
                 "render".to_string(),
             ));
         }
+        let _span = trace::span(Phase::Render, self.component_id);
+
+        let component_instance = self.component.lock().map_err(|_| {
+            ComponentError::LockError("Failed to lock component instance for rendering".to_string())
+        })?;
+        let inner_component = component_instance.instance.lock().map_err(|_| {
+            ComponentError::LockError("Failed to lock inner component for render".to_string())
+        })?;
+
+        let memo = component_instance
+            .memo
+            .and_then(|hook| Some((hook, hook.key(&**inner_component)?)));
+        let Some((hook, key)) = memo else {
+            return Ok(MemoizedRender {
+                nodes: inner_component.any_render()?,
+                cache_hit: false,
+            });
+        };
 
-        if let Ok(component_instance) = self.component.lock() {
-            let _inner_component = component_instance.instance.lock().map_err(|_| {
-                ComponentError::LockError("Failed to lock inner component for render".to_string())
-            })?;
-
-            // For now, return empty Vec since AnyComponent doesn't have render method
-            // In a real implementation, this would delegate to the Component trait's render method
-            Ok(vec![])
-        } else {
-            Err(ComponentError::LockError(
-                "Failed to lock component instance for rendering".to_string(),
-            ))
+        let mut cache = self
+            .render_cache
+            .lock()
+            .map_err(|_| ComponentError::LockError("Failed to lock render cache".to_string()))?;
+        if let Some((cached_key, nodes)) = cache.as_ref() {
+            if !hook.changed(&**inner_component, cached_key, &key) {
+                return Ok(MemoizedRender {
+                    nodes: nodes.clone(),
+                    cache_hit: true,
+                });
+            }
         }
+
+        let nodes = inner_component.any_render()?;
+        *cache = Some((key, nodes.clone()));
+        Ok(MemoizedRender {
+            nodes,
+            cache_hit: false,
+        })
     }
 
     /// Get a reference to the component's context
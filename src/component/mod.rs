@@ -3,16 +3,21 @@
 //! This module contains all the types and traits related to the component model,
 //! including lifecycle management, state, props, and rendering.
 
+mod accessibility;
 mod composition;
 mod context;
+mod diff;
 mod enhanced_context;
 mod error;
 mod hoc;
 mod lifecycle;
 mod node;
 mod performance;
+mod portal;
 pub mod props;
+mod refs;
 mod state_tracking;
+mod suspense;
 mod tree;
 mod update_scheduler;
 
@@ -20,35 +25,40 @@ mod update_scheduler;
 mod tests;
 
 // Re-export component module contents
+pub use accessibility::{AxNode, AxProperties, AxRole, AxState, AxTree};
 pub use composition::{
-    CompositionBuilder, CompoundComponent, FlexibleCompoundComponent, FlexibleCompoundProps,
-    RenderProp, RenderPropComponent, RenderPropProps, Slot, Slotted, SlottedComponent,
-    SlottedProps,
+    assign_slots, CompositionBuilder, CompoundComponent, FlexibleCompoundComponent,
+    FlexibleCompoundProps, RenderProp, RenderPropComponent, RenderPropProps, Slot, Slotted,
+    SlottedComponent, SlottedProps, DEFAULT_SLOT,
 };
 pub use context::{callback, Callback, ContextProvider};
+pub use diff::{apply_patches, diff, NodePath, Patch};
 pub use enhanced_context::Context as EnhancedContext;
 pub use error::ComponentError;
 pub use hoc::{
     HOCWrapper, HigherOrderComponent, LoggedComponent, MonitoredComponent, WithLogging,
     WithPerformanceMonitoring,
 };
-pub use lifecycle::LifecycleManager;
+pub use lifecycle::{LifecycleEvent, LifecycleListener, LifecycleManager, MemoizedRender};
 // Import Node from our own node module instead of component_single
 pub use node::Node;
 pub use performance::{
     LazyComponent, LoadTrigger, MemoCache, MemoComponent, Memoizable, PerformanceMonitor,
     PerformanceRegistry, RenderStatistics, RenderTimer, UpdateBatcher,
 };
+pub use portal::{Portal, PortalProps, PortalTarget};
+pub use refs::Ref;
 pub use state_tracking::{
-    ChangePriority, StateChange, StateChanges, StateSnapshot, StateTracker, StateTrackingConfig,
-    StateValue,
+    ChangePriority, StateChange, StateChanges, StateHistory, StateSnapshot, StateTracker,
+    StateTrackingConfig, StateValue,
 };
-pub use tree::{ComponentTree, TreeError, TreeResult};
+pub use suspense::{Resource, ResourceStatus, Suspense, SuspenseProps};
+pub use tree::{ChildReconciliation, ComponentTree, TreeError, TreeResult, TreeView};
 pub use update_scheduler::{UpdatePriority, UpdateScheduler as ModuleUpdateScheduler};
 
 use std::{
     any::TypeId,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc, Mutex,
@@ -56,14 +66,18 @@ use std::{
 };
 
 use crate::{
+    component::{performance::MemoHook, props::PropValidationError, refs::RefSlot},
     events::EventEmitter,
     layout::{LayoutNode, LayoutStyle},
-    state::{State, StateContainer},
+    state::{State, StateContainer, Subscription},
 };
 
 /// Global component ID counter for unique component identification
 static COMPONENT_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
+/// Set on IDs derived with [`ComponentId::child_of`], which the counter never reaches
+const DERIVED_ID_BIT: u64 = 1 << 63;
+
 /// Unique identifier for component instances
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ComponentId(u64);
@@ -74,10 +88,29 @@ impl ComponentId {
         Self(COMPONENT_ID_COUNTER.fetch_add(1, Ordering::SeqCst))
     }
 
+    /// ID for the `index`th child of `parent` when that child isn't a component
+    ///
+    /// The same parent and index always give the same ID, so plain elements
+    /// keep their identity from one render to the next. Derived IDs never
+    /// collide with ones from [`ComponentId::new`].
+    pub fn child_of(parent: ComponentId, index: usize) -> Self {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        (parent.0, index).hash(&mut hasher);
+        Self(hasher.finish() | DERIVED_ID_BIT)
+    }
+
     /// Get the raw ID value
     pub fn id(&self) -> u64 {
         self.0
     }
+
+    /// The ID as an event delegate key, or `None` where it doesn't fit a `usize`
+    pub fn delegate_key(&self) -> Option<usize> {
+        usize::try_from(self.0).ok()
+    }
 }
 
 impl Default for ComponentId {
@@ -281,6 +314,10 @@ pub struct ComponentInstance {
     pub props: Box<dyn Props>,
     /// Component type ID for type checking
     pub type_id: TypeId,
+    /// Refs filled while the component is mounted
+    refs: Vec<Arc<dyn RefSlot>>,
+    /// Access to the component's memo key, if it is `Memoizable`
+    pub(crate) memo: Option<MemoHook>,
 }
 
 impl std::fmt::Debug for ComponentInstance {
@@ -299,6 +336,8 @@ impl Clone for ComponentInstance {
             instance: Arc::clone(&self.instance),
             props: self.props.box_clone(),
             type_id: self.type_id,
+            refs: self.refs.clone(),
+            memo: self.memo,
         }
     }
 }
@@ -310,6 +349,44 @@ impl ComponentInstance {
             instance: Arc::new(Mutex::new(Box::new(instance) as Box<dyn AnyComponent>)),
             props: Box::new(props),
             type_id: TypeId::of::<C>(),
+            refs: Vec::new(),
+            memo: None,
+        }
+    }
+
+    /// Create an instance whose renders are cached by its memo key
+    ///
+    /// The lifecycle manager re-renders only when
+    /// [`Memoizable::should_memo_update`] reports a changed key, and reuses
+    /// the previous nodes otherwise.
+    pub fn new_memoized<C>(instance: C, props: C::Props) -> Self
+    where
+        C: Component + Memoizable + 'static,
+        C::MemoKey: Send + Sync + 'static,
+    {
+        Self {
+            memo: Some(MemoHook::of::<C>()),
+            ..Self::new(instance, props)
+        }
+    }
+
+    /// Fill `component_ref` with this component while it is mounted
+    pub fn with_ref<C: Component>(mut self, component_ref: &Ref<C>) -> Self {
+        self.refs.push(Arc::new(component_ref.clone()));
+        self
+    }
+
+    /// Point every attached ref at this instance
+    pub(crate) fn attach_refs(&self) {
+        for component_ref in &self.refs {
+            component_ref.attach(&self.instance);
+        }
+    }
+
+    /// Clear every attached ref
+    pub(crate) fn detach_refs(&self) {
+        for component_ref in &self.refs {
+            component_ref.detach();
         }
     }
 
@@ -504,9 +581,27 @@ pub trait AnyComponent: Send + Sync + std::any::Any {
     /// Called before component updates with new props
     fn any_before_update(&mut self, props: Box<dyn Props>) -> Result<(), ComponentError>;
 
+    /// Check new props before they are applied (type-erased)
+    fn any_validate_props(&self, props: &dyn Props) -> Result<(), ComponentError>;
+
+    /// Check whether new props require an update (type-erased)
+    ///
+    /// Props of the wrong type return `true`, so the update itself reports
+    /// the mismatch.
+    fn any_should_update(&self, props: &dyn Props) -> bool;
+
     /// Update component with new props (type-erased)
     fn any_update(&mut self, props: Box<dyn Props>) -> Result<(), ComponentError>;
 
+    /// Render the component (type-erased)
+    fn any_render(&self) -> Result<Vec<Node>, ComponentError>;
+
+    /// Describe the component to assistive technology (type-erased)
+    fn any_accessibility(&self) -> AxProperties;
+
+    /// State watched for changes between renders (type-erased)
+    fn any_tracked_state(&self) -> HashMap<String, StateValue>;
+
     /// Called after the component has updated
     fn any_after_update(&mut self) -> Result<(), ComponentError>;
     /// Called before component is unmounted
@@ -528,6 +623,9 @@ pub trait AnyComponent: Send + Sync + std::any::Any {
     /// Enhanced update with state changes
     fn any_on_update(&mut self, changes: &StateChanges) -> Result<(), ComponentError>;
 
+    /// Scheduled update hook
+    fn any_handle_updates(&mut self) -> Result<(), ComponentError>;
+
     /// Enhanced unmount with context
     fn any_on_unmount(&mut self, context: &UnmountContext) -> Result<(), ComponentError>;
 
@@ -586,6 +684,15 @@ pub trait Component: AnyComponent + Send + Sync + std::any::Any {
         Ok(())
     }
 
+    /// Called when an update scheduled for this component is flushed
+    ///
+    /// Runs just before the component re-renders, so work that finished
+    /// outside the component's other hooks, such as an asynchronous read,
+    /// can be applied to it.
+    fn handle_updates(&mut self) -> Result<(), ComponentError> {
+        Ok(())
+    }
+
     /// Request that this component be re-rendered
     fn request_update(&mut self) -> Result<(), ComponentError> {
         // Implementation provided by the framework
@@ -595,12 +702,24 @@ pub trait Component: AnyComponent + Send + Sync + std::any::Any {
 
     /// Check if component should update given new props
     /// Override for performance optimization
+    ///
+    /// When this returns false, the new props are stored but
+    /// `before_update`, `update` and `after_update` are skipped.
     fn should_update(&self, _new_props: &Self::Props) -> bool {
         // Default: always update
         // Override this for memoization and performance
         true
     }
 
+    /// Check new props before they are applied
+    ///
+    /// Runs before any update hooks. Returning an error aborts the update
+    /// with [`ComponentError::PropValidation`], leaving the component and its
+    /// lifecycle phase unchanged.
+    fn validate_props(&self, _props: &Self::Props) -> Result<(), PropValidationError> {
+        Ok(())
+    }
+
     /// Called before component updates with new props
     fn before_update(&mut self, _new_props: &Self::Props) -> Result<(), ComponentError> {
         Ok(())
@@ -647,6 +766,22 @@ pub trait Component: AnyComponent + Send + Sync + std::any::Any {
     /// Render component - returns child nodes
     fn render(&self) -> Result<Vec<Node>, ComponentError>;
 
+    /// Describe the component to assistive technology
+    ///
+    /// Components without their own semantics keep the default generic role.
+    fn accessibility(&self) -> AxProperties {
+        AxProperties::default()
+    }
+
+    /// State the component tree watches for changes between renders
+    ///
+    /// [`ComponentTree::detect_state_changes`] compares these fields with
+    /// the ones recorded when the component last rendered. Components that
+    /// report nothing are only re-rendered when an update is scheduled.
+    fn tracked_state(&self) -> HashMap<String, StateValue> {
+        HashMap::new()
+    }
+
     /// Get current lifecycle phase
     fn lifecycle_phase(&self) -> LifecyclePhase {
         LifecyclePhase::Created // Default, overridden by framework
@@ -826,6 +961,24 @@ impl<T: Component> AnyComponent for T {
         }
     }
 
+    fn any_validate_props(&self, props: &dyn Props) -> Result<(), ComponentError> {
+        if let Some(typed_props) = props.as_any().downcast_ref::<T::Props>() {
+            Ok(Component::validate_props(self, typed_props)?)
+        } else {
+            Err(ComponentError::PropsMismatch {
+                expected: TypeId::of::<T::Props>(),
+                got: props.as_any().type_id(),
+            })
+        }
+    }
+
+    fn any_should_update(&self, props: &dyn Props) -> bool {
+        props
+            .as_any()
+            .downcast_ref::<T::Props>()
+            .is_none_or(|typed_props| Component::should_update(self, typed_props))
+    }
+
     fn any_update(&mut self, props: Box<dyn Props>) -> Result<(), ComponentError> {
         // Try to downcast the props to the component's Props type
         if let Some(typed_props) = props.as_any().downcast_ref::<T::Props>() {
@@ -838,6 +991,18 @@ impl<T: Component> AnyComponent for T {
         }
     }
 
+    fn any_render(&self) -> Result<Vec<Node>, ComponentError> {
+        Component::render(self)
+    }
+
+    fn any_accessibility(&self) -> AxProperties {
+        Component::accessibility(self)
+    }
+
+    fn any_tracked_state(&self) -> HashMap<String, StateValue> {
+        Component::tracked_state(self)
+    }
+
     fn any_after_update(&mut self) -> Result<(), ComponentError> {
         Component::after_update(self)
     }
@@ -866,6 +1031,10 @@ impl<T: Component> AnyComponent for T {
         Component::on_update(self, changes)
     }
 
+    fn any_handle_updates(&mut self) -> Result<(), ComponentError> {
+        Component::handle_updates(self)
+    }
+
     fn any_on_unmount(&mut self, context: &UnmountContext) -> Result<(), ComponentError> {
         Component::on_unmount(self, context)
     }
@@ -898,28 +1067,94 @@ pub struct Context {
 
     /// Update scheduler for batching state changes
     update_scheduler: Arc<Mutex<UpdateScheduler>>,
+
+    /// State subscriptions that live as long as the context
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
 }
 
+/// Callback invoked once per component when pending updates are flushed
+pub type UpdateHandler = Arc<dyn Fn(ComponentId) + Send + Sync>;
+
 /// Manages batched updates for improved performance
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct UpdateScheduler {
     /// Components waiting for updates
     pending_updates: HashMap<ComponentId, bool>,
     /// Whether an update batch is currently scheduled
     batch_scheduled: bool,
+    /// Nesting depth of open batches; updates are deferred while nonzero
+    batch_depth: usize,
+    /// Handler that applies flushed updates
+    update_handler: Option<UpdateHandler>,
+    /// Components whose render depends on each component, keyed by dependency
+    dependents: HashMap<ComponentId, HashSet<ComponentId>>,
+}
+
+impl std::fmt::Debug for UpdateScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpdateScheduler")
+            .field("pending_updates", &self.pending_updates)
+            .field("batch_scheduled", &self.batch_scheduled)
+            .field("batch_depth", &self.batch_depth)
+            .field("update_handler", &self.update_handler.is_some())
+            .field("dependents", &self.dependents)
+            .finish()
+    }
 }
 
 impl UpdateScheduler {
     /// Schedule a component for update
+    ///
+    /// Components that depend on it, directly or through other
+    /// dependencies, are scheduled with it.
     pub fn schedule_update(&mut self, component_id: ComponentId) {
-        self.pending_updates.insert(component_id, true);
-        if !self.batch_scheduled {
-            self.batch_scheduled = true;
-            // In a real implementation, this would schedule a microtask
-            // For now, we'll handle updates immediately
+        let mut visited = HashSet::new();
+        let mut stack = vec![component_id];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            self.pending_updates.insert(id, true);
+            if let Some(dependents) = self.dependents.get(&id) {
+                stack.extend(dependents.iter().copied());
+            }
+        }
+        self.batch_scheduled = true;
+    }
+
+    /// Declare that `dependent` renders from `dependency`
+    ///
+    /// Scheduling `dependency` then schedules `dependent` too, so a parent
+    /// whose output depends on a child is not left stale when only the child
+    /// signals a change.
+    pub fn add_dependency(&mut self, dependent: ComponentId, dependency: ComponentId) {
+        if dependent != dependency {
+            self.dependents
+                .entry(dependency)
+                .or_default()
+                .insert(dependent);
+        }
+    }
+
+    /// Remove a dependency declared with [`Self::add_dependency`]
+    pub fn remove_dependency(&mut self, dependent: ComponentId, dependency: ComponentId) {
+        if let Some(dependents) = self.dependents.get_mut(&dependency) {
+            dependents.remove(&dependent);
+            if dependents.is_empty() {
+                self.dependents.remove(&dependency);
+            }
         }
     }
 
+    /// Forget every dependency from or on a component, as when it unmounts
+    pub fn clear_dependencies(&mut self, component_id: ComponentId) {
+        self.dependents.remove(&component_id);
+        self.dependents.retain(|_, dependents| {
+            dependents.remove(&component_id);
+            !dependents.is_empty()
+        });
+    }
+
     /// Check if a component has pending updates
     pub fn has_pending_update(&self, component_id: ComponentId) -> bool {
         self.pending_updates.contains_key(&component_id)
@@ -934,6 +1169,81 @@ impl UpdateScheduler {
     pub fn get_pending_components(&self) -> Vec<ComponentId> {
         self.pending_updates.keys().copied().collect()
     }
+
+    /// Open a batch, deferring flushes until the matching [`Self::end_batch`]
+    pub fn begin_batch(&mut self) {
+        self.batch_depth += 1;
+    }
+
+    /// Close a batch opened with [`Self::begin_batch`]
+    pub fn end_batch(&mut self) {
+        self.batch_depth = self.batch_depth.saturating_sub(1);
+    }
+
+    /// Whether a batch is currently open
+    pub fn is_batching(&self) -> bool {
+        self.batch_depth > 0
+    }
+
+    /// Set the handler that applies flushed updates
+    pub fn set_update_handler(&mut self, handler: UpdateHandler) {
+        self.update_handler = Some(handler);
+    }
+
+    /// Take the pending components and handler for a flush
+    ///
+    /// Returns `None` while a batch is open or when no handler is set, in
+    /// which case the pending updates stay queued.
+    fn take_flush(&mut self) -> Option<(Vec<ComponentId>, UpdateHandler)> {
+        if self.is_batching() || !self.batch_scheduled {
+            return None;
+        }
+        let handler = self.update_handler.clone()?;
+
+        self.batch_scheduled = false;
+        let components = self.pending_updates.drain().map(|(id, _)| id).collect();
+        Some((components, handler))
+    }
+}
+
+/// Flush the pending updates of `scheduler`, returning how many were applied
+///
+/// The handler runs after the scheduler lock is released, so it may schedule
+/// further updates.
+fn flush_scheduler(scheduler: &Mutex<UpdateScheduler>) -> usize {
+    let flush = match scheduler.lock() {
+        Ok(mut scheduler) => scheduler.take_flush(),
+        Err(_) => None,
+    };
+
+    match flush {
+        Some((components, handler)) => {
+            for &component_id in &components {
+                handler(component_id);
+            }
+            components.len()
+        }
+        None => 0,
+    }
+}
+
+/// Schedule an update and flush it right away unless a batch is open
+fn schedule_and_flush(scheduler: &Mutex<UpdateScheduler>, component_id: ComponentId) {
+    if let Ok(mut s) = scheduler.lock() {
+        s.schedule_update(component_id);
+    }
+    flush_scheduler(scheduler);
+}
+
+/// Closes a batch when dropped, so a panicking closure cannot leave it open
+struct BatchGuard<'a>(&'a Mutex<UpdateScheduler>);
+
+impl Drop for BatchGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut scheduler) = self.0.lock() {
+            scheduler.end_batch();
+        }
+    }
 }
 
 impl std::fmt::Debug for Context {
@@ -965,6 +1275,7 @@ impl Context {
             lifecycle_phase: LifecyclePhase::Created,
             context_provider: ContextProvider::new(),
             update_scheduler: Arc::new(Mutex::new(UpdateScheduler::default())),
+            subscriptions: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -994,10 +1305,46 @@ impl Context {
     }
 
     /// Schedule a component update
+    ///
+    /// Outside of [`Self::batch`] the update is flushed immediately.
     pub fn schedule_update(&self, component_id: ComponentId) {
+        schedule_and_flush(&self.update_scheduler, component_id);
+    }
+
+    /// Set the handler that re-renders components when updates are flushed
+    ///
+    /// Until a handler is set, scheduled updates stay pending.
+    pub fn set_update_handler<F>(&self, handler: F)
+    where
+        F: Fn(ComponentId) + Send + Sync + 'static,
+    {
+        if let Ok(mut scheduler) = self.update_scheduler.lock() {
+            scheduler.set_update_handler(Arc::new(handler));
+        }
+    }
+
+    /// Run `f` with updates deferred, then flush each dirty component once
+    ///
+    /// Nested batches coalesce into the outermost one, which does the flush.
+    pub fn batch<R>(&self, f: impl FnOnce() -> R) -> R {
         if let Ok(mut scheduler) = self.update_scheduler.lock() {
-            scheduler.schedule_update(component_id);
+            scheduler.begin_batch();
         }
+
+        let result = {
+            let _guard = BatchGuard(&self.update_scheduler);
+            f()
+        };
+
+        self.flush_updates();
+        result
+    }
+
+    /// Flush pending updates now, returning how many components were updated
+    ///
+    /// Does nothing while a batch is open.
+    pub fn flush_updates(&self) -> usize {
+        flush_scheduler(&self.update_scheduler)
     }
 
     /// Check if component has pending updates
@@ -1009,6 +1356,30 @@ impl Context {
         }
     }
 
+    /// Schedule `dependent` whenever `dependency` is scheduled
+    ///
+    /// See [`UpdateScheduler::add_dependency`].
+    pub fn add_render_dependency(&self, dependent: ComponentId, dependency: ComponentId) {
+        if let Ok(mut scheduler) = self.update_scheduler.lock() {
+            scheduler.add_dependency(dependent, dependency);
+        }
+    }
+
+    /// Forget every render dependency from or on a component
+    pub fn clear_render_dependencies(&self, component_id: ComponentId) {
+        if let Ok(mut scheduler) = self.update_scheduler.lock() {
+            scheduler.clear_dependencies(component_id);
+        }
+    }
+
+    /// Components with updates scheduled but not yet flushed
+    pub fn pending_updates(&self) -> Vec<ComponentId> {
+        match self.update_scheduler.lock() {
+            Ok(scheduler) => scheduler.get_pending_components(),
+            Err(_) => Vec::new(),
+        }
+    }
+
     /// Create a reactive state that triggers component updates
     pub fn create_reactive_state<T>(&self, initial_value: T, component_id: ComponentId) -> State<T>
     where
@@ -1016,13 +1387,16 @@ impl Context {
     {
         let state = self.state.create(initial_value);
 
-        // Set up state change listener to trigger component updates
+        // Set up state change listener to trigger component updates. This
+        // subscribes without reading the value, so the update handler is free
+        // to read the state while it runs.
         let scheduler = Arc::clone(&self.update_scheduler);
-        state.on_change(move |_| {
-            if let Ok(mut s) = scheduler.lock() {
-                s.schedule_update(component_id);
-            }
+        let subscription = state.subscribe(move || {
+            schedule_and_flush(&scheduler, component_id);
         });
+        if let Ok(mut subscriptions) = self.subscriptions.lock() {
+            subscriptions.push(subscription);
+        }
 
         state
     }
@@ -1055,4 +1429,38 @@ impl Context {
     pub fn context_provider(&self) -> &ContextProvider {
         &self.context_provider
     }
+
+    /// Create a context for a child component
+    ///
+    /// The child shares state, events and update scheduling with this
+    /// context, and has its own provider whose parent is this one, so it can
+    /// [`inject`](Self::inject) values provided here or by any ancestor.
+    pub fn child(&self) -> Self {
+        Self {
+            id: ComponentId::new(),
+            state: self.state.clone(),
+            events: self.events.clone(),
+            lifecycle_hooks: Arc::new(Mutex::new(LifecycleHooks::new())),
+            lifecycle_phase: LifecyclePhase::Created,
+            context_provider: ContextProvider::with_parent(self.context_provider.clone()),
+            update_scheduler: Arc::clone(&self.update_scheduler),
+            subscriptions: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Provide a value to this context and every context created from it
+    ///
+    /// Values are keyed by type; providing a second value of the same type
+    /// here replaces the first, while providing it in a descendant shadows
+    /// it for that subtree.
+    pub fn provide<T: Clone + Send + Sync + std::fmt::Debug + 'static>(&self, value: T) {
+        if let Err(e) = self.context_provider.provide(value) {
+            crate::trace::warn_event!("Could not provide context value: {e}");
+        }
+    }
+
+    /// Get the nearest value of type `T` provided here or by an ancestor
+    pub fn inject<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.context_provider.consume::<T>()
+    }
 }
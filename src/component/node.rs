@@ -3,9 +3,11 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-use crate::component::ComponentInstance;
+use crate::component::{ComponentId, ComponentInstance, PortalTarget};
 use crate::events::delegation::EventDelegate;
 use crate::events::Event;
+use crate::layout::{Point, Rect, TransformMatrix};
+use crate::style::Style;
 
 /// A node in the UI tree with event delegation support
 #[derive(Debug, Clone)]
@@ -25,6 +27,24 @@ pub struct Node {
 
     /// Event delegate for this node (not cloneable, so wrapped in Arc)
     event_delegate: Option<Arc<Mutex<EventDelegate>>>,
+
+    /// Computed layout rectangle, in viewport pixels
+    layout_rect: Option<Rect>,
+
+    /// Layout rectangle inside padding and borders, in viewport pixels
+    content_rect: Option<Rect>,
+
+    /// Resolved visual style used by renderers
+    style: Option<Style>,
+
+    /// Text content drawn inside the node
+    text: Option<String>,
+
+    /// Path or URL of an image drawn inside the node
+    image_source: Option<String>,
+
+    /// Where this node is laid out, if it is a portal
+    portal_target: Option<PortalTarget>,
 }
 
 #[allow(dead_code)]
@@ -40,6 +60,12 @@ impl Node {
             children: Vec::new(),
             id,
             event_delegate: Some(Arc::new(Mutex::new(EventDelegate::new(Some(id))))),
+            layout_rect: None,
+            content_rect: None,
+            style: None,
+            text: None,
+            image_source: None,
+            portal_target: None,
         }
     }
 
@@ -63,6 +89,20 @@ impl Node {
         &mut self.children
     }
 
+    /// Find the node rendered for a component in this subtree
+    pub fn find_component_mut(&mut self, id: ComponentId) -> Option<&mut Node> {
+        if self
+            .component
+            .as_ref()
+            .is_some_and(|component| component.id() == id)
+        {
+            return Some(self);
+        }
+        self.children
+            .iter_mut()
+            .find_map(|child| child.find_component_mut(id))
+    }
+
     /// Get the node's event delegate
     pub fn event_delegate(&self) -> Option<Arc<Mutex<EventDelegate>>> {
         self.event_delegate.clone()
@@ -91,6 +131,11 @@ impl Node {
         self.attributes.insert(key, value);
     }
 
+    /// Remove an attribute, returning its value
+    pub fn remove_attribute(&mut self, key: &str) -> Option<String> {
+        self.attributes.remove(key)
+    }
+
     /// Dispatch an event to this node
     pub fn dispatch_event<E: Event + Clone + 'static>(&self, event: &E) {
         if let Some(delegate) = &self.event_delegate {
@@ -114,6 +159,100 @@ impl Node {
     pub fn attributes(&self) -> &HashMap<String, String> {
         &self.attributes
     }
+
+    /// Get the computed layout rectangle, if layout has run
+    pub fn layout_rect(&self) -> Option<Rect> {
+        self.layout_rect
+    }
+
+    /// Set the computed layout rectangle
+    pub fn set_layout_rect(&mut self, rect: Rect) {
+        self.layout_rect = Some(rect);
+    }
+
+    /// Get the content rectangle, falling back to the layout rectangle
+    pub fn content_rect(&self) -> Option<Rect> {
+        self.content_rect.or(self.layout_rect)
+    }
+
+    /// Set the content rectangle
+    pub fn set_content_rect(&mut self, rect: Rect) {
+        self.content_rect = Some(rect);
+    }
+
+    /// Style transform of this node in viewport pixels, if it has one
+    ///
+    /// The transform origin is resolved against the layout rect the same way
+    /// layout resolves `LayoutResult::transform_matrix`, defaulting to the
+    /// center of the node.
+    pub fn transform_matrix(&self) -> Option<TransformMatrix> {
+        let rect = self.layout_rect?;
+        let style = self.style.as_ref()?;
+        let transform = style.transform.as_ref()?;
+        let origin = style.transform_origin.as_ref().map_or(
+            Point::new(rect.width() / 2.0, rect.height() / 2.0),
+            |origin| Point::new(origin.x, origin.y),
+        );
+        Some(transform.to_matrix_about(Point::new(rect.x() + origin.x, rect.y() + origin.y)))
+    }
+
+    /// Get the resolved style, if one has been applied
+    pub fn style(&self) -> Option<&Style> {
+        self.style.as_ref()
+    }
+
+    /// Set the resolved style
+    pub fn set_style(&mut self, style: Style) {
+        self.style = Some(style);
+    }
+
+    /// Remove the resolved style
+    pub fn clear_style(&mut self) {
+        self.style = None;
+    }
+
+    /// Get the text content, if any
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Set the text content
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = Some(text.into());
+    }
+
+    /// Remove the text content
+    pub fn clear_text(&mut self) {
+        self.text = None;
+    }
+
+    /// Get the image source, if this node displays an image
+    pub fn image_source(&self) -> Option<&str> {
+        self.image_source.as_deref()
+    }
+
+    /// Set the path or URL of the image to display
+    pub fn set_image_source(&mut self, source: impl Into<String>) {
+        self.image_source = Some(source.into());
+    }
+
+    /// Stop displaying an image
+    pub fn clear_image_source(&mut self) {
+        self.image_source = None;
+    }
+
+    /// Where this node is laid out, if it is a portal
+    ///
+    /// A portal node stays in place in the node tree but is laid out under
+    /// its target.
+    pub fn portal_target(&self) -> Option<PortalTarget> {
+        self.portal_target
+    }
+
+    /// Lay this node out under `target` instead of under its parent
+    pub fn set_portal_target(&mut self, target: PortalTarget) {
+        self.portal_target = Some(target);
+    }
 }
 
 impl Default for Node {
@@ -127,6 +266,12 @@ impl Default for Node {
             children: Vec::new(),
             id,
             event_delegate: Some(Arc::new(Mutex::new(EventDelegate::new(Some(id))))),
+            layout_rect: None,
+            content_rect: None,
+            style: None,
+            text: None,
+            image_source: None,
+            portal_target: None,
         }
     }
 }
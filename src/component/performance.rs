@@ -8,7 +8,9 @@ use std::hash::Hash;
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
-use crate::component::{Component, ComponentError, ComponentId, Context, Node, StateChanges};
+use crate::component::{
+    AnyComponent, Component, ComponentError, ComponentId, Context, Node, StateChanges,
+};
 
 /// Trait for memoizable components
 pub trait Memoizable {
@@ -23,6 +25,62 @@ pub trait Memoizable {
     }
 }
 
+/// Memo key with its type erased
+pub(crate) type ErasedMemoKey = Box<dyn std::any::Any + Send + Sync>;
+
+/// Type-erased access to a component's [`Memoizable`] implementation
+///
+/// Built where the concrete component type is known, so the lifecycle
+/// manager can memoize renders of a `dyn AnyComponent`.
+#[derive(Clone, Copy)]
+pub(crate) struct MemoHook {
+    /// Current memo key, or `None` if the component is not the expected type
+    key: fn(&dyn AnyComponent) -> Option<ErasedMemoKey>,
+    /// `should_memo_update` for two keys produced by `key`
+    changed: fn(&dyn AnyComponent, &ErasedMemoKey, &ErasedMemoKey) -> bool,
+}
+
+impl MemoHook {
+    pub(crate) fn of<C>() -> Self
+    where
+        C: Component + Memoizable + 'static,
+        C::MemoKey: Send + Sync + 'static,
+    {
+        Self {
+            key: |component| {
+                let component = component.as_any().downcast_ref::<C>()?;
+                Some(Box::new(component.memo_key()))
+            },
+            changed: |component, old, new| {
+                let component = component.as_any().downcast_ref::<C>();
+                let old = old.downcast_ref::<C::MemoKey>();
+                let new = new.downcast_ref::<C::MemoKey>();
+                match (component, old, new) {
+                    (Some(component), Some(old), Some(new)) => {
+                        component.should_memo_update(old, new)
+                    }
+                    _ => true,
+                }
+            },
+        }
+    }
+
+    /// Current memo key of a component
+    pub(crate) fn key(&self, component: &dyn AnyComponent) -> Option<ErasedMemoKey> {
+        (self.key)(component)
+    }
+
+    /// Whether a component must re-render going from `old` to `new`
+    pub(crate) fn changed(
+        &self,
+        component: &dyn AnyComponent,
+        old: &ErasedMemoKey,
+        new: &ErasedMemoKey,
+    ) -> bool {
+        (self.changed)(component, old, new)
+    }
+}
+
 /// Memoization cache for component render results
 pub struct MemoCache<K, V> {
     cache: RwLock<HashMap<K, CacheEntry<V>>>,
@@ -213,8 +271,9 @@ impl PerformanceMonitor {
         }
     }
 
-    pub fn start_render_timing(&self, component_id: ComponentId) -> RenderTimer {
-        RenderTimer::new(component_id, Arc::new(self.clone()))
+    /// Start timing a render, recorded when the returned timer is dropped
+    pub fn start_render_timing(self: &Arc<Self>, component_id: ComponentId) -> RenderTimer {
+        RenderTimer::new(component_id, Arc::clone(self))
     }
 
     pub fn record_render_time(&self, component_id: ComponentId, duration: Duration) {
@@ -267,6 +326,33 @@ impl PerformanceMonitor {
         None
     }
 
+    pub fn get_average_update_time(&self, component_id: ComponentId) -> Option<Duration> {
+        let times = self.update_times.read().ok()?;
+        let component_times = times.get(&component_id)?;
+        if component_times.is_empty() {
+            return None;
+        }
+        let total: Duration = component_times.iter().sum();
+        Some(total / component_times.len() as u32)
+    }
+
+    pub fn get_mount_time(&self, component_id: ComponentId) -> Option<Duration> {
+        self.mount_times.read().ok()?.get(&component_id).copied()
+    }
+
+    /// Forget every timing recorded for a component
+    pub fn remove_component(&self, component_id: ComponentId) {
+        if let Ok(mut times) = self.render_times.write() {
+            times.remove(&component_id);
+        }
+        if let Ok(mut times) = self.update_times.write() {
+            times.remove(&component_id);
+        }
+        if let Ok(mut times) = self.mount_times.write() {
+            times.remove(&component_id);
+        }
+    }
+
     pub fn get_render_statistics(&self, component_id: ComponentId) -> RenderStatistics {
         if let Ok(times) = self.render_times.read() {
             if let Some(component_times) = times.get(&component_id) {
@@ -379,15 +465,40 @@ impl UpdateBatcher {
     pub fn should_flush(&self, component_id: ComponentId) -> bool {
         if let Ok(pending) = self.pending_updates.lock() {
             if let Some(updates) = pending.get(&component_id) {
-                return updates.len() >= self.max_batch_size
-                    || updates
-                        .first()
-                        .map(|u| u.batch_timestamp.elapsed() >= self.batch_timeout)
-                        .unwrap_or(false);
+                return self.is_due(updates, Instant::now());
             }
         }
         false
     }
+
+    /// Take every component's batch that is due at `now`
+    ///
+    /// A batch is due once it holds `max_batch_size` updates, once its oldest
+    /// update is `batch_timeout` old, or as soon as any update in it is
+    /// marked immediate. Batches that are not due stay queued.
+    pub fn tick(&self, now: Instant) -> HashMap<ComponentId, Vec<StateChanges>> {
+        let Ok(mut pending) = self.pending_updates.lock() else {
+            return HashMap::new();
+        };
+
+        let due: Vec<ComponentId> = pending
+            .iter()
+            .filter(|(_, updates)| self.is_due(updates, now))
+            .map(|(id, _)| *id)
+            .collect();
+        due.into_iter()
+            .filter_map(|id| pending.remove_entry(&id))
+            .collect()
+    }
+
+    /// Whether a component's queued updates should be flushed at `now`
+    fn is_due(&self, updates: &[StateChanges], now: Instant) -> bool {
+        updates.len() >= self.max_batch_size
+            || updates.iter().any(|update| update.immediate)
+            || updates.first().is_some_and(|update| {
+                now.saturating_duration_since(update.batch_timestamp) >= self.batch_timeout
+            })
+    }
 }
 
 /// Lazy loading component wrapper
@@ -559,7 +670,7 @@ macro_rules! lazy {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::component::ComponentBase;
+    use crate::component::{ComponentBase, ComponentInstance, LifecycleManager};
 
     #[derive(Clone, Hash, PartialEq, Eq)]
     struct TestMemoKey {
@@ -577,6 +688,7 @@ mod tests {
     struct TestComponent {
         base: ComponentBase,
         props: TestProps,
+        renders: Arc<std::sync::atomic::AtomicUsize>,
     }
 
     impl Component for TestComponent {
@@ -590,6 +702,7 @@ mod tests {
             Self {
                 base: ComponentBase::new(context),
                 props,
+                renders: Arc::default(),
             }
         }
 
@@ -599,7 +712,9 @@ mod tests {
         }
 
         fn render(&self) -> Result<Vec<Node>, ComponentError> {
-            Ok(vec![])
+            self.renders
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![Node::default()])
         }
 
         fn as_any(&self) -> &dyn std::any::Any {
@@ -663,6 +778,47 @@ mod tests {
         assert!(updates.contains_key(&component_id));
     }
 
+    fn changes_at(batch_timestamp: Instant) -> StateChanges {
+        StateChanges {
+            changes: vec![],
+            batch_timestamp,
+            immediate: false,
+        }
+    }
+
+    #[test]
+    fn test_update_batcher_tick_flushes_after_timeout() {
+        let batcher = UpdateBatcher::new(Duration::from_millis(100), 5);
+        let component_id = ComponentId::new();
+        let start = Instant::now();
+
+        batcher.queue_update(component_id, changes_at(start));
+        batcher.queue_update(component_id, changes_at(start + Duration::from_millis(20)));
+
+        assert!(batcher.tick(start + Duration::from_millis(50)).is_empty());
+
+        let flushed = batcher.tick(start + Duration::from_millis(100));
+        assert_eq!(flushed[&component_id].len(), 2);
+        assert!(batcher.tick(start + Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn test_update_batcher_tick_flushes_at_size_cap() {
+        let batcher = UpdateBatcher::new(Duration::from_secs(60), 3);
+        let (full, partial) = (ComponentId::new(), ComponentId::new());
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            batcher.queue_update(full, changes_at(start));
+        }
+        batcher.queue_update(partial, changes_at(start));
+
+        let flushed = batcher.tick(start);
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[&full].len(), 3);
+        assert!(!batcher.should_flush(partial));
+    }
+
     #[test]
     fn test_memoized_component() {
         let context = Context::new();
@@ -672,4 +828,44 @@ mod tests {
 
         assert!(memo_component.render().is_ok());
     }
+
+    #[test]
+    fn test_lifecycle_render_reuses_nodes_for_unchanged_memo_key() {
+        let context = Context::new();
+        let props = TestProps { id: 1, version: 1 };
+        let component = TestComponent::create(props.clone(), context.clone());
+        let renders = component.renders.clone();
+        let instance = ComponentInstance::new_memoized(component, props);
+        let mut manager = LifecycleManager::new(instance, context);
+        manager.mount().unwrap();
+
+        let first = manager.render_with_memo().unwrap();
+        assert!(!first.cache_hit);
+        let second = manager.render_with_memo().unwrap();
+        assert!(second.cache_hit);
+        assert_eq!(second.nodes.len(), 1);
+        assert_eq!(renders.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // A new version changes the memo key
+        manager
+            .update(Box::new(TestProps { id: 1, version: 2 }))
+            .unwrap();
+        let third = manager.render_with_memo().unwrap();
+        assert!(!third.cache_hit);
+        assert_eq!(renders.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_lifecycle_render_without_memo_always_renders() {
+        let context = Context::new();
+        let props = TestProps { id: 1, version: 1 };
+        let component = TestComponent::create(props.clone(), context.clone());
+        let renders = component.renders.clone();
+        let mut manager = LifecycleManager::new(ComponentInstance::new(component, props), context);
+        manager.mount().unwrap();
+
+        assert!(!manager.render_with_memo().unwrap().cache_hit);
+        assert!(!manager.render_with_memo().unwrap().cache_hit);
+        assert_eq!(renders.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
 }
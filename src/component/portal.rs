@@ -0,0 +1,90 @@
+//! Portals render a subtree somewhere else in the layout tree
+//!
+//! Tooltips and modals belong to the component that opens them but must be
+//! laid out outside its box and clip. A [`Portal`] stays a logical child of
+//! the component rendering it, so lifecycle and bubbling events follow the
+//! component tree as usual, while its content is laid out under the
+//! portal's [`PortalTarget`].
+
+use crate::component::{Component, ComponentError, ComponentId, ComponentInstance, Context, Node};
+
+/// Where a portal's content is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PortalTarget {
+    /// A layer covering the whole viewport above the root component
+    #[default]
+    Overlay,
+    /// The subtree of a rendered component, falling back to the overlay
+    /// while that component isn't rendered
+    Component(ComponentId),
+}
+
+/// Portal props
+#[derive(Debug, Clone, Default)]
+pub struct PortalProps {
+    /// Where the content is laid out
+    pub target: PortalTarget,
+    /// Content rendered through the portal
+    pub children: Vec<Node>,
+}
+
+/// Renders its children under another part of the layout tree
+#[derive(Debug)]
+pub struct Portal {
+    id: ComponentId,
+    target: PortalTarget,
+    children: Vec<Node>,
+}
+
+impl Portal {
+    /// Create the node for a portal, to return from a component's `render`
+    ///
+    /// The node is marked with the portal's target, which is how layout
+    /// knows to move it.
+    pub fn node(props: PortalProps, context: Context) -> Node {
+        let target = props.target;
+        let portal = Self::create(props.clone(), context);
+        let mut node = Node::new(Some(ComponentInstance::new(portal, props)));
+        node.set_portal_target(target);
+        node
+    }
+
+    /// Where the content is laid out
+    pub fn target(&self) -> PortalTarget {
+        self.target
+    }
+}
+
+impl Component for Portal {
+    type Props = PortalProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, _context: Context) -> Self {
+        Self {
+            id: ComponentId::new(),
+            target: props.target,
+            children: props.children,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.target = props.target;
+        self.children = props.children;
+        Ok(())
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        Ok(self.children.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,171 @@
+//! Imperative handles to mounted component instances
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::component::{AnyComponent, Component};
+
+/// Shared storage behind a component instance
+type SharedInstance = Arc<Mutex<Box<dyn AnyComponent>>>;
+
+/// Weak handle to the storage behind a component instance
+type WeakInstance = Weak<Mutex<Box<dyn AnyComponent>>>;
+
+/// A handle a parent holds to call methods on a child component
+///
+/// Attach it to the child with [`ComponentInstance::with_ref`]. The ref is
+/// filled when the child mounts and cleared when it starts to unmount. It
+/// only holds a weak handle, so it never keeps the child alive.
+///
+/// [`ComponentInstance::with_ref`]: crate::component::ComponentInstance::with_ref
+pub struct Ref<T: Component> {
+    slot: Arc<Mutex<Option<WeakInstance>>>,
+    _component: PhantomData<fn() -> T>,
+}
+
+impl<T: Component> Ref<T> {
+    /// Create an empty ref
+    pub fn new() -> Self {
+        Self {
+            slot: Arc::new(Mutex::new(None)),
+            _component: PhantomData,
+        }
+    }
+
+    /// Run `f` on the child, if it is mounted
+    ///
+    /// Returns `None` when the child is not mounted, has been dropped, or is
+    /// not a `T`.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let instance = self.slot.lock().ok()?.as_ref()?.upgrade()?;
+        let mut component = instance.lock().ok()?;
+        component.as_any_mut().downcast_mut::<T>().map(f)
+    }
+
+    /// Whether the ref currently points at a mounted child
+    pub fn is_attached(&self) -> bool {
+        self.slot
+            .lock()
+            .ok()
+            .and_then(|slot| slot.as_ref().map(|weak| weak.strong_count() > 0))
+            .unwrap_or(false)
+    }
+}
+
+impl<T: Component> Clone for Ref<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: self.slot.clone(),
+            _component: PhantomData,
+        }
+    }
+}
+
+impl<T: Component> Default for Ref<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Component> fmt::Debug for Ref<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ref")
+            .field("component", &std::any::type_name::<T>())
+            .field("attached", &self.is_attached())
+            .finish()
+    }
+}
+
+/// Type-erased ref, filled and cleared by the lifecycle manager
+pub(crate) trait RefSlot: Send + Sync {
+    /// Point the ref at a mounted instance
+    fn attach(&self, instance: &SharedInstance);
+    /// Clear the ref
+    fn detach(&self);
+}
+
+impl<T: Component> RefSlot for Ref<T> {
+    fn attach(&self, instance: &SharedInstance) {
+        if let Ok(mut slot) = self.slot.lock() {
+            *slot = Some(Arc::downgrade(instance));
+        }
+    }
+
+    fn detach(&self) {
+        if let Ok(mut slot) = self.slot.lock() {
+            *slot = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{ComponentError, ComponentId, ComponentInstance, ComponentTree};
+    use crate::component::{Context, Node};
+
+    struct TextInput {
+        id: ComponentId,
+        focused: bool,
+    }
+
+    impl TextInput {
+        fn focus(&mut self) {
+            self.focused = true;
+        }
+    }
+
+    impl Component for TextInput {
+        type Props = ();
+
+        fn component_id(&self) -> ComponentId {
+            self.id
+        }
+
+        fn create(_props: Self::Props, _context: Context) -> Self {
+            Self {
+                id: ComponentId::new(),
+                focused: false,
+            }
+        }
+
+        fn update(&mut self, _props: Self::Props) -> Result<(), ComponentError> {
+            Ok(())
+        }
+
+        fn render(&self) -> Result<Vec<Node>, ComponentError> {
+            Ok(vec![])
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_ref_reaches_mounted_child_and_clears_on_unmount() {
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let input_ref: Ref<TextInput> = Ref::new();
+
+        let input = TextInput::create((), context);
+        let id = tree
+            .add_component(ComponentInstance::new(input, ()).with_ref(&input_ref))
+            .unwrap();
+        assert_eq!(input_ref.with(|input| input.focus()), None);
+
+        tree.mount_component_tree(id).unwrap();
+        assert!(input_ref.is_attached());
+        assert_eq!(input_ref.with(|input| input.focus()), Some(()));
+        assert_eq!(input_ref.with(|input| input.focused), Some(true));
+
+        tree.unmount_component(id).unwrap();
+        assert!(!input_ref.is_attached());
+        assert_eq!(input_ref.with(|input| input.focused), None);
+    }
+}
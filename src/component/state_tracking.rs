@@ -3,10 +3,11 @@
 //! This module provides efficient state change detection, dirty checking,
 //! and state diff computation for optimized component updates.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use crate::component::{ComponentError, ComponentId};
+use crate::component::{ComponentError, ComponentId, UpdateBatcher};
 
 /// Represents a snapshot of component state at a point in time
 #[derive(Debug, Clone)]
@@ -66,6 +67,9 @@ pub struct StateChanges {
     pub immediate: bool,
 }
 
+/// Callback notified of committed snapshots
+type SnapshotListener = Box<dyn FnMut(&StateSnapshot) + Send>;
+
 /// Tracks state changes for a component with dirty checking optimization
 pub struct StateTracker {
     /// ID of the component being tracked
@@ -80,6 +84,8 @@ pub struct StateTracker {
     dirty_fields: HashMap<String, bool>,
     /// Configuration for change detection
     config: StateTrackingConfig,
+    /// Called with each snapshot that changed the state
+    listeners: Vec<SnapshotListener>,
 }
 
 /// Configuration options for state tracking
@@ -226,6 +232,7 @@ impl StateTracker {
             change_batch: Vec::new(),
             dirty_fields: HashMap::new(),
             config,
+            listeners: Vec::new(),
         }
     }
 
@@ -267,6 +274,12 @@ impl StateTracker {
                 .collect()
         };
 
+        if !changes.is_empty() {
+            for listener in &mut self.listeners {
+                listener(&new_snapshot);
+            }
+        }
+
         // Update state snapshots
         self.previous_state = self.current_state.take();
         self.current_state = Some(new_snapshot); // Add changes to batch
@@ -283,6 +296,32 @@ impl StateTracker {
         }
     }
 
+    /// Call `listener` with every new snapshot that changes the state
+    ///
+    /// Snapshots dropped by the throttle or identical to the current state
+    /// are not reported, and neither are restored ones.
+    pub fn subscribe(&mut self, listener: impl FnMut(&StateSnapshot) + Send + 'static) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Make an earlier snapshot current again, bypassing the throttle
+    ///
+    /// Returns the changes from the current state, marked immediate, with
+    /// their fields flagged dirty.
+    pub fn restore(&mut self, snapshot: &StateSnapshot) -> StateChanges {
+        let restored = StateSnapshot::new(snapshot.fields.clone());
+        let changes = match &self.current_state {
+            Some(current) => current.diff(&restored),
+            None => StateSnapshot::new(HashMap::new()).diff(&restored),
+        };
+        for change in &changes {
+            self.dirty_fields.insert(change.field_name.clone(), true);
+        }
+
+        self.previous_state = self.current_state.replace(restored);
+        StateChanges::new(changes, true)
+    }
+
     /// Check if a specific field is dirty
     pub fn is_field_dirty(&self, field_name: &str) -> bool {
         self.dirty_fields.get(field_name).copied().unwrap_or(false)
@@ -410,7 +449,144 @@ impl StateChanges {
 
     /// Sort changes by priority
     pub fn sort_by_priority(&mut self) {
-        self.changes.sort_by(|a, b| b.priority.cmp(&a.priority));
+        self.changes
+            .sort_by_key(|change| std::cmp::Reverse(change.priority));
+    }
+}
+
+/// Recorded snapshots and the position of the one currently applied
+struct HistoryBuffer {
+    snapshots: VecDeque<StateSnapshot>,
+    capacity: usize,
+    cursor: usize,
+}
+
+/// Bounded history of a component's state for time-travel debugging
+///
+/// The history records every snapshot its tracker commits. Stepping back and
+/// forth restores a recorded snapshot into the tracker and queues the changes
+/// on an [`UpdateBatcher`], so the component receives them through
+/// `on_update` on the next tick. Recording a new change after stepping back
+/// discards the snapshots that could have been redone.
+#[derive(Clone)]
+pub struct StateHistory {
+    component_id: ComponentId,
+    buffer: Arc<Mutex<HistoryBuffer>>,
+}
+
+impl StateHistory {
+    /// Start recording a tracker's snapshots, keeping at most `capacity`
+    pub fn record(tracker: &mut StateTracker, capacity: usize) -> Self {
+        let buffer = Arc::new(Mutex::new(HistoryBuffer {
+            snapshots: VecDeque::new(),
+            capacity: capacity.max(1),
+            cursor: 0,
+        }));
+
+        let sink = Arc::clone(&buffer);
+        tracker.subscribe(move |snapshot| {
+            let Ok(mut buffer) = sink.lock() else {
+                return;
+            };
+            if !buffer.snapshots.is_empty() {
+                let redo_start = buffer.cursor + 1;
+                buffer.snapshots.truncate(redo_start);
+            }
+            buffer.snapshots.push_back(snapshot.clone());
+            if buffer.snapshots.len() > buffer.capacity {
+                buffer.snapshots.pop_front();
+            }
+            buffer.cursor = buffer.snapshots.len() - 1;
+        });
+
+        Self {
+            component_id: tracker.component_id(),
+            buffer,
+        }
+    }
+
+    /// Number of recorded snapshots
+    pub fn len(&self) -> usize {
+        self.buffer.lock().map(|b| b.snapshots.len()).unwrap_or(0)
+    }
+
+    /// Whether nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Index of the snapshot currently applied, if any were recorded
+    pub fn cursor(&self) -> Option<usize> {
+        let buffer = self.buffer.lock().ok()?;
+        (!buffer.snapshots.is_empty()).then_some(buffer.cursor)
+    }
+
+    /// Get a recorded snapshot, oldest first
+    pub fn snapshot(&self, index: usize) -> Option<StateSnapshot> {
+        self.buffer.lock().ok()?.snapshots.get(index).cloned()
+    }
+
+    /// Whether there is an earlier snapshot to step back to
+    pub fn can_undo(&self) -> bool {
+        self.cursor().is_some_and(|cursor| cursor > 0)
+    }
+
+    /// Whether there is a later snapshot to step forward to
+    pub fn can_redo(&self) -> bool {
+        self.cursor().is_some_and(|cursor| cursor + 1 < self.len())
+    }
+
+    /// Step back one snapshot, returning the snapshot restored
+    pub fn undo(
+        &self,
+        tracker: &mut StateTracker,
+        batcher: &UpdateBatcher,
+    ) -> Option<StateSnapshot> {
+        let index = self.cursor()?.checked_sub(1)?;
+        self.jump_to(index, tracker, batcher)
+    }
+
+    /// Step forward one snapshot, returning the snapshot restored
+    pub fn redo(
+        &self,
+        tracker: &mut StateTracker,
+        batcher: &UpdateBatcher,
+    ) -> Option<StateSnapshot> {
+        let index = self.cursor()? + 1;
+        self.jump_to(index, tracker, batcher)
+    }
+
+    /// Restore the snapshot at `index` and queue an update for the component
+    ///
+    /// Returns `None` without changing anything if there is no such snapshot.
+    pub fn jump_to(
+        &self,
+        index: usize,
+        tracker: &mut StateTracker,
+        batcher: &UpdateBatcher,
+    ) -> Option<StateSnapshot> {
+        let snapshot = {
+            let mut buffer = self.buffer.lock().ok()?;
+            let snapshot = buffer.snapshots.get(index)?.clone();
+            buffer.cursor = index;
+            snapshot
+        };
+
+        let changes = tracker.restore(&snapshot);
+        if !changes.is_empty() {
+            batcher.queue_update(self.component_id, changes);
+        }
+        Some(snapshot)
+    }
+}
+
+impl std::fmt::Debug for StateHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateHistory")
+            .field("component_id", &self.component_id)
+            .field("len", &self.len())
+            .field("cursor", &self.cursor())
+            .finish()
     }
 }
 
@@ -500,4 +676,74 @@ mod tests {
         let changes = changes2.unwrap();
         assert_eq!(changes.changes.len(), 2);
     }
+
+    #[test]
+    fn test_history_undo_and_redo() {
+        let component_id = ComponentId::new();
+        let mut tracker = StateTracker::new(
+            component_id,
+            StateTrackingConfig {
+                snapshot_throttle: Duration::ZERO,
+                ..Default::default()
+            },
+        );
+        let history = StateHistory::record(&mut tracker, 10);
+        let batcher = UpdateBatcher::new(Duration::from_millis(16), 10);
+        let count = |snapshot: Option<&StateSnapshot>| snapshot.unwrap().fields["count"].clone();
+
+        for value in 1..=3 {
+            let fields = HashMap::from([("count".to_string(), StateValue::Integer(value))]);
+            tracker.update_state(fields).unwrap();
+        }
+        assert_eq!((history.len(), history.cursor()), (3, Some(2)));
+
+        history.undo(&mut tracker, &batcher).unwrap();
+        let restored = history.undo(&mut tracker, &batcher).unwrap();
+        assert_eq!(count(Some(&restored)), StateValue::Integer(1));
+        assert_eq!(count(tracker.current_snapshot()), StateValue::Integer(1));
+        assert!(tracker.is_field_dirty("count"));
+        assert!(!history.can_undo());
+
+        // Each restore queued an update for the component, without being
+        // recorded as a new change
+        assert_eq!(batcher.flush_updates()[&component_id].len(), 2);
+        assert_eq!(history.len(), 3);
+
+        history.redo(&mut tracker, &batcher).unwrap();
+        history.redo(&mut tracker, &batcher).unwrap();
+        assert_eq!(count(tracker.current_snapshot()), StateValue::Integer(3));
+        assert!(!history.can_redo());
+        assert!(history.redo(&mut tracker, &batcher).is_none());
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_drops_redo_on_new_change() {
+        let mut tracker = StateTracker::new(
+            ComponentId::new(),
+            StateTrackingConfig {
+                snapshot_throttle: Duration::ZERO,
+                ..Default::default()
+            },
+        );
+        let history = StateHistory::record(&mut tracker, 2);
+        let batcher = UpdateBatcher::new(Duration::from_millis(16), 10);
+        let set = |tracker: &mut StateTracker, value| {
+            let fields = HashMap::from([("count".to_string(), StateValue::Integer(value))]);
+            tracker.update_state(fields).unwrap();
+        };
+
+        for value in 1..=3 {
+            set(&mut tracker, value);
+        }
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history.snapshot(0).unwrap().fields["count"],
+            StateValue::Integer(2)
+        );
+
+        history.undo(&mut tracker, &batcher).unwrap();
+        set(&mut tracker, 10);
+        assert_eq!((history.len(), history.cursor()), (2, Some(1)));
+        assert!(!history.can_redo());
+    }
 }
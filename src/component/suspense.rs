@@ -0,0 +1,325 @@
+//! Suspense boundaries show a fallback while async data loads
+//!
+//! A [`Resource`] is a value produced by a future spawned on an executor,
+//! the same kind of [`Spawn`] driver that async effects run on. A
+//! [`Suspense`] boundary is given the resources its content depends on and
+//! renders its fallback until every one of them has resolved, then
+//! re-renders with the content.
+
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use futures::task::{Spawn, SpawnExt};
+
+use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+
+type ReadyListener = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct StatusInner {
+    ready: bool,
+    /// Called once when the resource resolves
+    listeners: Vec<ReadyListener>,
+}
+
+/// Whether a resource has resolved, without its value
+///
+/// Lets a boundary wait on resources of different types.
+#[derive(Clone, Default)]
+pub struct ResourceStatus {
+    inner: Arc<Mutex<StatusInner>>,
+}
+
+impl ResourceStatus {
+    /// Whether the resource has resolved
+    pub fn is_ready(&self) -> bool {
+        self.inner.lock().unwrap().ready
+    }
+
+    /// Call `listener` once the resource resolves, or now if it already has
+    pub fn on_ready(&self, listener: impl FnOnce() + Send + 'static) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.ready {
+            drop(inner);
+            listener();
+        } else {
+            inner.listeners.push(Box::new(listener));
+        }
+    }
+
+    /// Whether `other` reports on the same resource
+    fn is_same(&self, other: &ResourceStatus) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    fn resolve(&self) {
+        let listeners = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.ready = true;
+            std::mem::take(&mut inner.listeners)
+        };
+        for listener in listeners {
+            listener();
+        }
+    }
+}
+
+impl fmt::Debug for ResourceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceStatus")
+            .field("ready", &self.is_ready())
+            .finish()
+    }
+}
+
+/// A value that becomes available when a future resolves
+#[derive(Clone)]
+pub struct Resource<T> {
+    value: Arc<Mutex<Option<T>>>,
+    status: ResourceStatus,
+}
+
+impl<T: Clone + Send + 'static> Resource<T> {
+    /// Spawn `future` on `spawner` and hold its output once it resolves
+    ///
+    /// The executor must be driven for the resource to make progress. If
+    /// the future can't be spawned the resource stays pending.
+    pub fn new<S, F>(spawner: &S, future: F) -> Self
+    where
+        S: Spawn + ?Sized,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let resource = Self::pending();
+        let (value, status) = (resource.value.clone(), resource.status.clone());
+        if let Err(e) = spawner.spawn(async move {
+            let output = future.await;
+            *value.lock().unwrap() = Some(output);
+            status.resolve();
+        }) {
+            crate::trace::warn_event!("Failed to spawn resource: {e}");
+        }
+        resource
+    }
+
+    /// A resource that has already resolved
+    pub fn ready(value: T) -> Self {
+        let resource = Self::pending();
+        *resource.value.lock().unwrap() = Some(value);
+        resource.status.resolve();
+        resource
+    }
+
+    fn pending() -> Self {
+        Self {
+            value: Arc::new(Mutex::new(None)),
+            status: ResourceStatus::default(),
+        }
+    }
+
+    /// The value, once resolved
+    pub fn get(&self) -> Option<T> {
+        self.value.lock().unwrap().clone()
+    }
+
+    /// Whether the future has resolved
+    pub fn is_ready(&self) -> bool {
+        self.status.is_ready()
+    }
+
+    /// Readiness of this resource, to hand to a [`Suspense`] boundary
+    pub fn status(&self) -> ResourceStatus {
+        self.status.clone()
+    }
+}
+
+impl<T> fmt::Debug for Resource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resource")
+            .field("ready", &self.status.is_ready())
+            .finish()
+    }
+}
+
+/// Suspense props
+#[derive(Debug, Clone, Default)]
+pub struct SuspenseProps {
+    /// Shown until every resource has resolved
+    pub fallback: Vec<Node>,
+    /// Content shown once every resource has resolved
+    pub children: Vec<Node>,
+    /// Resources the content waits for
+    pub resources: Vec<ResourceStatus>,
+}
+
+/// Renders a fallback until the resources its content needs have resolved
+///
+/// Several async children under one boundary are revealed together: the
+/// fallback stays until the last of them resolves. Each resolution
+/// schedules an update of the boundary through its context.
+#[derive(Debug)]
+pub struct Suspense {
+    id: ComponentId,
+    context: Context,
+    fallback: Vec<Node>,
+    children: Vec<Node>,
+    resources: Vec<ResourceStatus>,
+    /// Pending resources that already re-render the boundary on resolving
+    watched: Vec<ResourceStatus>,
+}
+
+impl Suspense {
+    /// Whether any resource is still pending
+    pub fn is_suspended(&self) -> bool {
+        !self.resources.iter().all(ResourceStatus::is_ready)
+    }
+
+    /// Re-render when each pending resource resolves
+    ///
+    /// A resource is only subscribed to once, however many updates pass
+    /// it in again.
+    fn watch(&mut self) {
+        self.watched.retain(|status| !status.is_ready());
+        for status in &self.resources {
+            if status.is_ready() || self.watched.iter().any(|w| w.is_same(status)) {
+                continue;
+            }
+            let (context, id) = (self.context.clone(), self.id);
+            status.on_ready(move || context.schedule_update(id));
+            self.watched.push(status.clone());
+        }
+    }
+}
+
+impl Component for Suspense {
+    type Props = SuspenseProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, context: Context) -> Self {
+        let mut suspense = Self {
+            id: ComponentId::new(),
+            context,
+            fallback: props.fallback,
+            children: props.children,
+            resources: props.resources,
+            watched: Vec::new(),
+        };
+        suspense.watch();
+        suspense
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.fallback = props.fallback;
+        self.children = props.children;
+        self.resources = props.resources;
+        self.watch();
+        Ok(())
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        if self.is_suspended() {
+            Ok(self.fallback.clone())
+        } else {
+            Ok(self.children.clone())
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::channel::oneshot;
+    use futures::executor::LocalPool;
+
+    fn text_node(text: &str) -> Node {
+        let mut node = Node::default();
+        node.set_text(text);
+        node
+    }
+
+    fn rendered_text(suspense: &Suspense) -> Vec<String> {
+        let nodes = suspense.render().unwrap();
+        nodes
+            .iter()
+            .map(|node| node.text().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_fallback_until_every_resource_resolves() {
+        let mut pool = LocalPool::new();
+        let spawner = pool.spawner();
+        let (send_user, user) = oneshot::channel::<&str>();
+        let (send_posts, posts) = oneshot::channel::<u32>();
+        let user = Resource::new(&spawner, async move { user.await.unwrap() });
+        let posts = Resource::new(&spawner, async move { posts.await.unwrap() });
+
+        let context = Context::new();
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let sink = updates.clone();
+        context.set_update_handler(move |id| sink.lock().unwrap().push(id));
+
+        let props = SuspenseProps {
+            fallback: vec![text_node("Loading")],
+            children: vec![text_node("Profile"), text_node("Posts")],
+            resources: vec![user.status(), posts.status()],
+        };
+        let suspense = Suspense::create(props, context);
+        pool.run_until_stalled();
+        assert!(suspense.is_suspended());
+        assert_eq!(rendered_text(&suspense), vec!["Loading"]);
+
+        // One of two resolving is not enough to show the content
+        send_user.send("ada").unwrap();
+        pool.run_until_stalled();
+        assert_eq!(user.get(), Some("ada"));
+        assert_eq!(rendered_text(&suspense), vec!["Loading"]);
+
+        send_posts.send(3).unwrap();
+        pool.run_until_stalled();
+        assert!(!suspense.is_suspended());
+        assert_eq!(rendered_text(&suspense), vec!["Profile", "Posts"]);
+        assert_eq!(
+            *updates.lock().unwrap(),
+            vec![suspense.component_id(), suspense.component_id()]
+        );
+    }
+
+    #[test]
+    fn test_updates_subscribe_to_each_resource_once() {
+        let mut pool = LocalPool::new();
+        let (send, value) = oneshot::channel::<u32>();
+        let resource = Resource::new(&pool.spawner(), async move { value.await.unwrap() });
+
+        let context = Context::new();
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let sink = updates.clone();
+        context.set_update_handler(move |id| sink.lock().unwrap().push(id));
+
+        let props = SuspenseProps {
+            fallback: vec![text_node("Loading")],
+            children: vec![text_node("Done")],
+            resources: vec![resource.status()],
+        };
+        let mut suspense = Suspense::create(props.clone(), context);
+        for _ in 0..3 {
+            suspense.update(props.clone()).unwrap();
+        }
+
+        send.send(1).unwrap();
+        pool.run_until_stalled();
+        assert_eq!(*updates.lock().unwrap(), vec![suspense.component_id()]);
+        assert_eq!(rendered_text(&suspense), vec!["Done"]);
+    }
+}
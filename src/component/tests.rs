@@ -1,10 +1,10 @@
 //! Tests for component lifecycle management
 
 use crate::component::{
-    Component, ComponentError, ComponentId, ComponentInstance, Context, LifecycleManager,
-    MountContext, Node, UnmountContext, UnmountReason,
+    props::PropValidationError, Component, ComponentError, ComponentId, ComponentInstance, Context,
+    LifecycleManager, MountContext, Node, UnmountContext, UnmountReason,
 };
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 // A simple test component with props
@@ -71,6 +71,16 @@ impl Component for TestComponent {
         Ok(())
     }
 
+    fn validate_props(&self, props: &Self::Props) -> Result<(), PropValidationError> {
+        if props.message.is_empty() {
+            return Err(PropValidationError::InvalidValue {
+                name: "message".to_string(),
+                reason: "must not be empty".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     fn before_update(&mut self, new_props: &Self::Props) -> Result<(), ComponentError> {
         self.state
             .before_update_called
@@ -217,6 +227,51 @@ fn test_component_lifecycle() {
     }
 }
 
+#[test]
+fn test_invalid_props_abort_update() {
+    let props = TestProps {
+        message: "Hello".to_string(),
+    };
+    let context = Context::new();
+    let component = TestComponent::create(props.clone(), context.clone());
+    let instance = ComponentInstance::new(component, props);
+    let mut lifecycle_manager = LifecycleManager::new(instance, context);
+    lifecycle_manager.initialize().unwrap();
+    lifecycle_manager.mount().unwrap();
+
+    let result = lifecycle_manager.update(Box::new(TestProps {
+        message: String::new(),
+    }));
+    assert!(matches!(
+        result,
+        Err(ComponentError::PropValidation(PropValidationError::InvalidValue { ref name, .. }))
+            if name == "message"
+    ));
+
+    // Nothing ran and nothing changed
+    assert_eq!(
+        lifecycle_manager.current_phase(),
+        crate::component::LifecyclePhase::Mounted
+    );
+    {
+        let instance = lifecycle_manager.get_component().lock().unwrap();
+        let stored = instance.props.as_any().downcast_ref::<TestProps>().unwrap();
+        assert_eq!(stored.message, "Hello");
+        let component = instance.instance.lock().unwrap();
+        let component = component.as_any().downcast_ref::<TestComponent>().unwrap();
+        assert!(!component.state.before_update_called.load(Ordering::SeqCst));
+        assert!(!component.is_updated());
+        assert_eq!(component.props.lock().unwrap().message, "Hello");
+    }
+
+    // Valid props still go through afterwards
+    lifecycle_manager
+        .update(Box::new(TestProps {
+            message: "Updated".to_string(),
+        }))
+        .expect("valid update should succeed");
+}
+
 // Additional tests for enhanced component system
 #[cfg(test)]
 mod enhanced_tests {
@@ -270,6 +325,47 @@ mod enhanced_tests {
         assert!(!scheduler.has_pending_update(component_id));
     }
 
+    #[test]
+    fn test_scheduling_a_dependency_enqueues_its_dependents() {
+        let mut scheduler = UpdateScheduler::default();
+        let grandparent = ComponentId::new();
+        let parent = ComponentId::new();
+        let child = ComponentId::new();
+        let sibling = ComponentId::new();
+        scheduler.add_dependency(parent, child);
+        scheduler.add_dependency(grandparent, parent);
+        // A cycle must not loop forever
+        scheduler.add_dependency(child, grandparent);
+
+        scheduler.schedule_update(child);
+        for id in [child, parent, grandparent] {
+            assert!(scheduler.has_pending_update(id));
+        }
+        assert!(!scheduler.has_pending_update(sibling));
+
+        // Without the dependency only the child is scheduled
+        for id in [child, parent, grandparent] {
+            scheduler.clear_pending(id);
+        }
+        scheduler.remove_dependency(parent, child);
+        scheduler.schedule_update(child);
+        assert_eq!(scheduler.get_pending_components(), vec![child]);
+    }
+
+    #[test]
+    fn test_context_flushes_dependents_with_their_dependency() {
+        let context = Context::new();
+        let parent = ComponentId::new();
+        let child = ComponentId::new();
+        let flushed = recording_handler(&context);
+        context.add_render_dependency(parent, child);
+
+        context.schedule_update(child);
+        let mut flushed = flushed.lock().unwrap().clone();
+        flushed.sort_by_key(|id| id.id());
+        assert_eq!(flushed, vec![parent, child]);
+    }
+
     #[test]
     fn test_context_reactive_state() {
         let context = Context::new();
@@ -283,6 +379,75 @@ mod enhanced_tests {
         assert!(context.has_pending_update(component_id));
     }
 
+    /// Record every flushed component in a shared list
+    fn recording_handler(context: &Context) -> Arc<Mutex<Vec<ComponentId>>> {
+        let flushed = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&flushed);
+        context.set_update_handler(move |id| sink.lock().unwrap().push(id));
+        flushed
+    }
+
+    #[test]
+    fn test_batch_flushes_each_component_once() {
+        let context = Context::new();
+        let component_id = ComponentId::new();
+        let flushed = recording_handler(&context);
+        let state = context.create_reactive_state(0, component_id);
+
+        context.batch(|| {
+            for i in 1..=10 {
+                state.set(i);
+            }
+            // Nothing is flushed until the batch closes
+            assert!(flushed.lock().unwrap().is_empty());
+        });
+
+        assert_eq!(*flushed.lock().unwrap(), vec![component_id]);
+        assert!(!context.has_pending_update(component_id));
+        assert_eq!(state.get(), 10);
+    }
+
+    #[test]
+    fn test_nested_batches_coalesce() {
+        let context = Context::new();
+        let first = ComponentId::new();
+        let second = ComponentId::new();
+        let flushed = recording_handler(&context);
+
+        let value = context.batch(|| {
+            context.schedule_update(first);
+            context.batch(|| {
+                context.schedule_update(first);
+                context.schedule_update(second);
+            });
+            // The inner batch defers to the outer one
+            assert!(flushed.lock().unwrap().is_empty());
+            7
+        });
+
+        assert_eq!(value, 7);
+        let mut flushed = flushed.lock().unwrap().clone();
+        flushed.sort_by_key(|id| id.id());
+        assert_eq!(flushed, vec![first, second]);
+    }
+
+    #[test]
+    fn test_updates_flush_immediately_outside_batch() {
+        let context = Context::new();
+        let component_id = ComponentId::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&count);
+        context.set_update_handler(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let state = context.create_reactive_state(0, component_id);
+        state.set(1);
+        state.set(2);
+
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_context_provider_basic() {
         let provider = crate::component::ContextProvider::new();
@@ -314,6 +479,26 @@ mod enhanced_tests {
         assert_eq!(child.consume::<String>(), Some("child_value".to_string()));
     }
 
+    #[test]
+    fn test_context_inject_walks_up_to_nearest_provider() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Locale(&'static str);
+
+        let root = Context::new();
+        root.provide(Locale("en"));
+        let child = root.child();
+        let grandchild = child.child();
+
+        assert_eq!(grandchild.inject::<Locale>(), Some(Locale("en")));
+        assert_eq!(grandchild.inject::<i32>(), None);
+
+        // A nearer provider shadows the root for its own subtree only
+        child.provide(Locale("fr"));
+        assert_eq!(grandchild.inject::<Locale>(), Some(Locale("fr")));
+        assert_eq!(root.inject::<Locale>(), Some(Locale("en")));
+        assert_eq!(root.child().inject::<Locale>(), Some(Locale("en")));
+    }
+
     // Test struct for Props trait
     #[derive(Debug, Clone, PartialEq)]
     struct TestNewProps {
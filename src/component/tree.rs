@@ -4,11 +4,15 @@
 //! parent-child relationships, efficient updates, and lifecycle coordination.
 
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::time::{Duration, Instant};
 
-#[cfg(test)]
-use crate::component::LifecyclePhase;
-use crate::component::{ComponentId, ComponentInstance, Context, LifecycleManager, Node};
+use crate::component::{
+    AxNode, AxTree, ComponentId, ComponentInstance, Context, LifecycleEvent, LifecycleListener,
+    LifecycleManager, LifecyclePhase, Node, PerformanceMonitor, StateChanges, StateTracker,
+    StateTrackingConfig, StateValue, UpdateBatcher,
+};
 
 /// Result type for tree operations
 pub type TreeResult<T> = Result<T, TreeError>;
@@ -35,11 +39,26 @@ pub enum TreeError {
     /// Invalid parent-child relationship
     #[error("Invalid parent-child relationship: {0}")]
     InvalidRelationship(String),
+
+    /// Components that could not apply their batched updates
+    #[error("Batched updates failed for {} component(s)", .0.len())]
+    UpdatesFailed(Vec<(ComponentId, TreeError)>),
 }
 
 /// Type alias for a thread-safe component instance
 pub type SharedComponentInstance = Arc<RwLock<ComponentInstance>>;
 
+/// Outcome of reconciling a parent's children against a new list of nodes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChildReconciliation {
+    /// Components that were added to the parent
+    pub created: Vec<ComponentId>,
+    /// Reused components whose position relative to their siblings changed
+    pub moved: Vec<ComponentId>,
+    /// Components that were unmounted and removed from the tree
+    pub removed: Vec<ComponentId>,
+}
+
 /// Manager for the component tree
 ///
 /// Handles parent-child relationships, lifecycle coordination, and efficient updates
@@ -56,13 +75,82 @@ pub struct ComponentTree {
     /// Map of component ID to its parent ID
     parents: RwLock<HashMap<ComponentId, ComponentId>>,
 
+    /// Map of component ID to the `key` attribute it was reconciled with
+    keys: RwLock<HashMap<ComponentId, String>>,
+
     /// Root component ID (if set)
     root: RwLock<Option<ComponentId>>,
 
+    /// Callbacks notified of every component's phase transitions
+    lifecycle_listeners: Arc<RwLock<Vec<LifecycleListener>>>,
+
+    /// Render, update and mount timings for every component
+    monitor: Arc<PerformanceMonitor>,
+
+    /// Read locks taken on the structure maps, for profiling traversals
+    read_locks: AtomicUsize,
+
+    /// Tracked state of each component as of its last render
+    trackers: Mutex<HashMap<ComponentId, StateTracker>>,
+
     /// Application context
     context: Context,
 }
 
+/// A read-only view of a [`ComponentTree`]'s structure
+///
+/// Created by [`ComponentTree::with_snapshot`], which holds the read locks
+/// for the view's lifetime, so lookups through it take no further locks.
+pub struct TreeView<'a> {
+    components: RwLockReadGuard<'a, HashMap<ComponentId, SharedComponentInstance>>,
+    children: RwLockReadGuard<'a, HashMap<ComponentId, Vec<ComponentId>>>,
+    parents: RwLockReadGuard<'a, HashMap<ComponentId, ComponentId>>,
+    root: Option<ComponentId>,
+}
+
+impl TreeView<'_> {
+    /// The root component ID, if set
+    pub fn root(&self) -> Option<ComponentId> {
+        self.root
+    }
+
+    /// Check if a component exists in the tree
+    pub fn has_component(&self, id: ComponentId) -> bool {
+        self.components.contains_key(&id)
+    }
+
+    /// Get a component instance by ID
+    pub fn component(&self, id: ComponentId) -> Option<&SharedComponentInstance> {
+        self.components.get(&id)
+    }
+
+    /// The children of a component, empty if it has none
+    pub fn children(&self, id: ComponentId) -> &[ComponentId] {
+        self.children.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The parent of a component
+    pub fn parent(&self, id: ComponentId) -> Option<ComponentId> {
+        self.parents.get(&id).copied()
+    }
+
+    /// A component followed by its ancestors up to the root
+    pub fn ancestors(&self, id: ComponentId) -> impl Iterator<Item = ComponentId> + '_ {
+        std::iter::successors(Some(id), |&id| self.parent(id))
+    }
+
+    /// A component and its descendants, parents before their children
+    pub fn descendants(&self, id: ComponentId) -> Vec<ComponentId> {
+        let mut order = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            stack.extend(self.children(id).iter().rev());
+        }
+        order
+    }
+}
+
 impl ComponentTree {
     /// Create a new component tree
     pub fn new(context: Context) -> Self {
@@ -71,11 +159,65 @@ impl ComponentTree {
             lifecycle_managers: RwLock::new(HashMap::new()),
             children: RwLock::new(HashMap::new()),
             parents: RwLock::new(HashMap::new()),
+            keys: RwLock::new(HashMap::new()),
             root: RwLock::new(None),
+            lifecycle_listeners: Arc::new(RwLock::new(Vec::new())),
+            monitor: Arc::new(PerformanceMonitor::new()),
+            read_locks: AtomicUsize::new(0),
+            trackers: Mutex::new(HashMap::new()),
             context,
         }
     }
 
+    /// Run `f` with a view of the tree's structure
+    ///
+    /// The components, children, parents and root are each read-locked once
+    /// for the whole call instead of once per lookup, which makes this the
+    /// cheaper way to walk many components. `f` must not modify the tree.
+    pub fn with_snapshot<R>(&self, f: impl FnOnce(&TreeView<'_>) -> R) -> TreeResult<R> {
+        let root = self.root_id()?;
+        let view = TreeView {
+            components: self.read_components()?,
+            children: self.read_children()?,
+            parents: self.read_parents()?,
+            root,
+        };
+        Ok(f(&view))
+    }
+
+    /// Number of read locks taken on the tree's structure so far
+    ///
+    /// Counts the locks behind lookups such as [`ComponentTree::get_children`]
+    /// and [`ComponentTree::with_snapshot`].
+    pub fn read_lock_count(&self) -> usize {
+        self.read_locks.load(Ordering::Relaxed)
+    }
+
+    fn read_components(
+        &self,
+    ) -> TreeResult<RwLockReadGuard<'_, HashMap<ComponentId, SharedComponentInstance>>> {
+        self.read_locks.fetch_add(1, Ordering::Relaxed);
+        self.components
+            .read()
+            .map_err(|_| TreeError::LockError("Failed to read components map".to_string()))
+    }
+
+    fn read_children(
+        &self,
+    ) -> TreeResult<RwLockReadGuard<'_, HashMap<ComponentId, Vec<ComponentId>>>> {
+        self.read_locks.fetch_add(1, Ordering::Relaxed);
+        self.children
+            .read()
+            .map_err(|_| TreeError::LockError("Failed to read children map".to_string()))
+    }
+
+    fn read_parents(&self) -> TreeResult<RwLockReadGuard<'_, HashMap<ComponentId, ComponentId>>> {
+        self.read_locks.fetch_add(1, Ordering::Relaxed);
+        self.parents
+            .read()
+            .map_err(|_| TreeError::LockError("Failed to read parents map".to_string()))
+    }
+
     /// Set the root component
     pub fn set_root(&self, component_id: ComponentId) -> TreeResult<()> {
         // Check if component exists
@@ -96,6 +238,7 @@ impl ComponentTree {
 
     /// Get the root component ID
     pub fn root_id(&self) -> TreeResult<Option<ComponentId>> {
+        self.read_locks.fetch_add(1, Ordering::Relaxed);
         let root = self
             .root
             .read()
@@ -113,8 +256,17 @@ impl ComponentTree {
             return Err(TreeError::ComponentAlreadyExists(id));
         }
 
-        // Create lifecycle manager
-        let lifecycle = LifecycleManager::new(component.clone(), self.context.clone());
+        // Create lifecycle manager, forwarding its transitions to the tree's listeners
+        let mut lifecycle = LifecycleManager::new(component.clone(), self.context.clone());
+        let listeners = self.lifecycle_listeners.clone();
+        lifecycle.set_listener(Some(Arc::new(move |event: &LifecycleEvent| {
+            if let Ok(listeners) = listeners.read() {
+                for listener in listeners.iter() {
+                    listener(event);
+                }
+            }
+        })));
+        lifecycle.set_performance_monitor(Some(self.monitor.clone()));
 
         // Add component to maps
         {
@@ -143,6 +295,9 @@ impl ComponentTree {
             children.insert(id, Vec::new());
         }
 
+        // Changes are measured from the state the component was created with
+        self.record_state(id)?;
+
         Ok(id)
     }
 
@@ -228,6 +383,21 @@ impl ComponentTree {
             parents.remove(&id);
         }
 
+        {
+            let mut keys = self
+                .keys
+                .write()
+                .map_err(|_| TreeError::LockError("Failed to lock keys map".to_string()))?;
+
+            keys.remove(&id);
+        }
+
+        if let Ok(mut trackers) = self.trackers.lock() {
+            trackers.remove(&id);
+        }
+        self.monitor.remove_component(id);
+        self.context.clear_render_dependencies(id);
+
         // If this was the root, unset it
         {
             let mut root = self
@@ -344,10 +514,7 @@ impl ComponentTree {
 
     /// Get a component instance by ID
     pub fn get_component(&self, id: ComponentId) -> TreeResult<SharedComponentInstance> {
-        let components = self
-            .components
-            .read()
-            .map_err(|_| TreeError::LockError("Failed to read components map".to_string()))?;
+        let components = self.read_components()?;
 
         if let Some(component) = components.get(&id) {
             Ok(component.clone())
@@ -372,6 +539,21 @@ impl ComponentTree {
         }
     }
 
+    /// Register a callback for every lifecycle phase transition in the tree
+    ///
+    /// Events are delivered synchronously, in the order the transitions happen,
+    /// for components added before or after registration. The callback runs
+    /// while the component's lifecycle manager is locked, so it must not call
+    /// back into the tree's lifecycle methods.
+    pub fn on_lifecycle_event<F>(&self, callback: F)
+    where
+        F: Fn(&LifecycleEvent) + Send + Sync + 'static,
+    {
+        if let Ok(mut listeners) = self.lifecycle_listeners.write() {
+            listeners.push(Arc::new(callback));
+        }
+    }
+
     /// Initialize a component
     pub fn initialize_component(&self, id: ComponentId) -> TreeResult<()> {
         let lifecycle_manager = self.get_lifecycle_manager(id)?;
@@ -408,6 +590,20 @@ impl ComponentTree {
         Ok(())
     }
 
+    /// Let a component apply an update scheduled for it before re-rendering
+    pub fn handle_updates(&self, id: ComponentId) -> TreeResult<()> {
+        let lifecycle_manager = self.get_lifecycle_manager(id)?;
+        let mut manager = lifecycle_manager
+            .write()
+            .map_err(|_| TreeError::LockError("Failed to lock lifecycle manager".to_string()))?;
+
+        manager
+            .handle_updates()
+            .map_err(TreeError::LifecycleError)?;
+
+        Ok(())
+    }
+
     /// Render a component
     pub fn render_component(&self, id: ComponentId) -> TreeResult<Vec<Node>> {
         let lifecycle_manager = self.get_lifecycle_manager(id)?;
@@ -415,42 +611,120 @@ impl ComponentTree {
             .read()
             .map_err(|_| TreeError::LockError("Failed to read lifecycle manager".to_string()))?;
 
-        manager.render().map_err(TreeError::LifecycleError)
-    }
-
-    /// Check if a component exists in the tree
-    pub fn has_component(&self, id: ComponentId) -> bool {
-        let components = match self.components.read() {
-            Ok(c) => c,
-            Err(_) => return false,
+        let nodes = {
+            let _timer = self.monitor.start_render_timing(id);
+            manager.render().map_err(TreeError::LifecycleError)?
         };
 
-        components.contains_key(&id)
+        // The rendered nodes reflect the current state
+        self.record_state(id)?;
+        Ok(nodes)
     }
 
-    /// Get the children of a component
-    pub fn get_children(&self, id: ComponentId) -> TreeResult<Vec<ComponentId>> {
-        let children_map = self
-            .children
+    /// The state a component currently reports through `tracked_state`
+    fn tracked_state(&self, id: ComponentId) -> TreeResult<HashMap<String, StateValue>> {
+        let shared = self.get_component(id)?;
+        let instance = shared
             .read()
-            .map_err(|_| TreeError::LockError("Failed to read children map".to_string()))?;
+            .map_err(|_| TreeError::LockError("Failed to read component".to_string()))?;
+        let component = instance
+            .instance
+            .lock()
+            .map_err(|_| TreeError::LockError("Failed to lock component".to_string()))?;
+        Ok(component.any_tracked_state())
+    }
+
+    /// Remember a component's current state as the one it last rendered
+    fn record_state(&self, id: ComponentId) -> TreeResult<()> {
+        let fields = self.tracked_state(id)?;
+        let mut trackers = self
+            .trackers
+            .lock()
+            .map_err(|_| TreeError::LockError("Failed to lock state trackers".to_string()))?;
+        let tracker = trackers.entry(id).or_insert_with(|| {
+            // Every render is recorded, however close together
+            let config = StateTrackingConfig {
+                snapshot_throttle: Duration::ZERO,
+                ..Default::default()
+            };
+            StateTracker::new(id, config)
+        });
+        tracker.update_state(fields)?;
+        Ok(())
+    }
 
-        if let Some(children) = children_map.get(&id) {
-            Ok(children.clone())
+    /// Build an accessibility tree from the root component down
+    ///
+    /// Bounds are left empty; fill them in with `AxTree::apply_layout` once
+    /// layout has run.
+    pub fn accessibility_snapshot(&self) -> TreeResult<AxTree> {
+        self.with_snapshot(|view| {
+            let root = match view.root() {
+                Some(root) => Some(accessibility_node(view, root)?),
+                None => None,
+            };
+            Ok(AxTree { root })
+        })?
+    }
+
+    /// Apply every batch in `batcher` that is due at `now`
+    ///
+    /// Call once per frame. Each due component receives its queued changes
+    /// merged into a single [`StateChanges`] through its `on_update`.
+    /// Batches for components no longer in the tree are dropped. Returns the
+    /// number of components updated.
+    ///
+    /// A component that fails to update doesn't stop the others; every
+    /// failure is reported together once all due batches have been applied.
+    pub fn tick_updates(&self, batcher: &UpdateBatcher, now: Instant) -> TreeResult<usize> {
+        let mut updated = 0;
+        let mut errors = Vec::new();
+        for (id, batches) in batcher.tick(now) {
+            let Ok(lifecycle_manager) = self.get_lifecycle_manager(id) else {
+                crate::trace::debug_event!("Dropping batched updates for removed component {id}");
+                continue;
+            };
+            let result = match lifecycle_manager.write() {
+                Ok(mut manager) => manager
+                    .on_update(&merge_batches(batches))
+                    .map_err(TreeError::LifecycleError),
+                Err(_) => Err(TreeError::LockError(
+                    "Failed to lock lifecycle manager".to_string(),
+                )),
+            };
+            match result {
+                Ok(()) => updated += 1,
+                Err(e) => errors.push((id, e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(updated)
         } else {
-            // Return empty Vec if component doesn't exist or has no children
-            Ok(Vec::new())
+            Err(TreeError::UpdatesFailed(errors))
         }
     }
 
+    /// Get the monitor recording render, update and mount times
+    pub fn performance_monitor(&self) -> &Arc<PerformanceMonitor> {
+        &self.monitor
+    }
+
+    /// Check if a component exists in the tree
+    pub fn has_component(&self, id: ComponentId) -> bool {
+        self.read_components()
+            .is_ok_and(|components| components.contains_key(&id))
+    }
+
+    /// Get the children of a component
+    pub fn get_children(&self, id: ComponentId) -> TreeResult<Vec<ComponentId>> {
+        // Empty if the component doesn't exist or has no children
+        Ok(self.read_children()?.get(&id).cloned().unwrap_or_default())
+    }
+
     /// Get the parent of a component
     pub fn get_parent(&self, id: ComponentId) -> TreeResult<Option<ComponentId>> {
-        let parents = self
-            .parents
-            .read()
-            .map_err(|_| TreeError::LockError("Failed to read parents map".to_string()))?;
-
-        Ok(parents.get(&id).cloned())
+        Ok(self.read_parents()?.get(&id).copied())
     }
 
     /// Recursively mount a component and all its children
@@ -522,41 +796,184 @@ impl ComponentTree {
         let _nodes = self.render_component(id)?;
 
         // Process rendered nodes to extract child props and update children
-        // This is a simplified version - in a real implementation, this would be more complex
-        // and would match rendered nodes to child components
+        // This is a simplified version - keyed child lists can be matched to
+        // existing child components with `reconcile_children`
 
         Ok(())
     }
 
-    /// Get all components in the tree
-    pub fn get_all_components(&self) -> TreeResult<Vec<ComponentId>> {
-        let components = self
-            .components
+    /// Reconcile a parent's children against a newly rendered list of nodes
+    ///
+    /// Nodes are matched to existing children through their `key` attribute, so
+    /// reordered list items keep their component instance and state. Nodes that
+    /// don't match an existing child are added (and mounted if the parent is
+    /// mounted), while existing children with no matching node are unmounted and
+    /// removed. Nodes without a component instance are ignored.
+    pub fn reconcile_children(
+        &self,
+        parent_id: ComponentId,
+        new_children: &[Node],
+    ) -> TreeResult<ChildReconciliation> {
+        if !self.has_component(parent_id) {
+            return Err(TreeError::ComponentNotFound(parent_id));
+        }
+
+        let old_children = self.get_children(parent_id)?;
+
+        // Index existing children by key
+        let mut keyed_children: HashMap<String, (usize, ComponentId)> = {
+            let keys = self
+                .keys
+                .read()
+                .map_err(|_| TreeError::LockError("Failed to read keys map".to_string()))?;
+
+            old_children
+                .iter()
+                .enumerate()
+                .filter_map(|(index, id)| keys.get(id).map(|key| (key.clone(), (index, *id))))
+                .collect()
+        };
+
+        let parent_mounted = self.component_phase(parent_id)? == LifecyclePhase::Mounted;
+        let mut result = ChildReconciliation::default();
+        let mut new_order = Vec::with_capacity(new_children.len());
+        // Old indices of reused children, in their new order
+        let mut reused: Vec<(usize, ComponentId)> = Vec::new();
+
+        for node in new_children {
+            let Some(instance) = node.component() else {
+                continue;
+            };
+            let key = node.attributes().get("key");
+
+            if let Some((old_index, id)) = key.and_then(|key| keyed_children.remove(key)) {
+                reused.push((old_index, id));
+                new_order.push(id);
+                continue;
+            }
+
+            // Unkeyed nodes may still carry an instance that is already a child
+            let instance_id = instance.id();
+            if let Some(old_index) = old_children.iter().position(|&id| id == instance_id) {
+                if !new_order.contains(&instance_id) {
+                    reused.push((old_index, instance_id));
+                    new_order.push(instance_id);
+                }
+                continue;
+            }
+
+            let id = self.add_component(instance.clone())?;
+            if let Some(key) = key {
+                let mut keys = self
+                    .keys
+                    .write()
+                    .map_err(|_| TreeError::LockError("Failed to lock keys map".to_string()))?;
+
+                keys.insert(id, key.clone());
+            }
+            {
+                let mut parents = self
+                    .parents
+                    .write()
+                    .map_err(|_| TreeError::LockError("Failed to write parents map".to_string()))?;
+
+                parents.insert(id, parent_id);
+            }
+            if parent_mounted {
+                self.mount_component_tree(id)?;
+            }
+
+            result.created.push(id);
+            new_order.push(id);
+        }
+
+        // Children that were kept in increasing old order don't need to move;
+        // everything outside that longest run is reported as moved
+        let old_order: Vec<usize> = reused.iter().map(|(old_index, _)| *old_index).collect();
+        let stable = super::diff::longest_increasing_run(&old_order);
+        result.moved = reused
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| !stable.contains(position))
+            .map(|(_, (_, id))| *id)
+            .collect();
+
+        // Install the new child order before removing stale children
+        {
+            let mut children_map = self
+                .children
+                .write()
+                .map_err(|_| TreeError::LockError("Failed to write children map".to_string()))?;
+
+            children_map.insert(parent_id, new_order.clone());
+        }
+
+        for id in old_children {
+            if new_order.contains(&id) {
+                continue;
+            }
+
+            if self.component_phase(id)? == LifecyclePhase::Mounted {
+                self.unmount_component_tree(id)?;
+            }
+            self.remove_component(id)?;
+            result.removed.push(id);
+        }
+
+        Ok(result)
+    }
+
+    /// Get the lifecycle phase of a component
+    fn component_phase(&self, id: ComponentId) -> TreeResult<LifecyclePhase> {
+        let lifecycle_manager = self.get_lifecycle_manager(id)?;
+        let manager = lifecycle_manager
             .read()
-            .map_err(|_| TreeError::LockError("Failed to read components map".to_string()))?;
+            .map_err(|_| TreeError::LockError("Failed to read lifecycle manager".to_string()))?;
+
+        Ok(manager.current_phase())
+    }
 
-        Ok(components.keys().cloned().collect())
+    /// Get all components in the tree
+    pub fn get_all_components(&self) -> TreeResult<Vec<ComponentId>> {
+        Ok(self.read_components()?.keys().cloned().collect())
     }
 
     /// Get all components that need updating
+    ///
+    /// These are the components with an update scheduled on the context,
+    /// followed by those whose tracked state changed since they last
+    /// rendered. Each appears once.
     pub fn get_components_to_update(&self) -> TreeResult<Vec<ComponentId>> {
-        // In a real implementation, this would check component dirty flags
-        // or other state to determine which components need updating
-        // For now, just return an empty vector
-        Ok(Vec::new())
+        let mut ids: Vec<ComponentId> = self
+            .context
+            .pending_updates()
+            .into_iter()
+            .filter(|&id| self.has_component(id))
+            .collect();
+
+        for id in self.get_all_components()? {
+            if !ids.contains(&id) && self.detect_state_changes(id)? {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
     }
 
     /// Perform state change detection for a component
+    ///
+    /// Compares the state the component reports now with the snapshot
+    /// recorded when it was added or last rendered.
     pub fn detect_state_changes(&self, id: ComponentId) -> TreeResult<bool> {
-        let component = self.get_component(id)?;
-        let _component = component
-            .read()
-            .map_err(|_| TreeError::LockError("Failed to read component".to_string()))?;
-
-        // This would compare previous state with current state
-        // and return true if changes are detected
-        // For now, just return false indicating no changes
-        Ok(false)
+        let fields = self.tracked_state(id)?;
+        let trackers = self
+            .trackers
+            .lock()
+            .map_err(|_| TreeError::LockError("Failed to lock state trackers".to_string()))?;
+
+        Ok(match trackers.get(&id).and_then(|t| t.current_snapshot()) {
+            Some(snapshot) => snapshot.fields != fields,
+            None => !fields.is_empty(),
+        })
     }
 
     /// Batch update multiple components
@@ -600,6 +1017,47 @@ impl std::fmt::Debug for ComponentTree {
     }
 }
 
+fn accessibility_node(view: &TreeView<'_>, id: ComponentId) -> TreeResult<AxNode> {
+    let properties = {
+        let shared = view.component(id).ok_or(TreeError::ComponentNotFound(id))?;
+        let instance = shared
+            .read()
+            .map_err(|_| TreeError::LockError("Failed to read component".to_string()))?;
+        let component = instance
+            .instance
+            .lock()
+            .map_err(|_| TreeError::LockError("Failed to lock component".to_string()))?;
+        component.any_accessibility()
+    };
+
+    let mut node = AxNode::new(id, properties);
+    node.children = view
+        .children(id)
+        .iter()
+        .map(|&child| accessibility_node(view, child))
+        .collect::<TreeResult<_>>()?;
+    Ok(node)
+}
+
+/// Combine a component's queued batches, oldest first, into one
+fn merge_batches(batches: Vec<StateChanges>) -> StateChanges {
+    let batch_timestamp = batches
+        .iter()
+        .map(|batch| batch.batch_timestamp)
+        .min()
+        .unwrap_or_else(Instant::now);
+    let immediate = batches.iter().any(|batch| batch.immediate);
+
+    StateChanges {
+        changes: batches
+            .into_iter()
+            .flat_map(|batch| batch.changes)
+            .collect(),
+        batch_timestamp,
+        immediate,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -611,6 +1069,8 @@ mod tests {
         #[allow(dead_code)]
         context: Context,
         name: String,
+        /// State changes received through `on_update`
+        batched_changes: usize,
     }
 
     // Empty props for test component
@@ -633,6 +1093,7 @@ mod tests {
                 id: ComponentId::new(),
                 context,
                 name: props.name,
+                batched_changes: 0,
             }
         }
 
@@ -641,6 +1102,15 @@ mod tests {
             Ok(())
         }
 
+        fn on_update(&mut self, changes: &StateChanges) -> Result<(), ComponentError> {
+            self.batched_changes += changes.changes.len();
+            Ok(())
+        }
+
+        fn tracked_state(&self) -> HashMap<String, StateValue> {
+            HashMap::from([("name".to_string(), StateValue::String(self.name.clone()))])
+        }
+
         fn render(&self) -> Result<Vec<Node>, ComponentError> {
             Ok(vec![])
         }
@@ -696,6 +1166,54 @@ mod tests {
         assert_eq!(parent, Some(root_id));
     }
 
+    #[test]
+    fn test_snapshot_traversal_takes_fewer_read_locks() {
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let root_id = tree
+            .add_component(create_test_component("root", context.clone()))
+            .unwrap();
+        tree.set_root(root_id).unwrap();
+        for i in 0..10 {
+            let branch = create_test_component(&format!("branch{i}"), context.clone());
+            let branch_id = tree.add_component(branch).unwrap();
+            tree.add_child(root_id, branch_id).unwrap();
+            for j in 0..10 {
+                let leaf = create_test_component(&format!("leaf{i}.{j}"), context.clone());
+                let leaf_id = tree.add_component(leaf).unwrap();
+                tree.add_child(branch_id, leaf_id).unwrap();
+            }
+        }
+
+        // Walking with the per-call methods locks for every lookup
+        let before = tree.read_lock_count();
+        let mut visited = Vec::new();
+        let mut stack = vec![tree.root_id().unwrap().unwrap()];
+        while let Some(id) = stack.pop() {
+            assert!(tree.has_component(id));
+            let _ = tree.get_parent(id).unwrap();
+            visited.push(id);
+            stack.extend(tree.get_children(id).unwrap().into_iter().rev());
+        }
+        let per_call = tree.read_lock_count() - before;
+        assert_eq!(visited.len(), 111);
+        assert_eq!(per_call, 1 + 3 * 111);
+
+        // A snapshot locks each map once for the whole walk
+        let before = tree.read_lock_count();
+        let walked = tree
+            .with_snapshot(|view| {
+                let order = view.descendants(view.root().unwrap());
+                assert!(order.iter().all(|&id| view.has_component(id)));
+                assert!(order[1..].iter().all(|&id| view.parent(id).is_some()));
+                order
+            })
+            .unwrap();
+        let snapshot = tree.read_lock_count() - before;
+        assert_eq!(walked, visited);
+        assert_eq!(snapshot, 4);
+    }
+
     #[test]
     fn test_component_tree_lifecycle() {
         // Create tree
@@ -737,6 +1255,56 @@ mod tests {
         assert_eq!(child1_phase, LifecyclePhase::Mounted);
     }
 
+    #[test]
+    fn test_lifecycle_events_follow_mount_order() {
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+
+        let root_id = tree
+            .add_component(create_test_component("root", context.clone()))
+            .unwrap();
+        let child1_id = tree
+            .add_component(create_test_component("child1", context.clone()))
+            .unwrap();
+        let child2_id = tree
+            .add_component(create_test_component("child2", context.clone()))
+            .unwrap();
+        tree.add_child(root_id, child1_id).unwrap();
+        tree.add_child(root_id, child2_id).unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        tree.on_lifecycle_event(move |event| recorded.lock().unwrap().push(*event));
+
+        tree.initialize_component(root_id).unwrap();
+        tree.mount_component_tree(root_id).unwrap();
+        tree.unmount_component(child2_id).unwrap();
+
+        use LifecyclePhase::*;
+        let events = events.lock().unwrap();
+        let sequence: Vec<_> = events
+            .iter()
+            .map(|event| (event.component_id, event.from, event.to))
+            .collect();
+        assert_eq!(
+            sequence,
+            vec![
+                (root_id, Created, Created),
+                // Parents are mounted before their children
+                (root_id, Created, Mounting),
+                (root_id, Mounting, Mounted),
+                (child1_id, Created, Mounting),
+                (child1_id, Mounting, Mounted),
+                (child2_id, Created, Mounting),
+                (child2_id, Mounting, Mounted),
+                (child2_id, Mounted, BeforeUnmount),
+                (child2_id, BeforeUnmount, Unmounting),
+                (child2_id, Unmounting, Unmounted),
+            ]
+        );
+        assert!(events.windows(2).all(|pair| pair[0].at <= pair[1].at));
+    }
+
     #[test]
     fn test_component_tree_removal() {
         // Create tree
@@ -825,4 +1393,375 @@ mod tests {
         // Should have updated 2 components
         assert_eq!(updated, 2);
     }
+
+    #[test]
+    fn test_changed_state_marks_component_for_update() {
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let first = tree
+            .add_component(create_test_component("first", context.clone()))
+            .unwrap();
+        let second = tree
+            .add_component(create_test_component("second", context.clone()))
+            .unwrap();
+        tree.mount_component(second).unwrap();
+        assert!(tree.get_components_to_update().unwrap().is_empty());
+
+        // Mutate the component's state without going through the scheduler
+        {
+            let shared = tree.get_component(second).unwrap();
+            let instance = shared.read().unwrap();
+            let mut component = instance.instance.lock().unwrap();
+            let component = component
+                .as_any_mut()
+                .downcast_mut::<TestComponent>()
+                .unwrap();
+            component.name = "renamed".to_string();
+        }
+        assert!(tree.detect_state_changes(second).unwrap());
+        assert!(!tree.detect_state_changes(first).unwrap());
+        assert_eq!(tree.get_components_to_update().unwrap(), vec![second]);
+
+        // Scheduled updates are included too, each component once
+        context.schedule_update(first);
+        context.schedule_update(second);
+        let mut expected = vec![first, second];
+        let mut pending = tree.get_components_to_update().unwrap();
+        pending.sort_by_key(|id| id.id());
+        expected.sort_by_key(|id| id.id());
+        assert_eq!(pending, expected);
+        context.set_update_handler(|_| {});
+        context.flush_updates();
+
+        // Rendering records the new state, so the component is clean again
+        tree.render_component(second).unwrap();
+        assert!(!tree.detect_state_changes(second).unwrap());
+        assert!(tree.get_components_to_update().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_render_update_and_mount_times_are_recorded() {
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let id = tree
+            .add_component(create_test_component("timed", context))
+            .unwrap();
+        tree.mount_component_tree(id).unwrap();
+
+        for _ in 0..3 {
+            tree.render_component(id).unwrap();
+        }
+        tree.update_component(
+            id,
+            TestProps {
+                name: "retimed".to_string(),
+            },
+        )
+        .unwrap();
+
+        let monitor = tree.performance_monitor();
+        let stats = monitor.get_render_statistics(id);
+        assert_eq!(stats.count, 3);
+        assert!(stats.min <= stats.average && stats.average <= stats.max);
+        assert_eq!(stats.average, stats.total / 3);
+        assert_eq!(monitor.get_average_render_time(id), Some(stats.average));
+        assert!(monitor.get_mount_time(id).is_some());
+        assert!(monitor.get_average_update_time(id).is_some());
+
+        // Removed components don't keep their timings around
+        tree.remove_component(id).unwrap();
+        assert_eq!(monitor.get_render_statistics(id).count, 0);
+        assert!(monitor.get_mount_time(id).is_none());
+        assert!(monitor.get_average_update_time(id).is_none());
+    }
+
+    #[test]
+    fn test_tick_updates_applies_due_batches() {
+        use crate::component::{ChangePriority, StateChange, StateValue};
+        use std::time::Duration;
+
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let id = tree
+            .add_component(create_test_component("batched", context))
+            .unwrap();
+        tree.mount_component_tree(id).unwrap();
+
+        let start = Instant::now();
+        let changes = |value: i64| StateChanges {
+            changes: vec![StateChange {
+                field_name: "count".to_string(),
+                old_value: None,
+                new_value: StateValue::Integer(value),
+                timestamp: start,
+                priority: ChangePriority::Normal,
+            }],
+            batch_timestamp: start,
+            immediate: false,
+        };
+        let batcher = UpdateBatcher::new(Duration::from_millis(16), 10);
+        batcher.queue_update(id, changes(1));
+        batcher.queue_update(id, changes(2));
+        batcher.queue_update(ComponentId::new(), changes(3));
+
+        assert_eq!(tree.tick_updates(&batcher, start).unwrap(), 0);
+        let later = start + Duration::from_millis(16);
+        assert_eq!(tree.tick_updates(&batcher, later).unwrap(), 1);
+
+        let manager = tree.get_lifecycle_manager(id).unwrap();
+        let manager = manager.read().unwrap();
+        let instance = manager.get_component().lock().unwrap();
+        let component = instance.instance.lock().unwrap();
+        let component = component.as_any().downcast_ref::<TestComponent>().unwrap();
+        assert_eq!(component.batched_changes, 2);
+    }
+
+    #[test]
+    fn test_tick_updates_reports_failures_after_applying_every_batch() {
+        use crate::component::{ChangePriority, StateChange, StateValue};
+        use std::time::Duration;
+
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        // Unmounted components reject updates
+        let unmounted = tree
+            .add_component(create_test_component("unmounted", context.clone()))
+            .unwrap();
+        let mounted = tree
+            .add_component(create_test_component("mounted", context))
+            .unwrap();
+        tree.mount_component_tree(mounted).unwrap();
+
+        let start = Instant::now();
+        let batcher = UpdateBatcher::new(Duration::from_millis(16), 10);
+        for id in [unmounted, mounted] {
+            let change = StateChange {
+                field_name: "count".to_string(),
+                old_value: None,
+                new_value: StateValue::Integer(1),
+                timestamp: start,
+                priority: ChangePriority::Normal,
+            };
+            batcher.queue_update(id, StateChanges::new(vec![change], false));
+        }
+
+        let later = Instant::now() + Duration::from_millis(16);
+        match tree.tick_updates(&batcher, later) {
+            Err(TreeError::UpdatesFailed(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].0, unmounted);
+            }
+            other => panic!("expected UpdatesFailed, got {other:?}"),
+        }
+
+        let manager = tree.get_lifecycle_manager(mounted).unwrap();
+        let manager = manager.read().unwrap();
+        let instance = manager.get_component().lock().unwrap();
+        let component = instance.instance.lock().unwrap();
+        let component = component.as_any().downcast_ref::<TestComponent>().unwrap();
+        assert_eq!(component.batched_changes, 1);
+    }
+
+    /// Component whose `should_update` always declines
+    struct FrozenComponent {
+        id: ComponentId,
+        updates: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[derive(Clone)]
+    struct FrozenProps {
+        label: String,
+        updates: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Component for FrozenComponent {
+        type Props = FrozenProps;
+
+        fn component_id(&self) -> ComponentId {
+            self.id
+        }
+
+        fn create(props: Self::Props, _context: Context) -> Self {
+            Self {
+                id: ComponentId::new(),
+                updates: props.updates,
+            }
+        }
+
+        fn should_update(&self, _new_props: &Self::Props) -> bool {
+            false
+        }
+
+        fn update(&mut self, _props: Self::Props) -> Result<(), ComponentError> {
+            self.updates
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn render(&self) -> Result<Vec<Node>, ComponentError> {
+            Ok(vec![])
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_should_update_false_skips_update_but_stores_props() {
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let updates = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let props = |label: &str| FrozenProps {
+            label: label.to_string(),
+            updates: updates.clone(),
+        };
+        let component = FrozenComponent::create(props("first"), context);
+        let id = tree
+            .add_component(ComponentInstance::new(component, props("first")))
+            .unwrap();
+        tree.mount_component_tree(id).unwrap();
+
+        tree.update_component(id, props("second")).unwrap();
+
+        assert_eq!(updates.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert_eq!(tree.component_phase(id).unwrap(), LifecyclePhase::Mounted);
+        let manager = tree.get_lifecycle_manager(id).unwrap();
+        let manager = manager.read().unwrap();
+        let instance = manager.get_component().lock().unwrap();
+        let stored = instance.props.as_any().downcast_ref::<FrozenProps>();
+        assert_eq!(stored.unwrap().label, "second");
+    }
+
+    fn keyed_node(name: &str, context: &Context) -> Node {
+        let mut node = Node::new(Some(create_test_component(name, context.clone())));
+        node.add_attribute("key".to_string(), name.to_string());
+        node
+    }
+
+    fn reconcile_setup() -> (ComponentTree, Context, ComponentId) {
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let root_id = tree
+            .add_component(create_test_component("root", context.clone()))
+            .unwrap();
+        tree.set_root(root_id).unwrap();
+        tree.mount_component_tree(root_id).unwrap();
+        (tree, context, root_id)
+    }
+
+    #[test]
+    fn test_reconcile_children_adds_keyed_children() {
+        let (tree, context, root_id) = reconcile_setup();
+
+        let nodes = vec![keyed_node("a", &context), keyed_node("b", &context)];
+        let result = tree.reconcile_children(root_id, &nodes).unwrap();
+
+        assert_eq!(result.created.len(), 2);
+        assert!(result.moved.is_empty());
+        assert!(result.removed.is_empty());
+        assert_eq!(tree.get_children(root_id).unwrap(), result.created);
+
+        for id in &result.created {
+            assert_eq!(tree.get_parent(*id).unwrap(), Some(root_id));
+            let manager = tree.get_lifecycle_manager(*id).unwrap();
+            assert_eq!(
+                manager.read().unwrap().current_phase(),
+                LifecyclePhase::Mounted
+            );
+        }
+    }
+
+    #[test]
+    fn test_reconcile_children_removes_missing_keys() {
+        let (tree, context, root_id) = reconcile_setup();
+
+        let nodes = vec![
+            keyed_node("a", &context),
+            keyed_node("b", &context),
+            keyed_node("c", &context),
+        ];
+        let initial = tree.reconcile_children(root_id, &nodes).unwrap();
+        let (a, b, c) = (initial.created[0], initial.created[1], initial.created[2]);
+
+        // Fresh nodes with the same keys reuse the existing instances
+        let nodes = vec![keyed_node("a", &context), keyed_node("c", &context)];
+        let result = tree.reconcile_children(root_id, &nodes).unwrap();
+
+        assert!(result.created.is_empty());
+        assert!(result.moved.is_empty());
+        assert_eq!(result.removed, vec![b]);
+        assert_eq!(tree.get_children(root_id).unwrap(), vec![a, c]);
+        assert!(!tree.has_component(b));
+    }
+
+    #[test]
+    fn test_reconcile_children_reorders_without_recreating() {
+        let (tree, context, root_id) = reconcile_setup();
+
+        let nodes = vec![
+            keyed_node("a", &context),
+            keyed_node("b", &context),
+            keyed_node("c", &context),
+        ];
+        let initial = tree.reconcile_children(root_id, &nodes).unwrap();
+        let (a, b, c) = (initial.created[0], initial.created[1], initial.created[2]);
+
+        // Move "c" to the front and add "d" at the end
+        let nodes = vec![
+            keyed_node("c", &context),
+            keyed_node("a", &context),
+            keyed_node("b", &context),
+            keyed_node("d", &context),
+        ];
+        let result = tree.reconcile_children(root_id, &nodes).unwrap();
+
+        assert_eq!(result.moved, vec![c]);
+        assert!(result.removed.is_empty());
+        assert_eq!(result.created.len(), 1);
+
+        let d = result.created[0];
+        assert_eq!(tree.get_children(root_id).unwrap(), vec![c, a, b, d]);
+    }
+
+    #[test]
+    fn test_accessibility_snapshot_describes_kit_button() {
+        use crate::component::AxRole;
+        use crate::kit::components::button::{Button, ButtonProps};
+
+        let context = Context::new();
+        let tree = ComponentTree::new(context.clone());
+        let root_id = tree
+            .add_component(create_test_component("root", context.clone()))
+            .unwrap();
+        tree.set_root(root_id).unwrap();
+
+        let props = ButtonProps {
+            text: "Save".to_string(),
+            variant: None,
+            disabled: Some(true),
+            size: None,
+            on_click: None,
+        };
+        let button = Button::create(props.clone(), context.clone());
+        let button_id = tree
+            .add_component(ComponentInstance::new(button, props))
+            .unwrap();
+        tree.add_child(root_id, button_id).unwrap();
+
+        let snapshot = tree.accessibility_snapshot().unwrap();
+        let root = snapshot.root.as_ref().unwrap();
+        assert_eq!(root.role, AxRole::Generic);
+        assert_eq!(root.children.len(), 1);
+
+        let button = snapshot.find(button_id).unwrap();
+        assert_eq!(button.role.as_str(), "button");
+        assert_eq!(button.name.as_deref(), Some("Save"));
+        assert!(button.state.disabled);
+        assert_eq!(button.bounds, None);
+    }
 }
@@ -178,7 +178,7 @@ impl UpdateScheduler {
             // Call the update function for this component
             if let Err(e) = update_component(update.component_id) {
                 // Log error but continue with other updates
-                eprintln!(
+                crate::trace::warn_event!(
                     "Error updating component {}: {}",
                     update.component_id.id(),
                     e
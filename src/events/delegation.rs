@@ -8,6 +8,7 @@
 use std::any::TypeId;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
 use crate::component_single::Node;
@@ -33,13 +34,16 @@ pub enum PropagationPhase {
 }
 
 /// Controls how an event propagates through the component tree
+///
+/// Clones share the stop and prevent-default flags, so a flag set through
+/// any clone is seen by the dispatch that created it.
 #[derive(Debug, Clone)]
 pub struct EventPropagation {
-    /// Whether the event should continue propagating
-    pub stopped: bool,
+    /// Whether the event should stop propagating
+    stopped: Arc<AtomicBool>,
 
     /// Whether the default action should be prevented
-    pub default_prevented: bool,
+    default_prevented: Arc<AtomicBool>,
 
     /// The current propagation phase
     pub phase: PropagationPhase,
@@ -55,8 +59,8 @@ impl EventPropagation {
     /// Create a new event propagation
     pub fn new(phase: PropagationPhase) -> Self {
         Self {
-            stopped: false,
-            default_prevented: false,
+            stopped: Arc::new(AtomicBool::new(false)),
+            default_prevented: Arc::new(AtomicBool::new(false)),
             phase,
             target_id: None,
             current_target_id: None,
@@ -64,22 +68,47 @@ impl EventPropagation {
     }
 
     /// Stop event propagation
-    pub fn stop_propagation(&mut self) {
-        self.stopped = true;
+    pub fn stop_propagation(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
     }
 
     /// Prevent the default action
-    pub fn prevent_default(&mut self) {
-        self.default_prevented = true;
+    pub fn prevent_default(&self) {
+        self.default_prevented.store(true, Ordering::Relaxed);
     }
 
     /// Check if propagation is stopped
     pub fn is_propagation_stopped(&self) -> bool {
-        self.stopped
+        self.stopped.load(Ordering::Relaxed)
     }
 
     /// Check if default is prevented
     pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented.load(Ordering::Relaxed)
+    }
+}
+
+/// What happened when an event was dispatched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchOutcome {
+    target: Option<usize>,
+    propagation_stopped: bool,
+    default_prevented: bool,
+}
+
+impl DispatchOutcome {
+    /// Component the event was delivered to, if any delegate matched
+    pub fn target(&self) -> Option<usize> {
+        self.target
+    }
+
+    /// Whether a handler stopped propagation
+    pub fn propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+
+    /// Whether a handler asked for the default action to be skipped
+    pub fn default_prevented(&self) -> bool {
         self.default_prevented
     }
 }
@@ -103,14 +132,29 @@ impl<E: Event> DelegatedEvent<E> {
     }
 
     /// Stop propagation of this event
-    pub fn stop_propagation(&mut self) {
+    pub fn stop_propagation(&self) {
         self.propagation.stop_propagation();
     }
 
     /// Prevent the default action for this event
-    pub fn prevent_default(&mut self) {
+    pub fn prevent_default(&self) {
         self.propagation.prevent_default();
     }
+
+    /// Current propagation phase
+    pub fn phase(&self) -> PropagationPhase {
+        self.propagation.phase
+    }
+
+    /// Check if propagation is stopped
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation.is_propagation_stopped()
+    }
+
+    /// Check if default is prevented
+    pub fn is_default_prevented(&self) -> bool {
+        self.propagation.is_default_prevented()
+    }
 }
 
 /// Type of callback for delegated events
@@ -226,124 +270,161 @@ impl EventDelegate {
         handlers.entry(type_id).or_default().push(boxed_handler);
     }
 
-    /// Dispatch an event starting from this delegate
-    pub fn dispatch<E: Event + 'static>(&self, event: &E, target_id: Option<usize>) {
-        // First, we do the capturing phase from the root down to the target
-        let mut propagation = EventPropagation {
-            stopped: false,
-            default_prevented: false,
-            phase: PropagationPhase::Capturing,
-            target_id,
-            current_target_id: self.component_id,
+    /// Register a handler that receives a [`DelegatedEvent`] in one phase
+    ///
+    /// The delegated event shares its flags with the dispatch, so calling
+    /// `stop_propagation` or `prevent_default` on it affects the whole
+    /// dispatch.
+    pub fn listen<E: Event + Clone + 'static>(
+        &self,
+        phase: PropagationPhase,
+        handler: impl Fn(&DelegatedEvent<E>) + Send + Sync + 'static,
+    ) {
+        let wrapped = move |event: &E, propagation: &EventPropagation| {
+            handler(&DelegatedEvent {
+                event: event.clone(),
+                propagation: propagation.clone(),
+            });
         };
-
-        self.dispatch_capturing(event, &mut propagation, target_id);
-
-        // Next, we handle the target phase
-        if !propagation.is_propagation_stopped() && self.component_id == target_id {
-            propagation.phase = PropagationPhase::Target;
-            propagation.current_target_id = self.component_id;
-            self.handle_event(event, &propagation);
+        match phase {
+            PropagationPhase::Capturing => self.capture(wrapped),
+            PropagationPhase::Target => self.on(wrapped),
+            PropagationPhase::Bubbling => self.bubble(wrapped),
         }
+    }
 
-        // Finally, we do the bubbling phase from the target up to the root
-        if !propagation.is_propagation_stopped() {
-            propagation.phase = PropagationPhase::Bubbling;
-            self.dispatch_bubbling(event, &mut propagation, target_id);
+    /// Dispatch an event to a delegate in this subtree
+    ///
+    /// Runs the event through the delegates between this one and the target
+    /// in three phases: capturing handlers from the top down, the target's
+    /// own handlers, then bubbling handlers from the target back up. Without
+    /// a target, only this delegate's handlers run.
+    pub fn dispatch<E: Event + 'static>(&self, event: &E, target_id: Option<usize>) {
+        match target_id {
+            Some(target_id) => {
+                self.dispatch_event(event, &[target_id]);
+            }
+            None => {
+                self.run_phases(event, &[], None);
+            }
         }
     }
 
-    // Internal method to handle capturing phase
-    fn dispatch_capturing<E: Event + 'static>(
+    /// Dispatch an event along a hit-test chain
+    ///
+    /// `target_chain` lists components innermost first, as returned by hit
+    /// testing. The first one with a delegate in this subtree becomes the
+    /// target, so components that never registered a delegate pass the
+    /// event on to their nearest ancestor that did. Once a handler stops
+    /// propagation no further delegates are visited.
+    pub fn dispatch_event<E: Event + 'static>(
         &self,
         event: &E,
-        propagation: &mut EventPropagation,
-        target_id: Option<usize>,
-    ) {
-        // Handle this component's capturing handlers
-        if !propagation.is_propagation_stopped() {
-            propagation.current_target_id = self.component_id;
-            self.handle_capturing_event(event, propagation);
+        target_chain: &[usize],
+    ) -> DispatchOutcome {
+        for &target_id in target_chain {
+            let mut path = Vec::new();
+            if self.component_id == Some(target_id) || self.find_path(target_id, &mut path) {
+                let propagation = self.run_phases(event, &path, Some(target_id));
+                return DispatchOutcome {
+                    target: Some(target_id),
+                    propagation_stopped: propagation.is_propagation_stopped(),
+                    default_prevented: propagation.is_default_prevented(),
+                };
+            }
         }
 
-        // If this is the target, stop capturing phase
-        if self.component_id == target_id {
-            return;
+        DispatchOutcome {
+            target: None,
+            propagation_stopped: false,
+            default_prevented: false,
         }
+    }
 
-        // Otherwise, continue capturing phase down to children
-        for child in &self.children {
-            if let Ok(child) = child.lock() {
-                if !propagation.is_propagation_stopped() {
-                    child.dispatch_capturing(event, propagation, target_id);
-                }
-            }
-        }
+    /// Dispatch an event to one delegate in this subtree
+    ///
+    /// Returns `false` if no delegate in the subtree has `target_id`.
+    pub fn dispatch_to<E: Event + 'static>(&self, event: &E, target_id: usize) -> bool {
+        self.dispatch_event(event, &[target_id]).target().is_some()
     }
 
-    // Internal method to handle bubbling phase
-    fn dispatch_bubbling<E: Event + 'static>(
+    /// Run the capturing, target and bubbling phases along a path from `find_path`
+    fn run_phases<E: Event + 'static>(
         &self,
         event: &E,
-        propagation: &mut EventPropagation,
-        _target_id: Option<usize>,
-    ) {
-        // Handle this component's bubbling handlers
-        if !propagation.is_propagation_stopped() {
-            propagation.current_target_id = self.component_id;
-            self.handle_bubbling_event(event, propagation);
+        path: &[Arc<Mutex<EventDelegate>>],
+        target_id: Option<usize>,
+    ) -> EventPropagation {
+        let target_depth = path.len();
+        let mut propagation = EventPropagation::new(PropagationPhase::Capturing);
+        propagation.target_id = target_id;
+
+        for depth in 0..target_depth {
+            self.with_path_delegate(path, depth, |delegate| {
+                propagation.current_target_id = delegate.component_id;
+                Self::run_handlers(&delegate.capturing_handlers, event, &propagation);
+            });
+            if propagation.is_propagation_stopped() {
+                return propagation;
+            }
         }
 
-        // Continue bubbling up to parent
-        if !propagation.is_propagation_stopped() {
-            if let Some(parent) = &self.parent {
-                if let Ok(parent) = parent.lock() {
-                    parent.dispatch_bubbling(event, propagation, _target_id);
-                }
+        propagation.phase = PropagationPhase::Target;
+        self.with_path_delegate(path, target_depth, |delegate| {
+            propagation.current_target_id = delegate.component_id;
+            Self::run_handlers(&delegate.target_handlers, event, &propagation);
+        });
+
+        propagation.phase = PropagationPhase::Bubbling;
+        for depth in (0..=target_depth).rev() {
+            if propagation.is_propagation_stopped() {
+                break;
             }
+            self.with_path_delegate(path, depth, |delegate| {
+                propagation.current_target_id = delegate.component_id;
+                Self::run_handlers(&delegate.bubbling_handlers, event, &propagation);
+            });
         }
+        propagation
     }
 
-    // Internal method to handle events during capturing phase
-    fn handle_capturing_event<E: Event + 'static>(
-        &self,
-        event: &E,
-        propagation: &EventPropagation,
-    ) {
-        let type_id = TypeId::of::<E>();
-        if let Ok(handlers) = self.capturing_handlers.read() {
-            if let Some(handlers) = handlers.get(&type_id) {
-                let mut boxed_event = event.box_clone();
-                for handler in handlers {
-                    handler(boxed_event.as_mut(), propagation);
-                    if propagation.is_propagation_stopped() {
-                        break;
-                    }
-                }
+    /// Collect the delegates from this one's children down to `target_id`
+    fn find_path(&self, target_id: usize, path: &mut Vec<Arc<Mutex<EventDelegate>>>) -> bool {
+        for child in &self.children {
+            let Ok(delegate) = child.lock() else {
+                continue;
+            };
+            path.push(child.clone());
+            if delegate.component_id == Some(target_id) || delegate.find_path(target_id, path) {
+                return true;
             }
+            path.pop();
         }
+        false
     }
 
-    // Internal method to handle events during bubbling phase
-    fn handle_bubbling_event<E: Event + 'static>(&self, event: &E, propagation: &EventPropagation) {
-        let type_id = TypeId::of::<E>();
-        if let Ok(handlers) = self.bubbling_handlers.read() {
-            if let Some(handlers) = handlers.get(&type_id) {
-                let mut boxed_event = event.box_clone();
-                for handler in handlers {
-                    handler(boxed_event.as_mut(), propagation);
-                    if propagation.is_propagation_stopped() {
-                        break;
-                    }
-                }
-            }
+    /// Run `f` on the delegate at `depth` along a path found by `find_path`
+    fn with_path_delegate(
+        &self,
+        path: &[Arc<Mutex<EventDelegate>>],
+        depth: usize,
+        f: impl FnOnce(&EventDelegate),
+    ) {
+        if depth == 0 {
+            f(self);
+        } else if let Ok(delegate) = path[depth - 1].lock() {
+            f(&delegate);
         }
     }
 
-    // Internal method to handle events at the target
-    fn handle_event<E: Event + 'static>(&self, event: &E, propagation: &EventPropagation) {
+    /// Run the handlers registered for an event's type until one stops propagation
+    fn run_handlers<E: Event + 'static>(
+        handlers: &HandlerMap,
+        event: &E,
+        propagation: &EventPropagation,
+    ) {
         let type_id = TypeId::of::<E>();
-        if let Ok(handlers) = self.target_handlers.read() {
+        if let Ok(handlers) = handlers.read() {
             if let Some(handlers) = handlers.get(&type_id) {
                 let mut boxed_event = event.box_clone();
                 for handler in handlers {
@@ -386,3 +467,127 @@ pub fn build_delegation_tree(
 
     delegate
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Click;
+
+    type Log = Arc<Mutex<Vec<(String, PropagationPhase)>>>;
+
+    fn recorder(log: &Log, name: &str) -> impl Fn(&Click, &EventPropagation) + Send + Sync {
+        let (log, name) = (log.clone(), name.to_string());
+        move |_, propagation| {
+            log.lock().unwrap().push((name.clone(), propagation.phase));
+        }
+    }
+
+    /// Build root(1) > parent(2) > child(3) with every phase recorded
+    fn tree(log: &Log) -> EventDelegate {
+        let mut root = EventDelegate::new(Some(1));
+        let parent = Arc::new(Mutex::new(EventDelegate::new(Some(2))));
+        let child = Arc::new(Mutex::new(EventDelegate::new(Some(3))));
+
+        for (delegate, name) in [(&parent, "parent"), (&child, "child")] {
+            let delegate = delegate.lock().unwrap();
+            delegate.capture(recorder(log, name));
+            delegate.on(recorder(log, name));
+            delegate.bubble(recorder(log, name));
+        }
+        root.capture(recorder(log, "root"));
+        root.bubble(recorder(log, "root"));
+
+        parent.lock().unwrap().add_child(child);
+        root.add_child(parent);
+        root
+    }
+
+    #[test]
+    fn test_capture_runs_before_target_then_bubble() {
+        let log = Log::default();
+        let root = tree(&log);
+
+        assert_eq!(root.dispatch_event(&Click, &[3, 2, 1]).target(), Some(3));
+
+        use PropagationPhase::*;
+        let expected = [
+            ("root", Capturing),
+            ("parent", Capturing),
+            ("child", Target),
+            ("child", Bubbling),
+            ("parent", Bubbling),
+            ("root", Bubbling),
+        ];
+        let log = log.lock().unwrap();
+        let actual: Vec<_> = log
+            .iter()
+            .map(|(name, phase)| (name.as_str(), *phase))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_stop_propagation_during_capture_skips_target() {
+        let log = Log::default();
+        let root = tree(&log);
+        root.capture(|_: &Click, propagation: &EventPropagation| {
+            propagation.stop_propagation();
+        });
+
+        let outcome = root.dispatch_event(&Click, &[3, 2, 1]);
+        assert!(outcome.propagation_stopped());
+
+        let log = log.lock().unwrap();
+        assert_eq!(
+            *log,
+            vec![("root".to_string(), PropagationPhase::Capturing)]
+        );
+    }
+
+    #[test]
+    fn test_chain_falls_back_to_nearest_delegate() {
+        let log = Log::default();
+        let root = tree(&log);
+
+        // Component 4 has no delegate, so its parent becomes the target
+        assert_eq!(root.dispatch_event(&Click, &[4, 2, 1]).target(), Some(2));
+        assert!(log
+            .lock()
+            .unwrap()
+            .contains(&("parent".to_string(), PropagationPhase::Target)));
+        assert_eq!(root.dispatch_event(&Click, &[4, 5]).target(), None);
+    }
+
+    #[test]
+    fn test_child_stop_propagation_skips_parent_bubble() {
+        let parent_ran = Arc::new(AtomicBool::new(false));
+        let mut parent = EventDelegate::new(Some(1));
+        let ran = parent_ran.clone();
+        parent.listen(
+            PropagationPhase::Bubbling,
+            move |_: &DelegatedEvent<Click>| {
+                ran.store(true, Ordering::SeqCst);
+            },
+        );
+
+        let child = EventDelegate::new(Some(2));
+        child.listen(PropagationPhase::Target, |event: &DelegatedEvent<Click>| {
+            assert_eq!(event.phase(), PropagationPhase::Target);
+            event.prevent_default();
+            event.stop_propagation();
+        });
+        parent.add_child(Arc::new(Mutex::new(child)));
+
+        let outcome = parent.dispatch_event(&Click, &[2, 1]);
+        assert!(!parent_ran.load(Ordering::SeqCst));
+        assert!(outcome.propagation_stopped());
+        assert!(outcome.default_prevented());
+
+        // Targeting the parent directly never reaches the child
+        let outcome = parent.dispatch_event(&Click, &[1]);
+        assert!(parent_ran.load(Ordering::SeqCst));
+        assert!(!outcome.default_prevented());
+    }
+}
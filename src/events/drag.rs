@@ -0,0 +1,286 @@
+//! Drag gesture recognition
+//!
+//! A drag starts when the pointer goes down on a drag source and then moves
+//! at least a threshold distance, so clicks with a little jitter never turn
+//! into drags.
+
+use crate::{component::ComponentId, layout::Point};
+
+/// Default distance in logical pixels the pointer must move to start a drag
+pub const DEFAULT_DRAG_THRESHOLD: f32 = 4.0;
+
+/// Raw pointer input fed to a [`DragRecognizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerInput {
+    /// The primary button went down at a position
+    Down(Point),
+    /// The pointer moved to a position
+    Move(Point),
+    /// The primary button was released at a position
+    Up(Point),
+    /// The pointer left the window
+    Leave,
+}
+
+/// Where a drag came from and how far it has moved
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragInfo {
+    /// Drag source the pointer went down on
+    pub source: ComponentId,
+    /// Pointer position when the button went down
+    pub start: Point,
+    /// Distance moved from `start`
+    pub delta: Point,
+}
+
+impl DragInfo {
+    /// Current pointer position
+    pub fn position(&self) -> Point {
+        Point::new(self.start.x + self.delta.x, self.start.y + self.delta.y)
+    }
+}
+
+/// A step of a drag gesture
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragEvent {
+    /// The pointer crossed the threshold and the drag began
+    Start(DragInfo),
+    /// The pointer moved during the drag
+    Move(DragInfo),
+    /// The button was released, completing the drag
+    End(DragInfo),
+    /// The drag was abandoned, for example because the pointer left the window
+    Cancel(DragInfo),
+}
+
+impl DragEvent {
+    /// Get the drag details common to every step
+    pub fn info(&self) -> &DragInfo {
+        match self {
+            DragEvent::Start(info)
+            | DragEvent::Move(info)
+            | DragEvent::End(info)
+            | DragEvent::Cancel(info) => info,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DragState {
+    /// No button is down on a drag source
+    Idle,
+    /// The button is down but the pointer has not moved far enough
+    Pending { source: ComponentId, start: Point },
+    /// A drag is in progress
+    Dragging(DragInfo),
+}
+
+/// Turns pointer input into drag events
+#[derive(Debug, Clone)]
+pub struct DragRecognizer {
+    threshold: f32,
+    state: DragState,
+}
+
+impl DragRecognizer {
+    /// Create a recognizer with the default threshold
+    pub fn new() -> Self {
+        Self::with_threshold(DEFAULT_DRAG_THRESHOLD)
+    }
+
+    /// Create a recognizer that starts drags after moving `threshold` pixels
+    pub fn with_threshold(threshold: f32) -> Self {
+        Self {
+            threshold,
+            state: DragState::Idle,
+        }
+    }
+
+    /// Distance the pointer must move to start a drag
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Whether a drag is in progress
+    pub fn is_dragging(&self) -> bool {
+        matches!(self.state, DragState::Dragging(_))
+    }
+
+    /// The button went down on `source`
+    ///
+    /// Cancels any drag still in progress, which can happen if the release
+    /// was never delivered.
+    pub fn pointer_down(&mut self, source: ComponentId, position: Point) -> Option<DragEvent> {
+        let cancelled = self.pointer_leave();
+        self.state = DragState::Pending {
+            source,
+            start: position,
+        };
+        cancelled
+    }
+
+    /// The pointer moved
+    pub fn pointer_move(&mut self, position: Point) -> Option<DragEvent> {
+        match self.state {
+            DragState::Idle => None,
+            DragState::Pending { source, start } => {
+                let delta = Point::new(position.x - start.x, position.y - start.y);
+                if delta.x.hypot(delta.y) < self.threshold {
+                    return None;
+                }
+                let info = DragInfo {
+                    source,
+                    start,
+                    delta,
+                };
+                self.state = DragState::Dragging(info);
+                Some(DragEvent::Start(info))
+            }
+            DragState::Dragging(mut info) => {
+                info.delta = Point::new(position.x - info.start.x, position.y - info.start.y);
+                self.state = DragState::Dragging(info);
+                Some(DragEvent::Move(info))
+            }
+        }
+    }
+
+    /// The button was released
+    ///
+    /// Releasing before the threshold was crossed ends the gesture without
+    /// any drag events, leaving it to be handled as a click.
+    pub fn pointer_up(&mut self, position: Point) -> Option<DragEvent> {
+        match std::mem::replace(&mut self.state, DragState::Idle) {
+            DragState::Dragging(mut info) => {
+                info.delta = Point::new(position.x - info.start.x, position.y - info.start.y);
+                Some(DragEvent::End(info))
+            }
+            DragState::Idle | DragState::Pending { .. } => None,
+        }
+    }
+
+    /// The pointer left the window, cancelling any drag in progress
+    pub fn pointer_leave(&mut self) -> Option<DragEvent> {
+        match std::mem::replace(&mut self.state, DragState::Idle) {
+            DragState::Dragging(info) => Some(DragEvent::Cancel(info)),
+            DragState::Idle | DragState::Pending { .. } => None,
+        }
+    }
+}
+
+impl Default for DragRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventDelegate, EventPropagation, EventSystem};
+    use crate::layout::{LayoutNode, LayoutStyle, Rect};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_jitter_under_threshold_never_starts_drag() {
+        let source = ComponentId::new();
+        let mut drag = DragRecognizer::new();
+
+        assert_eq!(drag.pointer_down(source, Point::new(10.0, 10.0)), None);
+        for (x, y) in [(11.0, 10.0), (12.0, 12.0), (9.0, 8.0), (13.0, 10.0)] {
+            assert_eq!(drag.pointer_move(Point::new(x, y)), None);
+        }
+        assert!(!drag.is_dragging());
+        assert_eq!(drag.pointer_up(Point::new(12.0, 11.0)), None);
+    }
+
+    #[test]
+    fn test_crossing_threshold_starts_drag() {
+        let source = ComponentId::new();
+        let start = Point::new(10.0, 10.0);
+        let mut drag = DragRecognizer::new();
+        drag.pointer_down(source, start);
+
+        let info = |dx, dy| DragInfo {
+            source,
+            start,
+            delta: Point::new(dx, dy),
+        };
+        assert_eq!(
+            drag.pointer_move(Point::new(14.0, 10.0)),
+            Some(DragEvent::Start(info(4.0, 0.0)))
+        );
+        assert_eq!(
+            drag.pointer_move(Point::new(20.0, 15.0)),
+            Some(DragEvent::Move(info(10.0, 5.0)))
+        );
+        assert_eq!(
+            drag.pointer_up(Point::new(21.0, 15.0)),
+            Some(DragEvent::End(info(11.0, 5.0)))
+        );
+        assert!(!drag.is_dragging());
+    }
+
+    #[test]
+    fn test_leaving_window_cancels_drag() {
+        let source = ComponentId::new();
+        let mut drag = DragRecognizer::with_threshold(1.0);
+        drag.pointer_down(source, Point::zero());
+        drag.pointer_move(Point::new(5.0, 0.0));
+
+        let cancel = drag.pointer_leave().unwrap();
+        assert!(matches!(cancel, DragEvent::Cancel(_)));
+        assert_eq!(cancel.info().position(), Point::new(5.0, 0.0));
+        assert_eq!(drag.pointer_move(Point::new(6.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_event_system_drags_registered_source() {
+        let mut item = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        item.layout.rect = Rect::new(0.0, 0.0, 100.0, 20.0);
+        let item_id = item.id;
+        let mut list = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        list.layout.rect = Rect::new(0.0, 0.0, 100.0, 200.0);
+        list.add_child(item);
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let item_delegate = EventDelegate::new(Some(item_id.id() as usize));
+        let log = received.clone();
+        item_delegate.on(move |event: &DragEvent, _: &EventPropagation| {
+            log.lock().unwrap().push(*event);
+        });
+
+        let mut events = EventSystem::new();
+        events
+            .delegator_mut()
+            .add_child(Arc::new(Mutex::new(item_delegate)));
+        events.register_drag_source(item_id);
+
+        // Pressing outside any drag source does nothing
+        let outside = Point::new(50.0, 100.0);
+        events
+            .process_drag_input(PointerInput::Down(outside), &list)
+            .unwrap();
+        let moved = events
+            .process_drag_input(PointerInput::Move(Point::new(50.0, 150.0)), &list)
+            .unwrap();
+        assert_eq!(moved, None);
+        events
+            .process_drag_input(PointerInput::Up(outside), &list)
+            .unwrap();
+
+        events
+            .process_drag_input(PointerInput::Down(Point::new(10.0, 10.0)), &list)
+            .unwrap();
+        let started = events
+            .process_drag_input(PointerInput::Move(Point::new(10.0, 30.0)), &list)
+            .unwrap();
+        assert!(matches!(started, Some(DragEvent::Start(info)) if info.source == item_id));
+        events
+            .process_drag_input(PointerInput::Leave, &list)
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert!(matches!(received[1], DragEvent::Cancel(_)));
+    }
+}
@@ -0,0 +1,176 @@
+//! Keyboard focus tracking for Orbit UI framework
+//!
+//! The tab order is the document order of focusable nodes in the layout tree,
+//! so moving focus forward visits parents before their children and earlier
+//! siblings before later ones.
+
+use crate::{component::ComponentId, layout::LayoutNode};
+
+use super::EventError;
+
+/// Tracks which component has keyboard focus
+#[derive(Debug, Default, Clone)]
+pub struct FocusManager {
+    /// Component that currently has focus
+    focused: Option<ComponentId>,
+    /// Focusable components in tab order
+    tab_order: Vec<ComponentId>,
+}
+
+impl FocusManager {
+    /// Create a focus manager with nothing focusable
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the focused component
+    pub fn focused(&self) -> Option<ComponentId> {
+        self.focused
+    }
+
+    /// Get the focusable components in tab order
+    pub fn tab_order(&self) -> &[ComponentId] {
+        &self.tab_order
+    }
+
+    /// Rebuild the tab order from the focusable nodes in a layout tree
+    ///
+    /// Focus is kept if the focused component is still focusable, and
+    /// cleared otherwise.
+    pub fn update_tab_order(&mut self, layout_root: &LayoutNode) {
+        self.tab_order.clear();
+        Self::collect_focusable(layout_root, &mut self.tab_order);
+
+        if let Some(focused) = self.focused {
+            if !self.tab_order.contains(&focused) {
+                self.focused = None;
+            }
+        }
+    }
+
+    fn collect_focusable(node: &LayoutNode, order: &mut Vec<ComponentId>) {
+        if node.focusable {
+            order.push(node.id);
+        }
+        for child in &node.children {
+            Self::collect_focusable(child, order);
+        }
+    }
+
+    /// Move focus to a component
+    ///
+    /// Fails if the component is not in the tab order.
+    pub fn focus(&mut self, id: ComponentId) -> Result<(), EventError> {
+        if !self.tab_order.contains(&id) {
+            return Err(EventError::ComponentNotFound(id));
+        }
+        self.focused = Some(id);
+        Ok(())
+    }
+
+    /// Clear focus, returning the component that had it
+    pub fn blur(&mut self) -> Option<ComponentId> {
+        self.focused.take()
+    }
+
+    /// Move focus to the next component in tab order, wrapping at the end
+    ///
+    /// With nothing focused, the first component receives focus.
+    pub fn focus_next(&mut self) -> Option<ComponentId> {
+        let len = self.tab_order.len();
+        let next = match self.focused_index() {
+            Some(index) => (index + 1) % len,
+            None => 0,
+        };
+        self.focus_index(next)
+    }
+
+    /// Move focus to the previous component in tab order, wrapping at the start
+    ///
+    /// With nothing focused, the last component receives focus.
+    pub fn focus_prev(&mut self) -> Option<ComponentId> {
+        let len = self.tab_order.len();
+        let prev = match self.focused_index() {
+            Some(index) => (index + len - 1) % len,
+            None => len.wrapping_sub(1),
+        };
+        self.focus_index(prev)
+    }
+
+    fn focused_index(&self) -> Option<usize> {
+        let focused = self.focused?;
+        self.tab_order.iter().position(|id| *id == focused)
+    }
+
+    fn focus_index(&mut self, index: usize) -> Option<ComponentId> {
+        self.focused = self.tab_order.get(index).copied();
+        self.focused
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::LayoutStyle;
+
+    fn node(focusable: bool) -> LayoutNode {
+        LayoutNode::new(ComponentId::new(), LayoutStyle::default()).with_focusable(focusable)
+    }
+
+    #[test]
+    fn test_focus_next_cycles_in_layout_order() {
+        // root
+        // ├── form
+        // │   ├── name (focusable)
+        // │   └── email (focusable)
+        // ├── label
+        // └── submit (focusable)
+        let mut form = node(false);
+        let name = node(true);
+        let email = node(true);
+        let (name_id, email_id) = (name.id, email.id);
+        form.add_child(name);
+        form.add_child(email);
+
+        let submit = node(true);
+        let submit_id = submit.id;
+        let mut root = node(false);
+        root.add_child(form);
+        root.add_child(node(false));
+        root.add_child(submit);
+
+        let mut focus = FocusManager::new();
+        focus.update_tab_order(&root);
+        assert_eq!(focus.tab_order(), &[name_id, email_id, submit_id]);
+
+        assert_eq!(focus.focus_next(), Some(name_id));
+        assert_eq!(focus.focus_next(), Some(email_id));
+        assert_eq!(focus.focus_next(), Some(submit_id));
+        assert_eq!(focus.focus_next(), Some(name_id));
+
+        assert_eq!(focus.focus_prev(), Some(submit_id));
+        assert_eq!(focus.blur(), Some(submit_id));
+        assert_eq!(focus.focus_prev(), Some(submit_id));
+    }
+
+    #[test]
+    fn test_focus_requires_focusable_node() {
+        let mut root = node(false);
+        let field = node(true);
+        let field_id = field.id;
+        root.add_child(field);
+
+        let mut focus = FocusManager::new();
+        focus.update_tab_order(&root);
+
+        assert!(focus.focus(root.id).is_err());
+        focus.focus(field_id).unwrap();
+        assert_eq!(focus.focused(), Some(field_id));
+
+        // Focus is dropped once the node is no longer in the tree
+        root.remove_child(field_id);
+        focus.update_tab_order(&root);
+        assert_eq!(focus.focused(), None);
+        assert_eq!(focus.focus_next(), None);
+    }
+}
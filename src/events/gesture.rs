@@ -0,0 +1,307 @@
+//! Tap, double-tap and long-press recognition
+//!
+//! A press becomes a gesture only while the pointer stays within a small
+//! distance of where it went down. Holding still long enough is a long
+//! press; releasing sooner is a tap, or a double tap when it closely follows
+//! another tap at the same spot.
+
+use std::time::{Duration, Instant};
+
+use crate::{component::ComponentId, layout::Point};
+
+/// Default distance in logical pixels the pointer may move during a press
+pub const DEFAULT_TAP_SLOP: f32 = 8.0;
+
+/// Default time the pointer must be held still for a long press
+pub const DEFAULT_LONG_PRESS_DELAY: Duration = Duration::from_millis(500);
+
+/// Default longest time between two taps for them to form a double tap
+pub const DEFAULT_DOUBLE_TAP_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Time and movement thresholds for gesture recognition
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// Distance the pointer may move before a press stops counting as one
+    pub slop: f32,
+    /// Time the pointer must be held still for a long press
+    pub long_press_delay: Duration,
+    /// Longest time from one tap's release to the next for a double tap
+    pub double_tap_interval: Duration,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            slop: DEFAULT_TAP_SLOP,
+            long_press_delay: DEFAULT_LONG_PRESS_DELAY,
+            double_tap_interval: DEFAULT_DOUBLE_TAP_INTERVAL,
+        }
+    }
+}
+
+/// A recognized gesture
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// The pointer was pressed and released quickly without moving
+    Tap {
+        target: ComponentId,
+        position: Point,
+    },
+    /// A tap closely followed a previous one at the same spot
+    DoubleTap {
+        target: ComponentId,
+        position: Point,
+    },
+    /// The pointer was held still past the long-press delay
+    LongPress {
+        target: ComponentId,
+        position: Point,
+    },
+}
+
+impl Gesture {
+    /// Component the pointer went down on
+    pub fn target(&self) -> ComponentId {
+        match self {
+            Gesture::Tap { target, .. }
+            | Gesture::DoubleTap { target, .. }
+            | Gesture::LongPress { target, .. } => *target,
+        }
+    }
+
+    /// Where the gesture happened
+    pub fn position(&self) -> Point {
+        match self {
+            Gesture::Tap { position, .. }
+            | Gesture::DoubleTap { position, .. }
+            | Gesture::LongPress { position, .. } => *position,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Press {
+    target: ComponentId,
+    start: Point,
+    at: Instant,
+    /// Whether a long press has already been reported for this press
+    long_pressed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LastTap {
+    target: ComponentId,
+    position: Point,
+    at: Instant,
+}
+
+/// Turns pointer input into tap, double-tap and long-press gestures
+///
+/// A tap is reported on release, and a second tap on the same target within
+/// the double-tap interval and slop is reported as a double tap instead.
+/// A long press is reported once the delay has passed, either by the next
+/// input or by [`tick`](Self::tick) while the pointer is held, and its
+/// release reports nothing.
+#[derive(Debug, Clone)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    press: Option<Press>,
+    last_tap: Option<LastTap>,
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer with the default thresholds
+    pub fn new() -> Self {
+        Self::with_config(GestureConfig::default())
+    }
+
+    /// Create a recognizer with custom thresholds
+    pub fn with_config(config: GestureConfig) -> Self {
+        Self {
+            config,
+            press: None,
+            last_tap: None,
+        }
+    }
+
+    /// Thresholds in use
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Whether a press is being tracked
+    pub fn is_pressed(&self) -> bool {
+        self.press.is_some()
+    }
+
+    /// The button went down on `target`
+    pub fn pointer_down(&mut self, target: ComponentId, position: Point, now: Instant) {
+        self.press = Some(Press {
+            target,
+            start: position,
+            at: now,
+            long_pressed: false,
+        });
+    }
+
+    /// The pointer moved
+    ///
+    /// Moving beyond the slop abandons the press, so it becomes neither a tap
+    /// nor a long press.
+    pub fn pointer_move(&mut self, position: Point, now: Instant) -> Option<Gesture> {
+        let press = self.press?;
+        if distance(press.start, position) > self.config.slop {
+            self.press = None;
+            return None;
+        }
+        self.tick(now)
+    }
+
+    /// The button was released
+    pub fn pointer_up(&mut self, position: Point, now: Instant) -> Option<Gesture> {
+        let long_press = self.tick(now);
+        let press = self.press.take()?;
+        if long_press.is_some() || press.long_pressed {
+            self.last_tap = None;
+            return long_press;
+        }
+        if distance(press.start, position) > self.config.slop {
+            return None;
+        }
+
+        let double = self.last_tap.take().is_some_and(|last| {
+            last.target == press.target
+                && now.saturating_duration_since(last.at) <= self.config.double_tap_interval
+                && distance(last.position, position) <= self.config.slop
+        });
+        let target = press.target;
+        if double {
+            return Some(Gesture::DoubleTap { target, position });
+        }
+        self.last_tap = Some(LastTap {
+            target,
+            position,
+            at: now,
+        });
+        Some(Gesture::Tap { target, position })
+    }
+
+    /// The pointer left the window, abandoning any press
+    pub fn pointer_leave(&mut self) {
+        self.press = None;
+        self.last_tap = None;
+    }
+
+    /// Report a long press once the pointer has been held long enough
+    ///
+    /// Call once per frame while [`is_pressed`](Self::is_pressed), since
+    /// holding still produces no input of its own.
+    pub fn tick(&mut self, now: Instant) -> Option<Gesture> {
+        let press = self.press.as_mut()?;
+        if press.long_pressed
+            || now.saturating_duration_since(press.at) < self.config.long_press_delay
+        {
+            return None;
+        }
+        press.long_pressed = true;
+        Some(Gesture::LongPress {
+            target: press.target,
+            position: press.start,
+        })
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventDelegate, EventPropagation, EventSystem, PointerInput};
+    use crate::layout::{LayoutNode, LayoutStyle, Rect};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_quick_press_is_a_tap_and_second_is_a_double_tap() {
+        let target = ComponentId::new();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut gestures = GestureRecognizer::new();
+
+        gestures.pointer_down(target, Point::new(10.0, 10.0), at(0));
+        assert_eq!(gestures.pointer_move(Point::new(13.0, 12.0), at(40)), None);
+        let position = Point::new(14.0, 12.0);
+        assert_eq!(
+            gestures.pointer_up(position, at(90)),
+            Some(Gesture::Tap { target, position })
+        );
+
+        gestures.pointer_down(target, position, at(250));
+        assert_eq!(
+            gestures.pointer_up(position, at(300)),
+            Some(Gesture::DoubleTap { target, position })
+        );
+
+        // Moving past the slop is neither a tap nor a long press
+        gestures.pointer_down(target, position, at(1000));
+        gestures.pointer_move(Point::new(40.0, 12.0), at(1050));
+        assert_eq!(gestures.tick(at(2000)), None);
+        assert_eq!(gestures.pointer_up(position, at(2000)), None);
+    }
+
+    #[test]
+    fn test_holding_still_is_a_long_press() {
+        let mut button = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        button.layout.rect = Rect::new(0.0, 0.0, 100.0, 40.0);
+        let button_id = button.id;
+        let mut root = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        root.layout.rect = Rect::new(0.0, 0.0, 200.0, 200.0);
+        root.add_child(button);
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let delegate = EventDelegate::new(Some(button_id.id() as usize));
+        let log = received.clone();
+        delegate.on(move |gesture: &Gesture, _: &EventPropagation| {
+            log.lock().unwrap().push(*gesture);
+        });
+        let mut events = EventSystem::new();
+        events
+            .delegator_mut()
+            .add_child(Arc::new(Mutex::new(delegate)));
+
+        let start = Instant::now();
+        let position = Point::new(20.0, 20.0);
+        events
+            .process_gesture_input(PointerInput::Down(position), start, &root)
+            .unwrap();
+        let early = start + Duration::from_millis(200);
+        assert_eq!(events.process_gesture_tick(early, &root).unwrap(), None);
+
+        let held = start + Duration::from_millis(520);
+        let long_press = Gesture::LongPress {
+            target: button_id,
+            position,
+        };
+        assert_eq!(
+            events.process_gesture_tick(held, &root).unwrap(),
+            Some(long_press)
+        );
+
+        // Releasing afterwards isn't also a tap
+        let up = start + Duration::from_millis(700);
+        assert_eq!(
+            events
+                .process_gesture_input(PointerInput::Up(position), up, &root)
+                .unwrap(),
+            None
+        );
+        assert_eq!(*received.lock().unwrap(), vec![long_press]);
+    }
+}
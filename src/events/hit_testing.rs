@@ -3,12 +3,19 @@
 //! This module provides efficient hit testing algorithms that work with
 //! the layout system to determine which components should receive events
 //! based on their position and layout properties.
+//!
+//! Points are given in screen space. Each node's transform, combined with
+//! those of its ancestors, is inverted to bring the point back into the
+//! node's layout space before testing it against the node's box.
 
 use std::collections::VecDeque;
 
 use crate::{
     component::ComponentId,
-    layout::{LayoutNode, Point, Rect},
+    layout::{
+        invert_matrix, multiply_matrices, transform_point, LayoutNode, Point, Rect,
+        TransformMatrix, IDENTITY_MATRIX,
+    },
 };
 
 use super::EventError;
@@ -53,7 +60,7 @@ impl HitTester {
         self.stats.hits_found = 0;
 
         let mut hits = Vec::new();
-        self.hit_test_recursive(point, layout_root, &mut hits)?;
+        self.hit_test_recursive(point, layout_root, &IDENTITY_MATRIX, &mut hits)?;
 
         // Update statistics
         let elapsed = start_time.elapsed();
@@ -67,22 +74,35 @@ impl HitTester {
     }
 
     /// Recursive hit testing implementation
+    ///
+    /// `parent_matrix` maps the parent's layout space to the screen.
     fn hit_test_recursive(
         &mut self,
         point: Point,
         node: &LayoutNode,
+        parent_matrix: &TransformMatrix,
         hits: &mut Vec<ComponentId>,
     ) -> Result<(), EventError> {
         self.stats.nodes_tested += 1;
 
-        // Check if point is within this node's bounds
-        if node.layout.rect.contains_point(point) {
+        let matrix = Self::screen_matrix(parent_matrix, node);
+        let Some(local) = Self::to_layout_space(&matrix, point) else {
+            return Ok(());
+        };
+
+        // Check if point is within this node's visible shape
+        if Self::node_contains(node, local) {
             // Add this node to hits (will be at the front for depth ordering)
             hits.insert(0, node.id);
 
+            // Children are only hit inside this node's clip region
+            if !Self::clip_contains(node, local) {
+                return Ok(());
+            }
+
             // Test children in reverse order (back to front)
             for child in node.children.iter().rev() {
-                self.hit_test_recursive(point, child, hits)?;
+                self.hit_test_recursive(point, child, &matrix, hits)?;
             }
         }
 
@@ -101,17 +121,23 @@ impl HitTester {
 
         let mut hits = Vec::new();
         let mut stack = VecDeque::new();
-        stack.push_back(layout_root);
+        stack.push_back((layout_root, IDENTITY_MATRIX));
 
-        while let Some(node) = stack.pop_back() {
+        while let Some((node, parent_matrix)) = stack.pop_back() {
             self.stats.nodes_tested += 1;
 
-            if node.layout.rect.contains_point(point) {
+            let matrix = Self::screen_matrix(&parent_matrix, node);
+            let Some(local) = Self::to_layout_space(&matrix, point) else {
+                continue;
+            };
+            if Self::node_contains(node, local) {
                 hits.push(node.id);
 
                 // Add children to stack in reverse order for proper traversal
-                for child in node.children.iter().rev() {
-                    stack.push_back(child);
+                if Self::clip_contains(node, local) {
+                    for child in node.children.iter().rev() {
+                        stack.push_back((child, matrix));
+                    }
                 }
             }
         }
@@ -138,6 +164,8 @@ impl HitTester {
     }
 
     /// Find all components within a rectangular region
+    ///
+    /// The region is compared with untransformed layout boxes.
     pub fn hit_test_region(
         &mut self,
         region: Rect,
@@ -183,6 +211,50 @@ impl HitTester {
         Ok(())
     }
 
+    /// Matrix mapping a node's layout space to the screen
+    ///
+    /// A node's transform is relative to the top-left corner of its box and
+    /// applies after those of its ancestors.
+    fn screen_matrix(parent_matrix: &TransformMatrix, node: &LayoutNode) -> TransformMatrix {
+        if node.layout.transform_matrix == IDENTITY_MATRIX {
+            return *parent_matrix;
+        }
+        let origin = node.layout.rect.origin;
+        let to_box = [1.0, 0.0, 0.0, 1.0, -origin.x, -origin.y];
+        let from_box = [1.0, 0.0, 0.0, 1.0, origin.x, origin.y];
+        let local = multiply_matrices(
+            &from_box,
+            &multiply_matrices(&node.layout.transform_matrix, &to_box),
+        );
+        multiply_matrices(parent_matrix, &local)
+    }
+
+    /// Map a screen point into the layout space `matrix` maps from
+    ///
+    /// Returns `None` for a matrix that flattens the node, which then can't
+    /// be hit at all.
+    fn to_layout_space(matrix: &TransformMatrix, point: Point) -> Option<Point> {
+        if *matrix == IDENTITY_MATRIX {
+            return Some(point);
+        }
+        invert_matrix(matrix).map(|inverse| transform_point(&inverse, point))
+    }
+
+    /// Check if a point is inside a node's bounds, excluding rounded-off corners
+    fn node_contains(node: &LayoutNode, point: Point) -> bool {
+        match &node.style.border_radius {
+            Some(radius) => radius.contains_point(node.layout.rect, point),
+            None => node.layout.rect.contains_point(point),
+        }
+    }
+
+    /// Check if a point is inside the region a node clips its children to
+    fn clip_contains(node: &LayoutNode, point: Point) -> bool {
+        node.layout
+            .clip_rect
+            .is_none_or(|clip| clip.contains_point(point))
+    }
+
     /// Check if two rectangles intersect
     fn rect_intersects(&self, rect1: Rect, rect2: Rect) -> bool {
         rect1.x() < rect2.max_x()
@@ -270,6 +342,106 @@ mod tests {
         assert_eq!(hit_tester.stats.nodes_tested, 0);
     }
 
+    #[test]
+    fn test_circle_rejects_corner() {
+        let style = LayoutStyle {
+            border_radius: Some(crate::style::BorderRadius::uniform(50.0)),
+            ..Default::default()
+        };
+        let mut circle = LayoutNode::new(ComponentId::new(), style);
+        circle.layout.rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+        let mut hit_tester = HitTester::new();
+        // Inside the bounding box but outside the circle
+        let corner = Point::new(5.0, 5.0);
+        assert!(hit_tester.hit_test(corner, &circle).unwrap().is_empty());
+        assert!(hit_tester
+            .hit_test_depth_first(corner, &circle)
+            .unwrap()
+            .is_empty());
+
+        let hits = hit_tester.hit_test(Point::new(50.0, 5.0), &circle).unwrap();
+        assert_eq!(hits, vec![circle.id]);
+        let hits = hit_tester
+            .hit_test(Point::new(85.0, 85.0), &circle)
+            .unwrap();
+        assert_eq!(hits, vec![circle.id]);
+    }
+
+    #[test]
+    fn test_clipped_child_not_hit_outside_clip() {
+        let mut parent = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        parent.layout.rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        parent.layout.clip_rect = Some(Rect::new(10.0, 10.0, 80.0, 80.0));
+
+        // The child overflows the clip on the right
+        let mut child = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        child.layout.rect = Rect::new(50.0, 20.0, 50.0, 20.0);
+        let child_id = child.id;
+        parent.add_child(child);
+
+        let mut hit_tester = HitTester::new();
+        let clipped = Point::new(95.0, 30.0);
+        assert_eq!(
+            hit_tester.hit_test(clipped, &parent).unwrap(),
+            vec![parent.id]
+        );
+        assert_eq!(
+            hit_tester.hit_test_depth_first(clipped, &parent).unwrap(),
+            vec![parent.id]
+        );
+
+        let visible = Point::new(60.0, 30.0);
+        assert_eq!(
+            hit_tester.hit_test(visible, &parent).unwrap(),
+            vec![child_id, parent.id]
+        );
+    }
+
+    #[test]
+    fn test_rotated_element_is_hit_where_it_is_drawn() {
+        let mut root = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        root.layout.rect = Rect::new(0.0, 0.0, 400.0, 300.0);
+
+        // A square rotated 45° about its center is drawn as a diamond
+        let mut square = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        square.layout.rect = Rect::new(100.0, 100.0, 100.0, 100.0);
+        square.layout.transform_matrix =
+            crate::style::Transform::Rotate(45.0).to_matrix_about(Point::new(50.0, 50.0));
+        let square_id = square.id;
+
+        // Its child rotates along with it
+        let mut child = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        child.layout.rect = Rect::new(100.0, 100.0, 100.0, 50.0);
+        let child_id = child.id;
+        square.add_child(child);
+        root.add_child(square);
+
+        let mut hit_tester = HitTester::new();
+
+        // The diamond's top point sticks out above the untransformed box
+        let top = Point::new(150.0, 85.0);
+        assert_eq!(
+            hit_tester.hit_test(top, &root).unwrap(),
+            vec![child_id, square_id, root.id]
+        );
+        assert_eq!(
+            hit_tester.hit_test_depth_first(top, &root).unwrap(),
+            vec![root.id, square_id, child_id]
+        );
+
+        // The box's corner is no longer covered once rotated
+        let corner = Point::new(105.0, 105.0);
+        assert_eq!(hit_tester.hit_test(corner, &root).unwrap(), vec![root.id]);
+
+        // The child's half of the box now lies to the upper right of center
+        let lower_left = Point::new(130.0, 170.0);
+        assert_eq!(
+            hit_tester.hit_test(lower_left, &root).unwrap(),
+            vec![square_id, root.id]
+        );
+    }
+
     #[test]
     fn test_simple_hit_test() {
         let mut hit_tester = HitTester::new();
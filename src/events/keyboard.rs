@@ -0,0 +1,266 @@
+//! Keyboard events for Orbit UI framework
+//!
+//! Key events are routed to the focused component rather than hit tested, and
+//! bubble from there to its ancestors through event delegation.
+
+/// A logical key, after keyboard layout and modifiers are applied
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A key that produces text, such as `"a"`, `"A"` or `"1"`
+    Character(String),
+    /// Enter or Return
+    Enter,
+    /// Tab
+    Tab,
+    /// Space bar
+    Space,
+    /// Backspace
+    Backspace,
+    /// Forward delete
+    Delete,
+    /// Escape
+    Escape,
+    /// Up arrow
+    ArrowUp,
+    /// Down arrow
+    ArrowDown,
+    /// Left arrow
+    ArrowLeft,
+    /// Right arrow
+    ArrowRight,
+    /// Home
+    Home,
+    /// End
+    End,
+    /// Page up
+    PageUp,
+    /// Page down
+    PageDown,
+    /// Either Shift key
+    Shift,
+    /// Either Control key
+    Control,
+    /// Either Alt or Option key
+    Alt,
+    /// Either Windows, Command or Super key
+    Meta,
+    /// A function key, `F(1)` through `F(24)`
+    F(u8),
+    /// A key Orbit does not have a name for
+    Unidentified,
+}
+
+/// Modifier keys held during a key event
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    /// Shift is held
+    pub shift: bool,
+    /// Control is held
+    pub ctrl: bool,
+    /// Alt or Option is held
+    pub alt: bool,
+    /// Windows, Command or Super is held
+    pub meta: bool,
+}
+
+impl Modifiers {
+    /// Whether no modifier is held
+    pub fn is_empty(&self) -> bool {
+        !(self.shift || self.ctrl || self.alt || self.meta)
+    }
+}
+
+/// Whether a key went down or up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyPhase {
+    /// The key was pressed
+    Pressed,
+    /// The key was released
+    Released,
+}
+
+/// A key press or release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// The key that changed
+    pub key: Key,
+    /// Modifiers held at the time of the event
+    pub modifiers: Modifiers,
+    /// Whether this press was generated by the key being held down
+    pub repeat: bool,
+    /// Whether the key went down or up
+    pub phase: KeyPhase,
+}
+
+impl KeyEvent {
+    /// Create a key press with no modifiers
+    pub fn pressed(key: Key) -> Self {
+        Self {
+            key,
+            modifiers: Modifiers::default(),
+            repeat: false,
+            phase: KeyPhase::Pressed,
+        }
+    }
+
+    /// Create a key release with no modifiers
+    pub fn released(key: Key) -> Self {
+        Self {
+            phase: KeyPhase::Released,
+            ..Self::pressed(key)
+        }
+    }
+
+    /// Set the modifiers held during the event
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+}
+
+#[cfg(feature = "desktop")]
+mod winit_conversions {
+    use super::{Key, KeyEvent, KeyPhase, Modifiers};
+    use winit::event::ElementState;
+    use winit::keyboard::{self, NamedKey};
+
+    impl From<&keyboard::Key> for Key {
+        fn from(key: &keyboard::Key) -> Self {
+            match key {
+                keyboard::Key::Character(text) => Key::Character(text.to_string()),
+                keyboard::Key::Named(named) => match named {
+                    NamedKey::Enter => Key::Enter,
+                    NamedKey::Tab => Key::Tab,
+                    NamedKey::Space => Key::Space,
+                    NamedKey::Backspace => Key::Backspace,
+                    NamedKey::Delete => Key::Delete,
+                    NamedKey::Escape => Key::Escape,
+                    NamedKey::ArrowUp => Key::ArrowUp,
+                    NamedKey::ArrowDown => Key::ArrowDown,
+                    NamedKey::ArrowLeft => Key::ArrowLeft,
+                    NamedKey::ArrowRight => Key::ArrowRight,
+                    NamedKey::Home => Key::Home,
+                    NamedKey::End => Key::End,
+                    NamedKey::PageUp => Key::PageUp,
+                    NamedKey::PageDown => Key::PageDown,
+                    NamedKey::Shift => Key::Shift,
+                    NamedKey::Control => Key::Control,
+                    NamedKey::Alt => Key::Alt,
+                    NamedKey::Super | NamedKey::Meta => Key::Meta,
+                    NamedKey::F1 => Key::F(1),
+                    NamedKey::F2 => Key::F(2),
+                    NamedKey::F3 => Key::F(3),
+                    NamedKey::F4 => Key::F(4),
+                    NamedKey::F5 => Key::F(5),
+                    NamedKey::F6 => Key::F(6),
+                    NamedKey::F7 => Key::F(7),
+                    NamedKey::F8 => Key::F(8),
+                    NamedKey::F9 => Key::F(9),
+                    NamedKey::F10 => Key::F(10),
+                    NamedKey::F11 => Key::F(11),
+                    NamedKey::F12 => Key::F(12),
+                    _ => Key::Unidentified,
+                },
+                _ => Key::Unidentified,
+            }
+        }
+    }
+
+    impl From<keyboard::ModifiersState> for Modifiers {
+        fn from(state: keyboard::ModifiersState) -> Self {
+            Self {
+                shift: state.shift_key(),
+                ctrl: state.control_key(),
+                alt: state.alt_key(),
+                meta: state.super_key(),
+            }
+        }
+    }
+
+    impl KeyEvent {
+        /// Convert a winit `WindowEvent::KeyboardInput` event
+        ///
+        /// winit reports modifiers separately through
+        /// `WindowEvent::ModifiersChanged`, so the latest state is passed in.
+        pub fn from_winit(
+            event: &winit::event::KeyEvent,
+            modifiers: keyboard::ModifiersState,
+        ) -> Self {
+            Self {
+                key: Key::from(&event.logical_key),
+                modifiers: modifiers.into(),
+                repeat: event.repeat,
+                phase: match event.state {
+                    ElementState::Pressed => KeyPhase::Pressed,
+                    ElementState::Released => KeyPhase::Released,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::ComponentId;
+    use crate::events::{EventDelegate, EventError, EventSystem};
+    use std::sync::{Arc, Mutex};
+
+    fn delegate(id: ComponentId) -> Arc<Mutex<EventDelegate>> {
+        Arc::new(Mutex::new(EventDelegate::new(Some(id.id() as usize))))
+    }
+
+    #[test]
+    fn test_key_event_reaches_focused_component_and_bubbles() {
+        let (form_id, input_id, button_id) =
+            (ComponentId::new(), ComponentId::new(), ComponentId::new());
+        let form = delegate(form_id);
+        let input = delegate(input_id);
+        let button = delegate(button_id);
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let record = |name: &'static str| {
+            let log = log.clone();
+            move |event: &KeyEvent, _: &crate::events::EventPropagation| {
+                log.lock().unwrap().push((name, event.key.clone()));
+            }
+        };
+        form.lock().unwrap().capture(record("form capture"));
+        form.lock().unwrap().bubble(record("form bubble"));
+        input.lock().unwrap().on(record("input"));
+        button.lock().unwrap().on(record("button"));
+        form.lock().unwrap().add_child(input);
+        form.lock().unwrap().add_child(button);
+
+        let mut events = EventSystem::new();
+        events.delegator_mut().add_child(form);
+
+        let event = KeyEvent::pressed(Key::Character("a".to_string()));
+        let target = events.process_key_event(event, Some(input_id)).unwrap();
+        assert_eq!(target, Some(input_id));
+
+        let a = Key::Character("a".to_string());
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                ("form capture", a.clone()),
+                ("input", a.clone()),
+                ("form bubble", a),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_event_without_focus_target() {
+        let mut events = EventSystem::new();
+        let event = KeyEvent::pressed(Key::Enter);
+
+        assert_eq!(events.process_key_event(event.clone(), None).unwrap(), None);
+
+        let missing = ComponentId::new();
+        assert!(matches!(
+            events.process_key_event(event, Some(missing)),
+            Err(EventError::ComponentNotFound(id)) if id == missing
+        ));
+    }
+}
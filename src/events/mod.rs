@@ -6,25 +6,59 @@
 //! - Dispatcher for strongly-typed event handling
 //! - Event delegation for component event propagation
 //! - Layout-aware hit testing for precise event targeting
+//! - Keyboard events routed to the focused component
+//! - Global and scoped keyboard shortcuts, including chords
+//! - Focus tracking and tab order navigation
+//! - Wheel events routed to the innermost scroll container
+//! - Drag gesture recognition for registered drag sources
+//! - Tap, double-tap and long-press recognition
+//! - Clicks recognized from pointer presses and releases
+//! - Hover, active, focus and disabled state for pseudo-class styling
+//! - Debounce and throttle wrappers for event handlers
 //! - Component ID integration for efficient event routing
 
 pub mod delegation;
 pub mod dispatcher;
+pub mod drag;
 pub mod emitter;
 pub mod event;
+pub mod focus;
+pub mod gesture;
 pub mod hit_testing;
+pub mod keyboard;
+pub mod shortcuts;
+pub mod timing;
+pub mod wheel;
 
 pub use delegation::*;
 pub use dispatcher::Dispatcher;
+pub use drag::{DragEvent, DragInfo, DragRecognizer, PointerInput};
 pub use emitter::EventEmitter;
 pub use event::Event;
+pub use focus::FocusManager;
+pub use gesture::{Gesture, GestureConfig, GestureRecognizer};
 pub use hit_testing::*;
+pub use keyboard::{Key, KeyEvent, KeyPhase, Modifiers};
+pub use shortcuts::{KeyCombo, ShortcutId, ShortcutRegistry};
+pub use timing::{debounce, throttle, Debounced, Handler, Ticker};
+pub use wheel::WheelEvent;
+
+use std::collections::HashSet;
+use std::time::Instant;
 
 use crate::{
     component::ComponentId,
     layout::{LayoutNode, Point},
+    style::ElementState,
 };
 
+/// The primary button was pressed and released over the same component
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Click {
+    /// Where the button was released
+    pub position: Point,
+}
+
 /// Enhanced event system that integrates with layout and components
 #[derive(Debug)]
 pub struct EventSystem {
@@ -32,6 +66,22 @@ pub struct EventSystem {
     hit_tester: HitTester,
     /// Event delegation system
     delegator: EventDelegate,
+    /// Keyboard focus and tab order
+    focus: FocusManager,
+    /// Drag gesture state
+    drag: DragRecognizer,
+    /// Components that can be dragged
+    drag_sources: HashSet<ComponentId>,
+    /// Tap, double-tap and long-press state
+    gestures: GestureRecognizer,
+    /// Component receiving all pointer events, bypassing hit testing
+    pointer_capture: Option<ComponentId>,
+    /// Components under the pointer, innermost first
+    hovered: Vec<ComponentId>,
+    /// Components the primary button went down on, innermost first
+    active: Vec<ComponentId>,
+    /// Components that don't accept interaction
+    disabled: HashSet<ComponentId>,
 }
 
 impl EventSystem {
@@ -40,30 +90,336 @@ impl EventSystem {
         Self {
             hit_tester: HitTester::new(),
             delegator: EventDelegate::new(None),
+            focus: FocusManager::new(),
+            drag: DragRecognizer::new(),
+            drag_sources: HashSet::new(),
+            gestures: GestureRecognizer::new(),
+            pointer_capture: None,
+            hovered: Vec::new(),
+            active: Vec::new(),
+            disabled: HashSet::new(),
         }
     }
 
     /// Process a pointer event (mouse, touch) with layout hit testing
+    ///
+    /// The innermost component under the pointer with a delegate is the
+    /// target. The event is captured down to it from the root, delivered to
+    /// it, then bubbled back up. Returns every component under the pointer,
+    /// innermost first.
+    ///
+    /// While a component holds pointer capture, the event goes to it
+    /// wherever the pointer is, and it is the only component returned.
     pub fn process_pointer_event<E: Event + Clone>(
         &mut self,
         event: E,
         position: Point,
         layout_root: &LayoutNode,
     ) -> Result<Vec<ComponentId>, EventError> {
-        // Perform hit testing to find target components
+        if let Some(captured) = self.pointer_capture {
+            let delivered = captured
+                .delegate_key()
+                .is_some_and(|key| self.delegator.dispatch_to(&event, key));
+            return if delivered {
+                Ok(vec![captured])
+            } else {
+                Err(EventError::ComponentNotFound(captured))
+            };
+        }
+
+        // Hit testing yields the target chain, innermost first
         let hit_targets = self.hit_tester.hit_test(position, layout_root)?;
+        let chain: Vec<usize> = hit_targets
+            .iter()
+            .filter_map(ComponentId::delegate_key)
+            .collect();
+        self.delegator.dispatch_event(&event, &chain);
+
+        Ok(hit_targets)
+    }
+
+    /// Process a keyboard event, routing it to the focused component
+    ///
+    /// The event runs through capturing handlers down to the focused
+    /// component, its own handlers, then bubbles to its ancestors. Returns the
+    /// component it was delivered to, or `None` when nothing has focus.
+    pub fn process_key_event(
+        &mut self,
+        event: KeyEvent,
+        focused: Option<ComponentId>,
+    ) -> Result<Option<ComponentId>, EventError> {
+        let Some(focused) = focused else {
+            return Ok(None);
+        };
+
+        let delivered = focused
+            .delegate_key()
+            .is_some_and(|key| self.delegator.dispatch_to(&event, key));
+        if delivered {
+            Ok(Some(focused))
+        } else {
+            Err(EventError::ComponentNotFound(focused))
+        }
+    }
+
+    /// Process a wheel event, routing it to the scroll container under the cursor
+    ///
+    /// The event goes to the innermost component at the position whose
+    /// overflow is `Scroll` or `Auto` on either axis, then bubbles to its
+    /// ancestors. Returns that component so its scroll offset can be updated,
+    /// or `None` if nothing under the cursor scrolls.
+    pub fn process_wheel_event(
+        &mut self,
+        event: WheelEvent,
+        layout_root: &LayoutNode,
+    ) -> Result<Option<ComponentId>, EventError> {
+        // Hits are ordered innermost first
+        let hits = self.hit_tester.hit_test(event.position, layout_root)?;
+        let target = hits.into_iter().find(|id| {
+            wheel::find_node(layout_root, *id).is_some_and(|node| {
+                node.style.overflow_x.is_scrollable() || node.style.overflow_y.is_scrollable()
+            })
+        });
+
+        if let Some(key) = target.and_then(|target| target.delegate_key()) {
+            self.delegator.dispatch_to(&event, key);
+        }
+
+        Ok(target)
+    }
+
+    /// Route all pointer events to a component until capture is released
+    ///
+    /// Used by sliders and drag handles so fast moves keep reaching them
+    /// after the pointer leaves their bounds. Replaces any existing capture.
+    pub fn set_pointer_capture(&mut self, id: ComponentId) {
+        self.pointer_capture = Some(id);
+    }
+
+    /// Release pointer capture, restoring hit testing
+    ///
+    /// Does nothing unless `id` holds the capture.
+    pub fn release_pointer_capture(&mut self, id: ComponentId) {
+        if self.pointer_capture == Some(id) {
+            self.pointer_capture = None;
+        }
+    }
+
+    /// Component currently holding pointer capture
+    pub fn pointer_capture(&self) -> Option<ComponentId> {
+        self.pointer_capture
+    }
 
-        // Create a delegated event for each hit target
-        let mut processed_targets = Vec::new();
+    /// Track hover and active state from pointer input
+    ///
+    /// As in CSS, every component under the pointer is hovered, not just the
+    /// innermost one. Pressing makes the hovered components active until the
+    /// button is released or the pointer leaves the window.
+    pub fn update_pointer_state(
+        &mut self,
+        input: PointerInput,
+        layout_root: &LayoutNode,
+    ) -> Result<(), EventError> {
+        match input {
+            PointerInput::Down(position) => {
+                self.hovered = self.hit_tester.hit_test(position, layout_root)?;
+                self.active = self.hovered.clone();
+            }
+            PointerInput::Move(position) => {
+                self.hovered = self.hit_tester.hit_test(position, layout_root)?;
+            }
+            PointerInput::Up(position) => {
+                self.hovered = self.hit_tester.hit_test(position, layout_root)?;
+                self.active.clear();
+            }
+            PointerInput::Leave => {
+                self.hovered.clear();
+                self.active.clear();
+            }
+        }
+        Ok(())
+    }
+
+    /// Feed pointer input from the platform
+    ///
+    /// Updates hover and active state, dispatches the input itself to the
+    /// component under the pointer, then dispatches a [`Click`] when the
+    /// button is released over the component it went down on. Returns the
+    /// click, if there was one.
+    pub fn process_pointer_input(
+        &mut self,
+        input: PointerInput,
+        layout_root: &LayoutNode,
+    ) -> Result<Option<Click>, EventError> {
+        let pressed = self.active.clone();
+        self.update_pointer_state(input, layout_root)?;
+
+        let position = match input {
+            PointerInput::Down(position) | PointerInput::Move(position) => position,
+            PointerInput::Up(position) => position,
+            PointerInput::Leave => return Ok(None),
+        };
+        self.process_pointer_event(input, position, layout_root)?;
+
+        let released_on_pressed = self
+            .hovered
+            .first()
+            .is_some_and(|target| pressed.contains(target));
+        if !matches!(input, PointerInput::Up(_)) || !released_on_pressed {
+            return Ok(None);
+        }
+
+        let click = Click { position };
+        self.process_pointer_event(click, position, layout_root)?;
+        Ok(Some(click))
+    }
+
+    /// Mark a component as disabled or enabled for `:disabled` styling
+    pub fn set_disabled(&mut self, id: ComponentId, disabled: bool) {
+        if disabled {
+            self.disabled.insert(id);
+        } else {
+            self.disabled.remove(&id);
+        }
+    }
+
+    /// Interactive state of a component, for matching style pseudo-classes
+    ///
+    /// Focus comes from the focus manager; hover and active state from the
+    /// input passed to [`update_pointer_state`](Self::update_pointer_state).
+    pub fn element_state(&self, id: ComponentId) -> ElementState {
+        let mut state = ElementState::empty();
+        state.set(ElementState::HOVERED, self.hovered.contains(&id));
+        state.set(ElementState::ACTIVE, self.active.contains(&id));
+        state.set(ElementState::FOCUSED, self.focus.focused() == Some(id));
+        state.set(ElementState::DISABLED, self.disabled.contains(&id));
+        state
+    }
+
+    /// Allow a component to start drags
+    pub fn register_drag_source(&mut self, id: ComponentId) {
+        self.drag_sources.insert(id);
+    }
+
+    /// Stop a component from starting new drags
+    pub fn unregister_drag_source(&mut self, id: ComponentId) {
+        self.drag_sources.remove(&id);
+    }
+
+    /// Feed pointer input to drag recognition
+    ///
+    /// Pressing starts tracking on the innermost registered drag source under
+    /// the pointer. Any drag event that results is dispatched to that source,
+    /// bubbling to its ancestors, and returned.
+    pub fn process_drag_input(
+        &mut self,
+        input: PointerInput,
+        layout_root: &LayoutNode,
+    ) -> Result<Option<DragEvent>, EventError> {
+        let event = match input {
+            PointerInput::Down(position) => {
+                let hits = self.hit_tester.hit_test(position, layout_root)?;
+                match hits.into_iter().find(|id| self.drag_sources.contains(id)) {
+                    Some(source) => self.drag.pointer_down(source, position),
+                    None => self.drag.pointer_leave(),
+                }
+            }
+            PointerInput::Move(position) => self.drag.pointer_move(position),
+            PointerInput::Up(position) => self.drag.pointer_up(position),
+            PointerInput::Leave => self.drag.pointer_leave(),
+        };
 
-        for target_id in hit_targets {
-            let _delegated_event = DelegatedEvent::new(event.clone(), PropagationPhase::Target); // Process the event through delegation
-            self.delegator
-                .dispatch(&event, Some(target_id.id() as usize));
-            processed_targets.push(target_id);
+        if let Some(event) = &event {
+            if let Some(key) = event.info().source.delegate_key() {
+                self.delegator.dispatch_to(event, key);
+            }
         }
 
-        Ok(processed_targets)
+        Ok(event)
+    }
+
+    /// Feed pointer input to tap, double-tap and long-press recognition
+    ///
+    /// Pressing starts tracking on the innermost component under the
+    /// pointer. A resulting gesture is dispatched like a pointer event at its
+    /// position, bubbling from the innermost component with a delegate, and
+    /// returned.
+    pub fn process_gesture_input(
+        &mut self,
+        input: PointerInput,
+        now: Instant,
+        layout_root: &LayoutNode,
+    ) -> Result<Option<Gesture>, EventError> {
+        let gesture = match input {
+            PointerInput::Down(position) => {
+                let hits = self.hit_tester.hit_test(position, layout_root)?;
+                match hits.first() {
+                    Some(&target) => self.gestures.pointer_down(target, position, now),
+                    None => self.gestures.pointer_leave(),
+                }
+                None
+            }
+            PointerInput::Move(position) => self.gestures.pointer_move(position, now),
+            PointerInput::Up(position) => self.gestures.pointer_up(position, now),
+            PointerInput::Leave => {
+                self.gestures.pointer_leave();
+                None
+            }
+        };
+        self.dispatch_gesture(gesture, layout_root)
+    }
+
+    /// Recognize a long press while the pointer is held still
+    ///
+    /// Call once per frame; holding produces no input of its own.
+    pub fn process_gesture_tick(
+        &mut self,
+        now: Instant,
+        layout_root: &LayoutNode,
+    ) -> Result<Option<Gesture>, EventError> {
+        let gesture = self.gestures.tick(now);
+        self.dispatch_gesture(gesture, layout_root)
+    }
+
+    fn dispatch_gesture(
+        &mut self,
+        gesture: Option<Gesture>,
+        layout_root: &LayoutNode,
+    ) -> Result<Option<Gesture>, EventError> {
+        if let Some(gesture) = &gesture {
+            self.process_pointer_event(*gesture, gesture.position(), layout_root)?;
+        }
+        Ok(gesture)
+    }
+
+    /// Get reference to the gesture recognizer
+    pub fn gesture_recognizer(&self) -> &GestureRecognizer {
+        &self.gestures
+    }
+
+    /// Replace the gesture recognizer, for example to change its thresholds
+    pub fn set_gesture_recognizer(&mut self, gestures: GestureRecognizer) {
+        self.gestures = gestures;
+    }
+
+    /// Get reference to the drag recognizer
+    pub fn drag_recognizer(&self) -> &DragRecognizer {
+        &self.drag
+    }
+
+    /// Get mutable reference to the drag recognizer
+    pub fn drag_recognizer_mut(&mut self) -> &mut DragRecognizer {
+        &mut self.drag
+    }
+
+    /// Get reference to the focus manager
+    pub fn focus_manager(&self) -> &FocusManager {
+        &self.focus
+    }
+
+    /// Get mutable reference to the focus manager
+    pub fn focus_manager_mut(&mut self) -> &mut FocusManager {
+        &mut self.focus
     }
 
     /// Get mutable reference to the hit tester for configuration
@@ -103,3 +459,137 @@ pub enum EventError {
     #[error("Invalid event data: {0}")]
     InvalidEventData(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{LayoutStyle, Rect};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone)]
+    struct PointerMove;
+
+    fn node(rect: Rect) -> LayoutNode {
+        let mut node = LayoutNode::new(ComponentId::new(), LayoutStyle::default());
+        node.layout.rect = rect;
+        node
+    }
+
+    #[test]
+    fn test_element_state_tracks_pointer_and_focus() {
+        let mut root = node(Rect::new(0.0, 0.0, 300.0, 100.0));
+        let button = node(Rect::new(0.0, 0.0, 100.0, 100.0)).with_focusable(true);
+        let button_id = button.id;
+        root.add_child(button);
+
+        let mut events = EventSystem::new();
+        events.focus_manager_mut().update_tab_order(&root);
+        assert_eq!(events.element_state(button_id), ElementState::empty());
+
+        let over = Point::new(50.0, 50.0);
+        events
+            .update_pointer_state(PointerInput::Move(over), &root)
+            .unwrap();
+        assert_eq!(events.element_state(button_id), ElementState::HOVERED);
+        assert!(events
+            .element_state(root.id)
+            .contains(ElementState::HOVERED));
+
+        events
+            .update_pointer_state(PointerInput::Down(over), &root)
+            .unwrap();
+        assert_eq!(
+            events.element_state(button_id),
+            ElementState::HOVERED | ElementState::ACTIVE
+        );
+
+        // Releasing outside clears both
+        events
+            .update_pointer_state(PointerInput::Up(Point::new(200.0, 50.0)), &root)
+            .unwrap();
+        assert_eq!(events.element_state(button_id), ElementState::empty());
+
+        events.focus_manager_mut().focus(button_id).unwrap();
+        events.set_disabled(button_id, true);
+        assert_eq!(
+            events.element_state(button_id),
+            ElementState::FOCUSED | ElementState::DISABLED
+        );
+    }
+
+    #[test]
+    fn test_pointer_capture_bypasses_hit_testing_until_released() {
+        // root
+        // ├── thumb (slider handle)
+        // └── other
+        let mut root = node(Rect::new(0.0, 0.0, 300.0, 100.0));
+        let thumb = node(Rect::new(0.0, 0.0, 20.0, 20.0));
+        let other = node(Rect::new(100.0, 0.0, 100.0, 100.0));
+        let (thumb_id, other_id) = (thumb.id, other.id);
+        root.add_child(thumb);
+        root.add_child(other);
+
+        let mut events = EventSystem::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        for id in [thumb_id, other_id] {
+            let delegate = EventDelegate::new(Some(id.id() as usize));
+            let log = received.clone();
+            delegate.on(move |_: &PointerMove, _: &EventPropagation| {
+                log.lock().unwrap().push(id);
+            });
+            events
+                .delegator_mut()
+                .add_child(Arc::new(Mutex::new(delegate)));
+        }
+
+        events.set_pointer_capture(thumb_id);
+        assert_eq!(events.pointer_capture(), Some(thumb_id));
+
+        // Well outside the thumb, over the other element
+        let position = Point::new(150.0, 50.0);
+        let targets = events
+            .process_pointer_event(PointerMove, position, &root)
+            .unwrap();
+        assert_eq!(targets, vec![thumb_id]);
+        assert_eq!(*received.lock().unwrap(), vec![thumb_id]);
+
+        // Only the holder can release
+        events.release_pointer_capture(other_id);
+        assert_eq!(events.pointer_capture(), Some(thumb_id));
+
+        events.release_pointer_capture(thumb_id);
+        assert_eq!(events.pointer_capture(), None);
+        let targets = events
+            .process_pointer_event(PointerMove, position, &root)
+            .unwrap();
+        assert_eq!(targets[0], other_id);
+        assert_eq!(*received.lock().unwrap(), vec![thumb_id, other_id]);
+    }
+
+    #[test]
+    fn test_click_requires_release_over_pressed_component() {
+        let mut root = node(Rect::new(0.0, 0.0, 300.0, 100.0));
+        let button = node(Rect::new(0.0, 0.0, 100.0, 100.0));
+        root.add_child(button);
+        let mut events = EventSystem::new();
+
+        let over = Point::new(50.0, 50.0);
+        let outside = Point::new(400.0, 50.0);
+        events
+            .process_pointer_input(PointerInput::Down(over), &root)
+            .unwrap();
+        let click = events
+            .process_pointer_input(PointerInput::Up(over), &root)
+            .unwrap();
+        assert_eq!(click, Some(Click { position: over }));
+
+        // Dragging off everything that was pressed cancels the click
+        events
+            .process_pointer_input(PointerInput::Down(over), &root)
+            .unwrap();
+        let click = events
+            .process_pointer_input(PointerInput::Up(outside), &root)
+            .unwrap();
+        assert_eq!(click, None);
+    }
+}
@@ -0,0 +1,303 @@
+//! Keyboard shortcuts
+//!
+//! Shortcuts are matched against key presses before they reach the focused
+//! component. A shortcut is a single key combination such as Ctrl+S or a
+//! chord of several pressed one after another, such as Ctrl+K then Ctrl+S.
+//! Scoped shortcuts only fire while focus is inside a component's subtree.
+
+use crate::component::{Callback, ComponentId};
+use crate::events::keyboard::{Key, KeyEvent, KeyPhase, Modifiers};
+
+/// A key pressed with an exact set of modifiers
+///
+/// Character keys match regardless of case, since Shift is part of the
+/// modifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    /// The key
+    pub key: Key,
+    /// Modifiers that must be held, and no others
+    pub modifiers: Modifiers,
+}
+
+impl KeyCombo {
+    /// Create a combination of a key without modifiers
+    pub fn new(key: Key) -> Self {
+        let key = match key {
+            Key::Character(text) => Key::Character(text.to_lowercase()),
+            key => key,
+        };
+        Self {
+            key,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Create a combination of a key with Control held
+    pub fn ctrl(key: Key) -> Self {
+        Self::new(key).with_modifiers(Modifiers {
+            ctrl: true,
+            ..Default::default()
+        })
+    }
+
+    /// Set the modifiers that must be held
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// The combination a key event was pressed with
+    pub fn from_event(event: &KeyEvent) -> Self {
+        Self::new(event.key.clone()).with_modifiers(event.modifiers)
+    }
+}
+
+/// Identifies a registered shortcut so it can be removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShortcutId(u64);
+
+struct Shortcut {
+    id: ShortcutId,
+    chord: Vec<KeyCombo>,
+    scope: Option<ComponentId>,
+    callback: Callback<KeyEvent>,
+}
+
+impl Shortcut {
+    /// How closely the shortcut's scope contains the focus, innermost first
+    ///
+    /// Global shortcuts rank after every scope, and `None` means the scope
+    /// isn't focused.
+    fn rank(&self, focus_path: &[ComponentId]) -> Option<usize> {
+        match self.scope {
+            None => Some(focus_path.len()),
+            Some(scope) => focus_path.iter().position(|&id| id == scope),
+        }
+    }
+}
+
+/// Registered shortcuts and the progress of any chord being typed
+///
+/// When several shortcuts match, the one scoped to the component closest to
+/// the focus wins, and global shortcuts come last. A single combination
+/// fires straight away even if it also starts a chord.
+#[derive(Default)]
+pub struct ShortcutRegistry {
+    shortcuts: Vec<Shortcut>,
+    /// Combinations of a chord typed so far
+    pending: Vec<KeyCombo>,
+    next_id: u64,
+}
+
+impl ShortcutRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a global shortcut for one key combination
+    pub fn register(&mut self, combo: KeyCombo, callback: Callback<KeyEvent>) -> ShortcutId {
+        self.add(vec![combo], None, callback)
+    }
+
+    /// Register a global shortcut for a chord of combinations
+    pub fn register_chord(
+        &mut self,
+        chord: Vec<KeyCombo>,
+        callback: Callback<KeyEvent>,
+    ) -> ShortcutId {
+        self.add(chord, None, callback)
+    }
+
+    /// Register a shortcut that only fires while `scope` or a descendant has focus
+    pub fn register_scoped(
+        &mut self,
+        scope: ComponentId,
+        chord: Vec<KeyCombo>,
+        callback: Callback<KeyEvent>,
+    ) -> ShortcutId {
+        self.add(chord, Some(scope), callback)
+    }
+
+    fn add(
+        &mut self,
+        chord: Vec<KeyCombo>,
+        scope: Option<ComponentId>,
+        callback: Callback<KeyEvent>,
+    ) -> ShortcutId {
+        let id = ShortcutId(self.next_id);
+        self.next_id += 1;
+        self.shortcuts.push(Shortcut {
+            id,
+            chord,
+            scope,
+            callback,
+        });
+        id
+    }
+
+    /// Remove a shortcut, returning whether it was registered
+    pub fn unregister(&mut self, id: ShortcutId) -> bool {
+        let count = self.shortcuts.len();
+        self.shortcuts.retain(|shortcut| shortcut.id != id);
+        self.pending.clear();
+        self.shortcuts.len() != count
+    }
+
+    /// Whether the first part of a chord has been typed
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Match a key event and invoke the shortcut it completes
+    ///
+    /// `focus_path` is the focused component followed by its ancestors. Only
+    /// presses are matched, and presses of modifier keys alone are ignored
+    /// so they don't interrupt a chord. Returns whether the event was
+    /// consumed, either by firing a shortcut or by continuing a chord.
+    pub fn handle(&mut self, event: &KeyEvent, focus_path: &[ComponentId]) -> bool {
+        if event.phase != KeyPhase::Pressed
+            || matches!(event.key, Key::Shift | Key::Control | Key::Alt | Key::Meta)
+        {
+            return false;
+        }
+
+        let combo = KeyCombo::from_event(event);
+        let mut typed = std::mem::take(&mut self.pending);
+        typed.push(combo.clone());
+        if !self.advance(typed, event, focus_path) {
+            // A key that breaks a chord may still start a shortcut of its own
+            return self.advance(vec![combo], event, focus_path);
+        }
+        true
+    }
+
+    /// Fire the shortcut `typed` completes, or keep waiting if it starts one
+    fn advance(
+        &mut self,
+        typed: Vec<KeyCombo>,
+        event: &KeyEvent,
+        focus_path: &[ComponentId],
+    ) -> bool {
+        let candidates = self.shortcuts.iter().filter_map(|shortcut| {
+            let rank = shortcut.rank(focus_path)?;
+            shortcut
+                .chord
+                .starts_with(&typed)
+                .then_some((rank, shortcut))
+        });
+
+        let mut completed = None;
+        let mut started = false;
+        for (rank, shortcut) in candidates {
+            if shortcut.chord.len() > typed.len() {
+                started = true;
+            } else if completed.is_none_or(|(best, _)| rank < best) {
+                completed = Some((rank, shortcut));
+            }
+        }
+
+        if let Some((_, shortcut)) = completed {
+            let callback = shortcut.callback.clone();
+            callback.call(event.clone());
+            return true;
+        }
+        if started {
+            self.pending = typed;
+            return true;
+        }
+        false
+    }
+}
+
+impl std::fmt::Debug for ShortcutRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShortcutRegistry")
+            .field("shortcuts", &self.shortcuts.len())
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::callback;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn counter() -> (Arc<AtomicUsize>, Callback<KeyEvent>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let calls = Arc::clone(&count);
+        let callback = callback(move |_: KeyEvent| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        });
+        (count, callback)
+    }
+
+    fn press(key: &str, modifiers: Modifiers) -> KeyEvent {
+        KeyEvent::pressed(Key::Character(key.to_string())).with_modifiers(modifiers)
+    }
+
+    const CTRL: Modifiers = Modifiers {
+        shift: false,
+        ctrl: true,
+        alt: false,
+        meta: false,
+    };
+
+    #[test]
+    fn test_ctrl_s_fires_only_with_exact_modifiers() {
+        let (saved, on_save) = counter();
+        let mut shortcuts = ShortcutRegistry::new();
+        shortcuts.register(KeyCombo::ctrl(Key::Character("s".to_string())), on_save);
+
+        // Pressing Control on its own is not a shortcut
+        assert!(!shortcuts.handle(&KeyEvent::pressed(Key::Control).with_modifiers(CTRL), &[]));
+        assert!(shortcuts.handle(&press("s", CTRL), &[]));
+        assert_eq!(saved.load(Ordering::SeqCst), 1);
+
+        let alt = Modifiers {
+            alt: true,
+            ..Default::default()
+        };
+        let ctrl_shift = Modifiers {
+            shift: true,
+            ..CTRL
+        };
+        for modifiers in [Modifiers::default(), alt, ctrl_shift] {
+            assert!(!shortcuts.handle(&press("s", modifiers), &[]));
+        }
+        assert_eq!(saved.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_chords_and_scopes() {
+        let (editor, panel) = (ComponentId::new(), ComponentId::new());
+        let (chord_count, on_chord) = counter();
+        let (scoped_count, on_scoped) = counter();
+        let (global_count, on_global) = counter();
+
+        let mut shortcuts = ShortcutRegistry::new();
+        let k = KeyCombo::ctrl(Key::Character("k".to_string()));
+        let s = KeyCombo::ctrl(Key::Character("s".to_string()));
+        shortcuts.register_chord(vec![k, s.clone()], on_chord);
+        shortcuts.register(s.clone(), on_global);
+        shortcuts.register_scoped(editor, vec![s], on_scoped);
+
+        // Ctrl+K waits for the rest of the chord, then Ctrl+S completes it
+        assert!(shortcuts.handle(&press("k", CTRL), &[]));
+        assert!(shortcuts.is_pending());
+        assert!(shortcuts.handle(&press("s", CTRL), &[]));
+        assert!(!shortcuts.is_pending());
+        assert_eq!(chord_count.load(Ordering::SeqCst), 1);
+
+        // Focus inside the editor prefers its scoped shortcut
+        shortcuts.handle(&press("S", CTRL), &[editor, panel]);
+        assert_eq!(scoped_count.load(Ordering::SeqCst), 1);
+        shortcuts.handle(&press("s", CTRL), &[panel]);
+        assert_eq!(global_count.load(Ordering::SeqCst), 1);
+        assert_eq!(chord_count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,261 @@
+//! Rate limiting for event handlers
+//!
+//! [`debounce`] and [`throttle`] wrap a handler so it is called less often
+//! than events arrive, for things like search-as-you-type or resize and
+//! scroll handlers.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::component::Callback;
+
+/// Work advanced once per frame, registered with
+/// [`App::add_ticker`](crate::app::App::add_ticker)
+///
+/// Tickers run on the UI thread at the start of every frame, after queued
+/// input and before pending updates are flushed, so updates they schedule
+/// land in the same frame.
+pub trait Ticker: Send {
+    /// Advance to `now`
+    fn tick(&self, now: Instant);
+
+    /// Whether further frames are needed even if nothing else changes
+    fn is_active(&self) -> bool;
+}
+
+/// Something that can handle an event by value
+///
+/// Implemented for closures and for [`Callback`], so either can be passed to
+/// [`debounce`] and [`throttle`].
+pub trait Handler<E>: Send + Sync + 'static {
+    /// Handle an event
+    fn handle(&self, event: E);
+}
+
+impl<E, F> Handler<E> for F
+where
+    F: Fn(E) + Send + Sync + 'static,
+{
+    fn handle(&self, event: E) {
+        self(event)
+    }
+}
+
+impl<E: 'static> Handler<E> for Callback<E> {
+    fn handle(&self, event: E) {
+        self.call(event)
+    }
+}
+
+/// The event waiting to be delivered by a [`Debounced`] handler
+struct DebounceState<E> {
+    /// Latest event received, delivered once the deadline passes
+    pending: Option<E>,
+    /// When the pending event is delivered, pushed back by each new event
+    deadline: Instant,
+}
+
+/// A handler that only sees the last event of a burst
+///
+/// Created by [`debounce`]. Events are stored with a deadline and delivered
+/// from [`Debounced::tick`], so the handler runs on whichever thread ticks
+/// it; register it with [`App::add_ticker`](crate::app::App::add_ticker) to
+/// have the app tick it every frame on the UI thread.
+///
+/// Clones share the pending event.
+pub struct Debounced<E> {
+    duration: Duration,
+    handler: Arc<dyn Handler<E>>,
+    state: Arc<Mutex<DebounceState<E>>>,
+}
+
+impl<E> Clone for Debounced<E> {
+    fn clone(&self) -> Self {
+        Self {
+            duration: self.duration,
+            handler: self.handler.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<E: Send + 'static> Debounced<E> {
+    /// Receive an event, replacing any pending one and restarting the timer
+    pub fn call(&self, event: E) {
+        let mut state = self.state.lock().unwrap();
+        state.pending = Some(event);
+        state.deadline = Instant::now() + self.duration;
+    }
+
+    /// A callback that passes its events to [`Debounced::call`]
+    pub fn callback(&self) -> Callback<E> {
+        let debounced = self.clone();
+        Callback::new(move |event: E| debounced.call(event))
+    }
+
+    /// Deliver the pending event if its deadline has passed by `now`
+    ///
+    /// Returns whether the handler ran.
+    pub fn tick(&self, now: Instant) -> bool {
+        let event = {
+            let mut state = self.state.lock().unwrap();
+            if now < state.deadline {
+                return false;
+            }
+            state.pending.take()
+        };
+        match event {
+            Some(event) => {
+                self.handler.handle(event);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether an event is waiting for its deadline
+    pub fn is_pending(&self) -> bool {
+        self.state.lock().unwrap().pending.is_some()
+    }
+}
+
+impl<E: Send + 'static> Ticker for Debounced<E> {
+    fn tick(&self, now: Instant) {
+        Debounced::tick(self, now);
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_pending()
+    }
+}
+
+/// Wrap a handler so it only sees the last event of a burst
+///
+/// Each event restarts a timer of `duration`. Once no event has arrived for
+/// `duration`, the next tick calls the handler once with the latest event.
+/// Earlier events in the burst are dropped.
+pub fn debounce<E, H>(duration: Duration, handler: H) -> Debounced<E>
+where
+    E: Send + 'static,
+    H: Handler<E>,
+{
+    Debounced {
+        duration,
+        handler: Arc::new(handler),
+        state: Arc::new(Mutex::new(DebounceState {
+            pending: None,
+            deadline: Instant::now(),
+        })),
+    }
+}
+
+/// A handler that drops events arriving within `interval` of the last one
+struct Throttle<E> {
+    interval: Duration,
+    handler: Box<dyn Handler<E>>,
+    last_call: Mutex<Option<Instant>>,
+}
+
+impl<E: 'static> Throttle<E> {
+    /// Handle `event` arriving at `now` unless the last call was too recent
+    fn call_at(&self, event: E, now: Instant) {
+        {
+            let mut last_call = self.last_call.lock().unwrap();
+            if last_call.is_some_and(|last| now.duration_since(last) < self.interval) {
+                return;
+            }
+            *last_call = Some(now);
+        }
+        self.handler.handle(event);
+    }
+}
+
+/// Wrap a handler so it runs at most once per `interval`
+///
+/// The first event is handled immediately. Events arriving less than
+/// `interval` after the last handled one are dropped.
+pub fn throttle<E, H>(interval: Duration, handler: H) -> Callback<E>
+where
+    E: 'static,
+    H: Handler<E>,
+{
+    let throttle = Throttle {
+        interval,
+        handler: Box::new(handler),
+        last_call: Mutex::new(None),
+    };
+    Callback::new(move |event: E| throttle.call_at(event, Instant::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::kit::components::spinner::{Spinner, SpinnerProps};
+    use crate::renderer::NullRenderer;
+
+    fn recorder() -> (Arc<Mutex<Vec<u32>>>, impl Fn(u32) + Send + Sync + 'static) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let sink = calls.clone();
+        (calls, move |value| sink.lock().unwrap().push(value))
+    }
+
+    #[test]
+    fn test_debounce_collapses_burst_into_trailing_call() {
+        let (calls, record) = recorder();
+        let debounced = debounce(Duration::from_millis(100), record);
+        let callback = debounced.callback();
+
+        for value in 0..10 {
+            callback.call(value);
+        }
+        let start = Instant::now();
+        assert!(!debounced.tick(start));
+        assert!(debounced.is_pending());
+        assert!(calls.lock().unwrap().is_empty());
+
+        // Only the latest event is delivered, once
+        assert!(debounced.tick(start + Duration::from_millis(150)));
+        assert!(!debounced.tick(start + Duration::from_millis(300)));
+        assert_eq!(*calls.lock().unwrap(), vec![9]);
+    }
+
+    #[test]
+    fn test_app_runs_debounced_handler_on_its_frame() {
+        let (calls, record) = recorder();
+        let debounced = debounce(Duration::from_millis(20), record);
+        let mut app = App::new::<Spinner>(SpinnerProps::default())
+            .with_renderer(Box::new(NullRenderer::new()));
+        app.add_ticker(debounced.clone());
+        let start = Instant::now();
+        app.frame_at(start).unwrap();
+
+        debounced.call(1);
+        debounced.call(2);
+        assert!(app.needs_frame());
+        app.frame_at(start).unwrap();
+        assert!(calls.lock().unwrap().is_empty());
+
+        app.frame_at(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec![2]);
+        assert!(!app.needs_frame());
+    }
+
+    #[test]
+    fn test_throttle_caps_calls_over_window() {
+        let (calls, record) = recorder();
+        let throttle = Throttle {
+            interval: Duration::from_millis(40),
+            handler: Box::new(record),
+            last_call: Mutex::new(None),
+        };
+
+        // An event every 10ms for 130ms
+        let start = Instant::now();
+        for step in 0..14 {
+            throttle.call_at(step, start + Duration::from_millis(step as u64 * 10));
+        }
+
+        assert_eq!(*calls.lock().unwrap(), vec![0, 4, 8, 12]);
+    }
+}
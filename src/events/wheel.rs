@@ -0,0 +1,105 @@
+//! Mouse wheel and trackpad scroll events
+
+use crate::{
+    component::ComponentId,
+    layout::{LayoutNode, Point},
+};
+
+use super::Modifiers;
+
+/// A scroll from a mouse wheel or trackpad
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelEvent {
+    /// Horizontal scroll distance in logical pixels, positive to the right
+    pub delta_x: f32,
+    /// Vertical scroll distance in logical pixels, positive downwards
+    pub delta_y: f32,
+    /// Cursor position when the event happened
+    pub position: Point,
+    /// Modifiers held at the time of the event
+    pub modifiers: Modifiers,
+}
+
+impl WheelEvent {
+    /// Create a wheel event with no modifiers
+    pub fn new(delta_x: f32, delta_y: f32, position: Point) -> Self {
+        Self {
+            delta_x,
+            delta_y,
+            position,
+            modifiers: Modifiers::default(),
+        }
+    }
+}
+
+/// Find a node by ID in a layout tree
+pub(crate) fn find_node(node: &LayoutNode, id: ComponentId) -> Option<&LayoutNode> {
+    if node.id == id {
+        return Some(node);
+    }
+    node.children.iter().find_map(|child| find_node(child, id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventDelegate, EventPropagation, EventSystem};
+    use crate::layout::{LayoutStyle, Overflow, Rect};
+    use std::sync::{Arc, Mutex};
+
+    fn node(rect: Rect, overflow_y: Overflow) -> LayoutNode {
+        let style = LayoutStyle {
+            overflow_y,
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+        node.layout.rect = rect;
+        node
+    }
+
+    #[test]
+    fn test_innermost_scroll_container_receives_wheel() {
+        // root (visible)
+        // └── outer (scroll)
+        //     └── inner (auto)
+        //         └── item (visible)
+        let mut inner = node(Rect::new(50.0, 50.0, 100.0, 100.0), Overflow::Auto);
+        inner.add_child(node(Rect::new(60.0, 60.0, 20.0, 20.0), Overflow::Visible));
+        let mut outer = node(Rect::new(0.0, 0.0, 300.0, 300.0), Overflow::Scroll);
+        let (outer_id, inner_id) = (outer.id, inner.id);
+        outer.add_child(inner);
+        let mut root = node(Rect::new(0.0, 0.0, 400.0, 300.0), Overflow::Visible);
+        root.add_child(outer);
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let inner_delegate = EventDelegate::new(Some(inner_id.id() as usize));
+        let log = received.clone();
+        inner_delegate.on(move |event: &WheelEvent, _: &EventPropagation| {
+            log.lock().unwrap().push(event.delta_y);
+        });
+        let mut events = EventSystem::new();
+        events
+            .delegator_mut()
+            .add_child(Arc::new(Mutex::new(inner_delegate)));
+
+        // Over the item inside the inner container
+        let wheel = WheelEvent::new(0.0, 40.0, Point::new(70.0, 70.0));
+        assert_eq!(
+            events.process_wheel_event(wheel, &root).unwrap(),
+            Some(inner_id)
+        );
+        assert_eq!(*received.lock().unwrap(), vec![40.0]);
+
+        // Inside the outer container only
+        let wheel = WheelEvent::new(0.0, 10.0, Point::new(200.0, 200.0));
+        assert_eq!(
+            events.process_wheel_event(wheel, &root).unwrap(),
+            Some(outer_id)
+        );
+
+        // Over the root, which does not scroll
+        let wheel = WheelEvent::new(0.0, 10.0, Point::new(350.0, 50.0));
+        assert_eq!(events.process_wheel_event(wheel, &root).unwrap(), None);
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+}
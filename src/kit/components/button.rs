@@ -1,6 +1,9 @@
 // Button component for OrbitKit
 
-use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::component::{
+    AxProperties, AxRole, AxState, Component, ComponentError, ComponentId, Context, Node,
+};
+use crate::kit::theme::{Theme, ThemeContext, ThemeSubscription};
 use std::any::Any;
 
 /// Button size variants
@@ -56,6 +59,10 @@ pub struct Button {
     pub size: ButtonSize,
     /// Click event handler
     pub on_click: Option<fn()>,
+    /// Theme provided by an enclosing `ThemeProvider`
+    theme: Option<ThemeContext>,
+    /// Re-renders the button when the theme mode changes
+    _theme_subscription: Option<ThemeSubscription>,
 }
 
 /// Properties for the Button component
@@ -82,6 +89,32 @@ impl Default for Button {
             disabled: false,
             size: ButtonSize::Medium,
             on_click: None,
+            theme: None,
+            _theme_subscription: None,
+        }
+    }
+}
+
+impl Button {
+    /// Background color for the variant in the current theme mode
+    pub fn background_color(&self) -> String {
+        let palette = Theme::current_palette(self.theme.as_ref());
+        match self.variant {
+            ButtonVariant::Primary => palette.accent,
+            ButtonVariant::Secondary => palette.surface_variant,
+            ButtonVariant::Outline | ButtonVariant::Ghost | ButtonVariant::Link => {
+                "transparent".to_string()
+            }
+        }
+    }
+
+    /// Text color for the variant in the current theme mode
+    pub fn text_color(&self) -> String {
+        let palette = Theme::current_palette(self.theme.as_ref());
+        match self.variant {
+            ButtonVariant::Primary => palette.on_accent,
+            ButtonVariant::Secondary | ButtonVariant::Ghost => palette.on_surface,
+            ButtonVariant::Outline | ButtonVariant::Link => palette.accent,
         }
     }
 }
@@ -93,14 +126,20 @@ impl Component for Button {
         self.id
     }
 
-    fn create(props: Self::Props, _context: Context) -> Self {
+    fn create(props: Self::Props, context: Context) -> Self {
+        let id = ComponentId::new();
+        let theme = context.inject::<ThemeContext>();
+        let theme_subscription = theme.as_ref().map(|theme| theme.subscribe(&context, id));
+
         Self {
-            id: ComponentId::new(),
+            id,
             text: props.text,
             variant: props.variant.unwrap_or(ButtonVariant::Primary),
             disabled: props.disabled.unwrap_or(false),
             size: props.size.unwrap_or(ButtonSize::Medium),
             on_click: props.on_click,
+            theme,
+            _theme_subscription: theme_subscription,
         }
     }
 
@@ -126,4 +165,13 @@ impl Component for Button {
         // In a real implementation, this would return the actual DOM nodes
         Ok(vec![])
     }
+
+    fn accessibility(&self) -> AxProperties {
+        AxProperties::new(AxRole::Button)
+            .with_name(self.text.as_str())
+            .with_state(AxState {
+                disabled: self.disabled,
+                ..Default::default()
+            })
+    }
 }
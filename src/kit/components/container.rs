@@ -0,0 +1,105 @@
+// Container component for OrbitKit
+
+use std::any::Any;
+
+use super::layout::layout_node;
+use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::layout::{AlignItems, CalcExpr, Dimension, EdgeDimensions, FlexDirection, LayoutStyle};
+
+/// Widest a container grows by default, in pixels
+const DEFAULT_MAX_WIDTH: f32 = 1200.0;
+
+/// Container component
+///
+/// Centers its content horizontally and caps its width, for page-level
+/// content that shouldn't stretch across wide windows. The side padding
+/// scales with the available width, so narrow windows keep more of their
+/// space for content.
+#[derive(Debug)]
+pub struct Container {
+    /// Component ID for tracking
+    id: ComponentId,
+    /// Widest the content area grows, in pixels
+    pub max_width: f32,
+    /// Padding on the left and right of the content
+    pub padding: Dimension,
+    /// Contained children
+    pub children: Vec<Node>,
+}
+
+/// Container props
+#[derive(Debug, Clone, Default)]
+pub struct ContainerProps {
+    /// Widest the content area grows, in pixels (defaults to 1200)
+    pub max_width: Option<f32>,
+    /// Padding on the left and right (defaults to `calc(16px + 2%)`)
+    pub padding: Option<Dimension>,
+    /// Contained children
+    pub children: Vec<Node>,
+}
+
+/// Side padding of 16px plus 2% of the available width
+fn responsive_padding() -> Dimension {
+    Dimension::Calc(CalcExpr::Add(
+        Box::new(CalcExpr::Points(16.0)),
+        Box::new(CalcExpr::Percent(2.0)),
+    ))
+}
+
+impl Container {
+    /// Layout of the content area, inside the full-width centering node
+    pub fn layout_style(&self) -> LayoutStyle {
+        LayoutStyle {
+            flex_direction: FlexDirection::Column,
+            width: Dimension::Percent(100.0),
+            max_width: Dimension::Points(self.max_width),
+            padding: EdgeDimensions::horizontal_vertical(
+                self.padding.clone(),
+                Dimension::Points(0.0),
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl Component for Container {
+    type Props = ContainerProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, _context: Context) -> Self {
+        Self {
+            id: ComponentId::new(),
+            max_width: props.max_width.unwrap_or(DEFAULT_MAX_WIDTH),
+            padding: props.padding.unwrap_or_else(responsive_padding),
+            children: props.children,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.max_width = props.max_width.unwrap_or(self.max_width);
+        self.padding = props.padding.unwrap_or_else(|| self.padding.clone());
+        self.children = props.children;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        let content = layout_node(self.layout_style(), &self.children);
+        let centering = LayoutStyle {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+        Ok(vec![layout_node(centering, &[content])])
+    }
+}
@@ -0,0 +1,191 @@
+// Grid component for OrbitKit
+
+use std::any::Any;
+use std::collections::HashSet;
+
+use super::layout::layout_node;
+use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::layout::{Dimension, Display, Gap, GridPlacement, LayoutStyle};
+
+/// Grid component
+///
+/// Lays its children out on the grid layout engine's tracks. Children
+/// without a grid placement of their own fill the free cells in order, left to
+/// right and then top to bottom, adding rows past `rows` as needed.
+#[derive(Debug)]
+pub struct Grid {
+    /// Component ID for tracking
+    id: ComponentId,
+    /// Column track sizes, such as `Fraction(1.0)` for `1fr`
+    pub columns: Vec<Dimension>,
+    /// Row track sizes; rows past these size to their content
+    pub rows: Vec<Dimension>,
+    /// Space between rows and between columns, in pixels
+    pub gap: Gap,
+    /// Grid items
+    pub children: Vec<Node>,
+}
+
+/// Grid props
+#[derive(Debug, Clone, Default)]
+pub struct GridProps {
+    /// Column track sizes
+    pub columns: Vec<Dimension>,
+    /// Row track sizes
+    pub rows: Vec<Dimension>,
+    /// Space between tracks (defaults to none)
+    pub gap: Option<Gap>,
+    /// Grid items
+    pub children: Vec<Node>,
+}
+
+impl Grid {
+    /// Grid layout of the grid's node
+    pub fn layout_style(&self) -> LayoutStyle {
+        LayoutStyle {
+            display: Display::Grid,
+            grid_template_columns: self.columns.clone(),
+            grid_template_rows: self.rows.clone(),
+            gap: self.gap,
+            ..Default::default()
+        }
+    }
+
+    /// Children with a free cell assigned to each one the author didn't place
+    fn placed_children(&self) -> Vec<Node> {
+        let columns = self.columns.len().max(1);
+        let occupied: HashSet<(usize, usize)> = self
+            .children
+            .iter()
+            .filter_map(|child| child.style()?.layout_style.as_ref())
+            .filter(|layout| is_placed(layout) && !layout.position_type.is_out_of_flow())
+            .flat_map(|layout| {
+                let (column, column_span) = layout.grid_column.tracks();
+                let (row, row_span) = layout.grid_row.tracks();
+                (row..row + row_span)
+                    .flat_map(move |row| (column..column + column_span).map(move |c| (c, row)))
+            })
+            .collect();
+
+        let mut next_cell = 0;
+        self.children
+            .iter()
+            .map(|child| {
+                let mut child = child.clone();
+                let mut style = child.style().cloned().unwrap_or_default();
+                let layout = style.layout_style.get_or_insert_with(LayoutStyle::default);
+                if !is_placed(layout) && !layout.position_type.is_out_of_flow() {
+                    while occupied.contains(&(next_cell % columns, next_cell / columns)) {
+                        next_cell += 1;
+                    }
+                    layout.grid_column = GridPlacement::line(next_cell % columns + 1);
+                    layout.grid_row = GridPlacement::line(next_cell / columns + 1);
+                    next_cell += 1;
+                    child.set_style(style);
+                }
+                child
+            })
+            .collect()
+    }
+}
+
+/// Whether the author gave a grid item a line of its own
+fn is_placed(layout: &LayoutStyle) -> bool {
+    layout.grid_column.start.is_some() || layout.grid_row.start.is_some()
+}
+
+impl Component for Grid {
+    type Props = GridProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, _context: Context) -> Self {
+        Self {
+            id: ComponentId::new(),
+            columns: props.columns,
+            rows: props.rows,
+            gap: props.gap.unwrap_or_default(),
+            children: props.children,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.columns = props.columns;
+        self.rows = props.rows;
+        self.gap = props.gap.unwrap_or(self.gap);
+        self.children = props.children;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        Ok(vec![layout_node(
+            self.layout_style(),
+            &self.placed_children(),
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::layout_tree;
+    use crate::layout::{LayoutEngine, Point, Size};
+    use crate::style::Style;
+
+    fn item(placement: Option<(usize, usize)>) -> Node {
+        let mut style = LayoutStyle {
+            height: Dimension::Points(20.0),
+            ..Default::default()
+        };
+        if let Some((column, row)) = placement {
+            style.grid_column = GridPlacement::line(column);
+            style.grid_row = GridPlacement::line(row);
+        }
+        let mut node = Node::default();
+        node.set_style(Style {
+            layout_style: Some(style),
+            ..Default::default()
+        });
+        node
+    }
+
+    #[test]
+    fn test_auto_placed_children_skip_cells_taken_by_placed_children() {
+        let props = GridProps {
+            columns: vec![Dimension::Points(50.0), Dimension::Points(50.0)],
+            children: vec![item(None), item(Some((2, 1))), item(None)],
+            ..Default::default()
+        };
+        let grid = Grid::create(props, Context::new());
+        let nodes = grid.render().unwrap();
+
+        let mut root = layout_tree(&nodes[0], &mut Vec::new());
+        LayoutEngine::new()
+            .calculate_layout(&mut root, Size::new(100.0, 100.0))
+            .unwrap();
+
+        let origins: Vec<Point> = root
+            .children
+            .iter()
+            .map(|child| child.layout.rect.origin)
+            .collect();
+        assert_eq!(
+            origins,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(50.0, 0.0),
+                Point::new(0.0, 20.0)
+            ]
+        );
+    }
+}
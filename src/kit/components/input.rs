@@ -1,6 +1,13 @@
 // Input component for OrbitKit
 
-use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::component::{
+    AxProperties, AxRole, AxState, Component, ComponentError, ComponentId, Context, Node,
+};
+use crate::events::{Key, KeyEvent, KeyPhase};
+use crate::kit::text_edit::{CursorDirection, TextEditState};
+use crate::platform::clipboard::{self, Clipboard};
+use crate::state::Signal;
+use std::sync::{Arc, Mutex};
 
 /// Input component
 #[derive(Debug)]
@@ -9,7 +16,7 @@ pub struct Input {
     id: ComponentId,
     /// Input type (text, password, email, etc.)
     pub input_type: String,
-    /// Input value
+    /// Input value, kept in step with the editing state
     pub value: String,
     /// Input placeholder
     pub placeholder: Option<String>,
@@ -25,6 +32,16 @@ pub struct Input {
     pub helper_text: Option<String>,
     /// On change handler
     pub on_change: Option<fn(String)>,
+    /// Signal bound two-way to the value, as generated for `o-model`
+    pub model: Option<Signal<String>>,
+    /// Text, caret and selection being edited
+    edit: TextEditState,
+    /// Clipboard used by copy, cut and paste
+    clipboard: Arc<dyn Clipboard>,
+    /// Pasted text that arrived after the paste key was handled
+    pending_paste: Arc<Mutex<Option<String>>>,
+    /// Context used to re-render the input once a paste arrives
+    context: Context,
 }
 
 /// Input props
@@ -48,6 +65,8 @@ pub struct InputProps {
     pub helper_text: Option<String>,
     /// On change handler
     pub on_change: Option<fn(String)>,
+    /// Signal bound two-way to the value, which takes precedence over `value`
+    pub model: Option<Signal<String>>,
 }
 
 impl Default for Input {
@@ -63,7 +82,203 @@ impl Default for Input {
             error: None,
             helper_text: None,
             on_change: None,
+            model: None,
+            edit: TextEditState::default(),
+            clipboard: clipboard::system_clipboard(),
+            pending_paste: Arc::default(),
+            context: Context::new(),
+        }
+    }
+}
+
+impl Input {
+    /// Caret position, in characters
+    pub fn cursor(&self) -> usize {
+        self.edit.text()[..self.edit.cursor()].chars().count()
+    }
+
+    /// Text, caret and selection being edited
+    pub fn edit_state(&self) -> &TextEditState {
+        &self.edit
+    }
+
+    /// Use another clipboard for copy, cut and paste
+    pub fn set_clipboard(&mut self, clipboard: Arc<dyn Clipboard>) {
+        self.clipboard = clipboard;
+    }
+
+    /// Apply text typed by the user, with the caret where it ended up
+    ///
+    /// Writes the value to the bound model and calls `on_change`. The model
+    /// write re-renders the input with the value it already shows, which
+    /// leaves the caret where typing put it.
+    pub fn handle_input(&mut self, value: String, cursor: usize) {
+        let offset = value
+            .char_indices()
+            .nth(cursor)
+            .map_or(value.len(), |(offset, _)| offset);
+        self.edit.set_text(value);
+        self.edit.set_cursor(offset);
+        self.commit();
+    }
+
+    /// Edit the value in response to a key press
+    ///
+    /// Handles typing, Backspace and Delete, the arrow keys, Home and End,
+    /// with Shift to select and Control or Alt to move by word, Ctrl+A, and
+    /// Ctrl+C, Ctrl+X and Ctrl+V for the clipboard. Command works in place
+    /// of Control for the shortcuts. Returns whether the key was used.
+    pub fn handle_key(&mut self, event: &KeyEvent) -> bool {
+        if self.disabled || event.phase != KeyPhase::Pressed {
+            return false;
+        }
+        self.apply_pending_paste();
+
+        let modifiers = event.modifiers;
+        if modifiers.ctrl || modifiers.meta {
+            if let Key::Character(c) = &event.key {
+                match c.to_lowercase().as_str() {
+                    "a" => {
+                        self.edit.select_all();
+                        return true;
+                    }
+                    "c" => return self.copy(),
+                    "x" => return self.cut(),
+                    "v" => {
+                        self.paste();
+                        return true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let by_word = modifiers.ctrl || modifiers.alt;
+        let direction = match event.key {
+            Key::ArrowLeft => Some(CursorDirection::Left),
+            Key::ArrowRight => Some(CursorDirection::Right),
+            Key::Home => Some(CursorDirection::Home),
+            Key::End => Some(CursorDirection::End),
+            _ => None,
+        };
+        if let Some(direction) = direction {
+            if modifiers.shift {
+                self.edit.extend_selection(direction, by_word);
+            } else {
+                self.edit.move_cursor(direction, by_word);
+            }
+            return true;
+        }
+
+        let text = self.edit.text().to_string();
+        match &event.key {
+            Key::Character(c) if !(modifiers.ctrl || modifiers.meta) => self.edit.insert(c),
+            Key::Space => self.edit.insert(" "),
+            Key::Backspace => self.edit.delete_backward(),
+            Key::Delete => self.edit.delete_forward(),
+            _ => return false,
+        }
+        if self.edit.text() != text {
+            self.commit();
         }
+        true
+    }
+
+    /// Insert text whose paste finished after the key press was handled
+    ///
+    /// Pasting on the web waits for the browser, so the text arrives later.
+    /// The input schedules an update when it does, and the framework applies
+    /// the text through [`Component::handle_updates`] before re-rendering.
+    /// Returns whether there was text to insert.
+    pub fn apply_pending_paste(&mut self) -> bool {
+        let Some(text) = self.pending_paste.lock().unwrap().take() else {
+            return false;
+        };
+        // A single-line input drops line breaks, as browsers do
+        self.edit.insert(&text.replace(['\r', '\n'], ""));
+        self.commit();
+        true
+    }
+
+    /// Copy the selection, returning whether there was one
+    ///
+    /// Password inputs never expose their value to the clipboard.
+    fn copy(&mut self) -> bool {
+        let Some(text) = self.edit.selected_text() else {
+            return false;
+        };
+        if self.input_type == "password" {
+            return false;
+        }
+        let written = self.clipboard.set_text(text.to_string());
+        clipboard::spawn(async move {
+            if let Err(e) = written.await {
+                crate::trace::warn_event!("Failed to copy to the clipboard: {e}");
+            }
+        });
+        true
+    }
+
+    /// Copy the selection and remove it, returning whether there was one
+    fn cut(&mut self) -> bool {
+        if !self.copy() {
+            return false;
+        }
+        self.edit.delete_backward();
+        self.commit();
+        true
+    }
+
+    /// Replace the selection with the clipboard's text
+    fn paste(&mut self) {
+        let read = self.clipboard.get_text();
+        let pending = Arc::clone(&self.pending_paste);
+        let context = self.context.clone();
+        let id = self.id;
+        clipboard::spawn(async move {
+            match read.await {
+                Ok(Some(text)) => {
+                    *pending.lock().unwrap() = Some(text);
+                    context.schedule_update(id);
+                }
+                Ok(None) => {}
+                Err(e) => crate::trace::warn_event!("Failed to paste from the clipboard: {e}"),
+            }
+        });
+        self.apply_pending_paste();
+    }
+
+    /// Publish the edited text to `value`, the bound model and `on_change`
+    fn commit(&mut self) {
+        self.value = self.edit.text().to_string();
+
+        if let Some(model) = &self.model {
+            if let Err(e) = model.set(self.value.clone()) {
+                crate::trace::warn_event!("Failed to update input model: {e}");
+            }
+        }
+        if let Some(on_change) = self.on_change {
+            on_change(self.value.clone());
+        }
+    }
+
+    /// Show a new value, keeping the caret unless the value changed
+    ///
+    /// A value changed from outside moves the caret to the end, like a
+    /// browser assigning `input.value`.
+    fn set_value(&mut self, value: String) {
+        if value != self.value {
+            self.edit.set_text(value.clone());
+            self.value = value;
+        }
+    }
+}
+
+/// The bound model's current value, or the `value` prop without one
+fn resolve_value(props: &InputProps) -> String {
+    match &props.model {
+        Some(model) => model.get().clone(),
+        None => props.value.clone(),
     }
 }
 
@@ -74,11 +289,13 @@ impl Component for Input {
         self.id
     }
 
-    fn create(props: Self::Props, _context: Context) -> Self {
+    fn create(props: Self::Props, context: Context) -> Self {
+        let value = resolve_value(&props);
         Self {
             id: ComponentId::new(),
             input_type: props.input_type.unwrap_or_else(|| "text".to_string()),
-            value: props.value,
+            edit: TextEditState::new(value.clone()),
+            value,
             placeholder: props.placeholder,
             disabled: props.disabled.unwrap_or(false),
             required: props.required.unwrap_or(false),
@@ -86,12 +303,21 @@ impl Component for Input {
             error: props.error,
             helper_text: props.helper_text,
             on_change: props.on_change,
+            model: props.model,
+            clipboard: clipboard::system_clipboard(),
+            pending_paste: Arc::default(),
+            context,
         }
     }
 
+    fn handle_updates(&mut self) -> Result<(), ComponentError> {
+        self.apply_pending_paste();
+        Ok(())
+    }
+
     fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.set_value(resolve_value(&props));
         self.input_type = props.input_type.unwrap_or_else(|| self.input_type.clone());
-        self.value = props.value;
         self.placeholder = props.placeholder;
         self.disabled = props.disabled.unwrap_or(self.disabled);
         self.required = props.required.unwrap_or(self.required);
@@ -99,6 +325,7 @@ impl Component for Input {
         self.error = props.error;
         self.helper_text = props.helper_text;
         self.on_change = props.on_change;
+        self.model = props.model;
         Ok(())
     }
 
@@ -115,4 +342,152 @@ impl Component for Input {
         // In a real implementation, this would return the actual DOM nodes
         Ok(vec![])
     }
+
+    fn accessibility(&self) -> AxProperties {
+        let (role, checked) = if self.input_type == "checkbox" {
+            let checked = matches!(self.value.as_str(), "true" | "on" | "checked");
+            (AxRole::Checkbox, Some(checked))
+        } else {
+            (AxRole::TextBox, None)
+        };
+        // Fall back to the placeholder when the input has no label
+        let name = self.label.as_ref().or(self.placeholder.as_ref());
+
+        AxProperties::new(role)
+            .with_name(name.cloned().unwrap_or_default())
+            .with_state(AxState {
+                checked,
+                disabled: self.disabled,
+                expanded: None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::events::Modifiers;
+    use crate::renderer::NullRenderer;
+    use crate::state::{create_signal, ReactiveScope};
+
+    fn props(model: &Signal<String>) -> InputProps {
+        InputProps {
+            input_type: None,
+            value: String::new(),
+            placeholder: None,
+            disabled: None,
+            required: None,
+            label: None,
+            error: None,
+            helper_text: None,
+            on_change: None,
+            model: Some(model.clone()),
+        }
+    }
+
+    #[test]
+    fn test_model_binding_keeps_cursor_while_typing() {
+        let model = create_signal(&ReactiveScope::new(), "helo".to_string());
+        let mut input = Input::create(props(&model), Context::new());
+        assert_eq!(input.value, "helo");
+        assert_eq!(input.cursor(), 4);
+
+        // Typing the missing "l" leaves the caret after it, mid-text
+        input.handle_input("hello".to_string(), 3);
+        assert_eq!(*model.get(), "hello");
+
+        // The re-render driven by the model write must not move the caret
+        input.update(props(&model)).unwrap();
+        assert_eq!(input.value, "hello");
+        assert_eq!(input.cursor(), 3);
+
+        // Changing the model from outside replaces the value
+        model.set("bye".to_string()).unwrap();
+        input.update(props(&model)).unwrap();
+        assert_eq!(input.value, "bye");
+        assert_eq!(input.cursor(), 3);
+    }
+
+    #[test]
+    fn test_key_presses_edit_the_value() {
+        let model = create_signal(&ReactiveScope::new(), "hello world".to_string());
+        let mut input = Input::create(props(&model), Context::new());
+        let ctrl_shift = Modifiers {
+            ctrl: true,
+            shift: true,
+            ..Default::default()
+        };
+
+        // Select the last word and type over it
+        assert!(input.handle_key(&KeyEvent::pressed(Key::ArrowLeft).with_modifiers(ctrl_shift)));
+        for c in ["t", "h", "e", "r", "e"] {
+            input.handle_key(&KeyEvent::pressed(Key::Character(c.to_string())));
+        }
+        assert_eq!(*model.get(), "hello there");
+
+        input.handle_key(&KeyEvent::pressed(Key::Backspace));
+        assert_eq!(input.value, "hello ther");
+        assert_eq!(input.cursor(), 10);
+        assert!(!input.handle_key(&KeyEvent::released(Key::Backspace)));
+    }
+
+    #[test]
+    fn test_cut_moves_the_selection_to_the_clipboard() {
+        let model = create_signal(&ReactiveScope::new(), "copy and paste".to_string());
+        let mut input = Input::create(props(&model), Context::new());
+        let clipboard = Arc::new(clipboard::MemoryClipboard::new());
+        input.set_clipboard(clipboard.clone());
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Default::default()
+        };
+        let shortcut =
+            |key: &str| KeyEvent::pressed(Key::Character(key.to_string())).with_modifiers(ctrl);
+
+        // Nothing is selected, so there is nothing to cut
+        assert!(!input.handle_key(&shortcut("x")));
+        assert_eq!(clipboard.text(), None);
+
+        let ctrl_shift = Modifiers {
+            shift: true,
+            ..ctrl
+        };
+        input.handle_key(&KeyEvent::pressed(Key::ArrowLeft).with_modifiers(ctrl_shift));
+        assert!(input.handle_key(&shortcut("x")));
+        assert_eq!(clipboard.text().as_deref(), Some("paste"));
+        assert_eq!(*model.get(), "copy and ");
+        assert_eq!(input.edit_state().selection(), None);
+
+        // Pasting at the start puts the cut text back
+        input.handle_key(&KeyEvent::pressed(Key::Home));
+        assert!(input.handle_key(&shortcut("v")));
+        assert_eq!(*model.get(), "pastecopy and ");
+        assert_eq!(input.cursor(), 5);
+    }
+
+    #[test]
+    fn test_paste_resolved_later_is_applied_on_the_next_frame() {
+        let model = create_signal(&ReactiveScope::new(), "value".to_string());
+        let mut app = App::new::<Input>(props(&model)).with_renderer(Box::new(NullRenderer::new()));
+        app.frame().unwrap();
+        assert!(!app.needs_frame());
+
+        let shared = app.tree().get_component(app.root_id()).unwrap();
+        let (pending, context, id) = {
+            let instance = shared.read().unwrap();
+            let component = instance.instance.lock().unwrap();
+            let input = component.as_any().downcast_ref::<Input>().unwrap();
+            (input.pending_paste.clone(), input.context.clone(), input.id)
+        };
+
+        // What the paste future does once the browser answers
+        *pending.lock().unwrap() = Some(" pasted".to_string());
+        context.schedule_update(id);
+        assert!(app.needs_frame());
+
+        app.frame().unwrap();
+        assert_eq!(*model.get(), "value pasted");
+        assert!(pending.lock().unwrap().is_none());
+    }
 }
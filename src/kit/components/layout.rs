@@ -1,6 +1,8 @@
 // Layout components for OrbitKit
 
 use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::layout::{AlignItems, JustifyContent, LayoutStyle};
+use crate::style::Style;
 use std::any::Any;
 
 /// Layout component
@@ -81,6 +83,43 @@ pub enum Justification {
     SpaceEvenly,
 }
 
+impl From<Alignment> for AlignItems {
+    fn from(align: Alignment) -> Self {
+        match align {
+            Alignment::Start => AlignItems::FlexStart,
+            Alignment::Center => AlignItems::Center,
+            Alignment::End => AlignItems::FlexEnd,
+            Alignment::Stretch => AlignItems::Stretch,
+        }
+    }
+}
+
+impl From<Justification> for JustifyContent {
+    fn from(justify: Justification) -> Self {
+        match justify {
+            Justification::Start => JustifyContent::FlexStart,
+            Justification::Center => JustifyContent::Center,
+            Justification::End => JustifyContent::FlexEnd,
+            Justification::SpaceBetween => JustifyContent::SpaceBetween,
+            Justification::SpaceAround => JustifyContent::SpaceAround,
+            Justification::SpaceEvenly => JustifyContent::SpaceEvenly,
+        }
+    }
+}
+
+/// A node laid out with `layout_style`, holding `children`
+pub(crate) fn layout_node(layout_style: LayoutStyle, children: &[Node]) -> Node {
+    let mut node = Node::default();
+    node.set_style(Style {
+        layout_style: Some(layout_style),
+        ..Default::default()
+    });
+    for child in children {
+        node.add_child(child.clone());
+    }
+    node
+}
+
 impl Default for Layout {
     fn default() -> Self {
         Self {
@@ -9,16 +9,34 @@ pub mod button;
 pub mod input;
 
 // Layout components
+pub mod container;
+pub mod grid;
 pub mod layout;
+pub mod stack;
 
 // Data display
 pub mod card;
+pub mod progress;
+pub mod spinner;
+
+// Navigation
+pub mod tabs;
+
+// Overlay
+pub mod toast;
 
 // Re-export commonly used components
 pub use button::Button;
 pub use card::Card;
+pub use container::Container;
+pub use grid::Grid;
 pub use input::Input;
 pub use layout::Layout;
+pub use progress::Progress;
+pub use spinner::Spinner;
+pub use stack::Stack;
+pub use tabs::Tabs;
+pub use toast::{ToastManager, Toaster};
 
 /*
 TODO: Component Roadmap
@@ -26,20 +44,21 @@ TODO: Component Roadmap
 Phase 1 - Core Components:
 - [ ] Select
 - [ ] Checkbox
-- [ ] Container
-- [ ] Stack
+- [x] Container
+- [x] Stack
 
 Phase 2 - Data Display:
 - [ ] Table
 - [ ] List
-- [ ] Progress
-- [ ] Spinner
+- [x] Progress
+- [x] Spinner
 
 Phase 3 - Navigation & Overlay:
 - [ ] Menu
-- [ ] Tabs
+- [x] Tabs
 - [ ] Modal
 - [ ] Dialog
+- [x] Toast
 
 Phase 4 - Advanced Components:
 - [ ] DataGrid
@@ -0,0 +1,225 @@
+// Progress component for OrbitKit
+
+use std::any::Any;
+
+use crate::component::{
+    AxProperties, AxRole, Component, ComponentError, ComponentId, Context, Node,
+};
+use crate::kit::theme::{Palette, Theme, ThemeContext, ThemeSubscription};
+use crate::layout::{Dimension, LayoutStyle};
+use crate::style::{BorderRadius, Color, Style};
+
+/// Share of the track an indeterminate bar fills
+const INDETERMINATE_FRACTION: f32 = 0.3;
+
+/// Progress component
+///
+/// A horizontal bar that fills as a task advances. Determinate bars show
+/// `value`, a fraction from 0 to 1; out-of-range values are clamped and
+/// NaN counts as no progress. Without a value the bar is indeterminate and
+/// shows a short segment instead.
+///
+/// The track carries ARIA-style `role="progressbar"` and `aria-valuenow`
+/// attributes, and the accessible value is the percentage, such as `40%`.
+#[derive(Debug)]
+pub struct Progress {
+    /// Component ID for tracking
+    id: ComponentId,
+    /// Completed fraction in [0, 1], or `None` when indeterminate
+    value: Option<f32>,
+    /// Thickness of the bar in pixels
+    pub height: f32,
+    /// Accessible name describing the task
+    pub label: Option<String>,
+    /// Theme provided by an enclosing `ThemeProvider`
+    theme: Option<ThemeContext>,
+    /// Re-renders the bar when the theme mode changes
+    _theme_subscription: Option<ThemeSubscription>,
+}
+
+/// Progress props
+#[derive(Debug, Clone, Default)]
+pub struct ProgressProps {
+    /// Completed fraction from 0 to 1, or `None` for an indeterminate bar
+    pub value: Option<f32>,
+    /// Thickness of the bar in pixels (defaults to 4)
+    pub height: Option<f32>,
+    /// Accessible name describing the task
+    pub label: Option<String>,
+}
+
+/// Clamp a progress value to [0, 1], treating NaN as no progress
+fn clamp_progress(value: f32) -> f32 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+impl Progress {
+    /// Completed fraction in [0, 1], or `None` when indeterminate
+    pub fn value(&self) -> Option<f32> {
+        self.value
+    }
+
+    /// Whether the bar shows activity without a known amount of progress
+    pub fn is_indeterminate(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// Width of the filled part of a track `track_width` pixels wide
+    pub fn fill_width(&self, track_width: f32) -> f32 {
+        track_width * self.fill_fraction()
+    }
+
+    /// Accessible value: the percentage complete, such as `40%`
+    pub fn value_text(&self) -> Option<String> {
+        self.value
+            .map(|value| format!("{}%", (value * 100.0).round()))
+    }
+
+    fn fill_fraction(&self) -> f32 {
+        self.value.unwrap_or(INDETERMINATE_FRACTION)
+    }
+
+    fn render_fill(&self, palette: &Palette) -> Node {
+        let mut fill = Node::default();
+        fill.set_style(Style {
+            background_color: Some(Color::Hex(palette.accent.clone())),
+            border_radius: Some(BorderRadius::uniform(self.height / 2.0)),
+            layout_style: Some(LayoutStyle {
+                width: Dimension::Percent(self.fill_fraction() * 100.0),
+                height: Dimension::Percent(100.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        fill
+    }
+}
+
+impl Component for Progress {
+    type Props = ProgressProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, context: Context) -> Self {
+        let id = ComponentId::new();
+        let theme = context.inject::<ThemeContext>();
+        let theme_subscription = theme.as_ref().map(|theme| theme.subscribe(&context, id));
+
+        Self {
+            id,
+            value: props.value.map(clamp_progress),
+            height: props.height.unwrap_or(4.0),
+            label: props.label,
+            theme,
+            _theme_subscription: theme_subscription,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.value = props.value.map(clamp_progress);
+        self.height = props.height.unwrap_or(self.height);
+        self.label = props.label;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        let palette = Theme::current_palette(self.theme.as_ref());
+        let mut track = Node::default();
+        track.set_style(Style {
+            background_color: Some(Color::Hex(palette.surface_variant.clone())),
+            border_radius: Some(BorderRadius::uniform(self.height / 2.0)),
+            layout_style: Some(LayoutStyle {
+                width: Dimension::Percent(100.0),
+                height: Dimension::Points(self.height),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        track.add_attribute("role".to_string(), "progressbar".to_string());
+        track.add_attribute("aria-valuemin".to_string(), "0".to_string());
+        track.add_attribute("aria-valuemax".to_string(), "100".to_string());
+        if let Some(value) = self.value {
+            let percent = (value * 100.0).round().to_string();
+            track.add_attribute("aria-valuenow".to_string(), percent);
+        }
+        if let Some(label) = &self.label {
+            track.add_attribute("aria-label".to_string(), label.clone());
+        }
+        track.add_child(self.render_fill(&palette));
+        Ok(vec![track])
+    }
+
+    fn accessibility(&self) -> AxProperties {
+        let properties = AxProperties::new(AxRole::ProgressBar)
+            .with_name(self.label.clone().unwrap_or_default());
+        match self.value_text() {
+            Some(value) => properties.with_value(value),
+            None => properties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(value: Option<f32>) -> Progress {
+        let props = ProgressProps {
+            value,
+            ..Default::default()
+        };
+        Progress::create(props, Context::new())
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_clamped() {
+        assert_eq!(progress(Some(1.5)).value(), Some(1.0));
+        assert_eq!(progress(Some(-0.2)).value(), Some(0.0));
+        assert_eq!(progress(Some(f32::NAN)).value(), Some(0.0));
+
+        let mut bar = progress(Some(0.4));
+        assert_eq!(bar.accessibility().value.as_deref(), Some("40%"));
+        bar.update(ProgressProps {
+            value: Some(7.0),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(bar.value(), Some(1.0));
+        assert_eq!(bar.accessibility().value.as_deref(), Some("100%"));
+        let track = &bar.render().unwrap()[0];
+        assert_eq!(track.attributes()["aria-valuenow"], "100");
+    }
+
+    #[test]
+    fn test_determinate_fill_width() {
+        let bar = progress(Some(0.25));
+        assert_eq!(bar.fill_width(200.0), 50.0);
+
+        let track = &bar.render().unwrap()[0];
+        let fill = track.children()[0].style().unwrap();
+        let width = fill.layout_style.as_ref().unwrap().width.resolve(200.0);
+        assert_eq!(width, 50.0);
+
+        // Indeterminate bars show a fixed segment and no value
+        let bar = progress(None);
+        assert!(bar.is_indeterminate());
+        assert!((bar.fill_width(200.0) - 60.0).abs() < 1e-3);
+        assert_eq!(bar.accessibility().value, None);
+        let track = &bar.render().unwrap()[0];
+        assert!(!track.attributes().contains_key("aria-valuenow"));
+    }
+}
@@ -0,0 +1,134 @@
+// Spinner component for OrbitKit
+
+use std::any::Any;
+use std::time::Duration;
+
+use crate::component::{
+    AxProperties, AxRole, Component, ComponentError, ComponentId, Context, Node,
+};
+use crate::kit::theme::{Theme, ThemeContext, ThemeSubscription};
+use crate::layout::{Dimension, EdgeValues, LayoutStyle};
+use crate::renderer::{AnimatedProperty, AnimationDriver};
+use crate::style::{BorderRadius, BorderStyle, Color, EdgeColors, Style};
+
+/// Spinner component
+///
+/// A ring with one colored side that turns while something loads. The
+/// spinner doesn't animate by itself: [`Spinner::start`] hands the rotation
+/// to an [`AnimationDriver`], which keeps it turning until
+/// [`Spinner::stop`].
+#[derive(Debug)]
+pub struct Spinner {
+    /// Component ID for tracking
+    id: ComponentId,
+    /// Diameter in pixels
+    pub size: f32,
+    /// Time for one full turn
+    pub period: Duration,
+    /// Accessible name describing what is loading
+    pub label: String,
+    /// Theme provided by an enclosing `ThemeProvider`
+    theme: Option<ThemeContext>,
+    /// Re-renders the spinner when the theme mode changes
+    _theme_subscription: Option<ThemeSubscription>,
+}
+
+/// Spinner props
+#[derive(Debug, Clone, Default)]
+pub struct SpinnerProps {
+    /// Diameter in pixels (defaults to 24)
+    pub size: Option<f32>,
+    /// Time for one full turn (defaults to 800ms)
+    pub period: Option<Duration>,
+    /// Accessible name (defaults to "Loading")
+    pub label: Option<String>,
+}
+
+impl Spinner {
+    /// Start turning, one full turn per period, until stopped
+    pub fn start(&self, driver: &mut AnimationDriver) {
+        driver.repeat(self.id, AnimatedProperty::Rotate, 0.0, 360.0, self.period);
+    }
+
+    /// Stop turning
+    pub fn stop(&self, driver: &mut AnimationDriver) {
+        driver.cancel(self.id, AnimatedProperty::Rotate);
+    }
+
+    /// Style of the ring: a faint track with the accent color on top
+    pub fn ring_style(&self) -> Style {
+        let palette = Theme::current_palette(self.theme.as_ref());
+        let track = Color::Hex(palette.surface_variant);
+        let thickness = (self.size / 8.0).max(1.0);
+        Style {
+            border_width: Some(EdgeValues::uniform(thickness)),
+            border_color: Some(EdgeColors {
+                top: Color::Hex(palette.accent),
+                right: track.clone(),
+                bottom: track.clone(),
+                left: track,
+            }),
+            border_style: Some(BorderStyle::Solid),
+            border_radius: Some(BorderRadius::uniform(self.size / 2.0)),
+            layout_style: Some(LayoutStyle {
+                width: Dimension::Points(self.size),
+                height: Dimension::Points(self.size),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+impl Component for Spinner {
+    type Props = SpinnerProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, context: Context) -> Self {
+        let id = ComponentId::new();
+        let theme = context.inject::<ThemeContext>();
+        let theme_subscription = theme.as_ref().map(|theme| theme.subscribe(&context, id));
+
+        Self {
+            id,
+            size: props.size.unwrap_or(24.0),
+            period: props.period.unwrap_or(Duration::from_millis(800)),
+            label: props.label.unwrap_or_else(|| "Loading".to_string()),
+            theme,
+            _theme_subscription: theme_subscription,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.size = props.size.unwrap_or(self.size);
+        self.period = props.period.unwrap_or(self.period);
+        if let Some(label) = props.label {
+            self.label = label;
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        let mut ring = Node::default();
+        ring.set_style(self.ring_style());
+        ring.add_attribute("role".to_string(), "progressbar".to_string());
+        ring.add_attribute("aria-label".to_string(), self.label.clone());
+        Ok(vec![ring])
+    }
+
+    fn accessibility(&self) -> AxProperties {
+        // No value: a spinner is always indeterminate
+        AxProperties::new(AxRole::ProgressBar).with_name(self.label.as_str())
+    }
+}
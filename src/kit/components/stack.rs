@@ -0,0 +1,162 @@
+// Stack component for OrbitKit
+
+use std::any::Any;
+
+use super::layout::{layout_node, Alignment, Justification};
+use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::layout::{FlexDirection, Gap, LayoutStyle};
+
+/// Stack component
+///
+/// Lays its children out one after another in a single column or row, with
+/// a fixed gap between them. `align` places children across the stack and
+/// `justify` distributes them along it, as flex `align-items` and
+/// `justify-content` do.
+#[derive(Debug)]
+pub struct Stack {
+    /// Component ID for tracking
+    id: ComponentId,
+    /// Whether children are stacked top to bottom or side by side
+    pub direction: StackDirection,
+    /// Space between neighbouring children, in pixels
+    pub gap: f32,
+    /// Alignment across the stack
+    pub align: Alignment,
+    /// Distribution along the stack
+    pub justify: Justification,
+    /// Stacked children
+    pub children: Vec<Node>,
+}
+
+/// Stack props
+#[derive(Debug, Clone, Default)]
+pub struct StackProps {
+    /// Stacking direction (defaults to vertical)
+    pub direction: Option<StackDirection>,
+    /// Space between neighbouring children, in pixels (defaults to 0)
+    pub gap: Option<f32>,
+    /// Alignment across the stack (defaults to stretch)
+    pub align: Option<Alignment>,
+    /// Distribution along the stack (defaults to start)
+    pub justify: Option<Justification>,
+    /// Stacked children
+    pub children: Vec<Node>,
+}
+
+/// Stack direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StackDirection {
+    /// Top to bottom, as a flex column
+    #[default]
+    Vertical,
+    /// Side by side, as a flex row
+    Horizontal,
+}
+
+impl Stack {
+    /// Flex layout of the stack's node
+    pub fn layout_style(&self) -> LayoutStyle {
+        let flex_direction = match self.direction {
+            StackDirection::Vertical => FlexDirection::Column,
+            StackDirection::Horizontal => FlexDirection::Row,
+        };
+        LayoutStyle {
+            flex_direction,
+            gap: Gap::uniform(self.gap),
+            align_items: self.align.into(),
+            justify_content: self.justify.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Component for Stack {
+    type Props = StackProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, _context: Context) -> Self {
+        Self {
+            id: ComponentId::new(),
+            direction: props.direction.unwrap_or_default(),
+            gap: props.gap.unwrap_or(0.0),
+            align: props.align.unwrap_or(Alignment::Stretch),
+            justify: props.justify.unwrap_or_default(),
+            children: props.children,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.direction = props.direction.unwrap_or(self.direction);
+        self.gap = props.gap.unwrap_or(self.gap);
+        self.align = props.align.unwrap_or(self.align);
+        self.justify = props.justify.unwrap_or(self.justify);
+        self.children = props.children;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        Ok(vec![layout_node(self.layout_style(), &self.children)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::layout_tree;
+    use crate::layout::{Dimension, LayoutEngine, Point, Size};
+
+    fn item(height: f32) -> Node {
+        let style = LayoutStyle {
+            height: Dimension::Points(height),
+            ..Default::default()
+        };
+        layout_node(style, &[])
+    }
+
+    #[test]
+    fn test_vertical_stack_lays_children_in_a_column_with_gap() {
+        let props = StackProps {
+            direction: Some(StackDirection::Vertical),
+            gap: Some(8.0),
+            children: vec![item(20.0), item(30.0), item(20.0)],
+            ..Default::default()
+        };
+        let stack = Stack::create(props, Context::new());
+        let nodes = stack.render().unwrap();
+        assert_eq!(nodes.len(), 1);
+
+        // The app gives the root node the viewport's size
+        let mut root = layout_tree(&nodes[0], &mut Vec::new());
+        root.style.height = Dimension::Points(400.0);
+        LayoutEngine::new()
+            .calculate_layout(&mut root, Size::new(200.0, 400.0))
+            .unwrap();
+
+        let origins: Vec<Point> = root
+            .children
+            .iter()
+            .map(|child| child.layout.rect.origin)
+            .collect();
+        assert_eq!(
+            origins,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 28.0),
+                Point::new(0.0, 66.0)
+            ]
+        );
+        // Children stretch across the column by default
+        assert_eq!(root.children[1].layout.rect.size, Size::new(200.0, 30.0));
+    }
+}
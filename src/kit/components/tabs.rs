@@ -0,0 +1,321 @@
+// Tabs component for OrbitKit
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::component::{Callback, Component, ComponentError, ComponentId, Context, Node};
+use crate::events::{EventPropagation, Key, KeyEvent, KeyPhase, PointerInput};
+
+/// Tabs component
+///
+/// Renders a tablist header followed by the active panel. Tabs are
+/// controlled: clicking a tab or moving between tabs with the arrow keys
+/// reports the new tab through `on_change`, and the parent passes it back
+/// as `active`.
+///
+/// Only the active tab is in the tab order (`tabindex="0"`), so Tab moves
+/// focus past the header and the arrow keys move between tabs. Nodes carry
+/// ARIA-style `role`, `aria-selected`, `aria-controls` and `hidden`
+/// attributes.
+#[derive(Debug, Default)]
+pub struct Tabs {
+    /// Component ID for tracking
+    id: ComponentId,
+    /// Tabs as `(id, label)` pairs, in display order
+    pub tabs: Vec<(String, String)>,
+    /// ID of the active tab
+    pub active: String,
+    /// Called with the ID of the tab the user selects
+    pub on_change: Option<Callback<String>>,
+    /// Panel content, keyed by tab ID
+    pub panels: HashMap<String, Vec<Node>>,
+    /// Whether inactive panels stay mounted, hidden
+    pub keep_mounted: bool,
+}
+
+/// Tabs props
+#[derive(Debug, Clone)]
+pub struct TabsProps {
+    /// Tabs as `(id, label)` pairs, in display order
+    pub tabs: Vec<(String, String)>,
+    /// ID of the active tab
+    pub active: String,
+    /// Called with the ID of the tab the user selects
+    pub on_change: Option<Callback<String>>,
+    /// Panel content, keyed by tab ID
+    pub panels: HashMap<String, Vec<Node>>,
+    /// Whether inactive panels stay mounted, hidden (defaults to false)
+    pub keep_mounted: Option<bool>,
+}
+
+impl Tabs {
+    /// Build the button for one tab, wired to report selection changes
+    fn render_tab(&self, index: usize) -> Node {
+        let (tab_id, label) = &self.tabs[index];
+        let selected = *tab_id == self.active;
+
+        let mut node = Node::default();
+        node.set_text(label.clone());
+        node.add_attribute("role".to_string(), "tab".to_string());
+        node.add_attribute("id".to_string(), tab_element_id(tab_id));
+        node.add_attribute("aria-controls".to_string(), panel_element_id(tab_id));
+        node.add_attribute("aria-selected".to_string(), selected.to_string());
+        let tabindex = if selected { "0" } else { "-1" };
+        node.add_attribute("tabindex".to_string(), tabindex.to_string());
+
+        if let (Some(on_change), Some(delegate)) = (&self.on_change, node.event_delegate()) {
+            let delegate = delegate.lock().unwrap();
+
+            // Only a release that ends a press on this tab activates it, not
+            // one ending a drag that started elsewhere
+            let (on_click, clicked) = (on_change.clone(), tab_id.clone());
+            let pressed = Arc::new(AtomicBool::new(false));
+            delegate.on(
+                move |input: &PointerInput, _: &EventPropagation| match input {
+                    PointerInput::Down(_) => pressed.store(true, Ordering::Relaxed),
+                    PointerInput::Up(_) => {
+                        if pressed.swap(false, Ordering::Relaxed) {
+                            on_click.call(clicked.clone());
+                        }
+                    }
+                    PointerInput::Leave => pressed.store(false, Ordering::Relaxed),
+                    PointerInput::Move(_) => {}
+                },
+            );
+
+            let (on_key, ids) = (on_change.clone(), self.tab_ids());
+            delegate.on(move |event: &KeyEvent, propagation: &EventPropagation| {
+                if event.phase != KeyPhase::Pressed {
+                    return;
+                }
+                if let Some(next) = neighbour(ids.len(), index, &event.key) {
+                    propagation.prevent_default();
+                    on_key.call(ids[next].clone());
+                }
+            });
+        }
+
+        node
+    }
+
+    /// Build the panel for one tab
+    fn render_panel(&self, tab_id: &str) -> Node {
+        let mut node = Node::default();
+        node.add_attribute("role".to_string(), "tabpanel".to_string());
+        node.add_attribute("id".to_string(), panel_element_id(tab_id));
+        node.add_attribute("aria-labelledby".to_string(), tab_element_id(tab_id));
+        if tab_id != self.active {
+            node.add_attribute("hidden".to_string(), "true".to_string());
+        }
+        for child in self.panels.get(tab_id).into_iter().flatten() {
+            node.add_child(child.clone());
+        }
+        node
+    }
+
+    fn tab_ids(&self) -> Vec<String> {
+        self.tabs.iter().map(|(id, _)| id.clone()).collect()
+    }
+}
+
+/// Index of the tab a navigation key moves to, wrapping at either end
+fn neighbour(count: usize, index: usize, key: &Key) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    match key {
+        Key::ArrowRight => Some((index + 1) % count),
+        Key::ArrowLeft => Some((index + count - 1) % count),
+        Key::Home => Some(0),
+        Key::End => Some(count - 1),
+        _ => None,
+    }
+}
+
+fn tab_element_id(tab_id: &str) -> String {
+    format!("tab-{tab_id}")
+}
+
+fn panel_element_id(tab_id: &str) -> String {
+    format!("tabpanel-{tab_id}")
+}
+
+impl Component for Tabs {
+    type Props = TabsProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, _context: Context) -> Self {
+        Self {
+            id: ComponentId::new(),
+            tabs: props.tabs,
+            active: props.active,
+            on_change: props.on_change,
+            panels: props.panels,
+            keep_mounted: props.keep_mounted.unwrap_or(false),
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        self.tabs = props.tabs;
+        self.active = props.active;
+        self.on_change = props.on_change;
+        self.panels = props.panels;
+        self.keep_mounted = props.keep_mounted.unwrap_or(self.keep_mounted);
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        let mut tablist = Node::default();
+        tablist.add_attribute("role".to_string(), "tablist".to_string());
+        for index in 0..self.tabs.len() {
+            tablist.add_child(self.render_tab(index));
+        }
+
+        let mut nodes = vec![tablist];
+        for (tab_id, _) in &self.tabs {
+            if self.keep_mounted || *tab_id == self.active {
+                nodes.push(self.render_panel(tab_id));
+            }
+        }
+        Ok(nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Point;
+    use std::sync::Mutex;
+
+    fn props(active: &str, on_change: Callback<String>) -> TabsProps {
+        let tabs = ["general", "privacy", "about"];
+        let panels = tabs
+            .iter()
+            .map(|id| {
+                let mut content = Node::default();
+                content.set_text(format!("{id} settings"));
+                (id.to_string(), vec![content])
+            })
+            .collect();
+
+        TabsProps {
+            tabs: tabs
+                .iter()
+                .map(|id| (id.to_string(), id.to_uppercase()))
+                .collect(),
+            active: active.to_string(),
+            on_change: Some(on_change),
+            panels,
+            keep_mounted: None,
+        }
+    }
+
+    fn recorder() -> (Arc<Mutex<Vec<String>>>, Callback<String>) {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let log = changes.clone();
+        let on_change = Callback::new(move |id: String| log.lock().unwrap().push(id));
+        (changes, on_change)
+    }
+
+    fn tab(nodes: &[Node], index: usize) -> &Node {
+        &nodes[0].children()[index]
+    }
+
+    fn attribute<'a>(node: &'a Node, key: &str) -> Option<&'a str> {
+        node.attributes().get(key).map(String::as_str)
+    }
+
+    #[test]
+    fn test_click_switches_active_tab() {
+        let (changes, on_change) = recorder();
+        let mut tabs = Tabs::create(props("general", on_change.clone()), Context::new());
+
+        let nodes = tabs.render().unwrap();
+        assert_eq!(attribute(&nodes[0], "role"), Some("tablist"));
+        assert_eq!(attribute(tab(&nodes, 0), "aria-selected"), Some("true"));
+        assert_eq!(attribute(tab(&nodes, 1), "aria-selected"), Some("false"));
+        // Only the active panel is mounted
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].children()[0].text(), Some("general settings"));
+
+        // A release ending a press that started off the tab does not activate
+        let privacy = tab(&nodes, 1);
+        tab(&nodes, 0).dispatch_event(&PointerInput::Down(Point::new(0.0, 0.0)));
+        privacy.dispatch_event(&PointerInput::Up(Point::new(0.0, 0.0)));
+        assert!(changes.lock().unwrap().is_empty());
+
+        // Nor does one cancelled by the pointer leaving the window
+        privacy.dispatch_event(&PointerInput::Down(Point::new(0.0, 0.0)));
+        privacy.dispatch_event(&PointerInput::Leave);
+        privacy.dispatch_event(&PointerInput::Up(Point::new(0.0, 0.0)));
+        assert!(changes.lock().unwrap().is_empty());
+
+        // Pressing alone does not activate
+        privacy.dispatch_event(&PointerInput::Down(Point::new(0.0, 0.0)));
+        assert!(changes.lock().unwrap().is_empty());
+        privacy.dispatch_event(&PointerInput::Up(Point::new(0.0, 0.0)));
+        assert_eq!(*changes.lock().unwrap(), vec!["privacy".to_string()]);
+
+        tabs.update(props("privacy", on_change)).unwrap();
+        let nodes = tabs.render().unwrap();
+        assert_eq!(attribute(tab(&nodes, 1), "aria-selected"), Some("true"));
+        assert_eq!(attribute(&nodes[1], "id"), Some("tabpanel-privacy"));
+        assert_eq!(nodes[1].children()[0].text(), Some("privacy settings"));
+    }
+
+    #[test]
+    fn test_arrow_keys_move_between_tabs_with_roving_tabindex() {
+        let (changes, on_change) = recorder();
+        let tabs = Tabs::create(props("general", on_change), Context::new());
+
+        let nodes = tabs.render().unwrap();
+        let tabindex: Vec<_> = (0..3)
+            .map(|i| attribute(tab(&nodes, i), "tabindex"))
+            .collect();
+        assert_eq!(tabindex, vec![Some("0"), Some("-1"), Some("-1")]);
+
+        let general = tab(&nodes, 0);
+        general.dispatch_event(&KeyEvent::pressed(Key::ArrowRight));
+        general.dispatch_event(&KeyEvent::pressed(Key::ArrowLeft));
+        general.dispatch_event(&KeyEvent::pressed(Key::End));
+        general.dispatch_event(&KeyEvent::released(Key::ArrowRight));
+        general.dispatch_event(&KeyEvent::pressed(Key::Enter));
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![
+                "privacy".to_string(),
+                "about".to_string(),
+                "about".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keep_mounted_hides_inactive_panels() {
+        let (_, on_change) = recorder();
+        let mut props = props("privacy", on_change);
+        props.keep_mounted = Some(true);
+        let tabs = Tabs::create(props, Context::new());
+
+        let nodes = tabs.render().unwrap();
+        let hidden: Vec<_> = nodes[1..]
+            .iter()
+            .map(|panel| attribute(panel, "hidden"))
+            .collect();
+        assert_eq!(hidden, vec![Some("true"), None, Some("true")]);
+    }
+}
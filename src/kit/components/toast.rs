@@ -0,0 +1,465 @@
+// Toast notifications for OrbitKit
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::events::Ticker;
+use crate::kit::theme::{Palette, Theme, ThemeContext};
+use crate::layout::{Dimension, EdgeDimensions, FlexDirection, Gap, LayoutStyle, PositionType};
+use crate::style::{BorderRadius, Color, Style};
+
+/// Toasts shown at once unless the manager is given another limit
+const DEFAULT_MAX_VISIBLE: usize = 3;
+
+/// Identifies a toast so it can be dismissed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+/// What a toast is about, which sets its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastKind {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// A notification to show
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastSpec {
+    /// Text of the notification
+    pub message: String,
+    /// Kind of notification
+    pub kind: ToastKind,
+    /// How long the toast stays once it is on screen
+    pub duration: Duration,
+}
+
+impl ToastSpec {
+    /// An info toast shown for four seconds
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            kind: ToastKind::Info,
+            duration: Duration::from_secs(4),
+        }
+    }
+}
+
+/// A toast that has been shown or queued
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// ID returned by [`ToastManager::show`]
+    pub id: ToastId,
+    /// What was asked for
+    pub spec: ToastSpec,
+    /// When the toast is dismissed, set on the first tick it is on screen
+    expires_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct ToastState {
+    visible: Vec<Toast>,
+    queued: VecDeque<Toast>,
+    max_visible: usize,
+    next_id: u64,
+    /// Component re-rendered when the toasts change
+    host: Option<(Context, ComponentId)>,
+}
+
+/// Transient notifications shared through the component context
+///
+/// Add one to an app with [`ToastManager::install`] and render a
+/// [`Toaster`] to show it. At most `max_visible` toasts are on screen at
+/// once; the rest wait in order and appear as earlier ones go. Each toast's
+/// duration counts from the first [`ToastManager::tick`] it is on screen.
+#[derive(Clone)]
+pub struct ToastManager {
+    inner: Arc<Mutex<ToastState>>,
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToastManager {
+    /// Create a manager showing up to three toasts at once
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ToastState {
+                visible: Vec::new(),
+                queued: VecDeque::new(),
+                max_visible: DEFAULT_MAX_VISIBLE,
+                next_id: 0,
+                host: None,
+            })),
+        }
+    }
+
+    /// Set how many toasts are on screen at once, at least one
+    pub fn with_max_visible(self, max_visible: usize) -> Self {
+        self.inner.lock().unwrap().max_visible = max_visible.max(1);
+        self
+    }
+
+    /// Show a toast, or queue it while the screen is full
+    pub fn show(&self, spec: ToastSpec) -> ToastId {
+        let id = {
+            let mut state = self.inner.lock().unwrap();
+            let id = ToastId(state.next_id);
+            state.next_id += 1;
+            let toast = Toast {
+                id,
+                spec,
+                expires_at: None,
+            };
+            if state.visible.len() < state.max_visible {
+                state.visible.push(toast);
+            } else {
+                state.queued.push_back(toast);
+            }
+            id
+        };
+        self.notify();
+        id
+    }
+
+    /// Remove a toast from the screen or the queue
+    ///
+    /// Returns whether the toast was still there.
+    pub fn dismiss(&self, id: ToastId) -> bool {
+        let removed = {
+            let mut state = self.inner.lock().unwrap();
+            let count = state.visible.len() + state.queued.len();
+            state.visible.retain(|toast| toast.id != id);
+            state.queued.retain(|toast| toast.id != id);
+            state.promote();
+            state.visible.len() + state.queued.len() != count
+        };
+        if removed {
+            self.notify();
+        }
+        removed
+    }
+
+    /// Dismiss toasts whose time is up and start the clock on new ones
+    ///
+    /// Returns whether any toast was dismissed.
+    pub fn tick(&self, now: Instant) -> bool {
+        let expired = {
+            let mut state = self.inner.lock().unwrap();
+            let count = state.visible.len();
+            state
+                .visible
+                .retain(|toast| toast.expires_at.is_none_or(|expires_at| now < expires_at));
+            state.promote();
+            for toast in &mut state.visible {
+                toast.expires_at.get_or_insert(now + toast.spec.duration);
+            }
+            state.visible.len() < count
+        };
+        if expired {
+            self.notify();
+        }
+        expired
+    }
+
+    /// Toasts on screen, oldest first
+    pub fn visible(&self) -> Vec<Toast> {
+        self.inner.lock().unwrap().visible.clone()
+    }
+
+    /// Number of toasts waiting for room on screen
+    pub fn queued_len(&self) -> usize {
+        self.inner.lock().unwrap().queued.len()
+    }
+
+    /// Whether any toast is on screen
+    pub fn is_active(&self) -> bool {
+        !self.inner.lock().unwrap().visible.is_empty()
+    }
+
+    /// Provide the manager in the app's context and tick it every frame
+    pub fn install(&self, app: &mut App) {
+        app.context().provide(self.clone());
+        app.add_ticker(self.clone());
+    }
+
+    /// Re-render `component_id` through `context` whenever the toasts change
+    pub fn attach(&self, context: &Context, component_id: ComponentId) {
+        self.inner.lock().unwrap().host = Some((context.clone(), component_id));
+    }
+
+    fn notify(&self) {
+        let host = self.inner.lock().unwrap().host.clone();
+        if let Some((context, id)) = host {
+            context.schedule_update(id);
+        }
+    }
+}
+
+impl Ticker for ToastManager {
+    fn tick(&self, now: Instant) {
+        ToastManager::tick(self, now);
+    }
+
+    fn is_active(&self) -> bool {
+        ToastManager::is_active(self)
+    }
+}
+
+impl ToastState {
+    /// Move queued toasts on screen while there is room
+    fn promote(&mut self) {
+        while self.visible.len() < self.max_visible {
+            let Some(toast) = self.queued.pop_front() else {
+                break;
+            };
+            self.visible.push(toast);
+        }
+    }
+}
+
+impl std::fmt::Debug for ToastManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.inner.lock().unwrap();
+        f.debug_struct("ToastManager")
+            .field("visible", &state.visible.len())
+            .field("queued", &state.queued.len())
+            .field("max_visible", &state.max_visible)
+            .finish()
+    }
+}
+
+/// Toaster component
+///
+/// Shows the toasts of a [`ToastManager`] stacked in the bottom-right
+/// corner of the viewport, above the rest of the app. Error toasts are
+/// announced with `role="alert"`, others with `role="status"`.
+#[derive(Debug)]
+pub struct Toaster {
+    /// Component ID for tracking
+    id: ComponentId,
+    /// Manager whose toasts are shown
+    manager: Option<ToastManager>,
+    /// Theme provided by an enclosing `ThemeProvider`
+    theme: Option<ThemeContext>,
+    /// Context a manager passed in later props is attached through
+    context: Context,
+}
+
+/// Toaster props
+#[derive(Debug, Clone, Default)]
+pub struct ToasterProps {
+    /// Manager to show, instead of the one provided in the context
+    pub manager: Option<ToastManager>,
+}
+
+impl Toaster {
+    fn render_toast(&self, toast: &Toast, palette: &Palette) -> Node {
+        let background = match toast.spec.kind {
+            ToastKind::Info => &palette.info,
+            ToastKind::Success => &palette.success,
+            ToastKind::Warning => &palette.warning,
+            ToastKind::Error => &palette.error,
+        };
+        let mut node = Node::default();
+        node.set_text(toast.spec.message.clone());
+        node.set_style(Style {
+            background_color: Some(Color::Hex(background.clone())),
+            color: Some(Color::Hex(palette.on_accent.clone())),
+            border_radius: Some(BorderRadius::uniform(6.0)),
+            layout_style: Some(LayoutStyle {
+                padding: EdgeDimensions::horizontal_vertical(
+                    Dimension::Points(16.0),
+                    Dimension::Points(12.0),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let role = match toast.spec.kind {
+            ToastKind::Error => "alert",
+            _ => "status",
+        };
+        node.add_attribute("role".to_string(), role.to_string());
+        node
+    }
+}
+
+impl Component for Toaster {
+    type Props = ToasterProps;
+
+    fn component_id(&self) -> ComponentId {
+        self.id
+    }
+
+    fn create(props: Self::Props, context: Context) -> Self {
+        let id = ComponentId::new();
+        let manager = props.manager.or_else(|| context.inject::<ToastManager>());
+        if let Some(manager) = &manager {
+            manager.attach(&context, id);
+        }
+
+        Self {
+            id,
+            manager,
+            theme: context.inject::<ThemeContext>(),
+            context,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
+        if let Some(manager) = props.manager {
+            manager.attach(&self.context, self.id);
+            self.manager = Some(manager);
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn render(&self) -> Result<Vec<Node>, ComponentError> {
+        let toasts = match &self.manager {
+            Some(manager) if manager.is_active() => manager.visible(),
+            _ => return Ok(vec![]),
+        };
+
+        let palette = Theme::current_palette(self.theme.as_ref());
+        let mut overlay = Node::default();
+        overlay.set_style(Style {
+            z_index: Some(1000),
+            layout_style: Some(LayoutStyle {
+                position_type: PositionType::Fixed,
+                right: Dimension::Points(16.0),
+                bottom: Dimension::Points(16.0),
+                width: Dimension::Points(320.0),
+                flex_direction: FlexDirection::Column,
+                gap: Gap::uniform(8.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        for toast in &toasts {
+            overlay.add_child(self.render_toast(toast, &palette));
+        }
+        Ok(vec![overlay])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::NullRenderer;
+
+    fn short(message: &str) -> ToastSpec {
+        ToastSpec {
+            duration: Duration::from_millis(100),
+            ..ToastSpec::new(message)
+        }
+    }
+
+    #[test]
+    fn test_short_toast_auto_dismisses_after_its_duration() {
+        let context = Context::new();
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let sink = updates.clone();
+        context.set_update_handler(move |id| sink.lock().unwrap().push(id));
+
+        let toasts = ToastManager::new();
+        context.provide(toasts.clone());
+        let toaster = Toaster::create(ToasterProps::default(), context);
+        toasts.show(short("Saved"));
+        assert_eq!(toaster.render().unwrap()[0].children().len(), 1);
+
+        // The clock starts on the first tick the toast is on screen
+        let start = Instant::now();
+        assert!(!toasts.tick(start));
+        assert!(!toasts.tick(start + Duration::from_millis(90)));
+        assert!(toasts.is_active());
+
+        assert!(toasts.tick(start + Duration::from_millis(110)));
+        assert!(!toasts.is_active());
+        assert!(toaster.render().unwrap().is_empty());
+        let id = toaster.component_id();
+        assert_eq!(*updates.lock().unwrap(), vec![id, id]);
+    }
+
+    #[test]
+    fn test_extra_toasts_wait_for_room() {
+        let toasts = ToastManager::new().with_max_visible(2);
+        let first = toasts.show(short("One"));
+        toasts.show(short("Two"));
+        let third = toasts.show(short("Three"));
+        let fourth = toasts.show(short("Four"));
+        assert_eq!(toasts.visible().len(), 2);
+        assert_eq!(toasts.queued_len(), 2);
+
+        // Dismissing a queued toast drops it without showing it
+        assert!(toasts.dismiss(fourth));
+        assert!(!toasts.dismiss(fourth));
+
+        // A queued toast gets the full duration from when it appears
+        let start = Instant::now();
+        toasts.tick(start);
+        assert!(toasts.dismiss(first));
+        let ids: Vec<ToastId> = toasts.visible().iter().map(|toast| toast.id).collect();
+        assert_eq!(ids[1], third);
+        toasts.tick(start + Duration::from_millis(50));
+        toasts.tick(start + Duration::from_millis(120));
+        assert_eq!(toasts.visible().len(), 1);
+        assert_eq!(toasts.visible()[0].id, third);
+        toasts.tick(start + Duration::from_millis(160));
+        assert!(!toasts.is_active());
+    }
+
+    #[test]
+    fn test_installed_manager_is_ticked_every_frame() {
+        let toasts = ToastManager::new();
+        let mut app = App::new::<Toaster>(ToasterProps {
+            manager: Some(toasts.clone()),
+        })
+        .with_renderer(Box::new(NullRenderer::new()));
+        toasts.install(&mut app);
+        assert!(app.context().inject::<ToastManager>().is_some());
+        app.frame().unwrap();
+        assert!(!app.needs_frame());
+
+        toasts.show(short("Saved"));
+        assert!(app.needs_frame());
+        let start = Instant::now();
+        app.frame_at(start).unwrap();
+        assert!(toasts.is_active());
+        app.frame_at(start + Duration::from_millis(110)).unwrap();
+        assert!(!toasts.is_active());
+        assert!(!app.needs_frame());
+    }
+
+    #[test]
+    fn test_manager_passed_in_update_rerenders_the_toaster() {
+        let context = Context::new();
+        let mut toaster = Toaster::create(ToasterProps::default(), context.clone());
+        let toasts = ToastManager::new();
+        toaster
+            .update(ToasterProps {
+                manager: Some(toasts.clone()),
+            })
+            .unwrap();
+
+        toasts.show(short("Saved"));
+        assert!(context.pending_updates().contains(&toaster.id));
+    }
+}
@@ -0,0 +1,420 @@
+// Form state and validation for OrbitKit
+
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+use regex::Regex;
+
+use crate::kit::components::input::InputProps;
+use crate::state::{create_effect, create_signal, Effect, ReactiveScope, Signal};
+
+type CustomRule = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+type FieldEffect = Effect<Box<dyn FnMut() + Send + Sync + 'static>>;
+
+/// A rule a field's value must follow
+///
+/// Length and pattern rules accept an empty value, so an optional field can
+/// be left blank; add [`Validator::required`] to reject that.
+#[derive(Clone)]
+pub enum Validator {
+    /// The value must contain something other than whitespace
+    Required,
+    /// The value must have at least this many characters
+    MinLength(usize),
+    /// The value must have at most this many characters
+    MaxLength(usize),
+    /// The value must match the pattern, or the message is reported
+    Pattern(Regex, String),
+    /// The function returns the error for a value it rejects
+    Custom(CustomRule),
+}
+
+impl Validator {
+    /// Reject empty and whitespace-only values
+    pub fn required() -> Self {
+        Validator::Required
+    }
+
+    /// Reject values shorter than `length` characters
+    pub fn min_length(length: usize) -> Self {
+        Validator::MinLength(length)
+    }
+
+    /// Reject values longer than `length` characters
+    pub fn max_length(length: usize) -> Self {
+        Validator::MaxLength(length)
+    }
+
+    /// Reject values `pattern` doesn't match, reporting `message`
+    pub fn pattern(pattern: &str, message: impl Into<String>) -> Result<Self, regex::Error> {
+        Ok(Validator::Pattern(Regex::new(pattern)?, message.into()))
+    }
+
+    /// Check values with a function returning the error for bad ones
+    pub fn custom(rule: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        Validator::Custom(Arc::new(rule))
+    }
+
+    /// The error for `value`, or `None` if it follows the rule
+    pub fn validate(&self, value: &str) -> Option<String> {
+        let length = value.chars().count();
+        match self {
+            Validator::Required if value.trim().is_empty() => {
+                Some("This field is required".to_string())
+            }
+            Validator::MinLength(min) if length > 0 && length < *min => {
+                Some(format!("Must be at least {min} characters"))
+            }
+            Validator::MaxLength(max) if length > *max => {
+                Some(format!("Must be at most {max} characters"))
+            }
+            Validator::Pattern(pattern, message) if length > 0 && !pattern.is_match(value) => {
+                Some(message.clone())
+            }
+            Validator::Custom(rule) => rule(value).err(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Validator::Required => write!(f, "Required"),
+            Validator::MinLength(min) => f.debug_tuple("MinLength").field(min).finish(),
+            Validator::MaxLength(max) => f.debug_tuple("MaxLength").field(max).finish(),
+            Validator::Pattern(pattern, message) => f
+                .debug_tuple("Pattern")
+                .field(&pattern.as_str())
+                .field(message)
+                .finish(),
+            Validator::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// A form field's value and validation state
+#[derive(Debug, Clone)]
+pub struct FieldState {
+    /// Current value
+    pub value: String,
+    /// Value the field started with
+    pub initial: String,
+    /// Errors from the last validation, empty when it passed
+    pub errors: Vec<String>,
+    /// Whether the user has left the field or tried to submit
+    pub touched: bool,
+    /// Whether the value differs from the initial one
+    pub dirty: bool,
+}
+
+struct Field {
+    name: String,
+    state: FieldState,
+    validators: Vec<Validator>,
+    /// Value bound two-way to the field's input
+    model: Signal<String>,
+}
+
+impl Field {
+    fn validate(&mut self) {
+        self.state.errors = self
+            .validators
+            .iter()
+            .filter_map(|validator| validator.validate(&self.state.value))
+            .collect();
+    }
+}
+
+#[derive(Default)]
+struct FormState {
+    fields: Vec<Field>,
+    /// Re-validate each field when its model changes
+    effects: Vec<FieldEffect>,
+    submitted: bool,
+}
+
+impl FormState {
+    fn field_mut(&mut self, name: &str) -> Option<&mut Field> {
+        self.fields.iter_mut().find(|field| field.name == name)
+    }
+}
+
+/// Field values, validators and their errors for a form
+///
+/// Each field has a signal bound to its input through
+/// [`Form::input_props`], so typing into the input re-validates the field.
+/// Errors appear once a field changes, and [`Form::submit`] validates every
+/// field, including ones the user never edited. [`Form::is_valid`] checks
+/// the current values whether or not they have been validated yet.
+///
+/// Clones share the same fields.
+#[derive(Clone, Default)]
+pub struct Form {
+    inner: Arc<Mutex<FormState>>,
+}
+
+impl Form {
+    /// Create a form without fields
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field holding `initial`, checked by `validators` in order
+    pub fn field(
+        self,
+        name: impl Into<String>,
+        initial: impl Into<String>,
+        validators: Vec<Validator>,
+    ) -> Self {
+        let name = name.into();
+        let initial = initial.into();
+        let model = create_signal(&ReactiveScope::new(), initial.clone());
+
+        let form = Arc::downgrade(&self.inner);
+        let changed = model.clone();
+        let field_name = name.clone();
+        let effect = create_effect(&ReactiveScope::new(), move || {
+            let value = changed.get().clone();
+            if let Some(inner) = Weak::upgrade(&form) {
+                Form { inner }.change(&field_name, value);
+            }
+        });
+
+        let mut state = self.inner.lock().unwrap();
+        state.fields.push(Field {
+            name,
+            state: FieldState {
+                value: initial.clone(),
+                initial,
+                errors: Vec::new(),
+                touched: false,
+                dirty: false,
+            },
+            validators,
+            model,
+        });
+        state.effects.push(effect);
+        drop(state);
+        self
+    }
+
+    /// Set a field's value, as if the user had entered it
+    pub fn set_value(&self, name: &str, value: impl Into<String>) {
+        if let Some(model) = self.model(name) {
+            if let Err(e) = model.set(value.into()) {
+                crate::trace::warn_event!("Failed to update form field {name}: {e}");
+            }
+        }
+    }
+
+    /// Mark a field as touched, as when its input loses focus
+    pub fn touch(&self, name: &str) {
+        if let Some(field) = self.inner.lock().unwrap().field_mut(name) {
+            field.state.touched = true;
+        }
+    }
+
+    /// Validate every field and mark them all touched
+    ///
+    /// Returns whether the form is valid.
+    pub fn submit(&self) -> bool {
+        let mut state = self.inner.lock().unwrap();
+        state.submitted = true;
+        for field in &mut state.fields {
+            field.state.touched = true;
+            field.validate();
+        }
+        state
+            .fields
+            .iter()
+            .all(|field| field.state.errors.is_empty())
+    }
+
+    /// Whether every field's current value passes its validators
+    pub fn is_valid(&self) -> bool {
+        self.inner.lock().unwrap().fields.iter().all(|field| {
+            field
+                .validators
+                .iter()
+                .all(|validator| validator.validate(&field.state.value).is_none())
+        })
+    }
+
+    /// Whether any field differs from its initial value
+    pub fn is_dirty(&self) -> bool {
+        let state = self.inner.lock().unwrap();
+        state.fields.iter().any(|field| field.state.dirty)
+    }
+
+    /// Whether [`Form::submit`] has been called
+    pub fn is_submitted(&self) -> bool {
+        self.inner.lock().unwrap().submitted
+    }
+
+    /// A field's value and validation state
+    pub fn field_state(&self, name: &str) -> Option<FieldState> {
+        let mut state = self.inner.lock().unwrap();
+        state.field_mut(name).map(|field| field.state.clone())
+    }
+
+    /// A field's current value
+    pub fn value(&self, name: &str) -> Option<String> {
+        self.field_state(name).map(|field| field.value)
+    }
+
+    /// Errors from a field's last validation
+    pub fn errors(&self, name: &str) -> Vec<String> {
+        self.field_state(name)
+            .map(|field| field.errors)
+            .unwrap_or_default()
+    }
+
+    /// Every field's name and value, in the order they were added
+    pub fn values(&self) -> Vec<(String, String)> {
+        let state = self.inner.lock().unwrap();
+        state
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), field.state.value.clone()))
+            .collect()
+    }
+
+    /// Signal bound to a field's value
+    pub fn model(&self, name: &str) -> Option<Signal<String>> {
+        let mut state = self.inner.lock().unwrap();
+        state.field_mut(name).map(|field| field.model.clone())
+    }
+
+    /// Props for an [`Input`](crate::kit::components::Input) editing a field
+    ///
+    /// The input is bound to the field's model, marked required when the
+    /// field is, and shows the field's first error.
+    pub fn input_props(&self, name: &str) -> InputProps {
+        let mut state = self.inner.lock().unwrap();
+        let field = state.field_mut(name);
+        InputProps {
+            input_type: None,
+            value: String::new(),
+            placeholder: None,
+            disabled: None,
+            required: field.as_ref().map(|field| {
+                field
+                    .validators
+                    .iter()
+                    .any(|validator| matches!(validator, Validator::Required))
+            }),
+            label: None,
+            error: field
+                .as_ref()
+                .and_then(|field| field.state.errors.first().cloned()),
+            helper_text: None,
+            on_change: None,
+            model: field.map(|field| field.model.clone()),
+        }
+    }
+
+    /// Record a field's new value and validate it
+    fn change(&self, name: &str, value: String) {
+        let mut state = self.inner.lock().unwrap();
+        let Some(field) = state.field_mut(name) else {
+            return;
+        };
+        // The effect's first run sees the initial value, which isn't a change
+        if field.state.value == value {
+            return;
+        }
+        field.state.dirty = value != field.state.initial;
+        field.state.value = value;
+        field.validate();
+    }
+}
+
+impl fmt::Debug for Form {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.lock().unwrap();
+        let mut form = f.debug_struct("Form");
+        for field in &state.fields {
+            form.field(&field.name, &field.state);
+        }
+        form.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{Component, Context};
+    use crate::kit::components::Input;
+
+    fn signup() -> Form {
+        Form::new().field(
+            "username",
+            "",
+            vec![Validator::required(), Validator::min_length(3)],
+        )
+    }
+
+    #[test]
+    fn test_required_and_length_errors_clear_with_valid_input() {
+        let form = signup();
+        assert!(!form.is_valid());
+        // Nothing is reported before the user changes the field or submits
+        assert!(form.errors("username").is_empty());
+
+        assert!(!form.submit());
+        assert_eq!(form.errors("username"), vec!["This field is required"]);
+        assert!(form.field_state("username").unwrap().touched);
+
+        // Typing into the bound input validates on change
+        let mut input = Input::create(form.input_props("username"), Context::new());
+        assert_eq!(input.error.as_deref(), Some("This field is required"));
+        assert!(input.required);
+        input.handle_input("ab".to_string(), 2);
+        assert_eq!(
+            form.errors("username"),
+            vec!["Must be at least 3 characters"]
+        );
+        assert!(form.field_state("username").unwrap().dirty);
+
+        input.handle_input("abc".to_string(), 3);
+        assert!(form.errors("username").is_empty());
+        assert!(form.is_valid());
+        assert!(form.submit());
+        assert_eq!(
+            form.values(),
+            vec![("username".to_string(), "abc".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_pattern_and_custom_validators() {
+        let form = Form::new()
+            .field(
+                "email",
+                "",
+                vec![Validator::pattern(r"^[^@\s]+@[^@\s]+$", "Enter an email address").unwrap()],
+            )
+            .field(
+                "age",
+                "30",
+                vec![Validator::custom(|value| match value.parse::<u32>() {
+                    Ok(age) if age >= 18 => Ok(()),
+                    _ => Err("Must be 18 or over".to_string()),
+                })],
+            );
+        // An optional field may stay empty
+        assert!(form.is_valid());
+
+        form.set_value("email", "not an email");
+        form.set_value("age", "12");
+        assert_eq!(form.errors("email"), vec!["Enter an email address"]);
+        assert_eq!(form.errors("age"), vec!["Must be 18 or over"]);
+
+        // Going back to the initial value is no longer dirty
+        form.set_value("age", "30");
+        assert!(!form.field_state("age").unwrap().dirty);
+        assert!(form.errors("age").is_empty());
+        assert!(form.is_dirty());
+    }
+}
@@ -1,6 +1,8 @@
 // OrbitKit Component Library (now part of orbit)
 
 pub mod components;
+pub mod form;
+pub mod text_edit;
 pub mod theme;
 pub mod utils;
 
@@ -11,7 +13,14 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION"); // This will now refer to o
 pub mod prelude {
     pub use crate::kit::components::button::Button;
     pub use crate::kit::components::card::Card;
+    pub use crate::kit::components::container::Container;
+    pub use crate::kit::components::grid::Grid;
     pub use crate::kit::components::input::Input;
     pub use crate::kit::components::layout::Layout;
-    pub use crate::kit::theme::{Theme, ThemeProvider};
+    pub use crate::kit::components::progress::Progress;
+    pub use crate::kit::components::spinner::Spinner;
+    pub use crate::kit::components::stack::{Stack, StackDirection};
+    pub use crate::kit::components::toast::{ToastKind, ToastManager, ToastSpec, Toaster};
+    pub use crate::kit::form::{Form, Validator};
+    pub use crate::kit::theme::{Palette, Theme, ThemeMode, ThemeProvider};
 }
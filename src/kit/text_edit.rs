@@ -0,0 +1,352 @@
+//! Text editing model for single-line inputs
+//!
+//! [`TextEditState`] holds the text, the caret and the selection, and
+//! applies editing operations to them. Positions are byte offsets into the
+//! text that always fall between grapheme clusters, so the caret never
+//! splits an accented letter or an emoji sequence.
+
+use std::ops::Range;
+
+/// Which way the caret moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    /// Towards the start of the text
+    Left,
+    /// Towards the end of the text
+    Right,
+    /// To the start of the text
+    Home,
+    /// To the end of the text
+    End,
+}
+
+/// Text with a caret and an optional selection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextEditState {
+    text: String,
+    /// Caret position, at one end of the selection when there is one
+    cursor: usize,
+    /// Selected byte range, never empty
+    selection: Option<Range<usize>>,
+}
+
+impl TextEditState {
+    /// Create a state for `text` with the caret at the end
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            cursor: text.len(),
+            text,
+            selection: None,
+        }
+    }
+
+    /// The text being edited
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Caret position as a byte offset
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Selected byte range, if any
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.selection.clone()
+    }
+
+    /// Selected text, if any
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection.clone().map(|range| &self.text[range])
+    }
+
+    /// Replace the text, moving the caret to the end and clearing the selection
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        *self = Self::new(text);
+    }
+
+    /// Place the caret, clearing the selection
+    ///
+    /// Positions inside a grapheme are moved back to its start.
+    pub fn set_cursor(&mut self, position: usize) {
+        self.cursor = self.snap(position);
+        self.selection = None;
+    }
+
+    /// Insert text at the caret, replacing the selection
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    /// Delete the selection, or the grapheme before the caret
+    pub fn delete_backward(&mut self) {
+        if !self.delete_selection() && self.cursor > 0 {
+            let start = prev_boundary(&self.text, self.cursor);
+            self.text.replace_range(start..self.cursor, "");
+            self.cursor = start;
+        }
+    }
+
+    /// Delete the selection, or the grapheme after the caret
+    pub fn delete_forward(&mut self) {
+        if !self.delete_selection() && self.cursor < self.text.len() {
+            let end = next_boundary(&self.text, self.cursor);
+            self.text.replace_range(self.cursor..end, "");
+        }
+    }
+
+    /// Move the caret, clearing the selection
+    ///
+    /// Moving left or right by a grapheme from a selection collapses it to
+    /// that side instead. By word, the caret moves to the start of the
+    /// previous word or the end of the next one.
+    pub fn move_cursor(&mut self, direction: CursorDirection, by_word: bool) {
+        let target = match (&self.selection, direction, by_word) {
+            (Some(range), CursorDirection::Left, false) => range.start,
+            (Some(range), CursorDirection::Right, false) => range.end,
+            _ => self.target(direction, by_word),
+        };
+        self.cursor = target;
+        self.selection = None;
+    }
+
+    /// Move the caret, extending the selection from its fixed end
+    pub fn extend_selection(&mut self, direction: CursorDirection, by_word: bool) {
+        let target = self.target(direction, by_word);
+        self.select_to(target);
+    }
+
+    /// Move the caret to `position`, selecting from the selection's fixed end
+    ///
+    /// Without a selection, the text between the caret and `position` is
+    /// selected.
+    pub fn select_to(&mut self, position: usize) {
+        let anchor = match &self.selection {
+            Some(range) if range.start == self.cursor => range.end,
+            Some(range) => range.start,
+            None => self.cursor,
+        };
+        self.cursor = self.snap(position);
+        self.selection =
+            (anchor != self.cursor).then(|| anchor.min(self.cursor)..anchor.max(self.cursor));
+    }
+
+    /// Select the whole text, leaving the caret at the end
+    pub fn select_all(&mut self) {
+        self.cursor = 0;
+        self.selection = None;
+        self.select_to(self.text.len());
+    }
+
+    /// Remove the selected text, returning whether there was any
+    fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection.take() else {
+            return false;
+        };
+        self.cursor = range.start;
+        self.text.replace_range(range, "");
+        true
+    }
+
+    /// Where the caret would move from its current position
+    fn target(&self, direction: CursorDirection, by_word: bool) -> usize {
+        match (direction, by_word) {
+            (CursorDirection::Home, _) => 0,
+            (CursorDirection::End, _) => self.text.len(),
+            (CursorDirection::Left, false) => prev_boundary(&self.text, self.cursor),
+            (CursorDirection::Right, false) => next_boundary(&self.text, self.cursor),
+            (CursorDirection::Left, true) => prev_word_start(&self.text, self.cursor),
+            (CursorDirection::Right, true) => next_word_end(&self.text, self.cursor),
+        }
+    }
+
+    /// The grapheme boundary at or before `position`
+    fn snap(&self, position: usize) -> usize {
+        if position >= self.text.len() {
+            return self.text.len();
+        }
+        boundaries(&self.text)
+            .take_while(|&boundary| boundary <= position)
+            .last()
+            .unwrap_or(0)
+    }
+}
+
+/// Characters that attach to the one before them in a grapheme cluster
+///
+/// Covers combining marks, variation selectors, emoji modifiers and the
+/// zero-width joiner, which is enough for text typed into an input without
+/// pulling in the full Unicode segmentation tables.
+fn is_extending(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{0483}'..='\u{0489}'
+        | '\u{0591}'..='\u{05BD}'
+        | '\u{0610}'..='\u{061A}'
+        | '\u{064B}'..='\u{065F}'
+        | '\u{0900}'..='\u{0903}'
+        | '\u{093A}'..='\u{094F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{1F3FB}'..='\u{1F3FF}'
+        | '\u{E0020}'..='\u{E007F}'
+        | '\u{E0100}'..='\u{E01EF}'
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// End of the grapheme starting at `position`
+fn next_boundary(text: &str, position: usize) -> usize {
+    let mut chars = text[position..].char_indices().peekable();
+    let Some((_, first)) = chars.next() else {
+        return text.len();
+    };
+
+    // A flag is a pair of regional indicators
+    if is_regional_indicator(first) {
+        if let Some(&(_, next)) = chars.peek() {
+            if is_regional_indicator(next) {
+                chars.next();
+            }
+        }
+    }
+
+    let mut previous = first;
+    while let Some(&(_, c)) = chars.peek() {
+        if !is_extending(c) && previous != ZERO_WIDTH_JOINER {
+            break;
+        }
+        previous = c;
+        chars.next();
+    }
+    chars
+        .peek()
+        .map_or(text.len(), |&(offset, _)| position + offset)
+}
+
+/// Start of the grapheme ending at `position`
+fn prev_boundary(text: &str, position: usize) -> usize {
+    boundaries(text)
+        .take_while(|&boundary| boundary < position)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Every grapheme boundary from the start of the text, excluding the end
+fn boundaries(text: &str) -> impl Iterator<Item = usize> + '_ {
+    std::iter::successors(Some(0), move |&position| {
+        let next = next_boundary(text, position);
+        (next < text.len()).then_some(next)
+    })
+    .filter(move |_| !text.is_empty())
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Start of the word before `position`, skipping the separators in between
+fn prev_word_start(text: &str, position: usize) -> usize {
+    let before = &text[..position];
+    let mut chars = before.char_indices().rev().peekable();
+    while chars.next_if(|&(_, c)| !is_word_char(c)).is_some() {}
+    let mut start = chars.peek().map_or(0, |&(offset, c)| offset + c.len_utf8());
+    while let Some((offset, _)) = chars.next_if(|&(_, c)| is_word_char(c) || is_extending(c)) {
+        start = offset;
+    }
+    start
+}
+
+/// End of the word after `position`, skipping the separators in between
+fn next_word_end(text: &str, position: usize) -> usize {
+    let after = &text[position..];
+    let mut chars = after.char_indices().peekable();
+    while chars.next_if(|&(_, c)| !is_word_char(c)).is_some() {}
+    while chars
+        .next_if(|&(_, c)| is_word_char(c) || is_extending(c))
+        .is_some()
+    {}
+    chars
+        .peek()
+        .map_or(text.len(), |&(offset, _)| position + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_into_the_middle_keeps_graphemes_whole() {
+        let mut edit = TextEditState::new("héllo wörld");
+        edit.set_cursor("héllo".len());
+        edit.insert(",");
+        assert_eq!(edit.text(), "héllo, wörld");
+        assert_eq!(edit.cursor(), "héllo,".len());
+
+        // "e" with a combining acute accent is a single grapheme
+        let mut edit = TextEditState::new("cafe\u{301}!");
+        edit.move_cursor(CursorDirection::Left, false);
+        edit.move_cursor(CursorDirection::Left, false);
+        assert_eq!(edit.cursor(), 3);
+        edit.delete_forward();
+        assert_eq!(edit.text(), "caf!");
+
+        // A family emoji joined with zero-width joiners deletes as one
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let mut edit = TextEditState::new(format!("a{family}"));
+        edit.delete_backward();
+        assert_eq!(edit.text(), "a");
+    }
+
+    #[test]
+    fn test_word_wise_cursor_movement() {
+        let mut edit = TextEditState::new("let total = price_1 * qty;");
+        edit.move_cursor(CursorDirection::Left, true);
+        assert_eq!(edit.cursor(), "let total = price_1 * ".len());
+        edit.move_cursor(CursorDirection::Left, true);
+        assert_eq!(edit.cursor(), "let total = ".len());
+
+        edit.move_cursor(CursorDirection::Home, false);
+        edit.move_cursor(CursorDirection::Right, true);
+        assert_eq!(edit.cursor(), "let".len());
+        edit.move_cursor(CursorDirection::Right, true);
+        assert_eq!(edit.cursor(), "let total".len());
+
+        // Shift+Ctrl+Right selects the next word
+        edit.extend_selection(CursorDirection::Right, true);
+        assert_eq!(edit.selected_text(), Some(" = price_1"));
+    }
+
+    #[test]
+    fn test_backspace_deletes_the_selection() {
+        let mut edit = TextEditState::new("hello brave new world");
+        edit.set_cursor("hello ".len());
+        edit.select_to("hello brave new ".len());
+        assert_eq!(edit.selected_text(), Some("brave new "));
+
+        // Selecting back past the anchor flips the selection
+        edit.select_to(2);
+        assert_eq!(edit.selected_text(), Some("llo "));
+        edit.select_to("hello brave new ".len());
+
+        edit.delete_backward();
+        assert_eq!(edit.text(), "hello world");
+        assert_eq!(edit.cursor(), "hello ".len());
+        assert_eq!(edit.selection(), None);
+
+        edit.delete_backward();
+        assert_eq!(edit.text(), "helloworld");
+    }
+}
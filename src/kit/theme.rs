@@ -1,7 +1,83 @@
 // Theme support for OrbitKit
 
 use crate::component::{Component, ComponentError, ComponentId, Context, Node};
+use crate::state::{
+    create_effect, create_signal, untrack, Effect, ReactiveScope, Signal, SignalError,
+};
 use std::any::Any;
+use std::sync::Arc;
+
+/// Which palette a theme is showing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ThemeMode {
+    /// Dark content on light surfaces
+    #[default]
+    Light,
+    /// Light content on dark surfaces
+    Dark,
+}
+
+/// Semantic color tokens
+///
+/// Components read colors by role rather than by value, so switching the
+/// palette restyles them without further changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    /// Background of cards, inputs and other containers
+    pub surface: String,
+    /// Text and icons drawn on `surface`
+    pub on_surface: String,
+    /// Subdued background for secondary controls
+    pub surface_variant: String,
+    /// Brand color for primary actions and highlights
+    pub accent: String,
+    /// Text and icons drawn on `accent`
+    pub on_accent: String,
+    /// Dividers and outlines
+    pub border: String,
+    /// Error color
+    pub error: String,
+    /// Success color
+    pub success: String,
+    /// Warning color
+    pub warning: String,
+    /// Info color
+    pub info: String,
+}
+
+impl Palette {
+    /// Default light palette
+    pub fn light() -> Self {
+        Self {
+            surface: "#ffffff".to_string(),
+            on_surface: "#333333".to_string(),
+            surface_variant: "#f5f5f5".to_string(),
+            accent: "#0070f3".to_string(),
+            on_accent: "#ffffff".to_string(),
+            border: "#e0e0e0".to_string(),
+            error: "#ff0000".to_string(),
+            success: "#00cc00".to_string(),
+            warning: "#ffcc00".to_string(),
+            info: "#0088cc".to_string(),
+        }
+    }
+
+    /// Default dark palette
+    pub fn dark() -> Self {
+        Self {
+            surface: "#121212".to_string(),
+            on_surface: "#e6e6e6".to_string(),
+            surface_variant: "#1e1e1e".to_string(),
+            accent: "#3291ff".to_string(),
+            on_accent: "#ffffff".to_string(),
+            border: "#333333".to_string(),
+            error: "#ff6b6b".to_string(),
+            success: "#4ade80".to_string(),
+            warning: "#facc15".to_string(),
+            info: "#38bdf8".to_string(),
+        }
+    }
+}
 
 /// Theme for OrbitKit
 #[derive(Debug, Clone)]
@@ -28,6 +104,28 @@ pub struct Theme {
     pub font_family: String,
     /// Font size
     pub font_size: String,
+    /// Palette used in light mode
+    pub light: Palette,
+    /// Palette used in dark mode
+    pub dark: Palette,
+}
+
+impl Theme {
+    /// Palette for a mode
+    pub fn palette(&self, mode: ThemeMode) -> &Palette {
+        match mode {
+            ThemeMode::Light => &self.light,
+            ThemeMode::Dark => &self.dark,
+        }
+    }
+
+    /// Palette of a provided theme, or the default light palette without one
+    pub fn current_palette(theme: Option<&ThemeContext>) -> Palette {
+        match theme {
+            Some(theme) => theme.palette(),
+            None => Theme::default().palette(ThemeMode::Light).clone(),
+        }
+    }
 }
 
 impl Default for Theme {
@@ -44,11 +142,104 @@ impl Default for Theme {
             border_radius: "4px".to_string(),
             font_family: "Arial, sans-serif".to_string(),
             font_size: "16px".to_string(),
+            light: Palette::light(),
+            dark: Palette::dark(),
         }
     }
 }
 
+/// The theme and current mode, shared with descendants of a [`ThemeProvider`]
+///
+/// Themed components consume this from their [`Context`] and call
+/// [`ThemeContext::subscribe`] so they re-render when the theme or mode
+/// changes.
+#[derive(Clone)]
+pub struct ThemeContext {
+    theme: Signal<Arc<Theme>>,
+    mode: Signal<ThemeMode>,
+}
+
+impl std::fmt::Debug for ThemeContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThemeContext")
+            .field("mode", &*self.mode.value.read().unwrap())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ThemeContext {
+    /// Create a theme context starting in `mode`
+    pub fn new(theme: Theme, mode: ThemeMode) -> Self {
+        let scope = ReactiveScope::new();
+        Self {
+            theme: create_signal(&scope, Arc::new(theme)),
+            mode: create_signal(&scope, mode),
+        }
+    }
+
+    /// The theme
+    pub fn theme(&self) -> Arc<Theme> {
+        self.theme.get().clone()
+    }
+
+    /// Replace the theme, notifying subscribed components
+    pub fn set_theme(&self, theme: Theme) -> Result<(), SignalError> {
+        self.theme.set(Arc::new(theme))
+    }
+
+    /// The current mode
+    pub fn mode(&self) -> ThemeMode {
+        *self.mode.get()
+    }
+
+    /// Switch modes, notifying subscribed components if it changed
+    pub fn set_mode(&self, mode: ThemeMode) -> Result<(), SignalError> {
+        self.mode.set_if_changed(mode)
+    }
+
+    /// Palette for the current mode
+    pub fn palette(&self) -> Palette {
+        self.theme().palette(self.mode()).clone()
+    }
+
+    /// Schedule an update of `component_id` through `context` on theme and
+    /// mode changes
+    ///
+    /// Updates stop when the returned subscription is dropped.
+    pub fn subscribe(&self, context: &Context, component_id: ComponentId) -> ThemeSubscription {
+        let (theme, mode) = (self.theme.clone(), self.mode.clone());
+        let context = context.clone();
+        let mut first_run = true;
+        let effect = create_effect(&ReactiveScope::new(), move || {
+            drop(theme.get());
+            let _ = *mode.get();
+            if std::mem::take(&mut first_run) {
+                return;
+            }
+            // Re-rendering reads the theme, which should not become a
+            // dependency of this effect
+            untrack(|| context.schedule_update(component_id));
+        });
+
+        ThemeSubscription { _effect: effect }
+    }
+}
+
+/// Keeps a component subscribed to theme and mode changes
+pub struct ThemeSubscription {
+    _effect: Effect<Box<dyn FnMut() + Send + Sync + 'static>>,
+}
+
+impl std::fmt::Debug for ThemeSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThemeSubscription").finish_non_exhaustive()
+    }
+}
+
 /// Theme provider component
+///
+/// Provides a [`ThemeContext`] to every component created with its context
+/// or a [`Context::child`] of it.
 #[derive(Debug)]
 pub struct ThemeProvider {
     /// Component ID for tracking
@@ -57,6 +248,8 @@ pub struct ThemeProvider {
     pub theme: Theme,
     /// Child content
     pub children: Option<String>,
+    /// Theme and mode shared with descendants
+    context: ThemeContext,
 }
 
 /// Theme provider props
@@ -64,10 +257,29 @@ pub struct ThemeProvider {
 pub struct ThemeProviderProps {
     /// Theme
     pub theme: Option<Theme>,
+    /// Initial mode (defaults to light)
+    pub mode: Option<ThemeMode>,
     /// Child content
     pub children: Option<String>,
 }
 
+impl ThemeProvider {
+    /// The current mode
+    pub fn mode(&self) -> ThemeMode {
+        self.context.mode()
+    }
+
+    /// Switch between light and dark, re-rendering themed components
+    pub fn set_mode(&self, mode: ThemeMode) -> Result<(), SignalError> {
+        self.context.set_mode(mode)
+    }
+
+    /// The context shared with descendants
+    pub fn theme_context(&self) -> &ThemeContext {
+        &self.context
+    }
+}
+
 impl Component for ThemeProvider {
     type Props = ThemeProviderProps;
 
@@ -75,17 +287,28 @@ impl Component for ThemeProvider {
         self.id
     }
 
-    fn create(props: Self::Props, _context: Context) -> Self {
+    fn create(props: Self::Props, context: Context) -> Self {
+        let theme = props.theme.unwrap_or_default();
+        let theme_context = ThemeContext::new(theme.clone(), props.mode.unwrap_or_default());
+        context.provide(theme_context.clone());
+
         Self {
             id: ComponentId::new(),
-            theme: props.theme.unwrap_or_default(),
+            theme,
             children: props.children,
+            context: theme_context,
         }
     }
 
     fn update(&mut self, props: Self::Props) -> Result<(), ComponentError> {
-        self.theme = props.theme.unwrap_or_else(|| self.theme.clone());
+        if let Some(theme) = props.theme {
+            self.context.set_theme(theme.clone())?;
+            self.theme = theme;
+        }
         self.children = props.children;
+        if let Some(mode) = props.mode {
+            self.set_mode(mode)?;
+        }
         Ok(())
     }
 
@@ -103,3 +326,85 @@ impl Component for ThemeProvider {
         Ok(vec![])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kit::components::button::{Button, ButtonProps, ButtonVariant};
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_switching_mode_rethemes_button() {
+        let context = Context::new();
+        let provider = ThemeProvider::create(
+            ThemeProviderProps {
+                theme: None,
+                mode: None,
+                children: None,
+            },
+            context.clone(),
+        );
+        let button = Button::create(
+            ButtonProps {
+                text: "Save".to_string(),
+                variant: Some(ButtonVariant::Primary),
+                disabled: None,
+                size: None,
+                on_click: None,
+            },
+            context.clone(),
+        );
+
+        let updated = Arc::new(Mutex::new(Vec::new()));
+        let log = updated.clone();
+        context.set_update_handler(move |id| log.lock().unwrap().push(id));
+
+        assert_eq!(button.background_color(), Palette::light().accent);
+
+        provider.set_mode(ThemeMode::Dark).unwrap();
+        assert_eq!(provider.mode(), ThemeMode::Dark);
+        assert_eq!(*updated.lock().unwrap(), vec![button.component_id()]);
+        assert_eq!(button.background_color(), Palette::dark().accent);
+
+        // Setting the same mode again does not re-render
+        provider.set_mode(ThemeMode::Dark).unwrap();
+        assert_eq!(updated.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_new_theme_prop_rethemes_button() {
+        let context = Context::new();
+        let props = ThemeProviderProps {
+            theme: None,
+            mode: None,
+            children: None,
+        };
+        let mut provider = ThemeProvider::create(props.clone(), context.clone());
+        let button = Button::create(
+            ButtonProps {
+                text: "Save".to_string(),
+                variant: Some(ButtonVariant::Primary),
+                disabled: None,
+                size: None,
+                on_click: None,
+            },
+            context.clone(),
+        );
+
+        let updated = Arc::new(Mutex::new(Vec::new()));
+        let log = updated.clone();
+        context.set_update_handler(move |id| log.lock().unwrap().push(id));
+
+        let mut brand = Theme::default();
+        brand.light.accent = "#7c3aed".to_string();
+        provider
+            .update(ThemeProviderProps {
+                theme: Some(brand),
+                ..props
+            })
+            .unwrap();
+
+        assert_eq!(*updated.lock().unwrap(), vec![button.component_id()]);
+        assert_eq!(button.background_color(), "#7c3aed");
+    }
+}
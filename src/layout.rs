@@ -6,10 +6,13 @@
 //! - Performance optimizations with incremental updates
 //! - Integration with the component system
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::sync::Arc;
 
 use crate::component::ComponentId;
+use crate::renderer::{TextMeasureCache, TextMeasureKey};
+use crate::style::{BorderRadius, FontWeight, Style, Transform};
 
 /// Represents a 2D point with x and y coordinates
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -101,6 +104,71 @@ impl Rect {
             && point.y >= self.y()
             && point.y <= self.max_y()
     }
+
+    /// Whether this rectangle overlaps `other` with a non-empty area
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x() < other.max_x()
+            && other.x() < self.max_x()
+            && self.y() < other.max_y()
+            && other.y() < self.max_y()
+    }
+
+    /// Smallest rectangle containing both rectangles
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x().min(other.x());
+        let y = self.y().min(other.y());
+        Rect::new(
+            x,
+            y,
+            self.max_x().max(other.max_x()) - x,
+            self.max_y().max(other.max_y()) - y,
+        )
+    }
+}
+
+/// 2D affine matrix in CSS `matrix(a, b, c, d, e, f)` order
+///
+/// A point maps to `(a * x + c * y + e, b * x + d * y + f)`.
+pub type TransformMatrix = [f32; 6];
+
+/// Matrix that leaves every point in place
+pub const IDENTITY_MATRIX: TransformMatrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// Compose two matrices so that `inner` applies first, then `outer`
+pub fn multiply_matrices(outer: &TransformMatrix, inner: &TransformMatrix) -> TransformMatrix {
+    let [a1, b1, c1, d1, e1, f1] = *outer;
+    let [a2, b2, c2, d2, e2, f2] = *inner;
+    [
+        a1 * a2 + c1 * b2,
+        b1 * a2 + d1 * b2,
+        a1 * c2 + c1 * d2,
+        b1 * c2 + d1 * d2,
+        a1 * e2 + c1 * f2 + e1,
+        b1 * e2 + d1 * f2 + f1,
+    ]
+}
+
+/// Invert a matrix, or `None` if it collapses the plane (e.g. `scale(0)`)
+pub fn invert_matrix(matrix: &TransformMatrix) -> Option<TransformMatrix> {
+    let [a, b, c, d, e, f] = *matrix;
+    let det = a * d - b * c;
+    if det.abs() < f32::EPSILON {
+        return None;
+    }
+    Some([
+        d / det,
+        -b / det,
+        -c / det,
+        a / det,
+        (c * f - d * e) / det,
+        (b * e - a * f) / det,
+    ])
+}
+
+/// Map a point through a matrix
+pub fn transform_point(matrix: &TransformMatrix, point: Point) -> Point {
+    let [a, b, c, d, e, f] = *matrix;
+    Point::new(a * point.x + c * point.y + e, b * point.x + d * point.y + f)
 }
 
 /// Flex direction determines the main axis
@@ -113,6 +181,18 @@ pub enum FlexDirection {
     ColumnReverse,
 }
 
+/// Inline direction of a node's content
+///
+/// In right-to-left layouts a row's main-axis start is its right edge, a
+/// column's cross-axis start is its right edge, and text aligned to the
+/// start is aligned right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
 /// Flex wrap determines whether items wrap to new lines
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FlexWrap {
@@ -158,12 +238,16 @@ pub enum AlignContent {
 }
 
 /// Dimension value can be auto, fixed, or percentage
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum Dimension {
     #[default]
     Auto,
     Points(f32),
     Percent(f32),
+    /// Fraction of the remaining free space, only meaningful for grid tracks
+    Fraction(f32),
+    /// Mixed-unit arithmetic, as in CSS `calc(100% - 20px)`
+    Calc(CalcExpr),
 }
 
 impl Dimension {
@@ -173,8 +257,110 @@ impl Dimension {
             Dimension::Auto => 0.0, // Will be calculated during layout
             Dimension::Points(points) => *points,
             Dimension::Percent(percent) => container_size * percent / 100.0,
+            Dimension::Fraction(_) => 0.0, // Distributed by the grid algorithm
+            Dimension::Calc(expr) => expr.resolve(container_size),
+        }
+    }
+}
+
+/// Expression tree of a CSS `calc()`
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcExpr {
+    Points(f32),
+    Percent(f32),
+    /// Unitless factor, as in `calc(100% / 3)`
+    Number(f32),
+    Add(Box<CalcExpr>, Box<CalcExpr>),
+    Sub(Box<CalcExpr>, Box<CalcExpr>),
+    Mul(Box<CalcExpr>, Box<CalcExpr>),
+    Div(Box<CalcExpr>, Box<CalcExpr>),
+}
+
+impl CalcExpr {
+    /// Evaluate the expression, with percentages relative to `container_size`
+    ///
+    /// Division by zero yields zero rather than an infinite length.
+    pub fn resolve(&self, container_size: f32) -> f32 {
+        match self {
+            CalcExpr::Points(points) => *points,
+            CalcExpr::Percent(percent) => container_size * percent / 100.0,
+            CalcExpr::Number(number) => *number,
+            CalcExpr::Add(lhs, rhs) => lhs.resolve(container_size) + rhs.resolve(container_size),
+            CalcExpr::Sub(lhs, rhs) => lhs.resolve(container_size) - rhs.resolve(container_size),
+            CalcExpr::Mul(lhs, rhs) => lhs.resolve(container_size) * rhs.resolve(container_size),
+            CalcExpr::Div(lhs, rhs) => {
+                let divisor = rhs.resolve(container_size);
+                if divisor == 0.0 {
+                    0.0
+                } else {
+                    lhs.resolve(container_size) / divisor
+                }
+            }
+        }
+    }
+}
+
+/// Overflow controls what happens to content that exceeds a node's bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    #[default]
+    Visible,
+    Hidden,
+    Scroll,
+    Auto,
+}
+
+impl Overflow {
+    /// Whether content that exceeds the bounds can be scrolled into view
+    pub fn is_scrollable(self) -> bool {
+        matches!(self, Overflow::Scroll | Overflow::Auto)
+    }
+}
+
+/// Display mode determines how a node lays out its children
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Display {
+    #[default]
+    Flex,
+    Grid,
+}
+
+/// Placement of a grid item along one axis, in 1-based grid lines
+///
+/// An item without a start line is placed in the first track; automatic
+/// placement is not supported yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GridPlacement {
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+}
+
+impl GridPlacement {
+    /// Place an item in the single track following `line`
+    pub fn line(line: usize) -> Self {
+        Self {
+            start: Some(line),
+            end: None,
+        }
+    }
+
+    /// Place an item between two grid lines
+    pub fn between(start: usize, end: usize) -> Self {
+        Self {
+            start: Some(start),
+            end: Some(end),
         }
     }
+
+    /// Resolve to a zero-based track index and the number of tracks spanned
+    pub(crate) fn tracks(&self) -> (usize, usize) {
+        let start = self.start.unwrap_or(1).max(1);
+        let span = match self.end {
+            Some(end) if end > start => end - start,
+            _ => 1,
+        };
+        (start - 1, span)
+    }
 }
 
 /// Edge values for margin, padding, border
@@ -223,6 +409,68 @@ impl Default for EdgeValues {
     }
 }
 
+/// Edge dimensions for margin and padding, which may be percentages
+///
+/// These are resolved into concrete [`EdgeValues`] during layout. As in CSS,
+/// percentages on every edge refer to the containing block's width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeDimensions {
+    pub top: Dimension,
+    pub right: Dimension,
+    pub bottom: Dimension,
+    pub left: Dimension,
+}
+
+impl EdgeDimensions {
+    pub fn new(top: Dimension, right: Dimension, bottom: Dimension, left: Dimension) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    pub fn uniform(value: Dimension) -> Self {
+        Self::new(value.clone(), value.clone(), value.clone(), value)
+    }
+
+    pub fn horizontal_vertical(horizontal: Dimension, vertical: Dimension) -> Self {
+        Self::new(vertical.clone(), horizontal.clone(), vertical, horizontal)
+    }
+
+    pub fn zero() -> Self {
+        Self::uniform(Dimension::Points(0.0))
+    }
+
+    /// Resolve every edge against the containing block's width
+    pub fn resolve(&self, containing_width: f32) -> EdgeValues {
+        EdgeValues::new(
+            self.top.resolve(containing_width),
+            self.right.resolve(containing_width),
+            self.bottom.resolve(containing_width),
+            self.left.resolve(containing_width),
+        )
+    }
+}
+
+impl Default for EdgeDimensions {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl From<EdgeValues> for EdgeDimensions {
+    fn from(values: EdgeValues) -> Self {
+        Self::new(
+            Dimension::Points(values.top),
+            Dimension::Points(values.right),
+            Dimension::Points(values.bottom),
+            Dimension::Points(values.left),
+        )
+    }
+}
+
 /// Layout style properties for a node
 #[derive(Debug, Clone, PartialEq)]
 pub struct LayoutStyle {
@@ -240,6 +488,11 @@ pub struct LayoutStyle {
     pub min_height: Dimension,
     pub max_width: Dimension,
     pub max_height: Dimension,
+    /// Preferred width / height ratio, used when only one dimension is known
+    pub aspect_ratio: Option<f32>,
+
+    /// Inline direction, inherited from the parent when `None`
+    pub direction: Option<Direction>,
 
     // Flexbox container properties
     pub flex_direction: FlexDirection,
@@ -255,10 +508,43 @@ pub struct LayoutStyle {
     pub align_self: Option<AlignItems>,
 
     // Spacing
-    pub margin: EdgeValues,
-    pub padding: EdgeValues,
+    pub margin: EdgeDimensions,
+    pub padding: EdgeDimensions,
     pub border: EdgeValues, // Gap
     pub gap: Gap,
+
+    // Overflow
+    pub overflow_x: Overflow,
+    pub overflow_y: Overflow,
+
+    // Shape, used to hit test rounded corners
+    pub border_radius: Option<BorderRadius>,
+
+    // Visual transform, applied after layout without moving siblings
+    pub transform: Option<Transform>,
+    /// Transform origin relative to the node's top-left corner, defaulting
+    /// to its center
+    pub transform_origin: Option<Point>,
+
+    // Text metrics
+    /// Font size used to approximate the baseline of this node and to
+    /// measure its text
+    pub font_size: Option<f32>,
+    /// Height of a line of text as a multiple of the font size
+    pub line_height: Option<f32>,
+    /// Font family used to measure this node's text
+    pub font_family: Option<String>,
+    /// Font weight used to measure this node's text
+    pub font_weight: Option<FontWeight>,
+
+    // Grid container properties
+    pub display: Display,
+    pub grid_template_columns: Vec<Dimension>,
+    pub grid_template_rows: Vec<Dimension>,
+
+    // Grid item properties
+    pub grid_column: GridPlacement,
+    pub grid_row: GridPlacement,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -266,6 +552,25 @@ pub enum PositionType {
     #[default]
     Relative,
     Absolute,
+    /// Laid out in flow, then kept inside the nearest scroll container's
+    /// viewport once scrolling passes its `top` or `left` threshold
+    Sticky,
+    /// Taken out of flow and placed against the viewport by its `top`,
+    /// `right`, `bottom` and `left`, so scrolling and ancestors' layout
+    /// don't move it
+    ///
+    /// Its rect is in viewport coordinates rather than relative to its
+    /// parent. As in CSS, an ancestor with a transform becomes the
+    /// containing block instead, and the node behaves like an absolutely
+    /// positioned one that moves with that ancestor.
+    Fixed,
+}
+
+impl PositionType {
+    /// Whether the node is left out of its parent's flex or grid flow
+    pub fn is_out_of_flow(self) -> bool {
+        matches!(self, PositionType::Absolute | PositionType::Fixed)
+    }
 }
 
 impl Default for LayoutStyle {
@@ -282,6 +587,8 @@ impl Default for LayoutStyle {
             min_height: Dimension::default(),
             max_width: Dimension::default(),
             max_height: Dimension::default(),
+            aspect_ratio: None,
+            direction: None,
             flex_direction: FlexDirection::default(),
             flex_wrap: FlexWrap::default(),
             justify_content: JustifyContent::default(),
@@ -291,10 +598,24 @@ impl Default for LayoutStyle {
             flex_shrink: 1.0,
             flex_basis: Dimension::Auto,
             align_self: None,
-            margin: EdgeValues::default(),
-            padding: EdgeValues::default(),
+            margin: EdgeDimensions::default(),
+            padding: EdgeDimensions::default(),
             border: EdgeValues::default(),
             gap: Gap::default(),
+            overflow_x: Overflow::default(),
+            overflow_y: Overflow::default(),
+            border_radius: None,
+            transform: None,
+            transform_origin: None,
+            font_size: None,
+            line_height: None,
+            font_family: None,
+            font_weight: None,
+            display: Display::default(),
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+            grid_column: GridPlacement::default(),
+            grid_row: GridPlacement::default(),
         }
     }
 }
@@ -308,6 +629,24 @@ pub struct LayoutResult {
     pub content_rect: Rect,
     /// Whether this layout is dirty and needs recalculation
     pub is_dirty: bool,
+    /// Region children are clipped to, set when overflow is not visible
+    pub clip_rect: Option<Rect>,
+    /// Scroll position of the content within the clip region
+    pub scroll_offset: Point,
+    /// How far a sticky node was moved from its in-flow position
+    pub sticky_offset: Point,
+    /// Margin resolved against the containing block
+    pub margin: EdgeValues,
+    /// Padding resolved against the containing block
+    pub padding: EdgeValues,
+    /// Visual transform relative to the top-left corner of `rect`
+    ///
+    /// Includes the transform origin. `rect` stays the untransformed layout
+    /// box, so siblings are unaffected; renderers and hit testing apply or
+    /// invert this matrix.
+    pub transform_matrix: TransformMatrix,
+    /// Inline direction after inheritance
+    pub direction: Direction,
 }
 
 impl Default for LayoutResult {
@@ -316,10 +655,39 @@ impl Default for LayoutResult {
             rect: Rect::zero(),
             content_rect: Rect::zero(),
             is_dirty: true,
+            clip_rect: None,
+            scroll_offset: Point::zero(),
+            sticky_offset: Point::zero(),
+            margin: EdgeValues::zero(),
+            padding: EdgeValues::zero(),
+            transform_matrix: IDENTITY_MATRIX,
+            direction: Direction::default(),
         }
     }
 }
 
+impl LayoutResult {
+    /// Map a point in layout space to where it is drawn
+    pub fn to_visual(&self, point: Point) -> Point {
+        let origin = self.rect.origin;
+        let local = Point::new(point.x - origin.x, point.y - origin.y);
+        let mapped = transform_point(&self.transform_matrix, local);
+        Point::new(mapped.x + origin.x, mapped.y + origin.y)
+    }
+
+    /// Map a drawn point back to layout space, for hit testing
+    ///
+    /// Returns `None` when the transform is not invertible, since nothing
+    /// under the pointer can belong to a node flattened to a line or point.
+    pub fn to_layout(&self, point: Point) -> Option<Point> {
+        let inverse = invert_matrix(&self.transform_matrix)?;
+        let origin = self.rect.origin;
+        let local = Point::new(point.x - origin.x, point.y - origin.y);
+        let mapped = transform_point(&inverse, local);
+        Some(Point::new(mapped.x + origin.x, mapped.y + origin.y))
+    }
+}
+
 /// A node in the layout tree
 #[derive(Debug)]
 pub struct LayoutNode {
@@ -333,6 +701,10 @@ pub struct LayoutNode {
     pub children: Vec<LayoutNode>,
     /// Parent node ID (if any)
     pub parent_id: Option<ComponentId>,
+    /// Whether the node can receive keyboard focus
+    pub focusable: bool,
+    /// Text drawn inside the node, which sizes an auto height
+    pub text: Option<String>,
 }
 
 impl LayoutNode {
@@ -344,9 +716,23 @@ impl LayoutNode {
             layout: LayoutResult::default(),
             children: Vec::new(),
             parent_id: None,
+            focusable: false,
+            text: None,
         }
     }
 
+    /// Set the text drawn inside the node
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Mark the node as able to receive keyboard focus
+    pub fn with_focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
     /// Add a child node
     pub fn add_child(&mut self, mut child: LayoutNode) {
         child.parent_id = Some(self.id);
@@ -366,10 +752,27 @@ impl LayoutNode {
         }
     }
 
-    /// Mark this node and all ancestors as dirty
+    /// Find this node or a descendant by ID
+    fn find_mut(&mut self, id: ComponentId) -> Option<&mut LayoutNode> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children
+            .iter_mut()
+            .find_map(|child| child.find_mut(id))
+    }
+
+    /// Whether the node clips and scrolls content that overflows it
+    fn is_scroll_container(&self) -> bool {
+        self.style.overflow_x.is_scrollable() || self.style.overflow_y.is_scrollable()
+    }
+
+    /// Mark this node as dirty
+    ///
+    /// Nodes only know their parent's id, so ancestors are not reached from
+    /// here; use [`LayoutEngine::mark_dirty`] to invalidate the whole chain.
     pub fn mark_dirty(&mut self) {
         self.layout.is_dirty = true;
-        // In a real implementation, we'd traverse up to mark ancestors dirty
     }
 
     /// Check if this node needs layout recalculation
@@ -394,11 +797,88 @@ impl LayoutNode {
     }
 }
 
+/// Font size used to measure text when a node doesn't set one
+pub const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+/// Line height, as a multiple of the font size, when a node doesn't set one
+pub const DEFAULT_LINE_HEIGHT: f32 = 1.2;
+
+/// Average advance of a character as a fraction of the font size
+const AVERAGE_ADVANCE: f32 = 0.5;
+
+/// Measures the width of text for layout
+///
+/// Renderers that shape text supply one through
+/// [`Renderer::text_measurer`](crate::renderer::Renderer::text_measurer), so
+/// text wraps where it will be drawn.
+pub trait TextMeasurer: Send + Sync + fmt::Debug {
+    /// Advance width of `text` on a single line, drawn with the font
+    /// properties of `style`
+    fn measure_width(&self, text: &str, style: &Style) -> f32;
+}
+
+/// Measures text without fonts, as if every character had the same advance
+///
+/// Used until a renderer supplies a real measurer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EstimatedTextMeasurer;
+
+impl TextMeasurer for EstimatedTextMeasurer {
+    fn measure_width(&self, text: &str, style: &Style) -> f32 {
+        let font_size = style.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+        text.chars().count() as f32 * font_size * AVERAGE_ADVANCE
+    }
+}
+
+/// Break text into the lines it wraps to at `max_width`
+///
+/// Lines break at whitespace and at newlines. A word wider than the line
+/// gets a line of its own rather than being split. Renderers draw the same
+/// lines layout sized the text for.
+pub fn wrap_lines(text: &str, max_width: f32, width_of: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() {
+                let candidate = format!("{line} {word}");
+                if width_of(&candidate) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                lines.push(std::mem::take(&mut line));
+            }
+            line = word.to_string();
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Wrap text at `max_width`, returning the widest line and the line count
+fn wrap_text(text: &str, max_width: f32, width_of: impl Fn(&str) -> f32) -> (f32, usize) {
+    let lines = wrap_lines(text, max_width, &width_of);
+    let widest = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| width_of(line))
+        .fold(0.0, f32::max);
+    (widest, lines.len())
+}
+
 /// Layout engine responsible for computing layouts
 #[derive(Debug)]
 pub struct LayoutEngine {
     /// Cache of computed layouts for performance
     layout_cache: HashMap<ComponentId, LayoutResult>,
+    /// Text measurements, shared with the renderer
+    text_cache: Arc<TextMeasureCache>,
+    /// Measures text that isn't in the cache yet
+    text_measurer: Arc<dyn TextMeasurer>,
+    /// Size of the root container, which fixed nodes are placed against
+    viewport: Size,
+    /// Whether the node being laid out has a transformed ancestor
+    in_transform: bool,
     /// Performance metrics
     pub stats: LayoutStats,
 }
@@ -423,24 +903,103 @@ impl LayoutEngine {
     pub fn new() -> Self {
         Self {
             layout_cache: HashMap::new(),
+            text_cache: TextMeasureCache::global(),
+            text_measurer: Arc::new(EstimatedTextMeasurer),
+            viewport: Size::zero(),
+            in_transform: false,
             stats: LayoutStats::default(),
         }
     }
 
+    /// Use a specific text measurement cache instead of the global one
+    ///
+    /// Pass the renderer's cache so text measured during layout is not
+    /// shaped again when it is painted.
+    pub fn with_text_cache(mut self, text_cache: Arc<TextMeasureCache>) -> Self {
+        self.text_cache = text_cache;
+        self
+    }
+
+    /// Get the text measurement cache
+    pub fn text_cache(&self) -> &Arc<TextMeasureCache> {
+        &self.text_cache
+    }
+
+    /// Measure text with `text_measurer` instead of estimating its width
+    pub fn with_text_measurer(mut self, text_measurer: Arc<dyn TextMeasurer>) -> Self {
+        self.set_text_measurer(text_measurer);
+        self
+    }
+
+    /// Replace the text measurer, as when a renderer becomes available
+    pub fn set_text_measurer(&mut self, text_measurer: Arc<dyn TextMeasurer>) {
+        self.text_measurer = text_measurer;
+    }
+
+    /// Measure text through the shared cache, calling `measure` on a miss
+    pub fn measure_text(
+        &self,
+        text: &str,
+        style: &Style,
+        max_width: Option<f32>,
+        measure: impl FnOnce() -> Size,
+    ) -> Size {
+        self.text_cache
+            .measure(TextMeasureKey::new(text, style, max_width), measure)
+    }
+
+    /// Height of a node's wrapped text plus its padding and border
+    ///
+    /// `width` is the node's border-box width; the text wraps inside its
+    /// content box. Returns `None` for nodes without text.
+    fn text_height(&self, node: &LayoutNode, width: f32, containing_width: f32) -> Option<f32> {
+        let text = node.text.as_deref()?;
+        let style = &node.style;
+        let padding = style.padding.resolve(containing_width);
+        let content_width = (width - padding.horizontal() - style.border.horizontal()).max(0.0);
+
+        let font_size = style.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+        let font = Style {
+            font_family: style.font_family.clone(),
+            font_size: Some(font_size),
+            font_weight: style.font_weight.clone(),
+            ..Default::default()
+        };
+        // Cached as the wrapped size with lines one font size tall, so the
+        // entry holds whatever the line height
+        let wrapped = self.measure_text(text, &font, Some(content_width), || {
+            let (widest, lines) = wrap_text(text, content_width, |line| {
+                self.text_measurer.measure_width(line, &font)
+            });
+            Size::new(widest, lines as f32 * font_size)
+        });
+        let line_height = style.line_height.unwrap_or(DEFAULT_LINE_HEIGHT);
+        Some(wrapped.height * line_height + padding.vertical() + style.border.vertical())
+    }
+
     /// Calculate layout for a node tree
     pub fn calculate_layout(
         &mut self,
         root: &mut LayoutNode,
         container_size: Size,
     ) -> Result<(), LayoutError> {
+        let _span = crate::trace::span(crate::trace::Phase::Layout, root.id);
         let start_time = std::time::Instant::now();
 
         // Clear dirty flags and prepare for layout
         self.prepare_layout(root);
+        self.viewport = container_size;
+        self.in_transform = false;
+        root.layout.direction = root.style.direction.unwrap_or_default();
 
         // Perform the actual layout calculation
         self.layout_node(root, container_size)?;
 
+        // The root is the viewport for sticky nodes outside any scroll container
+        if !root.is_scroll_container() {
+            Self::apply_sticky(root);
+        }
+
         // Update statistics
         let elapsed = start_time.elapsed();
         self.stats.layout_calculations += 1;
@@ -480,11 +1039,37 @@ impl LayoutEngine {
         &mut self,
         node: &mut LayoutNode,
         container_size: Size,
+    ) -> Result<(), LayoutError> {
+        self.layout_node_in(node, container_size, container_size.width)
+    }
+
+    /// Layout a node whose percentage margins and padding resolve against
+    /// `containing_width` rather than the size it is being laid out in
+    fn layout_node_in(
+        &mut self,
+        node: &mut LayoutNode,
+        container_size: Size,
+        containing_width: f32,
     ) -> Result<(), LayoutError> {
         // Check cache first
         if !node.layout.is_dirty {
             if let Some(cached_layout) = self.layout_cache.get(&node.id) {
+                // The parent may have moved the node since it was cached
+                let origin = node.layout.rect.origin;
                 node.layout = cached_layout.clone();
+                if node.style.position_type != PositionType::Fixed {
+                    let (dx, dy) = (
+                        origin.x - node.layout.rect.x(),
+                        origin.y - node.layout.rect.y(),
+                    );
+                    node.layout.rect.origin = origin;
+                    node.layout.content_rect.origin.x += dx;
+                    node.layout.content_rect.origin.y += dy;
+                    if let Some(clip) = &mut node.layout.clip_rect {
+                        clip.origin.x += dx;
+                        clip.origin.y += dy;
+                    }
+                }
                 self.stats.cache_hits += 1;
                 return Ok(());
             }
@@ -493,12 +1078,28 @@ impl LayoutEngine {
         self.stats.cache_misses += 1;
 
         // Calculate this node's size and position
-        self.calculate_node_size(node, container_size)?;
+        self.calculate_node_size(node, container_size, containing_width)?;
         self.calculate_node_position(node)?;
 
-        // Layout children using flexbox algorithm
+        // Children without a direction of their own inherit this node's
+        for child in &mut node.children {
+            child.layout.direction = child.style.direction.unwrap_or(node.layout.direction);
+        }
+
+        // Layout children using the container's layout algorithm
         if !node.children.is_empty() {
-            self.layout_flex_children(node)?;
+            let in_transform = self.in_transform;
+            self.in_transform |= node.style.transform.is_some();
+            match node.style.display {
+                Display::Flex => self.layout_flex_children(node)?,
+                Display::Grid => self.layout_grid_children(node)?,
+            }
+            self.in_transform = in_transform;
+        }
+
+        // Sticky descendants are placed once the whole content is laid out
+        if node.is_scroll_container() {
+            Self::apply_sticky(node);
         }
 
         // Mark as clean and cache the result
@@ -513,15 +1114,18 @@ impl LayoutEngine {
         &self,
         node: &mut LayoutNode,
         container_size: Size,
+        containing_width: f32,
     ) -> Result<(), LayoutError> {
         let style = &node.style;
+        let margin = style.margin.resolve(containing_width);
+        let padding = style.padding.resolve(containing_width);
 
         // Calculate width
         let width = match style.width {
             Dimension::Auto => {
                 // Auto width will be determined by children or content
                 // For now, use container width minus margins and padding
-                container_size.width - style.margin.horizontal() - style.padding.horizontal()
+                container_size.width - margin.horizontal() - padding.horizontal()
             }
             _ => style.width.resolve(container_size.width),
         };
@@ -529,16 +1133,16 @@ impl LayoutEngine {
         // Calculate height
         let height = match style.height {
             Dimension::Auto => {
-                // Auto height will be determined by children or content
-                // For now, use a minimal height
-                0.0
+                // Text wraps at the node's width and sets its height; other
+                // content doesn't size its container yet
+                self.text_height(node, width, containing_width)
+                    .unwrap_or(0.0)
             }
             _ => style.height.resolve(container_size.height),
         };
 
         // Apply min/max constraints (only if not Auto)
-        let final_width = {
-            let mut w = width;
+        let clamp_width = |mut w: f32| {
             if !matches!(style.min_width, Dimension::Auto) {
                 w = w.max(style.min_width.resolve(container_size.width));
             }
@@ -548,8 +1152,7 @@ impl LayoutEngine {
             w
         };
 
-        let final_height = {
-            let mut h = height;
+        let clamp_height = |mut h: f32| {
             if !matches!(style.min_height, Dimension::Auto) {
                 h = h.max(style.min_height.resolve(container_size.height));
             }
@@ -559,30 +1162,97 @@ impl LayoutEngine {
             h
         };
 
+        // Derive a missing dimension from the aspect ratio, clamping the known
+        // dimension first so the ratio holds against min/max where possible
+        let width_is_auto = matches!(style.width, Dimension::Auto);
+        let height_is_auto = matches!(style.height, Dimension::Auto);
+        let (final_width, final_height) = match style.aspect_ratio.filter(|r| *r > 0.0) {
+            Some(ratio) if width_is_auto && !height_is_auto => {
+                let h = clamp_height(height);
+                (clamp_width(h * ratio), h)
+            }
+            Some(ratio) if height_is_auto => {
+                let w = clamp_width(width);
+                (w, clamp_height(w / ratio))
+            }
+            _ => (clamp_width(width), clamp_height(height)),
+        };
+
         // Set the node's size
         node.layout.rect.size = Size::new(final_width, final_height);
 
         // Calculate content rect (excluding padding and border)
-        let content_x = node.layout.rect.x() + style.padding.left + style.border.left;
-        let content_y = node.layout.rect.y() + style.padding.top + style.border.top;
-        let content_width = final_width - style.padding.horizontal() - style.border.horizontal();
-        let content_height = final_height - style.padding.vertical() - style.border.vertical();
+        let content_x = node.layout.rect.x() + padding.left + style.border.left;
+        let content_y = node.layout.rect.y() + padding.top + style.border.top;
+        let content_width = final_width - padding.horizontal() - style.border.horizontal();
+        let content_height = final_height - padding.vertical() - style.border.vertical();
+        node.layout.margin = margin;
+        node.layout.padding = padding;
+        node.layout.sticky_offset = Point::zero();
 
         node.layout.content_rect = Rect::new(content_x, content_y, content_width, content_height);
 
+        // As in CSS, a visible axis behaves like auto once the other axis
+        // clips, so any non-visible overflow clips to the content box
+        let clips = style.overflow_x != Overflow::Visible || style.overflow_y != Overflow::Visible;
+        node.layout.clip_rect = clips.then_some(node.layout.content_rect);
+
+        node.layout.transform_matrix = match &style.transform {
+            Some(transform) => {
+                let origin = style
+                    .transform_origin
+                    .unwrap_or(Point::new(final_width / 2.0, final_height / 2.0));
+                transform.to_matrix_about(origin)
+            }
+            None => IDENTITY_MATRIX,
+        };
+
         Ok(())
     }
 
+    /// Layout an absolutely positioned or fixed child
+    ///
+    /// Fixed nodes are sized against the viewport unless an ancestor has a
+    /// transform, in which case they resolve against their parent like
+    /// absolutely positioned ones.
+    fn layout_out_of_flow(
+        &mut self,
+        child: &mut LayoutNode,
+        parent_content_size: Size,
+    ) -> Result<(), LayoutError> {
+        if child.style.position_type == PositionType::Fixed && !self.in_transform {
+            self.layout_node(child, self.viewport)
+        } else {
+            self.layout_node(child, parent_content_size)
+        }
+    }
+
     /// Calculate the position of a node
     fn calculate_node_position(&self, node: &mut LayoutNode) -> Result<(), LayoutError> {
         let style = &node.style;
 
         match style.position_type {
-            PositionType::Relative => {
+            PositionType::Relative | PositionType::Sticky => {
                 // Position will be set by parent's layout algorithm
                 // For now, keep current position
             }
-            PositionType::Absolute => {
+            PositionType::Fixed if !self.in_transform => {
+                let size = node.layout.rect.size;
+                let viewport = self.viewport;
+                // An inset of auto leaves that side free; with both auto the
+                // node sits at the viewport's origin
+                let place = |start: &Dimension, end: &Dimension, extent: f32, length: f32| match (
+                    start, end,
+                ) {
+                    (Dimension::Auto, Dimension::Auto) => 0.0,
+                    (Dimension::Auto, end) => extent - end.resolve(extent) - length,
+                    (start, _) => start.resolve(extent),
+                };
+                let x = place(&style.left, &style.right, viewport.width, size.width);
+                let y = place(&style.top, &style.bottom, viewport.height, size.height);
+                node.layout.rect.origin = Point::new(x, y);
+            }
+            PositionType::Absolute | PositionType::Fixed => {
                 // Position relative to containing block
                 let x = style.left.resolve(0.0); // TODO: Use actual containing block size
                 let y = style.top.resolve(0.0);
@@ -600,10 +1270,11 @@ impl LayoutEngine {
 
         let parent_content_size = parent.layout.content_rect.size;
         let parent_style = &parent.style;
+        let direction = parent.layout.direction;
 
         // Separate absolutely positioned children
         let (absolute_children, relative_children): (Vec<_>, Vec<_>) = (0..parent.children.len())
-            .partition(|&i| parent.children[i].style.position_type == PositionType::Absolute);
+            .partition(|&i| parent.children[i].style.position_type.is_out_of_flow());
 
         // Layout relatively positioned children with flexbox
         if !relative_children.is_empty() {
@@ -616,6 +1287,7 @@ impl LayoutEngine {
                     &relative_children,
                     parent_content_size,
                     parent_style,
+                    direction,
                 )?;
             } else {
                 self.layout_flex_line(
@@ -623,6 +1295,7 @@ impl LayoutEngine {
                     &relative_children,
                     parent_content_size,
                     parent_style,
+                    direction,
                 )?;
             }
         }
@@ -630,11 +1303,169 @@ impl LayoutEngine {
         // Layout absolutely positioned children
         for &child_index in &absolute_children {
             let child = &mut parent.children[child_index];
-            self.layout_node(child, parent_content_size)?;
+            self.layout_out_of_flow(child, parent_content_size)?;
+        }
+
+        Ok(())
+    }
+
+    /// Layout children into the cells of a grid container
+    fn layout_grid_children(&mut self, parent: &mut LayoutNode) -> Result<(), LayoutError> {
+        let content_size = parent.layout.content_rect.size;
+        let parent_style = &parent.style;
+
+        let (absolute_children, grid_children): (Vec<_>, Vec<_>) = (0..parent.children.len())
+            .partition(|&i| parent.children[i].style.position_type.is_out_of_flow());
+
+        // Items placed past the explicit grid get implicit auto tracks
+        let mut columns = parent_style.grid_template_columns.clone();
+        let mut rows = parent_style.grid_template_rows.clone();
+        for &child_index in &grid_children {
+            let style = &parent.children[child_index].style;
+            let (column, column_span) = style.grid_column.tracks();
+            let (row, row_span) = style.grid_row.tracks();
+            if columns.len() < column + column_span {
+                columns.resize(column + column_span, Dimension::Auto);
+            }
+            if rows.len() < row + row_span {
+                rows.resize(row + row_span, Dimension::Auto);
+            }
+        }
+
+        let column_sizes = self.resolve_grid_tracks(
+            &columns,
+            content_size.width,
+            content_size.width,
+            parent_style.gap.column,
+            &parent.children,
+            &grid_children,
+            true,
+        );
+        let row_sizes = self.resolve_grid_tracks(
+            &rows,
+            content_size.height,
+            content_size.width,
+            parent_style.gap.row,
+            &parent.children,
+            &grid_children,
+            false,
+        );
+        let column_offsets = Self::grid_track_offsets(&column_sizes, parent_style.gap.column);
+        let row_offsets = Self::grid_track_offsets(&row_sizes, parent_style.gap.row);
+
+        for &child_index in &grid_children {
+            let child = &mut parent.children[child_index];
+            let (column, column_span) = child.style.grid_column.tracks();
+            let (row, row_span) = child.style.grid_row.tracks();
+
+            let cell_end_x =
+                column_offsets[column + column_span - 1] + column_sizes[column + column_span - 1];
+            let cell_end_y = row_offsets[row + row_span - 1] + row_sizes[row + row_span - 1];
+            let cell = Rect::new(
+                column_offsets[column],
+                row_offsets[row],
+                cell_end_x - column_offsets[column],
+                cell_end_y - row_offsets[row],
+            );
+
+            let margin = child.style.margin.resolve(cell.width());
+            child.layout.rect.origin = Point::new(cell.x() + margin.left, cell.y() + margin.top);
+            self.layout_node(child, cell.size)?;
+        }
+
+        for &child_index in &absolute_children {
+            let child = &mut parent.children[child_index];
+            self.layout_out_of_flow(child, content_size)?;
         }
 
         Ok(())
     }
+
+    /// Resolve the sizes of grid tracks along one axis
+    ///
+    /// Fixed and percentage tracks are resolved first, auto tracks take the
+    /// size of the largest item placed solely in them, and `fr` tracks share
+    /// whatever space is left.
+    #[allow(clippy::too_many_arguments)]
+    fn resolve_grid_tracks(
+        &self,
+        tracks: &[Dimension],
+        available: f32,
+        containing_width: f32,
+        gap: f32,
+        children: &[LayoutNode],
+        child_indices: &[usize],
+        is_column: bool,
+    ) -> Vec<f32> {
+        let mut sizes: Vec<f32> = tracks
+            .iter()
+            .map(|track| match track {
+                Dimension::Points(_) | Dimension::Percent(_) | Dimension::Calc(_) => {
+                    track.resolve(available)
+                }
+                Dimension::Auto | Dimension::Fraction(_) => 0.0,
+            })
+            .collect();
+
+        for (track_index, track) in tracks.iter().enumerate() {
+            if *track != Dimension::Auto {
+                continue;
+            }
+            sizes[track_index] = child_indices
+                .iter()
+                .map(|&i| &children[i].style)
+                .filter(|style| {
+                    let placement = if is_column {
+                        style.grid_column
+                    } else {
+                        style.grid_row
+                    };
+                    placement.tracks() == (track_index, 1)
+                })
+                .map(|style| {
+                    let margin = style.margin.resolve(containing_width);
+                    if is_column {
+                        style.width.resolve(available) + margin.horizontal()
+                    } else {
+                        style.height.resolve(available) + margin.vertical()
+                    }
+                })
+                .fold(0.0, f32::max);
+        }
+
+        let total_fraction: f32 = tracks
+            .iter()
+            .map(|track| match track {
+                Dimension::Fraction(fraction) => fraction.max(0.0),
+                _ => 0.0,
+            })
+            .sum();
+        if total_fraction > 0.0 {
+            let total_gaps = gap * (tracks.len() as f32 - 1.0).max(0.0);
+            let free_space = (available - sizes.iter().sum::<f32>() - total_gaps).max(0.0);
+            for (size, track) in sizes.iter_mut().zip(tracks) {
+                if let Dimension::Fraction(fraction) = track {
+                    *size = free_space * fraction.max(0.0) / total_fraction;
+                }
+            }
+        }
+
+        sizes
+    }
+
+    /// Compute the start offset of each track from the track sizes
+    fn grid_track_offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+        let mut offset = 0.0;
+        sizes
+            .iter()
+            .map(|size| {
+                let start = offset;
+                offset += size + gap;
+                start
+            })
+            .collect()
+    }
+
     /// Layout a single flex line
     fn layout_flex_line(
         &mut self,
@@ -642,16 +1473,19 @@ impl LayoutEngine {
         child_indices: &[usize],
         container_size: Size,
         parent_style: &LayoutStyle,
+        direction: Direction,
     ) -> Result<(), LayoutError> {
         let flex_direction = parent_style.flex_direction;
         let is_row = matches!(
             flex_direction,
             FlexDirection::Row | FlexDirection::RowReverse
         );
+        let is_rtl = direction == Direction::Rtl;
+        // Right-to-left rows start on the right, which flips row-reverse back
         let is_reverse = matches!(
             flex_direction,
             FlexDirection::RowReverse | FlexDirection::ColumnReverse
-        );
+        ) != (is_row && is_rtl);
 
         let main_axis_size = if is_row {
             container_size.width
@@ -687,6 +1521,7 @@ impl LayoutEngine {
                 basis_size,
                 main_size,
                 cross_size,
+                baseline: Self::item_baseline(&child.style, cross_size),
                 flex_grow: child.style.flex_grow,
                 flex_shrink: child.style.flex_shrink,
             });
@@ -709,6 +1544,41 @@ impl LayoutEngine {
             total_flex_shrink,
         )?;
 
+        // Items with an aspect ratio and an auto cross size follow their main size
+        for (item, &child_index) in item_data.iter_mut().zip(child_indices) {
+            let style = &children[child_index].style;
+            let cross_is_auto = if is_row {
+                matches!(style.height, Dimension::Auto)
+            } else {
+                matches!(style.width, Dimension::Auto)
+            };
+            if let Some(ratio) = style.aspect_ratio.filter(|r| *r > 0.0 && cross_is_auto) {
+                item.cross_size = if is_row {
+                    item.main_size / ratio
+                } else {
+                    item.main_size * ratio
+                };
+                item.baseline = Self::item_baseline(style, item.cross_size);
+            }
+        }
+
+        // Baselines only line up along a horizontal cross axis; in columns
+        // baseline alignment falls back to flex-start
+        let line_baseline = is_row.then(|| {
+            item_data
+                .iter()
+                .zip(child_indices)
+                .filter(|(_, &child_index)| {
+                    children[child_index]
+                        .style
+                        .align_self
+                        .unwrap_or(parent_style.align_items)
+                        == AlignItems::Baseline
+                })
+                .map(|(item, _)| item.baseline)
+                .fold(0.0, f32::max)
+        });
+
         // Third pass: Apply justify-content for positioning
         let positions = self.calculate_justify_content_positions(
             &item_data,
@@ -725,12 +1595,17 @@ impl LayoutEngine {
             let main_pos = positions[i];
 
             // Calculate cross-axis position
-            let cross_pos = self.calculate_cross_axis_position(
+            let mut cross_pos = self.calculate_cross_axis_position(
                 child,
-                item.cross_size,
+                item,
                 cross_axis_size,
                 parent_style.align_items,
+                line_baseline,
             );
+            // A column's horizontal cross axis starts on the right in RTL
+            if !is_row && is_rtl {
+                cross_pos = cross_axis_size - cross_pos - item.cross_size;
+            }
 
             // Set final layout
             if is_row {
@@ -739,8 +1614,9 @@ impl LayoutEngine {
                 child.layout.rect = Rect::new(cross_pos, main_pos, item.cross_size, item.main_size);
             }
 
-            // Recursively layout this child
-            self.layout_node(child, child.layout.rect.size)?;
+            // Recursively layout this child; percentage edges refer to the
+            // flex container rather than the item's own size
+            self.layout_node_in(child, child.layout.rect.size, container_size.width)?;
         }
 
         Ok(())
@@ -750,6 +1626,25 @@ impl LayoutEngine {
         self.layout_cache.clear();
     }
 
+    /// Drop cached layouts of nodes that are not in `root`'s tree
+    pub fn retain_cache(&mut self, root: &LayoutNode) {
+        let mut ids = HashSet::new();
+        Self::collect_ids(root, &mut ids);
+        self.layout_cache.retain(|id, _| ids.contains(id));
+    }
+
+    fn collect_ids(node: &LayoutNode, ids: &mut HashSet<ComponentId>) {
+        ids.insert(node.id);
+        for child in &node.children {
+            Self::collect_ids(child, ids);
+        }
+    }
+
+    /// Number of nodes with a cached layout
+    pub fn cache_size(&self) -> usize {
+        self.layout_cache.len()
+    }
+
     /// Get layout statistics
     pub fn get_stats(&self) -> &LayoutStats {
         &self.stats
@@ -760,20 +1655,147 @@ impl LayoutEngine {
         self.stats = LayoutStats::default();
     }
 
-    // Helper methods for enhanced flexbox support
+    /// Mark the node with `id` and all of its ancestors dirty
+    ///
+    /// Their cached layouts are discarded so the next `calculate_layout`
+    /// recomputes the chain from `root` down to the changed node.
+    pub fn mark_dirty(
+        &mut self,
+        root: &mut LayoutNode,
+        id: ComponentId,
+    ) -> Result<(), LayoutError> {
+        if self.mark_path_dirty(root, id) {
+            Ok(())
+        } else {
+            Err(LayoutError::NodeNotFound(id))
+        }
+    }
 
-    /// Calculate flex basis size for an item
-    fn calculate_flex_basis_size(
-        &self,
-        child: &LayoutNode,
-        container_size: Size,
-        is_row: bool,
-    ) -> Result<f32, LayoutError> {
+    /// Scroll the node with `id` so `offset` is at the top-left of its viewport
+    ///
+    /// The node and its ancestors are invalidated, so the next
+    /// `calculate_layout` repositions the sticky nodes inside it.
+    pub fn set_scroll_offset(
+        &mut self,
+        root: &mut LayoutNode,
+        id: ComponentId,
+        offset: Point,
+    ) -> Result<(), LayoutError> {
+        let node = root.find_mut(id).ok_or(LayoutError::NodeNotFound(id))?;
+        node.layout.scroll_offset = offset;
+        self.mark_dirty(root, id)
+    }
+
+    /// Pin the sticky nodes inside a scroll container to its viewport
+    ///
+    /// Nested scroll containers pin their own sticky nodes.
+    fn apply_sticky(container: &mut LayoutNode) {
+        let scroll = container.layout.scroll_offset;
+        let size = container.layout.content_rect.size;
+        let viewport = Rect::new(scroll.x, scroll.y, size.width, size.height);
+        for child in &mut container.children {
+            // The scrolled content has no end to keep its direct children inside
+            Self::stick(child, Point::zero(), None, viewport);
+        }
+    }
+
+    /// Pin `node` if it is sticky, then look for sticky descendants
+    ///
+    /// `origin` is the parent's content box in the scroll container's
+    /// content coordinates, and `bounds` the size of that box, which a
+    /// sticky node doesn't leave.
+    fn stick(node: &mut LayoutNode, origin: Point, bounds: Option<Size>, viewport: Rect) {
+        if node.style.position_type == PositionType::Sticky {
+            let offset = node.layout.sticky_offset;
+            let flow = Point::new(
+                node.layout.rect.x() - offset.x,
+                node.layout.rect.y() - offset.y,
+            );
+            let size = node.layout.rect.size;
+            let pin =
+                |flow: f32, threshold: &Dimension, start: f32, extent: f32, room: Option<f32>| {
+                    if matches!(threshold, Dimension::Auto) {
+                        return flow;
+                    }
+                    let pinned = flow.max(start + threshold.resolve(extent));
+                    // Stop at the end of the parent, but never above the flow position
+                    room.map_or(pinned, |room| pinned.min(room.max(flow)))
+                };
+            let pinned = Point::new(
+                pin(
+                    flow.x,
+                    &node.style.left,
+                    viewport.x() - origin.x,
+                    viewport.width(),
+                    bounds.map(|bounds| bounds.width - size.width),
+                ),
+                pin(
+                    flow.y,
+                    &node.style.top,
+                    viewport.y() - origin.y,
+                    viewport.height(),
+                    bounds.map(|bounds| bounds.height - size.height),
+                ),
+            );
+            node.layout.rect.origin = pinned;
+            node.layout.sticky_offset = Point::new(pinned.x - flow.x, pinned.y - flow.y);
+        }
+
+        // Fixed nodes don't scroll, so neither do their sticky descendants
+        if node.is_scroll_container() || node.style.position_type == PositionType::Fixed {
+            return;
+        }
+        let content_origin = Point::new(
+            origin.x + node.layout.rect.x() + node.layout.padding.left + node.style.border.left,
+            origin.y + node.layout.rect.y() + node.layout.padding.top + node.style.border.top,
+        );
+        let content_size = node.layout.content_rect.size;
+        for child in &mut node.children {
+            Self::stick(child, content_origin, Some(content_size), viewport);
+        }
+    }
+
+    /// Invalidate every node on the path to `id`, returning whether it was found
+    fn mark_path_dirty(&mut self, node: &mut LayoutNode, id: ComponentId) -> bool {
+        let on_path = node.id == id
+            || node
+                .children
+                .iter_mut()
+                .any(|child| self.mark_path_dirty(child, id));
+
+        if on_path {
+            node.layout.is_dirty = true;
+            self.layout_cache.remove(&node.id);
+        }
+
+        on_path
+    }
+
+    // Helper methods for enhanced flexbox support
+
+    /// Calculate flex basis size for an item
+    fn calculate_flex_basis_size(
+        &self,
+        child: &LayoutNode,
+        container_size: Size,
+        is_row: bool,
+    ) -> Result<f32, LayoutError> {
         let basis_size = match child.style.flex_basis {
             Dimension::Auto => {
                 // Use main axis size if available, otherwise content size
                 if is_row {
                     child.style.width.resolve(container_size.width)
+                } else if matches!(child.style.height, Dimension::Auto) && child.text.is_some() {
+                    // Text in a column wraps at the width the item will get
+                    let width = match &child.style.width {
+                        Dimension::Auto => {
+                            let margin = child.style.margin.resolve(container_size.width);
+                            container_size.width - margin.horizontal()
+                        }
+                        width => width.resolve(container_size.width),
+                    };
+                    self.text_height(child, width, container_size.width)
+                        .unwrap_or(0.0)
                 } else {
                     child.style.height.resolve(container_size.height)
                 }
@@ -920,13 +1942,11 @@ impl LayoutEngine {
             }
         }
         if is_reverse {
-            // Reverse the positions for reverse flex directions
-            let positions_len = positions.len();
-            for (i, pos) in positions.iter_mut().enumerate() {
-                let item_index = positions_len - 1 - i;
-                *pos = container_size - *pos - items[item_index].main_size;
+            // Mirror each item across the main axis using its own size, so the
+            // first item ends up at the main-end edge
+            for (pos, item) in positions.iter_mut().zip(items) {
+                *pos = container_size - *pos - item.main_size;
             }
-            positions.reverse();
         }
 
         positions
@@ -936,12 +1956,14 @@ impl LayoutEngine {
     fn calculate_cross_axis_position(
         &self,
         child: &LayoutNode,
-        item_cross_size: f32,
+        item: &FlexItemData,
         container_cross_size: f32,
         align_items: AlignItems,
+        line_baseline: Option<f32>,
     ) -> f32 {
         // Check for align-self override
         let alignment = child.style.align_self.unwrap_or(align_items);
+        let item_cross_size = item.cross_size;
 
         match alignment {
             AlignItems::FlexStart => 0.0,
@@ -949,13 +1971,22 @@ impl LayoutEngine {
             AlignItems::Center => (container_cross_size - item_cross_size) / 2.0,
             AlignItems::Stretch => 0.0, // Item should already be sized to fill
             AlignItems::Baseline => {
-                // For now, treat baseline as flex-start
-                // TODO: Implement proper baseline alignment
-                0.0
+                // Shift the item down so its baseline meets the line's deepest one
+                line_baseline.map_or(0.0, |baseline| baseline - item.baseline)
             }
         }
     }
 
+    /// Approximate the distance from an item's top edge to its first baseline
+    ///
+    /// Without text shaping this assumes an ascent of 80% of the font size,
+    /// and items without a font size are aligned on their bottom edge.
+    fn item_baseline(style: &LayoutStyle, cross_size: f32) -> f32 {
+        style
+            .font_size
+            .map_or(cross_size, |font_size| font_size * 0.8)
+    }
+
     /// Layout children with flex wrap (multi-line)
     fn layout_flex_multiline(
         &mut self,
@@ -963,6 +1994,7 @@ impl LayoutEngine {
         child_indices: &[usize],
         container_size: Size,
         parent_style: &LayoutStyle,
+        direction: Direction,
     ) -> Result<(), LayoutError> {
         let flex_direction = parent_style.flex_direction;
         let is_row = matches!(
@@ -1019,7 +2051,13 @@ impl LayoutEngine {
             };
 
             // Layout this line
-            self.layout_flex_line(children, line_indices, line_container, parent_style)?;
+            self.layout_flex_line(
+                children,
+                line_indices,
+                line_container,
+                parent_style,
+                direction,
+            )?;
             // First, recursively layout all children in this line
             for &child_index in line_indices {
                 let child = &mut children[child_index];
@@ -1057,6 +2095,14 @@ impl LayoutEngine {
             }
         }
 
+        // Column lines stack from the right edge in RTL
+        if !is_row && direction == Direction::Rtl {
+            for &child_index in child_indices {
+                let rect = &mut children[child_index].layout.rect;
+                rect.origin.x = container_size.width - rect.origin.x - rect.width();
+            }
+        }
+
         Ok(())
     }
 
@@ -1136,14 +2182,18 @@ impl LayoutEngine {
                     match child.style.height {
                         Dimension::Points(h) => h,
                         Dimension::Percent(p) => p * child.layout.rect.height(),
-                        Dimension::Auto => child.layout.rect.height(),
+                        Dimension::Auto | Dimension::Fraction(_) | Dimension::Calc(_) => {
+                            child.layout.rect.height()
+                        }
                     }
                 } else {
                     // For column layout, the cross size is the width
                     match child.style.width {
                         Dimension::Points(w) => w,
                         Dimension::Percent(p) => p * child.layout.rect.width(),
-                        Dimension::Auto => child.layout.rect.width(),
+                        Dimension::Auto | Dimension::Fraction(_) | Dimension::Calc(_) => {
+                            child.layout.rect.width()
+                        }
                     }
                 }
             })
@@ -1160,6 +2210,8 @@ struct FlexItemData {
     basis_size: f32,
     main_size: f32,
     cross_size: f32,
+    /// Distance from the item's cross-start edge to its baseline
+    baseline: f32,
     flex_grow: f32,
     flex_shrink: f32,
 }
@@ -1257,6 +2309,15 @@ mod tests {
         let point_outside = Point::new(150.0, 100.0);
         assert!(rect.contains_point(point_inside));
         assert!(!rect.contains_point(point_outside));
+
+        let overlapping = Rect::new(100.0, 200.0, 50.0, 50.0);
+        let touching = Rect::new(110.0, 20.0, 10.0, 10.0);
+        assert!(rect.intersects(&overlapping));
+        assert!(!rect.intersects(&touching));
+        assert_eq!(
+            rect.union(&overlapping),
+            Rect::new(10.0, 20.0, 140.0, 230.0)
+        );
     }
 
     #[test]
@@ -1540,6 +2601,516 @@ mod tests {
         assert_eq!(parent.children[1].layout.rect.x(), 250.0); // 300 - 50
     }
 
+    #[test]
+    fn test_percentage_padding_resolves_against_width() {
+        let mut engine = LayoutEngine::new();
+        let style = LayoutStyle {
+            width: Dimension::Points(200.0),
+            height: Dimension::Points(100.0),
+            padding: EdgeDimensions::uniform(Dimension::Percent(5.0)),
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 300.0))
+            .unwrap();
+
+        // Every edge is 5% of the containing width (400), vertical ones included
+        assert_eq!(node.layout.padding, EdgeValues::uniform(20.0));
+        assert_eq!(node.layout.content_rect, Rect::new(20.0, 20.0, 160.0, 60.0));
+    }
+
+    #[test]
+    fn test_percentage_padding_in_flex_item_uses_container_width() {
+        let mut engine = LayoutEngine::new();
+        let parent_style = LayoutStyle {
+            width: Dimension::Points(300.0),
+            height: Dimension::Points(100.0),
+            ..Default::default()
+        };
+        let mut parent = LayoutNode::new(ComponentId::new(), parent_style);
+        parent.add_child(LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                width: Dimension::Points(100.0),
+                height: Dimension::Points(50.0),
+                padding: EdgeDimensions::new(
+                    Dimension::Points(0.0),
+                    Dimension::Percent(10.0),
+                    Dimension::Points(0.0),
+                    Dimension::Percent(10.0),
+                ),
+                ..Default::default()
+            },
+        ));
+
+        engine
+            .calculate_layout(&mut parent, Size::new(300.0, 100.0))
+            .unwrap();
+
+        let child = &parent.children[0];
+        assert_eq!(child.layout.padding, EdgeValues::new(0.0, 30.0, 0.0, 30.0));
+        assert_eq!(child.layout.content_rect.width(), 40.0);
+    }
+
+    #[test]
+    fn test_overflow_hidden_clips_to_content_rect() {
+        let mut engine = LayoutEngine::new();
+        let style = LayoutStyle {
+            width: Dimension::Points(200.0),
+            height: Dimension::Points(100.0),
+            padding: EdgeDimensions::uniform(Dimension::Points(10.0)),
+            overflow_x: Overflow::Hidden,
+            overflow_y: Overflow::Hidden,
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+        node.add_child(LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                width: Dimension::Points(500.0),
+                height: Dimension::Points(500.0),
+                ..Default::default()
+            },
+        ));
+
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+
+        assert_eq!(node.layout.clip_rect, Some(node.layout.content_rect));
+        assert_eq!(node.layout.content_rect, Rect::new(10.0, 10.0, 180.0, 80.0));
+        assert_eq!(node.children[0].layout.clip_rect, None);
+    }
+
+    #[test]
+    fn test_transform_list_composes_in_css_order() {
+        // As in CSS, the last transform in the list applies first
+        let transform = Transform::Multiple(vec![
+            Transform::Translate(10.0, 20.0),
+            Transform::Scale(2.0, 2.0),
+        ]);
+        let matrix = transform.to_matrix();
+
+        assert_eq!(matrix, [2.0, 0.0, 0.0, 2.0, 10.0, 20.0]);
+        assert_eq!(
+            transform_point(&matrix, Point::new(1.0, 1.0)),
+            Point::new(12.0, 22.0)
+        );
+    }
+
+    #[test]
+    fn test_auto_height_follows_wrapped_text() {
+        let mut engine = LayoutEngine::new();
+        // At 16px a character is measured as 8px wide, so five four-letter
+        // words and their spaces fill a 200px line
+        let text_node = |words: usize| {
+            let style = LayoutStyle {
+                width: Dimension::Points(200.0),
+                font_size: Some(16.0),
+                line_height: Some(1.5),
+                ..Default::default()
+            };
+            LayoutNode::new(ComponentId::new(), style).with_text(vec!["word"; words].join(" "))
+        };
+
+        let mut one_line = text_node(5);
+        engine
+            .calculate_layout(&mut one_line, Size::new(400.0, 400.0))
+            .unwrap();
+        assert_eq!(one_line.layout.rect.size, Size::new(200.0, 24.0));
+
+        let mut three_lines = text_node(15);
+        engine
+            .calculate_layout(&mut three_lines, Size::new(400.0, 400.0))
+            .unwrap();
+        assert_eq!(three_lines.layout.rect.height(), 72.0);
+
+        // A paragraph in a column gets the height of its lines, and the
+        // next item is placed below it
+        let column = LayoutStyle {
+            width: Dimension::Points(200.0),
+            height: Dimension::Points(300.0),
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        };
+        let mut root = LayoutNode::new(ComponentId::new(), column);
+        let mut paragraph = text_node(15);
+        paragraph.style.width = Dimension::Auto;
+        root.add_child(paragraph);
+        root.add_child(text_node(1));
+        engine
+            .calculate_layout(&mut root, Size::new(400.0, 400.0))
+            .unwrap();
+        assert_eq!(root.children[0].layout.rect.height(), 72.0);
+        assert_eq!(root.children[1].layout.rect.y(), 72.0);
+    }
+
+    #[test]
+    fn test_wrap_lines_breaks_at_spaces_and_newlines() {
+        let width_of = |line: &str| line.chars().count() as f32 * 10.0;
+        assert_eq!(
+            wrap_lines("one two three\nfour", 80.0, width_of),
+            vec!["one two", "three", "four"]
+        );
+        // A word wider than the line is kept whole
+        assert_eq!(
+            wrap_lines("a enormous b", 50.0, width_of),
+            vec!["a", "enormous", "b"]
+        );
+    }
+
+    #[test]
+    fn test_text_is_wrapped_with_its_font_family_and_weight() {
+        /// Bold text is twice as wide, and monospace a little wider
+        #[derive(Debug)]
+        struct FontAware;
+
+        impl TextMeasurer for FontAware {
+            fn measure_width(&self, text: &str, style: &Style) -> f32 {
+                let mut advance = 8.0;
+                if style.font_weight == Some(FontWeight::Bold) {
+                    advance *= 2.0;
+                }
+                if style.font_family.as_deref() == Some("monospace") {
+                    advance += 2.0;
+                }
+                text.chars().count() as f32 * advance
+            }
+        }
+
+        let mut engine = LayoutEngine::new()
+            .with_text_cache(Arc::new(TextMeasureCache::new(16)))
+            .with_text_measurer(Arc::new(FontAware));
+        // Five four-letter words fill a 200px line at 8px a character
+        let mut paragraph = |family: Option<&str>, weight: Option<FontWeight>| {
+            let style = LayoutStyle {
+                width: Dimension::Points(200.0),
+                font_size: Some(16.0),
+                line_height: Some(1.0),
+                font_family: family.map(str::to_string),
+                font_weight: weight,
+                ..Default::default()
+            };
+            let mut node =
+                LayoutNode::new(ComponentId::new(), style).with_text(["word"; 5].join(" "));
+            engine
+                .calculate_layout(&mut node, Size::new(400.0, 400.0))
+                .unwrap();
+            node.layout.rect.height()
+        };
+
+        assert_eq!(paragraph(None, None), 16.0);
+        assert_eq!(paragraph(None, Some(FontWeight::Bold)), 48.0);
+        assert_eq!(paragraph(Some("monospace"), None), 32.0);
+    }
+
+    #[test]
+    fn test_transform_keeps_layout_box_and_inverts() {
+        let mut engine = LayoutEngine::new();
+        let style = LayoutStyle {
+            width: Dimension::Points(100.0),
+            height: Dimension::Points(50.0),
+            transform: Some(Transform::Scale(2.0, 2.0)),
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+
+        // Scaling happens about the center, leaving the layout box alone
+        assert_eq!(node.layout.rect, Rect::new(0.0, 0.0, 100.0, 50.0));
+        let corner = node.layout.to_visual(Point::new(0.0, 0.0));
+        assert_eq!(corner, Point::new(-50.0, -25.0));
+        assert_eq!(node.layout.to_layout(corner), Some(Point::zero()));
+    }
+
+    #[test]
+    fn test_overflow_single_axis_still_clips() {
+        let mut engine = LayoutEngine::new();
+        let style = LayoutStyle {
+            width: Dimension::Points(200.0),
+            height: Dimension::Points(100.0),
+            overflow_y: Overflow::Scroll,
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+
+        assert_eq!(node.layout.clip_rect, Some(node.layout.content_rect));
+        assert_eq!(node.layout.scroll_offset, Point::zero());
+    }
+
+    #[test]
+    fn test_mark_dirty_recomputes_ancestors() {
+        let mut engine = LayoutEngine::new();
+        let parent_style = LayoutStyle {
+            width: Dimension::Points(300.0),
+            height: Dimension::Points(100.0),
+            ..Default::default()
+        };
+        let mut parent = LayoutNode::new(ComponentId::new(), parent_style);
+        let child_id = ComponentId::new();
+        parent.add_child(LayoutNode::new(
+            child_id,
+            LayoutStyle {
+                width: Dimension::Points(50.0),
+                height: Dimension::Points(50.0),
+                ..Default::default()
+            },
+        ));
+
+        let container_size = Size::new(300.0, 100.0);
+        engine
+            .calculate_layout(&mut parent, container_size)
+            .unwrap();
+        assert!(!parent.is_dirty());
+
+        // Without invalidation the clean parent is served from the cache
+        parent.children[0].style.width = Dimension::Points(80.0);
+        engine.reset_stats();
+        engine
+            .calculate_layout(&mut parent, container_size)
+            .unwrap();
+        assert_eq!(engine.stats.cache_hits, 1);
+        assert_eq!(parent.children[0].layout.rect.width(), 50.0);
+
+        engine.mark_dirty(&mut parent, child_id).unwrap();
+        assert!(parent.layout.is_dirty);
+
+        engine.reset_stats();
+        engine
+            .calculate_layout(&mut parent, container_size)
+            .unwrap();
+        assert_eq!(engine.stats.cache_misses, 2);
+        assert_eq!(parent.children[0].layout.rect.width(), 80.0);
+        assert!(!parent.is_dirty());
+
+        assert!(matches!(
+            engine.mark_dirty(&mut parent, ComponentId::new()),
+            Err(LayoutError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_row_reverse_positions() {
+        let mut engine = LayoutEngine::new();
+        let parent_style = LayoutStyle {
+            flex_direction: FlexDirection::RowReverse,
+            width: Dimension::Points(300.0),
+            height: Dimension::Points(100.0),
+            gap: Gap::new(0.0, 10.0),
+            ..Default::default()
+        };
+        let mut parent = LayoutNode::new(ComponentId::new(), parent_style);
+
+        for width in [50.0, 60.0, 70.0] {
+            let child_style = LayoutStyle {
+                width: Dimension::Points(width),
+                height: Dimension::Points(20.0),
+                flex_shrink: 0.0,
+                ..Default::default()
+            };
+            parent.add_child(LayoutNode::new(ComponentId::new(), child_style));
+        }
+
+        engine
+            .calculate_layout(&mut parent, Size::new(300.0, 100.0))
+            .unwrap();
+
+        // Packed against the right edge, first child rightmost, no overlaps
+        let xs: Vec<_> = parent
+            .children
+            .iter()
+            .map(|child| child.layout.rect.x())
+            .collect();
+        assert_eq!(xs, vec![250.0, 180.0, 100.0]);
+    }
+
+    #[test]
+    fn test_rtl_row_starts_at_the_right_edge() {
+        let mut engine = LayoutEngine::new();
+        let mut parent = LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                direction: Some(Direction::Rtl),
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::FlexStart,
+                width: Dimension::Points(300.0),
+                height: Dimension::Points(100.0),
+                ..Default::default()
+            },
+        );
+        parent.add_child(LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                width: Dimension::Points(50.0),
+                height: Dimension::Points(20.0),
+                ..Default::default()
+            },
+        ));
+
+        // A nested column inherits RTL, so its cross-axis start is the right
+        let mut column = LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexStart,
+                width: Dimension::Points(100.0),
+                height: Dimension::Points(100.0),
+                ..Default::default()
+            },
+        );
+        column.add_child(LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                width: Dimension::Points(30.0),
+                height: Dimension::Points(30.0),
+                ..Default::default()
+            },
+        ));
+        parent.add_child(column);
+
+        engine
+            .calculate_layout(&mut parent, Size::new(300.0, 100.0))
+            .unwrap();
+
+        assert_eq!(parent.children[0].layout.rect.x(), 250.0);
+        assert_eq!(parent.children[1].layout.rect.x(), 150.0);
+        let column = &parent.children[1];
+        assert_eq!(column.layout.direction, Direction::Rtl);
+        assert_eq!(column.children[0].layout.rect.x(), 70.0);
+    }
+
+    #[test]
+    fn test_fixed_node_stays_put_in_the_viewport() {
+        let mut engine = LayoutEngine::new();
+        let mut page = LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                flex_direction: FlexDirection::Column,
+                overflow_y: Overflow::Scroll,
+                ..Default::default()
+            },
+        );
+        let banner_id = ComponentId::new();
+        page.add_child(LayoutNode::new(
+            banner_id,
+            LayoutStyle {
+                height: Dimension::Points(40.0),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+        ));
+        let mut section = LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                height: Dimension::Points(200.0),
+                padding: EdgeDimensions::uniform(Dimension::Points(10.0)),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+        );
+        section.add_child(LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                position_type: PositionType::Fixed,
+                right: Dimension::Points(16.0),
+                bottom: Dimension::Points(16.0),
+                width: Dimension::Points(56.0),
+                height: Dimension::Points(56.0),
+                ..Default::default()
+            },
+        ));
+        page.add_child(section);
+        let page_id = page.id;
+
+        let viewport = Size::new(400.0, 300.0);
+        let fab_rect = |page: &LayoutNode| page.children[1].children[0].layout.rect;
+        engine.calculate_layout(&mut page, viewport).unwrap();
+        assert_eq!(fab_rect(&page), Rect::new(328.0, 228.0, 56.0, 56.0));
+
+        // Growing the banner pushes the section down but not the button
+        page.children[0].style.height = Dimension::Points(120.0);
+        engine.mark_dirty(&mut page, banner_id).unwrap();
+        engine.calculate_layout(&mut page, viewport).unwrap();
+        assert_eq!(page.children[1].layout.rect.y(), 120.0);
+        assert_eq!(fab_rect(&page), Rect::new(328.0, 228.0, 56.0, 56.0));
+
+        engine
+            .set_scroll_offset(&mut page, page_id, Point::new(0.0, 80.0))
+            .unwrap();
+        engine.calculate_layout(&mut page, viewport).unwrap();
+        assert_eq!(fab_rect(&page), Rect::new(328.0, 228.0, 56.0, 56.0));
+    }
+
+    #[test]
+    fn test_sticky_header_pins_past_its_position() {
+        let mut engine = LayoutEngine::new();
+        let mut list = LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                flex_direction: FlexDirection::Column,
+                height: Dimension::Points(100.0),
+                overflow_y: Overflow::Scroll,
+                ..Default::default()
+            },
+        );
+        for (height, position_type) in [
+            (40.0, PositionType::Relative),
+            (20.0, PositionType::Sticky),
+            (400.0, PositionType::Relative),
+        ] {
+            list.add_child(LayoutNode::new(
+                ComponentId::new(),
+                LayoutStyle {
+                    position_type,
+                    top: Dimension::Points(0.0),
+                    height: Dimension::Points(height),
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+            ));
+        }
+        let list_id = list.id;
+        let container_size = Size::new(200.0, 100.0);
+        engine.calculate_layout(&mut list, container_size).unwrap();
+        assert_eq!(list.children[1].layout.rect.y(), 40.0);
+
+        // Before the threshold the header scrolls with the content
+        engine
+            .set_scroll_offset(&mut list, list_id, Point::new(0.0, 30.0))
+            .unwrap();
+        engine.calculate_layout(&mut list, container_size).unwrap();
+        assert_eq!(list.children[1].layout.rect.y(), 40.0);
+
+        for scroll in [40.0, 75.0, 250.0] {
+            engine
+                .set_scroll_offset(&mut list, list_id, Point::new(0.0, scroll))
+                .unwrap();
+            engine.calculate_layout(&mut list, container_size).unwrap();
+            assert_eq!(list.children[1].layout.rect.y(), scroll);
+            // Its in-flow space stays reserved
+            assert_eq!(list.children[2].layout.rect.y(), 60.0);
+        }
+
+        engine
+            .set_scroll_offset(&mut list, list_id, Point::zero())
+            .unwrap();
+        engine.calculate_layout(&mut list, container_size).unwrap();
+        assert_eq!(list.children[1].layout.rect.y(), 40.0);
+        assert!(matches!(
+            engine.set_scroll_offset(&mut list, ComponentId::new(), Point::zero()),
+            Err(LayoutError::NodeNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_align_items_center() {
         let mut engine = LayoutEngine::new();
@@ -1642,6 +3213,46 @@ mod tests {
         assert!((parent.children[1].layout.rect.width() - 183.33).abs() < 0.1);
     }
 
+    #[test]
+    fn test_align_items_baseline() {
+        let mut engine = LayoutEngine::new();
+        let parent_style = LayoutStyle {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Baseline,
+            width: Dimension::Points(300.0),
+            height: Dimension::Points(100.0),
+            ..Default::default()
+        };
+        let mut parent = LayoutNode::new(ComponentId::new(), parent_style);
+
+        // (height, font size): baselines at 24, 8 and the bottom edge (10)
+        for (height, font_size) in [(40.0, Some(30.0)), (20.0, Some(10.0)), (10.0, None)] {
+            let child_style = LayoutStyle {
+                width: Dimension::Points(50.0),
+                height: Dimension::Points(height),
+                font_size,
+                ..Default::default()
+            };
+            parent.add_child(LayoutNode::new(ComponentId::new(), child_style));
+        }
+
+        engine
+            .calculate_layout(&mut parent, Size::new(300.0, 100.0))
+            .unwrap();
+
+        let ys: Vec<_> = parent
+            .children
+            .iter()
+            .map(|child| child.layout.rect.y())
+            .collect();
+        assert_eq!(ys, vec![0.0, 16.0, 14.0]);
+
+        // Every baseline lands at the same offset
+        assert_eq!(ys[0] + 30.0 * 0.8, 24.0);
+        assert_eq!(ys[1] + 10.0 * 0.8, 24.0);
+        assert_eq!(ys[2] + 10.0, 24.0);
+    }
+
     #[test]
     fn test_gap_spacing() {
         let mut engine = LayoutEngine::new();
@@ -1741,6 +3352,184 @@ mod tests {
         assert!(parent.children[2].layout.rect.y() > parent.children[0].layout.rect.y());
     }
 
+    #[test]
+    fn test_aspect_ratio_width_driven() {
+        let mut engine = LayoutEngine::new();
+        let style = LayoutStyle {
+            width: Dimension::Points(200.0),
+            aspect_ratio: Some(16.0 / 9.0),
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+
+        assert_eq!(node.layout.rect.width(), 200.0);
+        assert_eq!(node.layout.rect.height(), 112.5);
+    }
+
+    #[test]
+    fn test_aspect_ratio_height_driven() {
+        let mut engine = LayoutEngine::new();
+        let style = LayoutStyle {
+            height: Dimension::Points(50.0),
+            aspect_ratio: Some(2.0),
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+
+        assert_eq!(node.layout.rect.width(), 100.0);
+        assert_eq!(node.layout.rect.height(), 50.0);
+
+        // Both dimensions explicit: the ratio is ignored
+        let style = LayoutStyle {
+            width: Dimension::Points(30.0),
+            height: Dimension::Points(50.0),
+            aspect_ratio: Some(2.0),
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+        assert_eq!(node.layout.rect.size, Size::new(30.0, 50.0));
+    }
+
+    #[test]
+    fn test_aspect_ratio_min_max_clamp() {
+        let mut engine = LayoutEngine::new();
+
+        // The clamped width drives the derived height
+        let style = LayoutStyle {
+            width: Dimension::Points(50.0),
+            min_width: Dimension::Points(100.0),
+            aspect_ratio: Some(2.0),
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+        assert_eq!(node.layout.rect.size, Size::new(100.0, 50.0));
+
+        // The derived height still respects its own max
+        let style = LayoutStyle {
+            width: Dimension::Points(300.0),
+            max_height: Dimension::Points(100.0),
+            aspect_ratio: Some(2.0),
+            ..Default::default()
+        };
+        let mut node = LayoutNode::new(ComponentId::new(), style);
+        engine
+            .calculate_layout(&mut node, Size::new(400.0, 400.0))
+            .unwrap();
+        assert_eq!(node.layout.rect.size, Size::new(300.0, 100.0));
+    }
+
+    #[test]
+    fn test_aspect_ratio_flex_item_cross_size() {
+        let mut engine = LayoutEngine::new();
+        let parent_style = LayoutStyle {
+            width: Dimension::Points(300.0),
+            height: Dimension::Points(200.0),
+            align_items: AlignItems::FlexStart,
+            ..Default::default()
+        };
+        let mut parent = LayoutNode::new(ComponentId::new(), parent_style);
+        parent.add_child(LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                width: Dimension::Points(120.0),
+                aspect_ratio: Some(4.0 / 3.0),
+                ..Default::default()
+            },
+        ));
+
+        engine
+            .calculate_layout(&mut parent, Size::new(300.0, 200.0))
+            .unwrap();
+
+        assert_eq!(parent.children[0].layout.rect.size, Size::new(120.0, 90.0));
+    }
+
+    #[test]
+    fn test_grid_fixed_and_fraction_tracks() {
+        let mut engine = LayoutEngine::new();
+        let parent_style = LayoutStyle {
+            display: Display::Grid,
+            width: Dimension::Points(400.0),
+            height: Dimension::Points(300.0),
+            grid_template_columns: vec![Dimension::Points(100.0), Dimension::Fraction(1.0)],
+            grid_template_rows: vec![Dimension::Fraction(1.0), Dimension::Fraction(2.0)],
+            gap: Gap::new(30.0, 20.0),
+            ..Default::default()
+        };
+        let mut parent = LayoutNode::new(ComponentId::new(), parent_style);
+
+        for row in 1..=2 {
+            for column in 1..=2 {
+                let child_style = LayoutStyle {
+                    width: Dimension::Percent(100.0),
+                    height: Dimension::Percent(100.0),
+                    grid_column: GridPlacement::line(column),
+                    grid_row: GridPlacement::line(row),
+                    ..Default::default()
+                };
+                parent.add_child(LayoutNode::new(ComponentId::new(), child_style));
+            }
+        }
+
+        engine
+            .calculate_layout(&mut parent, Size::new(400.0, 300.0))
+            .unwrap();
+
+        // Columns: 100 fixed, then 400 - 100 - 20 gap = 280 for the fr track
+        // Rows: 300 - 30 gap = 270 split 1:2 into 90 and 180
+        let rects: Vec<_> = parent.children.iter().map(|c| c.layout.rect).collect();
+        assert_eq!(rects[0], Rect::new(0.0, 0.0, 100.0, 90.0));
+        assert_eq!(rects[1], Rect::new(120.0, 0.0, 280.0, 90.0));
+        assert_eq!(rects[2], Rect::new(0.0, 120.0, 100.0, 180.0));
+        assert_eq!(rects[3], Rect::new(120.0, 120.0, 280.0, 180.0));
+    }
+
+    #[test]
+    fn test_grid_item_spanning_lines() {
+        let mut engine = LayoutEngine::new();
+        let parent_style = LayoutStyle {
+            display: Display::Grid,
+            width: Dimension::Points(300.0),
+            height: Dimension::Points(100.0),
+            grid_template_columns: vec![Dimension::Fraction(1.0); 3],
+            grid_template_rows: vec![Dimension::Points(50.0)],
+            ..Default::default()
+        };
+        let mut parent = LayoutNode::new(ComponentId::new(), parent_style);
+        parent.add_child(LayoutNode::new(
+            ComponentId::new(),
+            LayoutStyle {
+                width: Dimension::Percent(100.0),
+                height: Dimension::Percent(100.0),
+                grid_column: GridPlacement::between(2, 4),
+                ..Default::default()
+            },
+        ));
+
+        engine
+            .calculate_layout(&mut parent, Size::new(300.0, 100.0))
+            .unwrap();
+
+        assert_eq!(
+            parent.children[0].layout.rect,
+            Rect::new(100.0, 0.0, 200.0, 50.0)
+        );
+    }
+
     #[test]
     fn test_gap_structure() {
         let gap = Gap::new(10.0, 20.0);
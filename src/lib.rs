@@ -1,4 +1,5 @@
 // Core module of the Orbit UI Framework
+pub mod app;
 pub mod component;
 pub mod component_single;
 pub mod events;
@@ -8,6 +9,7 @@ pub mod platform;
 pub mod renderer;
 pub mod state;
 pub mod style;
+mod trace;
 
 pub mod kit; // Added for OrbitKit components
 
@@ -16,6 +18,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Re-export of common types for convenience
 pub mod prelude {
+    pub use crate::app::{App, FrameReport};
     pub use crate::component::{
         callback,
         props::{PropValidationError, PropValidator},
@@ -24,28 +27,30 @@ pub mod prelude {
     pub use crate::component_single::{Context, Node};
     pub use crate::events::{
         delegation::{DelegatedEvent, EventDelegate, PropagationPhase},
-        Event,
+        Event, Ticker,
     };
     pub use crate::layout::{
-        AlignContent, AlignItems, Dimension, EdgeValues, FlexDirection, FlexWrap, JustifyContent,
-        LayoutEngine, LayoutNode, LayoutResult, LayoutStats, LayoutStyle, Point, PositionType,
-        Rect, Size,
+        AlignContent, AlignItems, Dimension, Direction, EdgeValues, FlexDirection, FlexWrap,
+        JustifyContent, LayoutEngine, LayoutNode, LayoutResult, LayoutStats, LayoutStyle, Point,
+        PositionType, Rect, Size, TextMeasurer,
     };
     pub use crate::renderer::Renderer;
     pub use crate::state::{
         create_computed, create_effect, create_signal, Computed, Effect, Signal, State,
-        StateContainer,
+        StateContainer, StateId, Subscription,
     };
     #[cfg(feature = "desktop")]
     pub use winit::event::MouseButton;
 }
 
 /// Initialize the Orbit framework with default settings
-pub fn init() -> Result<(), Error> {
+///
+/// Returns the platform for the current target: winit on native targets
+/// and the browser on wasm.
+pub fn init() -> Result<Box<dyn platform::Platform>, Error> {
     // Initialize logging
     // Initialize default renderer
-    // Set up platform adapters
-    Ok(())
+    platform::native_platform()
 }
 
 /// Errors that can occur in the Orbit framework
@@ -2,6 +2,9 @@
 
 use std::collections::HashMap;
 
+use crate::component::{assign_slots, DEFAULT_SLOT};
+use crate::events::Key;
+
 /// Represents a parsed .orbit file
 #[derive(Debug, Clone)]
 pub struct OrbitAst {
@@ -16,18 +19,169 @@ pub enum TemplateNode {
     Element {
         tag: String,
         attributes: HashMap<String, AttributeValue>,
-        events: HashMap<String, String>,
+        /// Event bindings, keyed by the `@` attribute as written, e.g. `keydown.enter`
+        events: HashMap<String, EventBinding>,
         children: Vec<TemplateNode>,
+        /// Field bound two-way by `o-model="field"`
+        ///
+        /// Codegen binds the element's `value` to the field and adds an
+        /// `input` handler writing typed text back to it.
+        model: Option<String>,
     },
     Expression(String),
     Text(String),
+    /// `<!-- -->` comment, kept only when the parser is asked to preserve them
+    Comment(String),
+    /// List rendering produced by `o-for="item in items"` or `o-for="(item, i) in items"`
+    Loop {
+        item_binding: String,
+        index_binding: Option<String>,
+        iterable: String,
+        body: Box<TemplateNode>,
+    },
+    /// Outlet for children projected by the parent, `<slot>` or `<slot name="header">`
+    ///
+    /// Unnamed slots receive children without a `slot` attribute; `fallback`
+    /// renders when nothing is projected into the slot.
+    Slot {
+        name: Option<String>,
+        fallback: Vec<TemplateNode>,
+    },
+    /// Sibling nodes without a wrapping element, such as a template with
+    /// several top-level elements
+    Fragment(Vec<TemplateNode>),
+}
+
+impl TemplateNode {
+    /// Replace the slots in this template with children from the parent
+    ///
+    /// A child with `slot="name"` goes to the matching named slot, and the
+    /// `slot` attribute is dropped; other children go to the default slot.
+    /// Slots nobody projects into keep their fallback content. Children are
+    /// sorted into slots by [`assign_slots`], as for `SlottedProps`.
+    pub fn project_slots(self, children: Vec<TemplateNode>) -> TemplateNode {
+        let mut projected = assign_slots(children, |child| match child {
+            TemplateNode::Element { attributes, .. } => match attributes.remove("slot") {
+                Some(AttributeValue::Static(name)) => Some(name),
+                Some(dynamic) => {
+                    attributes.insert("slot".to_string(), dynamic);
+                    None
+                }
+                None => None,
+            },
+            _ => None,
+        });
+
+        single_node(self.fill_slots(&mut projected))
+    }
+
+    /// Expand slots in this node, taking their content from `projected`
+    fn fill_slots(self, projected: &mut HashMap<String, Vec<TemplateNode>>) -> Vec<TemplateNode> {
+        match self {
+            TemplateNode::Slot { name, fallback } => {
+                match projected.remove(name.as_deref().unwrap_or(DEFAULT_SLOT)) {
+                    Some(nodes) => nodes,
+                    None => fallback
+                        .into_iter()
+                        .flat_map(|node| node.fill_slots(projected))
+                        .collect(),
+                }
+            }
+            TemplateNode::Element {
+                tag,
+                attributes,
+                events,
+                children,
+                model,
+            } => vec![TemplateNode::Element {
+                tag,
+                attributes,
+                events,
+                children: children
+                    .into_iter()
+                    .flat_map(|node| node.fill_slots(projected))
+                    .collect(),
+                model,
+            }],
+            TemplateNode::Loop {
+                item_binding,
+                index_binding,
+                iterable,
+                body,
+            } => vec![TemplateNode::Loop {
+                item_binding,
+                index_binding,
+                iterable,
+                body: Box::new(single_node(body.fill_slots(projected))),
+            }],
+            TemplateNode::Fragment(nodes) => nodes
+                .into_iter()
+                .flat_map(|node| node.fill_slots(projected))
+                .collect(),
+            node => vec![node],
+        }
+    }
+}
+
+/// Turn slot expansion output back into one node
+///
+/// A slot standing in for a root or loop body can expand to any number of
+/// nodes; more or fewer than one are kept in a [`TemplateNode::Fragment`].
+pub(super) fn single_node(mut nodes: Vec<TemplateNode>) -> TemplateNode {
+    if nodes.len() == 1 {
+        return nodes.remove(0);
+    }
+    TemplateNode::Fragment(nodes)
+}
+
+/// An `@event="handler"` binding with its modifiers
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventBinding {
+    /// Event name without modifiers, e.g. `click`
+    pub event: String,
+    /// Handler expression
+    pub handler: String,
+    /// `.stop`: stop propagation after the handler runs
+    pub stop: bool,
+    /// `.prevent`: prevent the default action
+    pub prevent: bool,
+    /// `.once`: remove the handler after it first fires
+    pub once: bool,
+    /// Key filter such as `.enter` or `.esc`; other keys don't fire the handler
+    pub key: Option<Key>,
+}
+
+impl EventBinding {
+    /// A binding with no modifiers
+    pub fn new(event: impl Into<String>, handler: impl Into<String>) -> Self {
+        Self {
+            event: event.into(),
+            handler: handler.into(),
+            stop: false,
+            prevent: false,
+            once: false,
+            key: None,
+        }
+    }
+
+    /// Whether a key event for `key` passes the key filter
+    pub fn accepts_key(&self, key: &Key) -> bool {
+        self.key.as_ref().is_none_or(|filter| filter == key)
+    }
 }
 
 /// Represents an attribute value that can be either static or dynamic
 #[derive(Debug, Clone)]
 pub enum AttributeValue {
     Static(String),
-    Dynamic(String), // Expression inside {{ }}
+    Dynamic(String), // Expression inside {{ }}, or bound with :attr / o-bind:attr
+    /// Both a static value and a bound expression, as in
+    /// `class="card" :class="state"`, in either order; a class list merges
+    /// the two
+    Mixed {
+        value: String,
+        expr: String,
+    },
 }
 
 /// Represents the style section
@@ -0,0 +1,40 @@
+//! Error types for .orbit file parsing
+
+use std::error::Error;
+use std::fmt;
+
+/// An error detected while parsing an .orbit file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Description of the problem
+    pub message: String,
+    /// Line where the problem was detected (1-based)
+    pub line: usize,
+    /// Column where the problem was detected (1-based)
+    pub column: usize,
+}
+
+impl ParseError {
+    /// Create a new parse error at the given position
+    pub fn new(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+
+    /// Shift the error down by `lines`, used when a section was parsed on its own
+    pub(crate) fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
@@ -1,26 +1,47 @@
 //! Main parser module for .orbit files
 
 mod ast;
+mod error;
+mod script;
 mod template;
 mod tokenizer;
 
-pub use ast::{OrbitAst, TemplateNode};
+pub use ast::{EventBinding, OrbitAst, PropDefinition, ScriptNode, StateDefinition, TemplateNode};
+pub use error::ParseError;
 
 use std::fs;
 use std::path::Path;
 
+/// Options controlling how .orbit files are parsed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// Keep template comments as [`TemplateNode::Comment`] nodes
+    pub preserve_comments: bool,
+}
+
 /// Main parser for .orbit files
 #[derive(Default)]
 pub struct OrbitParser;
 
 impl OrbitParser {
     /// Parse an .orbit file into an AST
-    pub fn parse(content: &str) -> Result<OrbitAst, String> {
+    pub fn parse(content: &str) -> Result<OrbitAst, ParseError> {
+        Self::parse_with_options(content, ParseOptions::default())
+    }
+
+    /// Parse an .orbit file into an AST with the given options
+    pub fn parse_with_options(
+        content: &str,
+        options: ParseOptions,
+    ) -> Result<OrbitAst, ParseError> {
         // Split into sections first
         let sections = Self::split_sections(content)?;
 
-        // Parse each section
-        let template_node = template::TemplateParser::new(&sections.template).parse()?;
+        // Parse each section, mapping positions back onto the whole file
+        let template_node = template::TemplateParser::new(&sections.template)
+            .preserve_comments(options.preserve_comments)
+            .parse()
+            .map_err(|e| e.offset_lines(sections.template_offset))?;
 
         // TODO: Implement style parser
         let style_node = ast::StyleNode {
@@ -28,100 +49,143 @@ impl OrbitParser {
             scoped: false,
         };
 
-        // TODO: Implement script parser
-        let script_node = ast::ScriptNode {
-            imports: Vec::new(),
-            component_name: String::new(),
-            props: Vec::new(),
-            state: Vec::new(),
-            methods: Vec::new(),
-            lifecycle: Vec::new(),
-        };
+        let script_node = script::ScriptParser::new(&sections.script)
+            .parse()
+            .map_err(|e| e.offset_lines(sections.script_offset))?;
 
         Ok(OrbitAst::new(template_node, style_node, script_node))
     }
 
     /// Parse an .orbit file from a file path
+    ///
+    /// Errors are prefixed with the path, e.g. `foo.orbit:12:3: ...`
     pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<OrbitAst, String> {
-        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {e}"))?;
-        Self::parse(&content)
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("{}: Failed to read file: {e}", path.display()))?;
+        Self::parse(&content).map_err(|e| format!("{}:{e}", path.display()))
     }
 
     /// Split an .orbit file into its constituent sections
-    fn split_sections(content: &str) -> Result<Sections, String> {
-        let mut template = String::new();
-        let mut style = String::new();
-        let mut script = String::new();
+    fn split_sections(content: &str) -> Result<Sections, ParseError> {
+        let mut sections = Sections::default();
 
-        let mut current_section = None;
+        // The open section, along with the line and column of its opening tag
+        let mut current_section: Option<(Section, usize, usize)> = None;
         let mut current_content = String::new();
+        let mut line_count = 0;
 
-        for line in content.lines() {
-            match line.trim() {
-                "<template>" => {
-                    current_section = Some(Section::Template);
-                    continue;
-                }
-                "</template>" => {
-                    template = current_content.clone();
-                    current_content.clear();
-                    current_section = None;
-                    continue;
-                }
-                "<style>" => {
-                    current_section = Some(Section::Style);
-                    continue;
-                }
-                "</style>" => {
-                    style = current_content.clone();
-                    current_content.clear();
-                    current_section = None;
-                    continue;
-                }
-                "<script>" | "<code>" | "<code lang=\"rust\">" => {
-                    current_section = Some(Section::Script);
-                    continue;
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let column = line.len() - line.trim_start().len() + 1;
+            line_count = line_number;
+
+            let opened = match line.trim() {
+                "<template>" => Some(Section::Template),
+                "<style>" => Some(Section::Style),
+                "<script>" | "<code>" | "<code lang=\"rust\">" => Some(Section::Script),
+                _ => None,
+            };
+            if let Some(section) = opened {
+                if let Some((open, _, _)) = current_section {
+                    return Err(ParseError::new(
+                        format!("{} opened before {} was closed", section.tag(), open.tag()),
+                        line_number,
+                        column,
+                    ));
                 }
-                "</script>" | "</code>" => {
-                    script = current_content.clone();
-                    current_content.clear();
-                    current_section = None;
-                    continue;
+                current_section = Some((section, line_number, column));
+                continue;
+            }
+
+            let closed = match line.trim() {
+                "</template>" => Some(Section::Template),
+                "</style>" => Some(Section::Style),
+                "</script>" | "</code>" => Some(Section::Script),
+                _ => None,
+            };
+            if let Some(section) = closed {
+                match current_section {
+                    Some((open, open_line, _)) if open == section => {
+                        let body = std::mem::take(&mut current_content);
+                        match section {
+                            Section::Template => {
+                                sections.template = body;
+                                sections.template_offset = open_line;
+                            }
+                            Section::Style => sections.style = body,
+                            Section::Script => {
+                                sections.script = body;
+                                sections.script_offset = open_line;
+                            }
+                        }
+                        current_section = None;
+                    }
+                    _ => {
+                        return Err(ParseError::new(
+                            format!("Unexpected closing tag for {} section", section.tag()),
+                            line_number,
+                            column,
+                        ));
+                    }
                 }
-                _ => {}
+                continue;
             }
 
-            if let Some(_section) = current_section {
+            if current_section.is_some() {
                 current_content.push_str(line);
                 current_content.push('\n');
             }
         }
 
-        if template.is_empty() {
-            return Err("Missing <template> section".to_string());
+        if let Some((section, line, column)) = current_section {
+            return Err(ParseError::new(
+                format!("Unclosed {} section", section.tag()),
+                line,
+                column,
+            ));
         }
 
-        Ok(Sections {
-            template,
-            style,
-            script,
-        })
+        if sections.template.is_empty() {
+            return Err(ParseError::new(
+                "Missing <template> section",
+                line_count.max(1),
+                1,
+            ));
+        }
+
+        Ok(sections)
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Section {
     Template,
     Style,
     Script,
 }
 
-#[derive(Debug)]
+impl Section {
+    /// The opening tag used in error messages
+    fn tag(&self) -> &'static str {
+        match self {
+            Section::Template => "<template>",
+            Section::Style => "<style>",
+            Section::Script => "<script>",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
 #[allow(dead_code)]
 struct Sections {
     template: String,
     style: String,
     script: String,
+    /// Line of the `<template>` tag; template line N is file line N + offset
+    template_offset: usize,
+    /// Line of the `<script>` tag; script line N is file line N + offset
+    script_offset: usize,
 }
 
 #[cfg(test)]
@@ -173,6 +237,7 @@ impl Component for Greeting {
                 attributes,
                 events: _,
                 children,
+                ..
             } => {
                 assert_eq!(tag, "div");
                 assert!(attributes.contains_key("class"));
@@ -180,5 +245,55 @@ impl Component for Greeting {
             }
             _ => panic!("Expected element node"),
         }
+
+        // Verify script introspection
+        assert_eq!(ast.script.component_name, "Greeting");
+        assert_eq!(ast.script.state.len(), 2);
+        assert_eq!(ast.script.state[0].name, "name");
+        assert_eq!(ast.script.state[1].ty, "i32");
+    }
+
+    #[test]
+    fn test_missing_template_error_position() {
+        let content = "<style>\n.a { color: red; }\n</style>\n";
+        let err = OrbitParser::parse(content).unwrap_err();
+        assert_eq!(err.message, "Missing <template> section");
+        assert_eq!(err.line, 3);
+        assert_eq!(err.to_string(), "3:1: Missing <template> section");
+    }
+
+    #[test]
+    fn test_unclosed_section_error_position() {
+        let content = "<template>\n<div></div>\n</template>\n\n  <script>\npub struct A {}\n";
+        let err = OrbitParser::parse(content).unwrap_err();
+        assert_eq!(err.message, "Unclosed <script> section");
+        assert_eq!((err.line, err.column), (5, 3));
+    }
+
+    #[test]
+    fn test_template_error_maps_to_file_line() {
+        let content = r#"
+<template>
+  <div>
+    <span>Hello</p>
+  </div>
+</template>
+"#;
+        let err = OrbitParser::parse(content).unwrap_err();
+        assert_eq!(err.message, "Mismatched closing tag </p>: expected </span>");
+        assert_eq!((err.line, err.column), (4, 16));
+    }
+
+    #[test]
+    fn test_parse_file_prefixes_path() {
+        let path =
+            std::env::temp_dir().join(format!("orbit_parse_error_{}.orbit", std::process::id()));
+        fs::write(&path, "<template>\n<div>\n</template>\n").unwrap();
+
+        let err = OrbitParser::parse_file(&path).unwrap_err();
+        fs::remove_file(&path).ok();
+
+        assert!(err.starts_with(&format!("{}:", path.display())));
+        assert!(err.contains("2:1: Unclosed tag <div>"));
     }
 }
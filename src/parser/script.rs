@@ -0,0 +1,420 @@
+//! Parser for script sections of .orbit files
+//!
+//! This is not a full Rust parser. It scans the script source for `use`
+//! declarations, struct definitions and `impl Component for ...` blocks, which
+//! is enough for tooling to introspect a component without compiling it.
+
+use super::{
+    ast::{PropDefinition, ScriptNode, StateDefinition},
+    error::ParseError,
+};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// A struct definition found in the script section
+#[derive(Debug, Clone)]
+struct StructDefinition {
+    name: String,
+    fields: Vec<(String, String)>, // (name, type)
+}
+
+/// Parses script sections in .orbit files
+pub struct ScriptParser<'a> {
+    input: &'a str,
+}
+
+impl<'a> ScriptParser<'a> {
+    /// Create a new script parser
+    pub fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    /// Parse the script section into a script node
+    pub fn parse(&self) -> Result<ScriptNode, ParseError> {
+        let source = Self::strip_comments(self.input);
+
+        let mut script = ScriptNode {
+            imports: Self::parse_imports(&source),
+            component_name: String::new(),
+            props: Vec::new(),
+            state: Vec::new(),
+            methods: Vec::new(),
+            lifecycle: Vec::new(),
+        };
+
+        // A script section is optional, so there is nothing to introspect
+        if source.trim().is_empty() {
+            return Ok(script);
+        }
+
+        let structs = Self::parse_structs(&source)?;
+        let component = Self::find_component_struct(&source, &structs).ok_or_else(|| {
+            ParseError::new(
+                "No component struct found in script section (expected `pub struct Name { ... }`)",
+                1,
+                1,
+            )
+        })?;
+
+        script.component_name = component.name.clone();
+        script.state = component
+            .fields
+            .iter()
+            .map(|(name, ty)| StateDefinition {
+                name: name.clone(),
+                ty: ty.clone(),
+                initial: None,
+            })
+            .collect();
+
+        let props_name =
+            Self::find_props_type(&source).unwrap_or_else(|| format!("{}Props", component.name));
+        if let Some(props) = structs.iter().find(|s| s.name == props_name) {
+            script.props = props
+                .fields
+                .iter()
+                .map(|(name, ty)| PropDefinition {
+                    name: name.clone(),
+                    ty: ty.clone(),
+                    required: !ty.starts_with("Option<"),
+                    default: None,
+                })
+                .collect();
+        }
+
+        Ok(script)
+    }
+
+    /// Remove line and block comments so they don't confuse the scanner
+    fn strip_comments(input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut chars = input.chars().peekable();
+        let mut in_string = false;
+
+        while let Some(ch) = chars.next() {
+            if in_string {
+                output.push(ch);
+                if ch == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        output.push(escaped);
+                    }
+                } else if ch == '"' {
+                    in_string = false;
+                }
+                continue;
+            }
+
+            match (ch, chars.peek()) {
+                ('"', _) => {
+                    in_string = true;
+                    output.push(ch);
+                }
+                ('/', Some('/')) => {
+                    // Skip to the end of the line but keep the newline
+                    for next in chars.by_ref() {
+                        if next == '\n' {
+                            output.push('\n');
+                            break;
+                        }
+                    }
+                }
+                ('/', Some('*')) => {
+                    chars.next();
+                    let mut prev = '\0';
+                    for next in chars.by_ref() {
+                        if next == '\n' {
+                            output.push('\n');
+                        }
+                        if prev == '*' && next == '/' {
+                            break;
+                        }
+                        prev = next;
+                    }
+                }
+                _ => output.push(ch),
+            }
+        }
+
+        output
+    }
+
+    /// Collect the paths of all `use` declarations
+    fn parse_imports(source: &str) -> Vec<String> {
+        source
+            .lines()
+            .map(str::trim)
+            .filter_map(|line| {
+                line.strip_prefix("pub use ")
+                    .or_else(|| line.strip_prefix("use "))
+            })
+            .map(|path| path.trim_end_matches(';').trim().to_string())
+            .collect()
+    }
+
+    /// Find all braced struct definitions along with their fields
+    fn parse_structs(source: &str) -> Result<Vec<StructDefinition>, ParseError> {
+        static HEADER: OnceLock<Regex> = OnceLock::new();
+        let header = HEADER.get_or_init(|| {
+            Regex::new(r"(?:^|\s)(?:pub(?:\([^)]*\))?\s+)?struct\s+(\w+)(?:\s*<[^{;]*>)?\s*\{")
+                .expect("valid struct header regex")
+        });
+
+        let mut structs = Vec::new();
+        for captures in header.captures_iter(source) {
+            let name = captures[1].to_string();
+            let header_match = captures.get(1).expect("struct name group");
+            let body_start = captures.get(0).map(|m| m.end()).unwrap_or_default();
+            let body = Self::extract_block(&source[body_start..]).ok_or_else(|| {
+                let (line, column) = Self::position_of(source, header_match.start());
+                ParseError::new(format!("Unclosed body for struct `{name}`"), line, column)
+            })?;
+
+            let fields = Self::split_top_level(body)
+                .into_iter()
+                .filter_map(|field| Self::parse_field(&field))
+                .collect();
+
+            structs.push(StructDefinition { name, fields });
+        }
+
+        Ok(structs)
+    }
+
+    /// Convert a byte offset into a 1-based (line, column) position
+    fn position_of(source: &str, offset: usize) -> (usize, usize) {
+        let before = &source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        (line, column)
+    }
+
+    /// Return the contents of a block up to (not including) its matching `}`
+    fn extract_block(source: &str) -> Option<&str> {
+        let mut depth = 1;
+        for (index, ch) in source.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&source[..index]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Split a struct body on commas that are not nested inside generics or groups
+    fn split_top_level(body: &str) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut current = String::new();
+        let mut depth = 0i32;
+        let mut previous = None;
+
+        for ch in body.chars() {
+            match ch {
+                '<' | '(' | '[' | '{' => depth += 1,
+                // The `>` of a `->` return type closes nothing
+                '>' if previous == Some('-') => {}
+                '>' | ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(std::mem::take(&mut current));
+                    previous = Some(ch);
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(ch);
+            previous = Some(ch);
+        }
+        parts.push(current);
+
+        parts
+    }
+
+    /// Parse a single `name: Type` field, skipping attributes and visibility
+    fn parse_field(field: &str) -> Option<(String, String)> {
+        let mut field = field.trim();
+
+        // Strip attributes such as #[serde(default)]
+        while field.starts_with("#[") {
+            let end = field.find(']')?;
+            field = field[end + 1..].trim_start();
+        }
+
+        if let Some(rest) = field.strip_prefix("pub") {
+            field = rest.trim_start();
+            if field.starts_with('(') {
+                let end = field.find(')')?;
+                field = field[end + 1..].trim_start();
+            }
+        }
+
+        let (name, ty) = field.split_once(':')?;
+        let name = name.trim();
+        let ty = ty.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if name.is_empty() || ty.is_empty() {
+            return None;
+        }
+
+        Some((name.to_string(), ty))
+    }
+
+    /// Pick the struct that implements `Component`, falling back to the first
+    /// struct that does not look like a props type
+    fn find_component_struct<'s>(
+        source: &str,
+        structs: &'s [StructDefinition],
+    ) -> Option<&'s StructDefinition> {
+        static IMPL_COMPONENT: OnceLock<Regex> = OnceLock::new();
+        let impl_component = IMPL_COMPONENT.get_or_init(|| {
+            Regex::new(r"impl\s*(?:<[^>]*>\s*)?Component\s+for\s+(\w+)").expect("valid impl regex")
+        });
+
+        if let Some(name) = impl_component
+            .captures(source)
+            .map(|captures| captures[1].to_string())
+        {
+            if let Some(component) = structs.iter().find(|s| s.name == name) {
+                return Some(component);
+            }
+        }
+
+        structs.iter().find(|s| !s.name.ends_with("Props"))
+    }
+
+    /// Find the props type declared with `type Props = ...;`
+    fn find_props_type(source: &str) -> Option<String> {
+        static PROPS_TYPE: OnceLock<Regex> = OnceLock::new();
+        let props_type = PROPS_TYPE.get_or_init(|| {
+            Regex::new(r"type\s+Props\s*=\s*([\w:]+)\s*;").expect("valid props type regex")
+        });
+
+        props_type
+            .captures(source)
+            .map(|captures| captures[1].rsplit("::").next().unwrap_or("").to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_component_with_props() {
+        let input = r#"
+use orbit::prelude::*;
+
+pub struct Greeting {
+    name: String,
+    count: i32,
+    items: HashMap<String, Vec<u32>>,
+}
+
+pub struct GreetingProps {
+    pub name: String,
+    pub subtitle: Option<String>,
+}
+
+impl Props for GreetingProps {}
+
+impl Component for Greeting {
+    type Props = GreetingProps;
+}
+"#;
+        let script = ScriptParser::new(input).parse().unwrap();
+
+        assert_eq!(script.imports, vec!["orbit::prelude::*"]);
+        assert_eq!(script.component_name, "Greeting");
+
+        let state: Vec<_> = script
+            .state
+            .iter()
+            .map(|s| (s.name.as_str(), s.ty.as_str()))
+            .collect();
+        assert_eq!(
+            state,
+            vec![
+                ("name", "String"),
+                ("count", "i32"),
+                ("items", "HashMap<String, Vec<u32>>")
+            ]
+        );
+
+        assert_eq!(script.props.len(), 2);
+        assert_eq!(script.props[0].name, "name");
+        assert_eq!(script.props[0].ty, "String");
+        assert!(script.props[0].required);
+        assert_eq!(script.props[1].name, "subtitle");
+        assert!(!script.props[1].required);
+    }
+
+    #[test]
+    fn test_component_struct_found_by_impl() {
+        let input = r#"
+/// Props come first here
+pub struct CounterProps {
+    pub initial: i32, // starting value
+}
+
+pub struct Counter {
+    #[allow(dead_code)]
+    pub(crate) value: i32,
+}
+
+impl Component for Counter {
+    type Props = CounterProps;
+}
+"#;
+        let script = ScriptParser::new(input).parse().unwrap();
+
+        assert_eq!(script.component_name, "Counter");
+        assert_eq!(script.state.len(), 1);
+        assert_eq!(script.state[0].name, "value");
+        assert_eq!(script.props.len(), 1);
+        assert_eq!(script.props[0].name, "initial");
+    }
+
+    #[test]
+    fn test_fn_pointer_field_does_not_swallow_the_next_field() {
+        let input = r#"
+pub struct Stepper {
+    step: Box<dyn Fn(i32) -> i32>,
+    cb: fn(i32) -> i32,
+    next: u8,
+}
+"#;
+        let script = ScriptParser::new(input).parse().unwrap();
+
+        let state: Vec<_> = script
+            .state
+            .iter()
+            .map(|s| (s.name.as_str(), s.ty.as_str()))
+            .collect();
+        assert_eq!(
+            state,
+            vec![
+                ("step", "Box<dyn Fn(i32) -> i32>"),
+                ("cb", "fn(i32) -> i32"),
+                ("next", "u8")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_component_struct() {
+        let input = "use orbit::prelude::*;\n\nfn helper() {}\n";
+        let err = ScriptParser::new(input).parse().unwrap_err();
+        assert!(err.message.contains("No component struct found"));
+    }
+
+    #[test]
+    fn test_unclosed_struct_position() {
+        let input = "use orbit::prelude::*;\n\npub struct Broken {\n    value: i32,\n";
+        let err = ScriptParser::new(input).parse().unwrap_err();
+        assert_eq!((err.line, err.column), (3, 12));
+    }
+}
@@ -1,14 +1,18 @@
 //! Parser for template sections of .orbit files
 
 use super::{
-    ast::{AttributeValue, TemplateNode},
-    tokenizer::{Token, Tokenizer},
+    ast::{single_node, AttributeValue, EventBinding, TemplateNode},
+    error::ParseError,
+    tokenizer::{is_void_element, Token, Tokenizer},
 };
+use crate::events::Key;
 use std::collections::HashMap;
 
 /// Parses template sections in .orbit files
 pub struct TemplateParser<'a> {
     tokenizer: Tokenizer<'a>,
+    /// Keep `<!-- -->` comments as [`TemplateNode::Comment`] instead of dropping them
+    preserve_comments: bool,
 }
 
 impl<'a> TemplateParser<'a> {
@@ -16,51 +20,129 @@ impl<'a> TemplateParser<'a> {
     pub fn new(input: &'a str) -> Self {
         Self {
             tokenizer: Tokenizer::new(input),
+            preserve_comments: false,
         }
     }
 
+    /// Keep comments in the parsed tree, for tooling that needs them
+    pub fn preserve_comments(mut self, preserve: bool) -> Self {
+        self.preserve_comments = preserve;
+        self
+    }
+
     /// Parse the template section into an AST
-    pub fn parse(&mut self) -> Result<TemplateNode, String> {
+    ///
+    /// A template with several top-level elements parses into a
+    /// [`TemplateNode::Fragment`] of them. Comments around top-level
+    /// elements are kept as siblings of them when comments are preserved,
+    /// so a leading header comment also makes a fragment.
+    pub fn parse(&mut self) -> Result<TemplateNode, ParseError> {
+        let mut roots = Vec::new();
+        let mut has_element = false;
+        loop {
+            match self.next_token()? {
+                Token::Comment(text) => {
+                    if self.preserve_comments {
+                        roots.push(TemplateNode::Comment(text));
+                    }
+                }
+                Token::Text(text) if text.trim().is_empty() => continue,
+                Token::OpenTag(tag) => {
+                    roots.push(self.parse_element(tag)?);
+                    has_element = true;
+                }
+                Token::Eof if has_element => return Ok(single_node(roots)),
+                token => return Err(self.error(format!("Expected opening tag, got {token:?}"))),
+            }
+        }
+    }
+
+    /// Next token, with tokenizer errors turned into parse errors
+    fn next_token(&mut self) -> Result<Token, ParseError> {
         match self.tokenizer.next_token() {
-            Token::OpenTag(tag) => self.parse_element(tag),
-            token => Err(format!("Expected opening tag, got {token:?}")),
+            Token::Error(message) => Err(self.error(message)),
+            token => Ok(token),
         }
     }
 
+    /// Build an error pointing at the start of the most recent token
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        let (line, column) = self.tokenizer.token_start();
+        ParseError::new(message, line, column)
+    }
+
     /// Parse an element node
-    fn parse_element(&mut self, tag: String) -> Result<TemplateNode, String> {
+    fn parse_element(&mut self, tag: String) -> Result<TemplateNode, ParseError> {
         let mut attributes = HashMap::new();
         let mut events = HashMap::new();
         let mut children = Vec::new();
+        let mut loop_directive = None;
+        let mut model = None;
 
-        loop {
-            match self.tokenizer.next_token() {
-                Token::AttrName(name) => match self.tokenizer.next_token() {
-                    Token::Equal => match self.tokenizer.next_token() {
+        // Attributes up to the end of the opening tag
+        let self_closing = loop {
+            match self.next_token()? {
+                Token::AttrName(name) => match self.next_token()? {
+                    Token::Equal => match self.next_token()? {
                         Token::String(value) => {
                             // Check if this is an event handler (@click, @input, etc.)
-                            if name.starts_with('@') {
-                                let event_name = name.trim_start_matches('@');
-                                events.insert(event_name.to_string(), value);
+                            if let Some(spec) = name.strip_prefix('@') {
+                                let binding = self.parse_event_binding(spec, value)?;
+                                events.insert(spec.to_string(), binding);
+                            } else if name == "o-for" {
+                                loop_directive = Some(self.parse_loop_directive(&value)?);
+                            } else if name == "o-model" {
+                                model = Some(self.parse_model_directive(&tag, &value)?);
+                            } else if let Some(bound) = self.bound_attribute(&name)? {
+                                let expr = value.trim();
+                                if expr.is_empty() {
+                                    return Err(
+                                        self.error(format!("Empty expression bound to {name}"))
+                                    );
+                                }
+                                let value = AttributeValue::Dynamic(expr.to_string());
+                                insert_attribute(&mut attributes, bound, value);
                             } else {
-                                attributes.insert(name, AttributeValue::Static(value));
+                                insert_attribute(
+                                    &mut attributes,
+                                    name,
+                                    AttributeValue::Static(value),
+                                );
                             }
                         }
                         Token::ExprStart => {
                             let expr = self.parse_expression()?;
-                            attributes.insert(name, AttributeValue::Dynamic(expr));
+                            let name = self.bound_attribute(&name)?.unwrap_or(name);
+                            insert_attribute(&mut attributes, name, AttributeValue::Dynamic(expr));
+                        }
+                        token => {
+                            return Err(
+                                self.error(format!("Expected attribute value, got {token:?}"))
+                            )
                         }
-                        token => return Err(format!("Expected attribute value, got {token:?}")),
                     },
-                    token => return Err(format!("Expected =, got {token:?}")),
+                    token => return Err(self.error(format!("Expected =, got {token:?}"))),
                 },
+                Token::TagEnd => break false,
+                Token::SelfClosingTagEnd => break true,
+                Token::Eof => return Err(self.error(format!("Unclosed opening tag <{tag}>"))),
+                token => return Err(self.error(format!("Unexpected token in <{tag}>: {token:?}"))),
+            }
+        };
+
+        // Void elements never have children or a closing tag
+        while !self_closing && !is_void_element(&tag) {
+            match self.next_token()? {
                 Token::CloseTag(close_tag) => {
                     if close_tag != tag {
-                        return Err(format!("Mismatched tags: {tag} and {close_tag}"));
+                        // Tolerate explicit closers for void elements, e.g. <input></input>
+                        if is_void_element(&close_tag) {
+                            continue;
+                        }
+                        return Err(self.error(format!("Mismatched tags: {tag} and {close_tag}")));
                     }
                     break;
                 }
-                Token::SelfClosingTag(_) => break,
                 Token::Text(text) => {
                     // Only add non-whitespace text nodes
                     if !text.trim().is_empty() {
@@ -95,26 +177,164 @@ impl<'a> TemplateParser<'a> {
                 Token::OpenTag(child_tag) => {
                     children.push(self.parse_element(child_tag)?);
                 }
-                Token::Eof => return Err("Unexpected end of template".to_string()),
-                token => return Err(format!("Unexpected token: {token:?}")),
+                Token::Comment(text) => {
+                    if self.preserve_comments {
+                        children.push(TemplateNode::Comment(text));
+                    }
+                }
+                Token::Eof => return Err(self.error("Unexpected end of template")),
+                token => return Err(self.error(format!("Unexpected token: {token:?}"))),
             }
         }
 
-        Ok(TemplateNode::Element {
-            tag,
-            attributes,
-            events,
-            children,
+        let element = if tag == "slot" {
+            let name = match attributes.remove("name") {
+                Some(AttributeValue::Static(name)) => Some(name),
+                Some(AttributeValue::Dynamic(_) | AttributeValue::Mixed { .. }) => {
+                    return Err(self.error("Slot names must be static"))
+                }
+                None => None,
+            };
+            TemplateNode::Slot {
+                name,
+                fallback: children,
+            }
+        } else {
+            TemplateNode::Element {
+                tag,
+                attributes,
+                events,
+                children,
+                model,
+            }
+        };
+
+        // The element carrying o-for becomes the loop body
+        Ok(match loop_directive {
+            Some((item_binding, index_binding, iterable)) => TemplateNode::Loop {
+                item_binding,
+                index_binding,
+                iterable,
+                body: Box::new(element),
+            },
+            None => element,
         })
     }
+
+    /// Parse an `@event.modifier...` binding such as `@keydown.enter.prevent`
+    fn parse_event_binding(&self, spec: &str, handler: String) -> Result<EventBinding, ParseError> {
+        let mut parts = spec.split('.');
+        let event = parts.next().unwrap_or_default();
+        if event.is_empty() {
+            return Err(self.error(format!("Missing event name in @{spec}")));
+        }
+
+        let mut binding = EventBinding::new(event, handler);
+        for modifier in parts {
+            match modifier {
+                "stop" => binding.stop = true,
+                "prevent" => binding.prevent = true,
+                "once" => binding.once = true,
+                _ => {
+                    let key = key_modifier(modifier).ok_or_else(|| {
+                        self.error(format!("Unknown event modifier .{modifier} in @{spec}"))
+                    })?;
+                    if !KEY_EVENTS.contains(&event) {
+                        return Err(self.error(format!(
+                            "Key modifier .{modifier} only applies to key events, not {event}"
+                        )));
+                    }
+                    binding.key = Some(key);
+                }
+            }
+        }
+        Ok(binding)
+    }
+
+    /// The attribute a `:attr` or `o-bind:attr` name binds, if it is a binding
+    fn bound_attribute(&self, name: &str) -> Result<Option<String>, ParseError> {
+        let Some(attr) = name
+            .strip_prefix(':')
+            .or_else(|| name.strip_prefix("o-bind:"))
+        else {
+            return Ok(None);
+        };
+        if attr.is_empty() {
+            return Err(self.error(format!("Missing attribute name in {name:?}")));
+        }
+        Ok(Some(attr.to_string()))
+    }
+
+    /// Parse an `o-for` value of the form `item in items` or `(item, i) in items`
+    ///
+    /// Returns the item binding, optional index binding and iterable expression.
+    fn parse_loop_directive(
+        &self,
+        value: &str,
+    ) -> Result<(String, Option<String>, String), ParseError> {
+        let (bindings, iterable) = value
+            .split_once(" in ")
+            .ok_or_else(|| self.error(format!("Invalid o-for expression: {value:?}")))?;
+
+        let iterable = iterable.trim();
+        if iterable.is_empty() {
+            return Err(self.error(format!("Missing iterable in o-for: {value:?}")));
+        }
+
+        let bindings = bindings.trim();
+        let (item, index) = match bindings.strip_prefix('(').and_then(|b| b.strip_suffix(')')) {
+            Some(inner) => match inner.split_once(',') {
+                Some((item, index)) => (item.trim(), Some(index.trim())),
+                None => (inner.trim(), None),
+            },
+            None => (bindings, None),
+        };
+
+        let is_identifier = |name: &str| {
+            name.chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        };
+        if !is_identifier(item) || !index.is_none_or(is_identifier) {
+            return Err(self.error(format!("Invalid o-for binding: {bindings:?}")));
+        }
+
+        Ok((
+            item.to_string(),
+            index.map(str::to_string),
+            iterable.to_string(),
+        ))
+    }
+
+    /// Parse an `o-model` value, the field path bound to an input's value
+    fn parse_model_directive(&self, tag: &str, value: &str) -> Result<String, ParseError> {
+        if !MODEL_ELEMENTS.contains(&tag) {
+            return Err(self.error(format!("o-model is not supported on <{tag}>")));
+        }
+
+        let field = value.trim();
+        let is_identifier = |name: &str| {
+            name.chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        };
+        if !field.split('.').all(is_identifier) {
+            return Err(self.error(format!("Invalid o-model field: {value:?}")));
+        }
+
+        Ok(field.to_string())
+    }
+
     /// Parse an expression inside {{ }}
-    fn parse_expression(&mut self) -> Result<String, String> {
+    fn parse_expression(&mut self) -> Result<String, ParseError> {
         let mut expr = String::new();
         let mut prev_was_operator = false;
         let mut prev_was_identifier = false;
 
         loop {
-            match self.tokenizer.next_token() {
+            match self.next_token()? {
                 Token::ExprEnd => break,
                 Token::Identifier(ident) => {
                     if prev_was_identifier {
@@ -194,8 +414,10 @@ impl<'a> TemplateParser<'a> {
                     prev_was_identifier = false;
                     prev_was_operator = false;
                 }
-                Token::Eof => return Err("Unclosed expression".to_string()),
-                token => return Err(format!("Unexpected token in expression: {token:?}")),
+                Token::Eof => return Err(self.error("Unclosed expression")),
+                token => {
+                    return Err(self.error(format!("Unexpected token in expression: {token:?}")))
+                }
             }
         }
 
@@ -203,6 +425,50 @@ impl<'a> TemplateParser<'a> {
     }
 }
 
+/// Add an attribute, keeping both a static value and a bound expression
+///
+/// `class="card" :class="state"` gives the same [`AttributeValue::Mixed`]
+/// in either order. Repeating the same kind of value replaces it.
+fn insert_attribute(
+    attributes: &mut HashMap<String, AttributeValue>,
+    name: String,
+    value: AttributeValue,
+) {
+    let value = match (attributes.remove(&name), value) {
+        (Some(AttributeValue::Static(value)), AttributeValue::Dynamic(expr))
+        | (Some(AttributeValue::Dynamic(expr)), AttributeValue::Static(value))
+        | (Some(AttributeValue::Mixed { value, .. }), AttributeValue::Dynamic(expr))
+        | (Some(AttributeValue::Mixed { expr, .. }), AttributeValue::Static(value)) => {
+            AttributeValue::Mixed { value, expr }
+        }
+        (_, value) => value,
+    };
+    attributes.insert(name, value);
+}
+
+/// Events whose bindings accept key modifiers
+const KEY_EVENTS: &[&str] = &["keydown", "keyup", "keypress"];
+
+/// The key a modifier such as `.enter` filters for
+fn key_modifier(modifier: &str) -> Option<Key> {
+    Some(match modifier {
+        "enter" => Key::Enter,
+        "tab" => Key::Tab,
+        "space" => Key::Space,
+        "esc" => Key::Escape,
+        "delete" => Key::Delete,
+        "backspace" => Key::Backspace,
+        "up" => Key::ArrowUp,
+        "down" => Key::ArrowDown,
+        "left" => Key::ArrowLeft,
+        "right" => Key::ArrowRight,
+        _ => return None,
+    })
+}
+
+/// Elements whose value `o-model` can bind: form controls and the kit `Input`
+const MODEL_ELEMENTS: &[&str] = &["input", "textarea", "select", "Input"];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -219,6 +485,7 @@ mod tests {
                 attributes,
                 events,
                 children,
+                ..
             } => {
                 assert_eq!(tag, "div");
                 assert_eq!(attributes.len(), 1);
@@ -251,6 +518,7 @@ mod tests {
                 attributes,
                 events,
                 children,
+                ..
             } => {
                 assert_eq!(tag, "div");
                 assert_eq!(attributes.len(), 0);
@@ -278,13 +546,14 @@ mod tests {
                 attributes,
                 events,
                 children,
+                ..
             } => {
                 assert_eq!(tag, "button");
                 assert_eq!(attributes.len(), 0);
                 assert_eq!(events.len(), 1);
                 assert_eq!(children.len(), 1);
 
-                assert_eq!(events.get("click").unwrap(), "increment");
+                assert_eq!(events["click"].handler, "increment");
 
                 match &children[0] {
                     TemplateNode::Text(text) => assert_eq!(text, "+"),
@@ -294,4 +563,558 @@ mod tests {
             _ => panic!("Expected element node"),
         }
     }
+
+    #[test]
+    fn test_parse_self_closing_element() {
+        let input = r#"<img src="x"/>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        match node {
+            TemplateNode::Element {
+                tag,
+                attributes,
+                children,
+                ..
+            } => {
+                assert_eq!(tag, "img");
+                assert!(children.is_empty());
+                match attributes.get("src").unwrap() {
+                    AttributeValue::Static(value) => assert_eq!(value, "x"),
+                    _ => panic!("Expected static attribute"),
+                }
+            }
+            _ => panic!("Expected element node"),
+        }
+    }
+
+    #[test]
+    fn test_parse_void_element_without_close_tag() {
+        let input = r#"<p>First<br>Second<hr/></p>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        match node {
+            TemplateNode::Element { tag, children, .. } => {
+                assert_eq!(tag, "p");
+                assert_eq!(children.len(), 4);
+
+                match &children[1] {
+                    TemplateNode::Element { tag, children, .. } => {
+                        assert_eq!(tag, "br");
+                        assert!(children.is_empty());
+                    }
+                    _ => panic!("Expected br element"),
+                }
+                match &children[3] {
+                    TemplateNode::Element { tag, .. } => assert_eq!(tag, "hr"),
+                    _ => panic!("Expected hr element"),
+                }
+            }
+            _ => panic!("Expected element node"),
+        }
+    }
+
+    #[test]
+    fn test_parse_self_closing_component() {
+        let input = r#"<div><MyComp prop="v" @change="onChange" /><input></input></div>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        match node {
+            TemplateNode::Element { children, .. } => {
+                assert_eq!(children.len(), 2);
+
+                match &children[0] {
+                    TemplateNode::Element {
+                        tag,
+                        attributes,
+                        events,
+                        children,
+                        ..
+                    } => {
+                        assert_eq!(tag, "MyComp");
+                        assert!(children.is_empty());
+                        assert!(attributes.contains_key("prop"));
+                        assert_eq!(events["change"].handler, "onChange");
+                    }
+                    _ => panic!("Expected component element"),
+                }
+                match &children[1] {
+                    TemplateNode::Element { tag, .. } => assert_eq!(tag, "input"),
+                    _ => panic!("Expected input element"),
+                }
+            }
+            _ => panic!("Expected element node"),
+        }
+    }
+
+    #[test]
+    fn test_parse_loop_directive() {
+        let input = r#"<ul><li o-for="item in items" class="row">{{ item.name }}</li></ul>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        let TemplateNode::Element { children, .. } = node else {
+            panic!("Expected element node");
+        };
+        match &children[0] {
+            TemplateNode::Loop {
+                item_binding,
+                index_binding,
+                iterable,
+                body,
+            } => {
+                assert_eq!(item_binding, "item");
+                assert_eq!(index_binding, &None);
+                assert_eq!(iterable, "items");
+
+                match body.as_ref() {
+                    TemplateNode::Element {
+                        tag,
+                        attributes,
+                        children,
+                        ..
+                    } => {
+                        assert_eq!(tag, "li");
+                        assert!(!attributes.contains_key("o-for"));
+                        assert!(attributes.contains_key("class"));
+                        assert_eq!(children.len(), 1);
+                    }
+                    _ => panic!("Expected element loop body"),
+                }
+            }
+            _ => panic!("Expected loop node"),
+        }
+    }
+
+    #[test]
+    fn test_parse_loop_with_index_binding() {
+        let input = r#"<div o-for="(user, i) in state.users"><span>{{ i }}</span></div>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        match node {
+            TemplateNode::Loop {
+                item_binding,
+                index_binding,
+                iterable,
+                ..
+            } => {
+                assert_eq!(item_binding, "user");
+                assert_eq!(index_binding.as_deref(), Some("i"));
+                assert_eq!(iterable, "state.users");
+            }
+            _ => panic!("Expected loop node"),
+        }
+    }
+
+    #[test]
+    fn test_parse_nested_loops() {
+        let input = r#"<table><tr o-for="row in rows"><td o-for="cell in row.cells">{{ cell }}</td></tr></table>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        let TemplateNode::Element { children, .. } = node else {
+            panic!("Expected element node");
+        };
+        let TemplateNode::Loop {
+            item_binding, body, ..
+        } = &children[0]
+        else {
+            panic!("Expected outer loop");
+        };
+        assert_eq!(item_binding, "row");
+
+        let TemplateNode::Element { children, .. } = body.as_ref() else {
+            panic!("Expected outer loop body");
+        };
+        match &children[0] {
+            TemplateNode::Loop {
+                item_binding,
+                iterable,
+                ..
+            } => {
+                assert_eq!(item_binding, "cell");
+                assert_eq!(iterable, "row.cells");
+            }
+            _ => panic!("Expected inner loop"),
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_loop_directive() {
+        let input = r#"<li o-for="items">x</li>"#;
+        let err = TemplateParser::new(input).parse().unwrap_err();
+        assert!(err.message.contains("Invalid o-for expression"));
+    }
+
+    #[test]
+    fn test_parse_model_directive() {
+        let input = r#"<form><input type="text" o-model="form.name" /><Input o-model="email"></Input></form>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        let TemplateNode::Element { children, .. } = node else {
+            panic!("Expected element node");
+        };
+        match &children[0] {
+            TemplateNode::Element {
+                tag,
+                attributes,
+                model,
+                ..
+            } => {
+                assert_eq!(tag, "input");
+                assert_eq!(model.as_deref(), Some("form.name"));
+                assert!(!attributes.contains_key("o-model"));
+                assert!(attributes.contains_key("type"));
+            }
+            _ => panic!("Expected input element"),
+        }
+        match &children[1] {
+            TemplateNode::Element { tag, model, .. } => {
+                assert_eq!(tag, "Input");
+                assert_eq!(model.as_deref(), Some("email"));
+            }
+            _ => panic!("Expected Input component"),
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_model_directive() {
+        let err = TemplateParser::new(r#"<div o-model="name"></div>"#)
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("not supported on <div>"));
+
+        let err = TemplateParser::new(r#"<input o-model="a + b" />"#)
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("Invalid o-model field"));
+    }
+
+    /// Text of each node, with elements reduced to their tag
+    fn outline(nodes: &[TemplateNode]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|node| match node {
+                TemplateNode::Element { tag, .. } => format!("<{tag}>"),
+                TemplateNode::Text(text) => text.trim().to_string(),
+                other => format!("{other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_slots() {
+        let input = r#"<div><slot name="header"><h1>Title</h1></slot><slot /></div>"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        let TemplateNode::Element { children, .. } = node else {
+            panic!("Expected element node");
+        };
+        match &children[0] {
+            TemplateNode::Slot { name, fallback } => {
+                assert_eq!(name.as_deref(), Some("header"));
+                assert_eq!(outline(fallback), vec!["<h1>"]);
+            }
+            _ => panic!("Expected named slot"),
+        }
+        match &children[1] {
+            TemplateNode::Slot { name, fallback } => {
+                assert!(name.is_none());
+                assert!(fallback.is_empty());
+            }
+            _ => panic!("Expected default slot"),
+        }
+
+        let err = TemplateParser::new(r#"<slot name={{ which }}></slot>"#)
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("Slot names must be static"));
+    }
+
+    #[test]
+    fn test_project_children_into_slots() {
+        let card = TemplateParser::new(
+            r#"<div><header><slot name="header"><h1>Untitled</h1></slot></header><slot /><footer><slot name="footer">Fallback</slot></footer></div>"#,
+        )
+        .parse()
+        .unwrap();
+        let children = TemplateParser::new(
+            r#"<div><p>Body</p><h2 slot="header">Hello</h2><span>More</span></div>"#,
+        )
+        .parse()
+        .unwrap();
+        let TemplateNode::Element { children, .. } = children else {
+            panic!("Expected element node");
+        };
+
+        let TemplateNode::Element { children, .. } = card.project_slots(children) else {
+            panic!("Expected element node");
+        };
+        assert_eq!(
+            outline(&children),
+            vec!["<header>", "<p>", "<span>", "<footer>"]
+        );
+
+        // The named child lands in the header without its slot attribute
+        let TemplateNode::Element {
+            children: header, ..
+        } = &children[0]
+        else {
+            panic!("Expected header element");
+        };
+        match &header[0] {
+            TemplateNode::Element {
+                tag, attributes, ..
+            } => {
+                assert_eq!(tag, "h2");
+                assert!(!attributes.contains_key("slot"));
+            }
+            _ => panic!("Expected projected heading"),
+        }
+
+        // Nothing targeted the footer, so its fallback renders
+        let TemplateNode::Element {
+            children: footer, ..
+        } = &children[3]
+        else {
+            panic!("Expected footer element");
+        };
+        assert_eq!(outline(footer), vec!["Fallback"]);
+    }
+
+    #[test]
+    fn test_comments_are_stripped() {
+        let input = "<!-- header -->\n<div><!-- a <b>bold</b> note --><p>Hi</p></div>";
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        let TemplateNode::Element { children, .. } = node else {
+            panic!("Expected element node");
+        };
+        assert_eq!(outline(&children), vec!["<p>"]);
+    }
+
+    #[test]
+    fn test_comments_can_be_preserved() {
+        let input = "<div>\n  <!-- first line\n       second -> line -->\n  <p>Hi</p>\n</div>";
+        let node = TemplateParser::new(input)
+            .preserve_comments(true)
+            .parse()
+            .unwrap();
+
+        let TemplateNode::Element { children, .. } = node else {
+            panic!("Expected element node");
+        };
+        match &children[0] {
+            TemplateNode::Comment(text) => {
+                assert_eq!(text, " first line\n       second -> line ");
+            }
+            _ => panic!("Expected comment node"),
+        }
+        assert_eq!(outline(&children[1..]), vec!["<p>"]);
+
+        // A leading license comment sits beside the root element
+        let node = TemplateParser::new("<!-- SPDX-License-Identifier: MIT -->\n<div></div>")
+            .preserve_comments(true)
+            .parse()
+            .unwrap();
+        let TemplateNode::Fragment(roots) = node else {
+            panic!("Expected fragment node");
+        };
+        assert!(
+            matches!(&roots[0], TemplateNode::Comment(text) if text == " SPDX-License-Identifier: MIT ")
+        );
+        assert_eq!(outline(&roots[1..]), vec!["<div>"]);
+
+        // Comments alone are not a template
+        assert!(TemplateParser::new("<!-- only -->")
+            .preserve_comments(true)
+            .parse()
+            .is_err());
+
+        let err = TemplateParser::new("<div><!-- never closed</div>")
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("Unclosed comment"));
+    }
+
+    #[test]
+    fn test_parse_bound_attributes() {
+        let input =
+            r#"<img class="foo" :class="active" o-bind:src="user.avatar" :alt={{ name }} />"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        let TemplateNode::Element { attributes, .. } = node else {
+            panic!("Expected element node");
+        };
+        assert_eq!(attributes.len(), 3);
+        assert!(matches!(
+            &attributes["class"],
+            AttributeValue::Mixed { value, expr } if value == "foo" && expr == "active"
+        ));
+        assert!(matches!(
+            &attributes["src"],
+            AttributeValue::Dynamic(expr) if expr == "user.avatar"
+        ));
+        assert!(matches!(
+            &attributes["alt"],
+            AttributeValue::Dynamic(expr) if expr == "name"
+        ));
+
+        let node = TemplateParser::new(r#"<div class="foo"></div>"#)
+            .parse()
+            .unwrap();
+        let TemplateNode::Element { attributes, .. } = node else {
+            panic!("Expected element node");
+        };
+        assert!(matches!(
+            &attributes["class"],
+            AttributeValue::Static(value) if value == "foo"
+        ));
+    }
+
+    #[test]
+    fn test_static_and_bound_class_are_kept_in_either_order() {
+        for input in [
+            r#"<div class="card" :class="state"></div>"#,
+            r#"<div :class="state" class="card"></div>"#,
+            r#"<div class="card" class={{ state }}></div>"#,
+        ] {
+            let node = TemplateParser::new(input).parse().unwrap();
+            let TemplateNode::Element { attributes, .. } = node else {
+                panic!("Expected element node");
+            };
+            assert!(
+                matches!(
+                    &attributes["class"],
+                    AttributeValue::Mixed { value, expr } if value == "card" && expr == "state"
+                ),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_bound_attribute() {
+        let err = TemplateParser::new(r#"<div :="x"></div>"#)
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("Missing attribute name"));
+
+        let err = TemplateParser::new(r#"<div :class=" "></div>"#)
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("Empty expression bound to :class"));
+    }
+
+    #[test]
+    fn test_parse_event_modifiers() {
+        let input = r#"<input @click.stop.prevent="select" @keydown.enter="submit" @keydown.esc.once="cancel" />"#;
+        let node = TemplateParser::new(input).parse().unwrap();
+
+        let TemplateNode::Element { events, .. } = node else {
+            panic!("Expected element node");
+        };
+        let click = &events["click.stop.prevent"];
+        assert_eq!(click.event, "click");
+        assert_eq!(click.handler, "select");
+        assert!(click.stop && click.prevent && !click.once);
+        assert!(click.key.is_none());
+
+        let submit = &events["keydown.enter"];
+        assert_eq!(submit.event, "keydown");
+        assert_eq!(submit.key, Some(Key::Enter));
+        assert!(submit.accepts_key(&Key::Enter));
+        assert!(!submit.accepts_key(&Key::Escape));
+
+        let cancel = &events["keydown.esc.once"];
+        assert_eq!(cancel.key, Some(Key::Escape));
+        assert!(cancel.once && !cancel.stop);
+    }
+
+    #[test]
+    fn test_parse_invalid_event_modifiers() {
+        let err = TemplateParser::new(r#"<div @click.later="x"></div>"#)
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("Unknown event modifier .later"));
+
+        let err = TemplateParser::new(r#"<div @click.enter="x"></div>"#)
+            .parse()
+            .unwrap_err();
+        assert!(err.message.contains("only applies to key events"));
+    }
+
+    #[test]
+    fn test_unterminated_tag_reports_the_opener() {
+        let err = TemplateParser::new("<div>\n  <section>\n    <p>Text</p>\n</div>")
+            .parse()
+            .unwrap_err();
+        assert_eq!(
+            err.message,
+            "Mismatched closing tag </div>: expected </section>"
+        );
+        assert_eq!((err.line, err.column), (4, 1));
+
+        let err = TemplateParser::new("<div>\n  <span>Text\n")
+            .parse()
+            .unwrap_err();
+        assert_eq!(err.message, "Unclosed tag <span>");
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = TemplateParser::new("<div>\n  <img src=\"a.png\"")
+            .parse()
+            .unwrap_err();
+        assert_eq!(err.message, "Unterminated tag <img>: missing >");
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_mismatched_closer_names_both_tags() {
+        let err = TemplateParser::new("<ul>\n  <li>One</ul>\n</li>")
+            .parse()
+            .unwrap_err();
+        assert_eq!(err.message, "Mismatched closing tag </ul>: expected </li>");
+        assert_eq!((err.line, err.column), (2, 10));
+
+        // Void elements need no closer, and a redundant one is tolerated
+        let template = TemplateParser::new("<div><br><input></input><hr/></div>")
+            .parse()
+            .unwrap();
+        let TemplateNode::Element { children, .. } = template else {
+            panic!("expected an element");
+        };
+        assert_eq!(children.len(), 3);
+    }
+
+    #[test]
+    fn test_stray_closer_without_opener() {
+        let err = TemplateParser::new("<div>\n  <p>Text</p>\n</div>\n</div>")
+            .parse()
+            .unwrap_err();
+        assert_eq!(
+            err.message,
+            "Closing tag </div> has no matching opening tag"
+        );
+        assert_eq!((err.line, err.column), (4, 1));
+
+        let err = TemplateParser::new("</div>").parse().unwrap_err();
+        assert_eq!(
+            err.message,
+            "Closing tag </div> has no matching opening tag"
+        );
+    }
+
+    #[test]
+    fn test_sibling_roots_parse_into_a_fragment() {
+        let input = "<div class=\"header\">Top</div>\n\n  <!-- between -->\n<div>Bottom</div>\n";
+        let TemplateNode::Fragment(roots) = TemplateParser::new(input).parse().unwrap() else {
+            panic!("Expected fragment node");
+        };
+        assert_eq!(outline(&roots), vec!["<div>", "<div>"]);
+        let TemplateNode::Element { children, .. } = &roots[1] else {
+            panic!("Expected element node");
+        };
+        assert_eq!(outline(children), vec!["Bottom"]);
+
+        // A single root stays a plain element
+        let node = TemplateParser::new("  <div></div>  ").parse().unwrap();
+        assert!(matches!(node, TemplateNode::Element { .. }));
+    }
 }
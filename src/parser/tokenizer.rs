@@ -1,8 +1,23 @@
 //! Tokenizer for .orbit files
+//!
+//! Besides splitting the input into tokens, the tokenizer keeps the stack of
+//! open elements, so unterminated tags, mismatched closing tags and closing
+//! tags without an opener come back as [`Token::Error`] at the offending tag.
 
 use std::iter::Peekable;
 use std::str::Chars;
 
+/// HTML elements that never have children and may omit their closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Check whether a tag is an HTML void element
+pub(super) fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag.to_ascii_lowercase().as_str())
+}
+
 /// Token types that can appear in a template
 #[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
@@ -10,10 +25,12 @@ pub enum Token {
     // Template tokens
     OpenTag(String),
     CloseTag(String),
-    SelfClosingTag(String),
+    TagEnd,            // > ending an opening tag
+    SelfClosingTagEnd, // /> ending an opening tag
     AttrName(String),
     AttrValue(String),
     Text(String),
+    Comment(String), // <!-- ... -->
 
     // Expression tokens
     ExprStart, // {{
@@ -53,6 +70,11 @@ pub struct Tokenizer<'a> {
     input: Peekable<Chars<'a>>,
     line: usize,
     column: usize,
+    token_start: (usize, usize),
+    /// Whether we are between an opening tag name and its closing `>` or `/>`
+    in_tag: bool,
+    /// Elements opened and not yet closed, with where their `<` is
+    open_tags: Vec<(String, (usize, usize))>,
 }
 
 impl<'a> Tokenizer<'a> {
@@ -61,16 +83,50 @@ impl<'a> Tokenizer<'a> {
         Self {
             input: input.chars().peekable(),
             line: 1,
-            column: 0,
+            column: 1,
+            token_start: (1, 1),
+            in_tag: false,
+            open_tags: Vec::new(),
         }
     }
 
+    /// Get the current (line, column) position, both 1-based
+    pub fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    /// Get the (line, column) where the most recently returned token started
+    pub fn token_start(&self) -> (usize, usize) {
+        self.token_start
+    }
+
     /// Get the next token from the input
     pub fn next_token(&mut self) -> Token {
         self.skip_whitespace();
+        self.token_start = self.position();
 
         match self.peek() {
-            None => Token::Eof,
+            None => self.end_of_input(),
+            Some('>') if self.in_tag => {
+                self.advance();
+                self.in_tag = false;
+                // Void elements are complete once their opening tag ends
+                if self
+                    .open_tags
+                    .last()
+                    .is_some_and(|(tag, _)| is_void_element(tag))
+                {
+                    self.open_tags.pop();
+                }
+                Token::TagEnd
+            }
+            Some('/') if self.in_tag && self.peek_next() == Some('>') => {
+                self.advance(); // Skip /
+                self.advance(); // Skip >
+                self.in_tag = false;
+                self.open_tags.pop();
+                Token::SelfClosingTagEnd
+            }
             Some(ch) => match ch {
                 '<' => self.read_tag(),
                 '{' => {
@@ -100,7 +156,22 @@ impl<'a> Tokenizer<'a> {
                     name.push('@'); // Keep the @ prefix in the attribute name
 
                     while let Some(ch) = self.peek() {
-                        if ch.is_alphanumeric() || ch == '-' {
+                        // `.` separates modifiers, as in `@click.stop`
+                        if ch.is_alphanumeric() || ch == '-' || ch == '.' {
+                            name.push(ch);
+                            self.advance();
+                        } else {
+                            break;
+                        }
+                    }
+                    Token::AttrName(name)
+                }
+                ':' if self.in_tag => {
+                    self.advance(); // Skip :
+                    let mut name = String::from(":"); // Keep the : prefix marking a binding
+
+                    while let Some(ch) = self.peek() {
+                        if ch.is_alphanumeric() || ch == '-' || ch == '_' {
                             name.push(ch);
                             self.advance();
                         } else {
@@ -150,10 +221,13 @@ impl<'a> Tokenizer<'a> {
                 ch if ch.is_alphabetic() || ch == '_' => {
                     // Check if we're parsing an attribute name
                     let saved_pos = self.input.clone();
+                    let saved_position = self.position();
                     let mut ident = String::new();
 
                     while let Some(ch) = self.peek() {
-                        if ch.is_alphanumeric() || ch == '_' || ch == '-' {
+                        // Attribute names may be namespaced, as in `o-bind:src`
+                        let namespaced = ch == ':' && self.in_tag;
+                        if ch.is_alphanumeric() || ch == '_' || ch == '-' || namespaced {
                             ident.push(ch);
                             self.advance();
                         } else {
@@ -170,6 +244,7 @@ impl<'a> Tokenizer<'a> {
                     } else {
                         // Otherwise, reset position and read as normal identifier
                         self.input = saved_pos;
+                        (self.line, self.column) = saved_position;
                         self.read_identifier()
                     }
                 }
@@ -178,49 +253,78 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
-    /// Read a complete tag (opening, closing, or self-closing)
+    /// Read a tag name (opening or closing)
+    ///
+    /// Opening tags only consume the name; attributes follow as separate tokens
+    /// and the tag is terminated by [`Token::TagEnd`] or [`Token::SelfClosingTagEnd`].
     fn read_tag(&mut self) -> Token {
         self.advance(); // Skip <
         let mut name = String::new();
 
+        if self.starts_with("!--") {
+            return self.read_comment();
+        }
+
         if self.peek() == Some('/') {
             self.advance(); // Skip /
             while let Some(ch) = self.peek() {
                 if ch == '>' {
                     self.advance();
-                    return Token::CloseTag(name);
+                    return self.close_tag(name.trim().to_string());
                 }
                 name.push(ch);
                 self.advance();
             }
+            return Token::Error(format!("Unterminated closing tag </{}>", name.trim()));
         }
 
-        // Read the tag name only (stop at whitespace or >)
+        // Read the tag name only (stop at whitespace, / or >)
         while let Some(ch) = self.peek() {
-            match ch {
-                '>' => {
-                    self.advance();
-                    return Token::OpenTag(name);
-                }
-                '/' => {
-                    self.advance();
-                    if self.peek() == Some('>') {
-                        self.advance();
-                        return Token::SelfClosingTag(name);
-                    }
-                }
-                ch if ch.is_whitespace() => {
-                    // Stop at whitespace, the attribute parsing will continue from here
-                    return Token::OpenTag(name);
-                }
-                _ => {
-                    name.push(ch);
-                    self.advance();
-                }
+            if ch == '>' || ch == '/' || ch.is_whitespace() {
+                self.in_tag = true;
+                self.open_tags.push((name.clone(), self.token_start));
+                return Token::OpenTag(name);
             }
+            name.push(ch);
+            self.advance();
         }
 
-        Token::Error("Unclosed tag".to_string())
+        Token::Error(format!("Unterminated tag <{name}>: missing >"))
+    }
+
+    /// Match a closing tag against the innermost open element
+    ///
+    /// Closing tags for void elements, as in `<input></input>`, are let
+    /// through without closing anything.
+    fn close_tag(&mut self, name: String) -> Token {
+        if is_void_element(&name) {
+            return Token::CloseTag(name);
+        }
+        match self.open_tags.last() {
+            Some((open, _)) if *open == name => {
+                self.open_tags.pop();
+                Token::CloseTag(name)
+            }
+            Some((open, _)) => Token::Error(format!(
+                "Mismatched closing tag </{name}>: expected </{open}>"
+            )),
+            None => Token::Error(format!("Closing tag </{name}> has no matching opening tag")),
+        }
+    }
+
+    /// The token at the end of the input, reporting elements left open
+    ///
+    /// Errors point at the opening tag of the innermost unclosed element.
+    fn end_of_input(&mut self) -> Token {
+        let Some((tag, start)) = self.open_tags.last() else {
+            return Token::Eof;
+        };
+        self.token_start = *start;
+        if self.in_tag {
+            Token::Error(format!("Unterminated tag <{tag}>: missing >"))
+        } else {
+            Token::Error(format!("Unclosed tag <{tag}>"))
+        }
     }
 
     // Event handlers are now handled directly in the next_token method
@@ -230,6 +334,28 @@ impl<'a> Tokenizer<'a> {
         Token::Error("EventHandler is deprecated".to_string())
     }
 
+    /// Read a comment body after its `<`, up to and including `-->`
+    ///
+    /// The body may span lines and contain `<` and `>`.
+    fn read_comment(&mut self) -> Token {
+        for _ in 0..3 {
+            self.advance(); // Skip !--
+        }
+
+        let mut body = String::new();
+        while self.peek().is_some() {
+            if self.starts_with("-->") {
+                for _ in 0..3 {
+                    self.advance();
+                }
+                return Token::Comment(body);
+            }
+            body.extend(self.advance());
+        }
+
+        Token::Error("Unclosed comment".to_string())
+    }
+
     /// Read a string literal
     fn read_string(&mut self) -> Token {
         let quote = self.advance().unwrap();
@@ -318,12 +444,6 @@ impl<'a> Tokenizer<'a> {
     fn skip_whitespace(&mut self) {
         while let Some(ch) = self.peek() {
             if ch.is_whitespace() {
-                if ch == '\n' {
-                    self.line += 1;
-                    self.column = 0;
-                } else {
-                    self.column += 1;
-                }
                 self.advance();
             } else {
                 break;
@@ -343,11 +463,22 @@ impl<'a> Tokenizer<'a> {
         iter.next() // Get next
     }
 
+    /// Check whether the remaining input starts with `prefix`
+    fn starts_with(&self, prefix: &str) -> bool {
+        let mut iter = self.input.clone();
+        prefix.chars().all(|expected| iter.next() == Some(expected))
+    }
+
     /// Advance to the next character
     fn advance(&mut self) -> Option<char> {
         let ch = self.input.next();
-        if let Some(_ch) = ch {
-            self.column += 1;
+        match ch {
+            Some('\n') => {
+                self.line += 1;
+                self.column = 1;
+            }
+            Some(_) => self.column += 1,
+            None => {}
         }
         ch
     }
@@ -1,5 +1,14 @@
 // Platform adapters for the Orbit UI framework
 
+use crate::events::{KeyEvent, PointerInput};
+
+pub mod clipboard;
+
+#[cfg(feature = "desktop")]
+pub use desktop::WinitPlatform;
+#[cfg(feature = "web")]
+pub use web::WebPlatform;
+
 /// Trait for platform adapters
 pub trait PlatformAdapter {
     /// Initialize the platform adapter
@@ -12,12 +21,145 @@ pub trait PlatformAdapter {
     fn shutdown(&mut self) -> Result<(), crate::Error>;
 }
 
+/// Settings for a new window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowConfig {
+    /// Window title; on the web this becomes the document title
+    pub title: String,
+    /// Inner width in logical pixels
+    pub width: u32,
+    /// Inner height in logical pixels
+    pub height: u32,
+    /// Whether the user can resize the window
+    pub resizable: bool,
+}
+
+impl WindowConfig {
+    /// Set the window title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the inner size in logical pixels
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set whether the user can resize the window
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "Orbit UI Application".to_string(),
+            width: 800,
+            height: 600,
+            resizable: true,
+        }
+    }
+}
+
+/// Identifies a window created by a [`Platform`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowHandle(u64);
+
+/// Input and window events delivered to the event loop handler
+///
+/// Sizes and positions are in logical pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlatformEvent {
+    /// The window's inner size changed
+    Resized {
+        window: WindowHandle,
+        width: u32,
+        height: u32,
+    },
+    /// The window should draw a frame
+    RedrawRequested(WindowHandle),
+    /// The user asked to close the window
+    CloseRequested(WindowHandle),
+    /// Pointer input over the window
+    Pointer(WindowHandle, PointerInput),
+    /// Key input while the window has focus
+    Key(WindowHandle, KeyEvent),
+}
+
+/// What the event loop should do after the handler returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopControl {
+    /// Wait for the next event
+    Continue,
+    /// Schedule a redraw of a window
+    Redraw(WindowHandle),
+    /// Stop the event loop
+    Exit,
+}
+
+/// Callback receiving every platform event
+pub type EventHandler = Box<dyn FnMut(PlatformEvent) -> LoopControl>;
+
+/// Windowing and event loop integration for one target
+///
+/// Apps get the platform for their target from [`crate::init`], create
+/// their windows, then hand control to [`Platform::run_event_loop`].
+pub trait Platform {
+    /// Create a window, or on the web a canvas
+    fn create_window(&mut self, config: WindowConfig) -> Result<WindowHandle, crate::Error>;
+
+    /// Deliver events to `handler` until it returns [`LoopControl::Exit`]
+    ///
+    /// On native targets this blocks; on the web it returns once the handler
+    /// is installed and the browser drives it from then on.
+    fn run_event_loop(&mut self, handler: EventHandler) -> Result<(), crate::Error>;
+
+    /// Ask for a [`PlatformEvent::RedrawRequested`] for a window
+    ///
+    /// From inside the handler, return [`LoopControl::Redraw`] instead.
+    fn request_redraw(&self, window: WindowHandle);
+}
+
+/// The platform for the current target
+pub fn native_platform() -> Result<Box<dyn Platform>, crate::Error> {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        Ok(Box::new(web::WebPlatform::new()))
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "desktop"))]
+    {
+        Ok(Box::new(desktop::WinitPlatform::new()))
+    }
+
+    #[cfg(not(any(
+        all(target_arch = "wasm32", feature = "web"),
+        all(not(target_arch = "wasm32"), feature = "desktop")
+    )))]
+    {
+        Err(crate::Error::Platform(
+            "No platform for this target; enable the `desktop` or `web` feature".into(),
+        ))
+    }
+}
+
 /// WebAssembly platform adapter
 #[cfg(feature = "web")]
 pub mod web {
-    use super::PlatformAdapter;
-    // Remove unused import
-    // use wasm_bindgen::prelude::*;
+    use super::{
+        EventHandler, LoopControl, Platform, PlatformAdapter, PlatformEvent, WindowConfig,
+        WindowHandle,
+    };
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
 
     /// WebAssembly platform adapter
     pub struct WebAdapter {
@@ -53,13 +195,112 @@ pub mod web {
             Ok(())
         }
     }
+
+    /// Handler shared with pending animation frame callbacks
+    type SharedHandler = Rc<RefCell<Option<EventHandler>>>;
+
+    /// Browser platform drawing into `<canvas>` elements
+    ///
+    /// Redraws are delivered on animation frames. Pointer and key input is
+    /// not forwarded yet.
+    pub struct WebPlatform {
+        canvases: HashMap<WindowHandle, web_sys::Element>,
+        handler: SharedHandler,
+        next_id: u64,
+    }
+
+    impl WebPlatform {
+        /// Create a platform with no canvases
+        pub fn new() -> Self {
+            Self {
+                canvases: HashMap::new(),
+                handler: Rc::new(RefCell::new(None)),
+                next_id: 0,
+            }
+        }
+    }
+
+    impl Default for WebPlatform {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Platform for WebPlatform {
+        fn create_window(&mut self, config: WindowConfig) -> Result<WindowHandle, crate::Error> {
+            let platform_error = |e: wasm_bindgen::JsValue| {
+                crate::Error::Platform(format!("Failed to create canvas: {e:?}"))
+            };
+            let document = web_sys::window()
+                .and_then(|window| window.document())
+                .ok_or_else(|| crate::Error::Platform("No document to add a canvas to".into()))?;
+            let body = document
+                .body()
+                .ok_or_else(|| crate::Error::Platform("Document has no body".into()))?;
+
+            let canvas = document.create_element("canvas").map_err(platform_error)?;
+            canvas
+                .set_attribute("width", &config.width.to_string())
+                .map_err(platform_error)?;
+            canvas
+                .set_attribute("height", &config.height.to_string())
+                .map_err(platform_error)?;
+            body.append_child(&canvas).map_err(platform_error)?;
+            document.set_title(&config.title);
+
+            let handle = WindowHandle(self.next_id);
+            self.next_id += 1;
+            self.canvases.insert(handle, canvas);
+            Ok(handle)
+        }
+
+        fn run_event_loop(&mut self, handler: EventHandler) -> Result<(), crate::Error> {
+            *self.handler.borrow_mut() = Some(handler);
+            for &window in self.canvases.keys() {
+                schedule_redraw(&self.handler, window);
+            }
+            Ok(())
+        }
+
+        fn request_redraw(&self, window: WindowHandle) {
+            if self.canvases.contains_key(&window) {
+                schedule_redraw(&self.handler, window);
+            }
+        }
+    }
+
+    /// Deliver a redraw on the next animation frame
+    fn schedule_redraw(handler: &SharedHandler, window: WindowHandle) {
+        let Some(browser) = web_sys::window() else {
+            return;
+        };
+        let handler = handler.clone();
+        let callback = Closure::once_into_js(move || {
+            let control = match handler.borrow_mut().as_mut() {
+                Some(handler) => handler(PlatformEvent::RedrawRequested(window)),
+                None => return,
+            };
+            match control {
+                LoopControl::Continue => {}
+                LoopControl::Redraw(window) => schedule_redraw(&handler, window),
+                LoopControl::Exit => *handler.borrow_mut() = None,
+            }
+        });
+        let _ = browser.request_animation_frame(callback.unchecked_ref());
+    }
 }
 
 /// Desktop platform adapter
 #[cfg(feature = "desktop")]
 pub mod desktop {
-    use super::PlatformAdapter;
+    use super::{
+        EventHandler, LoopControl, Platform, PlatformAdapter, PlatformEvent, WindowConfig,
+        WindowHandle,
+    };
+    use crate::events::{KeyEvent, PointerInput};
+    use crate::layout::Point;
     use crate::renderer::{Renderer, RendererType};
+    use std::collections::HashMap;
 
     // Add Rc and RefCell for shared mutable state
     use std::cell::RefCell;
@@ -72,9 +313,10 @@ pub mod desktop {
     use glutin::surface::WindowSurface;
     use glutin_winit::GlWindow;
     use winit::dpi::LogicalSize;
-    use winit::event::{Event, WindowEvent};
+    use winit::event::{ElementState, Event, MouseButton, WindowEvent};
     use winit::event_loop::{ControlFlow, EventLoop};
-    use winit::window::WindowBuilder;
+    use winit::keyboard::ModifiersState;
+    use winit::window::{Window, WindowBuilder, WindowId};
 
     /// Desktop platform adapter
     pub struct DesktopAdapter {
@@ -94,7 +336,9 @@ pub mod desktop {
                 Ok(r) => r,
                 Err(e) => {
                     // Fall back to Skia renderer if Auto selection fails
-                    eprintln!("Failed to create Auto renderer: {e}, falling back to Skia");
+                    crate::trace::warn_event!(
+                        "Failed to create Auto renderer: {e}, falling back to Skia"
+                    );
                     crate::renderer::create_renderer(RendererType::Skia)
                         .expect("Failed to create fallback Skia renderer")
                 }
@@ -116,7 +360,7 @@ pub mod desktop {
                 Ok(r) => r,
                 Err(e) => {
                     // Fall back to Skia renderer if requested renderer fails
-                    eprintln!(
+                    crate::trace::warn_event!(
                         "Failed to create {renderer_type:?} renderer: {e}, falling back to Skia"
                     );
                     crate::renderer::create_renderer(RendererType::Skia)
@@ -303,13 +547,13 @@ pub mod desktop {
                         if let Err(e) = renderer_rc.borrow_mut().render(&node, &mut render_context)
                         {
                             // Use cloned Rc and borrow_mut()
-                            eprintln!("Rendering error: {e}");
+                            crate::trace::error_event!("Rendering error: {e}");
                         }
 
                         // Flush changes
                         if let Err(e) = renderer_rc.borrow_mut().flush() {
                             // Use cloned Rc and borrow_mut()
-                            eprintln!("Flush error: {e}");
+                            crate::trace::error_event!("Flush error: {e}");
                         }
 
                         // Finish drawing
@@ -336,6 +580,151 @@ pub mod desktop {
             Ok(())
         }
     }
+
+    /// Native platform backed by winit
+    ///
+    /// The event loop is created along with the first window, so
+    /// constructing the platform doesn't need a display connection.
+    pub struct WinitPlatform {
+        event_loop: Option<EventLoop<()>>,
+        windows: HashMap<WindowHandle, Window>,
+        next_id: u64,
+    }
+
+    impl WinitPlatform {
+        /// Create a platform with no windows
+        pub fn new() -> Self {
+            Self {
+                event_loop: None,
+                windows: HashMap::new(),
+                next_id: 0,
+            }
+        }
+
+        /// Number of open windows
+        pub fn window_count(&self) -> usize {
+            self.windows.len()
+        }
+
+        /// The event loop, created on first use
+        fn event_loop(&mut self) -> Result<&EventLoop<()>, crate::Error> {
+            let event_loop = match self.event_loop.take() {
+                Some(event_loop) => event_loop,
+                None => EventLoop::new().map_err(|e| {
+                    crate::Error::Platform(format!("Failed to create event loop: {e}"))
+                })?,
+            };
+            Ok(self.event_loop.insert(event_loop))
+        }
+    }
+
+    impl Default for WinitPlatform {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Platform for WinitPlatform {
+        fn create_window(&mut self, config: WindowConfig) -> Result<WindowHandle, crate::Error> {
+            let window = WindowBuilder::new()
+                .with_title(&config.title)
+                .with_inner_size(LogicalSize::new(config.width, config.height))
+                .with_resizable(config.resizable)
+                .build(self.event_loop()?)
+                .map_err(|e| crate::Error::Platform(format!("Failed to create window: {e}")))?;
+
+            let handle = WindowHandle(self.next_id);
+            self.next_id += 1;
+            self.windows.insert(handle, window);
+            Ok(handle)
+        }
+
+        fn run_event_loop(&mut self, mut handler: EventHandler) -> Result<(), crate::Error> {
+            let event_loop = self.event_loop.take().ok_or_else(|| {
+                crate::Error::Platform("Create a window before running the event loop".into())
+            })?;
+            let windows = std::mem::take(&mut self.windows);
+            let handles: HashMap<WindowId, WindowHandle> = windows
+                .iter()
+                .map(|(&handle, window)| (window.id(), handle))
+                .collect();
+
+            // winit reports the cursor position and modifiers separately from
+            // the button and key events that need them
+            let mut cursor = Point::zero();
+            let mut modifiers = ModifiersState::default();
+
+            event_loop
+                .run(move |event, target| {
+                    target.set_control_flow(ControlFlow::Wait);
+
+                    let Event::WindowEvent { window_id, event } = event else {
+                        return;
+                    };
+                    let Some(&window) = handles.get(&window_id) else {
+                        return;
+                    };
+                    let scale = windows[&window].scale_factor();
+
+                    let event = match event {
+                        WindowEvent::Resized(size) => {
+                            let size = size.to_logical::<u32>(scale);
+                            PlatformEvent::Resized {
+                                window,
+                                width: size.width,
+                                height: size.height,
+                            }
+                        }
+                        WindowEvent::RedrawRequested => PlatformEvent::RedrawRequested(window),
+                        WindowEvent::CloseRequested => PlatformEvent::CloseRequested(window),
+                        WindowEvent::CursorMoved { position, .. } => {
+                            let position = position.to_logical::<f32>(scale);
+                            cursor = Point::new(position.x, position.y);
+                            PlatformEvent::Pointer(window, PointerInput::Move(cursor))
+                        }
+                        WindowEvent::CursorLeft { .. } => {
+                            PlatformEvent::Pointer(window, PointerInput::Leave)
+                        }
+                        WindowEvent::MouseInput {
+                            state,
+                            button: MouseButton::Left,
+                            ..
+                        } => PlatformEvent::Pointer(
+                            window,
+                            match state {
+                                ElementState::Pressed => PointerInput::Down(cursor),
+                                ElementState::Released => PointerInput::Up(cursor),
+                            },
+                        ),
+                        WindowEvent::ModifiersChanged(changed) => {
+                            modifiers = changed.state();
+                            return;
+                        }
+                        WindowEvent::KeyboardInput { event, .. } => {
+                            PlatformEvent::Key(window, KeyEvent::from_winit(&event, modifiers))
+                        }
+                        _ => return,
+                    };
+
+                    match handler(event) {
+                        LoopControl::Continue => {}
+                        LoopControl::Redraw(window) => {
+                            if let Some(window) = windows.get(&window) {
+                                window.request_redraw();
+                            }
+                        }
+                        LoopControl::Exit => target.exit(),
+                    }
+                })
+                .map_err(|e| crate::Error::Platform(format!("Event loop failed: {e}")))
+        }
+
+        fn request_redraw(&self, window: WindowHandle) {
+            if let Some(window) = self.windows.get(&window) {
+                window.request_redraw();
+            }
+        }
+    }
 }
 
 /// Factory function to create the appropriate platform adapter
@@ -382,3 +771,33 @@ pub enum PlatformType {
     /// Automatically detect platform
     Auto,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_config_defaults() {
+        let config = WindowConfig::default();
+        assert_eq!(config.title, "Orbit UI Application");
+        assert_eq!((config.width, config.height), (800, 600));
+        assert!(config.resizable);
+
+        let config = config.with_title("Editor").with_size(1024, 768);
+        assert_eq!(config.title, "Editor");
+        assert_eq!((config.width, config.height), (1024, 768));
+    }
+
+    #[cfg(feature = "desktop")]
+    #[test]
+    fn test_native_platform_starts_without_windows() {
+        let platform = WinitPlatform::new();
+        assert_eq!(platform.window_count(), 0);
+    }
+
+    #[cfg(not(any(feature = "desktop", feature = "web")))]
+    #[test]
+    fn test_native_platform_requires_a_platform_feature() {
+        assert!(matches!(native_platform(), Err(crate::Error::Platform(_))));
+    }
+}
@@ -0,0 +1,228 @@
+//! Clipboard access
+//!
+//! The browser only offers the clipboard asynchronously, so [`Clipboard`]
+//! returns futures everywhere. Native backends resolve them straight away,
+//! and [`spawn`] runs a clipboard future to completion on the current
+//! platform: immediately on native targets, and on the browser's task queue
+//! on the web.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Errors from reading or writing the clipboard
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ClipboardError {
+    #[error("Clipboard unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Clipboard access failed: {0}")]
+    Access(String),
+}
+
+/// Result of a clipboard operation, resolved later on the web
+pub type ClipboardFuture<T> = Pin<Box<dyn Future<Output = Result<T, ClipboardError>>>>;
+
+/// Plain-text access to a clipboard
+pub trait Clipboard: Send + Sync + std::fmt::Debug {
+    /// Read the clipboard's text, or `None` when it holds no text
+    fn get_text(&self) -> ClipboardFuture<Option<String>>;
+
+    /// Replace the clipboard's contents with `text`
+    fn set_text(&self, text: String) -> ClipboardFuture<()>;
+}
+
+/// The clipboard of the platform being built for
+///
+/// The browser's clipboard on the web, the operating system's on native
+/// targets, and an in-process one anywhere else.
+pub fn system_clipboard() -> Arc<dyn Clipboard> {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        Arc::new(WebClipboard::new())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Arc::new(SystemClipboard::new())
+    }
+    #[cfg(all(target_arch = "wasm32", not(feature = "web")))]
+    {
+        Arc::new(MemoryClipboard::new())
+    }
+}
+
+/// Run a clipboard future to completion
+///
+/// Native targets block until it resolves, which is immediate for the
+/// clipboards in this module. On the web the future is queued and this
+/// returns before it has run.
+pub fn spawn(future: impl Future<Output = ()> + 'static) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    wasm_bindgen_futures::spawn_local(future);
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    futures::executor::block_on(future);
+}
+
+/// A clipboard private to the process
+///
+/// Useful in tests and on targets without a system clipboard.
+#[derive(Debug, Default)]
+pub struct MemoryClipboard {
+    text: Mutex<Option<String>>,
+}
+
+impl MemoryClipboard {
+    /// Create an empty clipboard
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The clipboard's text, read synchronously
+    pub fn text(&self) -> Option<String> {
+        self.text.lock().unwrap().clone()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&self) -> ClipboardFuture<Option<String>> {
+        Box::pin(std::future::ready(Ok(self.text())))
+    }
+
+    fn set_text(&self, text: String) -> ClipboardFuture<()> {
+        *self.text.lock().unwrap() = Some(text);
+        Box::pin(std::future::ready(Ok(())))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::SystemClipboard;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use super::{Clipboard, ClipboardError, ClipboardFuture};
+    use std::sync::Mutex;
+
+    /// The operating system's clipboard
+    ///
+    /// The connection to the clipboard is opened on first use and kept, so
+    /// text copied under X11, where the owner has to keep serving it, stays
+    /// available while the handle is alive.
+    #[derive(Default)]
+    pub struct SystemClipboard {
+        clipboard: Mutex<Option<arboard::Clipboard>>,
+    }
+
+    impl SystemClipboard {
+        /// Create a handle to the system clipboard
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Run `f` with the clipboard, connecting to it first if needed
+        fn with<T>(
+            &self,
+            f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, ClipboardError>,
+        ) -> Result<T, ClipboardError> {
+            let mut clipboard = self.clipboard.lock().unwrap();
+            if clipboard.is_none() {
+                let connected = arboard::Clipboard::new()
+                    .map_err(|e| ClipboardError::Unavailable(e.to_string()))?;
+                *clipboard = Some(connected);
+            }
+            f(clipboard.as_mut().expect("connected above"))
+        }
+
+        fn read(&self) -> Result<Option<String>, ClipboardError> {
+            self.with(|clipboard| match clipboard.get_text() {
+                Ok(text) => Ok(Some(text)),
+                // An empty clipboard, or one holding something other than text
+                Err(arboard::Error::ContentNotAvailable) => Ok(None),
+                Err(e) => Err(clipboard_error(e)),
+            })
+        }
+
+        fn write(&self, text: String) -> Result<(), ClipboardError> {
+            self.with(|clipboard| clipboard.set_text(text).map_err(clipboard_error))
+        }
+    }
+
+    impl std::fmt::Debug for SystemClipboard {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SystemClipboard")
+                .field(
+                    "connected",
+                    &self.clipboard.lock().is_ok_and(|c| c.is_some()),
+                )
+                .finish()
+        }
+    }
+
+    fn clipboard_error(error: arboard::Error) -> ClipboardError {
+        match error {
+            arboard::Error::ClipboardNotSupported => ClipboardError::Unavailable(error.to_string()),
+            error => ClipboardError::Access(error.to_string()),
+        }
+    }
+
+    impl Clipboard for SystemClipboard {
+        fn get_text(&self) -> ClipboardFuture<Option<String>> {
+            Box::pin(std::future::ready(self.read()))
+        }
+
+        fn set_text(&self, text: String) -> ClipboardFuture<()> {
+            Box::pin(std::future::ready(self.write(text)))
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+pub use web::WebClipboard;
+
+#[cfg(feature = "web")]
+mod web {
+    use super::{Clipboard, ClipboardError, ClipboardFuture};
+    use wasm_bindgen_futures::JsFuture;
+
+    /// The browser's asynchronous clipboard, `navigator.clipboard`
+    ///
+    /// Browsers only allow access from a secure context, and reading
+    /// usually prompts the user for permission, so reads can fail or take a
+    /// while to resolve.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct WebClipboard;
+
+    impl WebClipboard {
+        /// Create a handle to the browser's clipboard
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    fn navigator_clipboard() -> Result<web_sys::Clipboard, ClipboardError> {
+        web_sys::window()
+            .map(|window| window.navigator().clipboard())
+            .ok_or_else(|| ClipboardError::Unavailable("no browser window".into()))
+    }
+
+    fn js_error(error: wasm_bindgen::JsValue) -> ClipboardError {
+        ClipboardError::Access(format!("{error:?}"))
+    }
+
+    impl Clipboard for WebClipboard {
+        fn get_text(&self) -> ClipboardFuture<Option<String>> {
+            Box::pin(async {
+                let promise = navigator_clipboard()?.read_text();
+                let text = JsFuture::from(promise).await.map_err(js_error)?;
+                Ok(text.as_string().filter(|text| !text.is_empty()))
+            })
+        }
+
+        fn set_text(&self, text: String) -> ClipboardFuture<()> {
+            Box::pin(async move {
+                let promise = navigator_clipboard()?.write_text(&text);
+                JsFuture::from(promise).await.map_err(js_error)?;
+                Ok(())
+            })
+        }
+    }
+}
@@ -0,0 +1,645 @@
+//! Drives transitions and springs forward once per frame
+//!
+//! [`StyleTransitions`] connects the driver to the style engine, starting
+//! CSS transitions when a component's computed style changes.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::component::{ComponentId, Node};
+use crate::renderer::RenderContext;
+use crate::style::{
+    ComputedStyle, Spring, Style, StyleContext, StyleEngine, StyleError, TimingFunction, Transform,
+};
+
+/// Longest time step a spring is advanced by in one tick
+///
+/// After a stall (a breakpoint, a hidden window) springs resume from where
+/// they were instead of jumping to the end.
+const MAX_SPRING_STEP: Duration = Duration::from_millis(100);
+
+/// A style property that can be animated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimatedProperty {
+    /// `opacity`
+    Opacity,
+    /// Horizontal translation, written as a `translate` transform
+    TranslateX,
+    /// Vertical translation, written as a `translate` transform
+    TranslateY,
+    /// Rotation in degrees, written as a `rotate` transform
+    Rotate,
+}
+
+impl AnimatedProperty {
+    /// Every animatable property
+    pub const ALL: [AnimatedProperty; 4] = [
+        AnimatedProperty::Opacity,
+        AnimatedProperty::TranslateX,
+        AnimatedProperty::TranslateY,
+        AnimatedProperty::Rotate,
+    ];
+
+    /// Animatable properties whose values differ between two styles
+    pub fn changed(previous: &Style, current: &Style) -> Vec<AnimatedProperty> {
+        Self::ALL
+            .into_iter()
+            .filter(|property| property.value(current) != property.value(previous))
+            .collect()
+    }
+
+    /// Name of the CSS property the value is written through
+    pub fn css_name(self) -> &'static str {
+        match self {
+            AnimatedProperty::Opacity => "opacity",
+            AnimatedProperty::TranslateX
+            | AnimatedProperty::TranslateY
+            | AnimatedProperty::Rotate => "transform",
+        }
+    }
+
+    /// The property's value in a style, or its initial value when unset
+    pub fn value(self, style: &Style) -> f32 {
+        let (x, y) = match style.transform {
+            Some(Transform::Translate(x, y)) => (x, y),
+            Some(Transform::TranslateX(x)) => (x, 0.0),
+            Some(Transform::TranslateY(y)) => (0.0, y),
+            _ => (0.0, 0.0),
+        };
+        match self {
+            AnimatedProperty::Opacity => style.opacity.unwrap_or(1.0),
+            AnimatedProperty::TranslateX => x,
+            AnimatedProperty::TranslateY => y,
+            AnimatedProperty::Rotate => match style.transform {
+                Some(Transform::Rotate(degrees)) => degrees,
+                _ => 0.0,
+            },
+        }
+    }
+
+    /// Write `value` into a style
+    ///
+    /// Translations keep the other axis of an existing translation, and
+    /// translations and rotations replace any other kind of transform.
+    fn apply(self, style: &mut Style, value: f32) {
+        let (x, y) = match style.transform {
+            Some(Transform::Translate(x, y)) => (x, y),
+            Some(Transform::TranslateX(x)) => (x, 0.0),
+            Some(Transform::TranslateY(y)) => (0.0, y),
+            _ => (0.0, 0.0),
+        };
+        match self {
+            AnimatedProperty::Opacity => style.opacity = Some(value),
+            AnimatedProperty::TranslateX => style.transform = Some(Transform::Translate(value, y)),
+            AnimatedProperty::TranslateY => style.transform = Some(Transform::Translate(x, value)),
+            AnimatedProperty::Rotate => style.transform = Some(Transform::Rotate(value)),
+        }
+    }
+}
+
+/// How an animated value moves
+#[derive(Debug, Clone)]
+enum Motion {
+    /// Fixed-duration transition, which starts on its first tick
+    Transition {
+        from: f32,
+        to: f32,
+        duration: Duration,
+        timing_function: TimingFunction,
+        started: Option<Instant>,
+        current: f32,
+    },
+    /// Transition that starts over each time it ends, until cancelled
+    Loop {
+        from: f32,
+        to: f32,
+        period: Duration,
+        started: Option<Instant>,
+        current: f32,
+    },
+    /// Spring carrying its velocity between ticks
+    Spring {
+        spring: Spring,
+        value: f32,
+        target: f32,
+        velocity: f32,
+    },
+}
+
+impl Motion {
+    /// Advance to `now`, returning the new value and whether motion has ended
+    fn advance(&mut self, now: Instant, dt: Duration) -> (f32, bool) {
+        match self {
+            Motion::Transition {
+                from,
+                to,
+                duration,
+                timing_function,
+                started,
+                current,
+            } => {
+                let started = *started.get_or_insert(now);
+                let progress = if duration.is_zero() {
+                    1.0
+                } else {
+                    (now.duration_since(started).as_secs_f32() / duration.as_secs_f32()).min(1.0)
+                };
+                *current = *from + (*to - *from) * timing_function.evaluate(progress);
+                (*current, progress >= 1.0)
+            }
+            Motion::Loop {
+                from,
+                to,
+                period,
+                started,
+                current,
+            } => {
+                let started = *started.get_or_insert(now);
+                let progress = if period.is_zero() {
+                    0.0
+                } else {
+                    (now.duration_since(started).as_secs_f32() / period.as_secs_f32()).fract()
+                };
+                *current = *from + (*to - *from) * progress;
+                (*current, false)
+            }
+            Motion::Spring {
+                spring,
+                value,
+                target,
+                velocity,
+            } => {
+                let dt = dt.min(MAX_SPRING_STEP).as_secs_f32();
+                (*value, *velocity) = spring.step(dt, *value, *target, *velocity);
+                if spring.settled(*value, *target, *velocity) {
+                    *value = *target;
+                    return (*target, true);
+                }
+                (*value, false)
+            }
+        }
+    }
+
+    /// Value written by the last tick
+    fn value(&self) -> f32 {
+        match self {
+            Motion::Transition { current, .. } | Motion::Loop { current, .. } => *current,
+            Motion::Spring { value, .. } => *value,
+        }
+    }
+}
+
+/// Active animations, advanced once per frame
+///
+/// Each component property has at most one animation; starting another
+/// replaces it. Every tick writes the animated values into the styles of the
+/// components' nodes, marks those components dirty in the render context and
+/// drops animations that have finished.
+///
+/// With vsync the platform paces frames, so every tick advances. Without it,
+/// ticks closer together than `RenderContext::target_fps` allows are skipped.
+#[derive(Debug, Default)]
+pub struct AnimationDriver {
+    animations: HashMap<(ComponentId, AnimatedProperty), Motion>,
+    last_tick: Option<Instant>,
+}
+
+impl AnimationDriver {
+    /// Create a driver with no animations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Animate a property from `from` to `to` over `duration`
+    ///
+    /// The transition's clock starts on the next tick.
+    pub fn transition(
+        &mut self,
+        component: ComponentId,
+        property: AnimatedProperty,
+        from: f32,
+        to: f32,
+        duration: Duration,
+        timing_function: TimingFunction,
+    ) {
+        self.animations.insert(
+            (component, property),
+            Motion::Transition {
+                from,
+                to,
+                duration,
+                timing_function,
+                started: None,
+                current: from,
+            },
+        );
+    }
+
+    /// Animate a property linearly from `from` to `to` once every `period`,
+    /// starting over each time, until it is cancelled
+    ///
+    /// The loop's clock starts on the next tick.
+    pub fn repeat(
+        &mut self,
+        component: ComponentId,
+        property: AnimatedProperty,
+        from: f32,
+        to: f32,
+        period: Duration,
+    ) {
+        self.animations.insert(
+            (component, property),
+            Motion::Loop {
+                from,
+                to,
+                period,
+                started: None,
+                current: from,
+            },
+        );
+    }
+
+    /// Animate a property towards `target` with a spring
+    ///
+    /// If the property is already springing, it keeps its current value and
+    /// velocity and only the target changes, so retargeting stays smooth.
+    pub fn spring(
+        &mut self,
+        component: ComponentId,
+        property: AnimatedProperty,
+        spring: Spring,
+        from: f32,
+        target: f32,
+    ) {
+        let key = (component, property);
+        let (value, velocity) = match self.animations.get(&key) {
+            Some(Motion::Spring {
+                value, velocity, ..
+            }) => (*value, *velocity),
+            Some(motion) => (motion.value(), 0.0),
+            None => (from, 0.0),
+        };
+        self.animations.insert(
+            key,
+            Motion::Spring {
+                spring,
+                value,
+                target,
+                velocity,
+            },
+        );
+    }
+
+    /// Stop animating a property, leaving its last written value in place
+    pub fn cancel(&mut self, component: ComponentId, property: AnimatedProperty) {
+        self.animations.remove(&(component, property));
+    }
+
+    /// Whether a property is being animated
+    pub fn is_animating(&self, component: ComponentId, property: AnimatedProperty) -> bool {
+        self.animations.contains_key(&(component, property))
+    }
+
+    /// Value a property's animation last wrote, or `None` if it isn't animating
+    pub fn current_value(&self, component: ComponentId, property: AnimatedProperty) -> Option<f32> {
+        self.animations
+            .get(&(component, property))
+            .map(Motion::value)
+    }
+
+    /// Number of active animations
+    pub fn len(&self) -> usize {
+        self.animations.len()
+    }
+
+    /// Whether no animations are active
+    pub fn is_empty(&self) -> bool {
+        self.animations.is_empty()
+    }
+
+    /// Advance every animation to `now` and write the values into `root`
+    ///
+    /// Returns whether the tick ran; it is skipped when it comes too soon
+    /// after the previous one for the context's frame rate.
+    pub fn tick(&mut self, now: Instant, root: &mut Node, context: &mut RenderContext) -> bool {
+        let dt = match self.last_tick {
+            Some(last) => {
+                let dt = now.saturating_duration_since(last);
+                if dt < Self::frame_interval(context) {
+                    return false;
+                }
+                dt
+            }
+            None => Duration::ZERO,
+        };
+        self.last_tick = Some(now);
+
+        let mut finished = Vec::new();
+        for (&(component, property), motion) in &mut self.animations {
+            let (value, done) = motion.advance(now, dt);
+            if let Some(node) = root.find_component_mut(component) {
+                let mut style = node.style().cloned().unwrap_or_default();
+                property.apply(&mut style, value);
+                node.set_style(style);
+            }
+            context.mark_dirty(component);
+            if done {
+                finished.push((component, property));
+            }
+        }
+        for key in finished {
+            self.animations.remove(&key);
+        }
+
+        if self.animations.is_empty() {
+            self.last_tick = None;
+        }
+        true
+    }
+
+    /// Shortest time between ticks for a context
+    fn frame_interval(context: &RenderContext) -> Duration {
+        if context.vsync_enabled || context.target_fps == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f32(1.0 / context.target_fps as f32)
+        }
+    }
+}
+
+/// Starts CSS transitions when components' computed styles change
+///
+/// Remembers the style last computed for each component and diffs the next
+/// one against it. If the new style has a `transition_duration`, each
+/// changed animatable property named in `transition_property`, or every one
+/// when it names none, is transitioned in the driver. A property that is
+/// still animating starts from the value on screen rather than its previous
+/// computed value, so interrupted transitions don't jump.
+/// `transition_delay` is not applied yet.
+#[derive(Debug, Default)]
+pub struct StyleTransitions {
+    styles: HashMap<ComponentId, ComputedStyle>,
+}
+
+impl StyleTransitions {
+    /// Create a tracker that has seen no styles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute a component's style and transition the properties that changed
+    pub fn compute_style(
+        &mut self,
+        engine: &mut StyleEngine,
+        component_id: ComponentId,
+        base_style: &Style,
+        context: &StyleContext,
+        driver: &mut AnimationDriver,
+    ) -> Result<ComputedStyle, StyleError> {
+        let computed = engine.compute_style(component_id, base_style, context)?;
+        if let Some(previous) = self.styles.insert(component_id, computed.clone()) {
+            Self::start(component_id, &previous.style, &computed.style, driver);
+        }
+        Ok(computed)
+    }
+
+    /// Stop tracking a component's style, as when it unmounts
+    pub fn forget(&mut self, component_id: ComponentId) {
+        self.styles.remove(&component_id);
+    }
+
+    /// Start a transition for each changed property that `style` transitions
+    fn start(
+        component_id: ComponentId,
+        previous: &Style,
+        style: &Style,
+        driver: &mut AnimationDriver,
+    ) {
+        let Some(duration) = style.transition_duration.filter(|seconds| *seconds > 0.0) else {
+            return;
+        };
+        let listed = |property: AnimatedProperty| match &style.transition_property {
+            Some(names) => names
+                .iter()
+                .any(|name| name == "all" || name == property.css_name()),
+            None => true,
+        };
+
+        for property in AnimatedProperty::changed(previous, style) {
+            if listed(property) {
+                let from = driver
+                    .current_value(component_id, property)
+                    .unwrap_or_else(|| property.value(previous));
+                driver.transition(
+                    component_id,
+                    property,
+                    from,
+                    property.value(style),
+                    Duration::from_secs_f32(duration),
+                    style
+                        .transition_timing_function
+                        .clone()
+                        .unwrap_or(TimingFunction::Ease),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{ComponentBase, ComponentInstance, Context};
+
+    fn component_node() -> (Node, ComponentId) {
+        let component = ComponentBase::new(Context::new());
+        let id = component.id();
+        let node = Node::new(Some(ComponentInstance::new(component, ())));
+        (node, id)
+    }
+
+    fn opacity(node: &Node) -> Option<f32> {
+        node.style().and_then(|style| style.opacity)
+    }
+
+    #[test]
+    fn test_opacity_transition_finishes_and_is_removed() {
+        let (mut node, id) = component_node();
+        let mut context = RenderContext::new(100, 100);
+        let mut driver = AnimationDriver::new();
+        driver.transition(
+            id,
+            AnimatedProperty::Opacity,
+            0.0,
+            1.0,
+            Duration::from_millis(200),
+            TimingFunction::Linear,
+        );
+
+        let start = Instant::now();
+        assert!(driver.tick(start, &mut node, &mut context));
+        assert_eq!(opacity(&node), Some(0.0));
+        assert!(context.is_dirty(id));
+
+        driver.tick(start + Duration::from_millis(100), &mut node, &mut context);
+        assert!((opacity(&node).unwrap() - 0.5).abs() < 1e-6);
+        assert!(driver.is_animating(id, AnimatedProperty::Opacity));
+
+        for frame in 7..=13 {
+            let now = start + Duration::from_millis(frame * 16);
+            driver.tick(now, &mut node, &mut context);
+        }
+        assert_eq!(opacity(&node), Some(1.0));
+        assert!(driver.is_empty());
+    }
+
+    #[test]
+    fn test_ticks_are_paced_without_vsync() {
+        let (mut node, id) = component_node();
+        let mut context = RenderContext::new(100, 100);
+        context.vsync_enabled = false;
+        context.target_fps = 30;
+
+        let mut driver = AnimationDriver::new();
+        driver.spring(
+            id,
+            AnimatedProperty::TranslateX,
+            Spring::default(),
+            0.0,
+            50.0,
+        );
+
+        let start = Instant::now();
+        assert!(driver.tick(start, &mut node, &mut context));
+        assert!(!driver.tick(start + Duration::from_millis(16), &mut node, &mut context));
+        assert!(driver.tick(start + Duration::from_millis(34), &mut node, &mut context));
+
+        // The spring moved towards its target through the transform
+        let Some(Transform::Translate(x, 0.0)) = node.style().unwrap().transform else {
+            panic!("expected a translate transform");
+        };
+        assert!(x > 0.0 && x < 50.0);
+    }
+
+    #[test]
+    fn test_repeat_wraps_around_until_cancelled() {
+        let (mut node, id) = component_node();
+        let mut context = RenderContext::new(100, 100);
+        let mut driver = AnimationDriver::new();
+        driver.repeat(
+            id,
+            AnimatedProperty::Rotate,
+            0.0,
+            360.0,
+            Duration::from_millis(800),
+        );
+
+        let rotation = |node: &Node| match node.style().unwrap().transform {
+            Some(Transform::Rotate(degrees)) => degrees,
+            ref other => panic!("expected a rotate transform, got {other:?}"),
+        };
+        let start = Instant::now();
+        driver.tick(start, &mut node, &mut context);
+        driver.tick(start + Duration::from_millis(200), &mut node, &mut context);
+        assert!((rotation(&node) - 90.0).abs() < 1e-3);
+
+        // Past the end of a period the loop starts over
+        driver.tick(start + Duration::from_millis(1000), &mut node, &mut context);
+        assert!((rotation(&node) - 90.0).abs() < 1e-3);
+        assert!(driver.is_animating(id, AnimatedProperty::Rotate));
+
+        driver.cancel(id, AnimatedProperty::Rotate);
+        assert!(driver.is_empty());
+    }
+
+    #[test]
+    fn test_changed_opacity_starts_a_transition() {
+        let mut engine = StyleEngine::new();
+        let mut transitions = StyleTransitions::new();
+        let mut driver = AnimationDriver::new();
+        let id = ComponentId::new();
+        let context = StyleContext::default();
+        let faded = |opacity: f32, font: &str| Style {
+            opacity: Some(opacity),
+            font_family: Some(font.to_string()),
+            transition_property: Some(vec!["opacity".to_string()]),
+            transition_duration: Some(0.3),
+            ..Default::default()
+        };
+        let mut compute = |style: &Style, driver: &mut AnimationDriver| {
+            transitions
+                .compute_style(&mut engine, id, style, &context, driver)
+                .unwrap()
+        };
+
+        let first = compute(&faded(1.0, "serif"), &mut driver);
+        assert!(driver.is_empty());
+
+        // A property that can't animate changes without starting anything
+        let renamed = compute(&faded(1.0, "monospace"), &mut driver);
+        assert!(AnimatedProperty::changed(&first.style, &renamed.style).is_empty());
+        assert!(driver.is_empty());
+
+        let dimmed = compute(&faded(0.4, "monospace"), &mut driver);
+        assert_eq!(
+            AnimatedProperty::changed(&renamed.style, &dimmed.style),
+            vec![AnimatedProperty::Opacity]
+        );
+        assert!(driver.is_animating(id, AnimatedProperty::Opacity));
+        assert_eq!(driver.len(), 1);
+
+        // Translations change too, but only opacity is listed
+        let mut moved = faded(0.4, "monospace");
+        moved.transform = Some(Transform::Translate(10.0, 0.0));
+        driver.cancel(id, AnimatedProperty::Opacity);
+        compute(&moved, &mut driver);
+        assert!(driver.is_empty());
+    }
+
+    #[test]
+    fn test_interrupted_transition_starts_from_the_value_on_screen() {
+        let (mut node, id) = component_node();
+        let mut render_context = RenderContext::new(100, 100);
+        let mut engine = StyleEngine::new();
+        let mut transitions = StyleTransitions::new();
+        let mut driver = AnimationDriver::new();
+        let context = StyleContext::default();
+        let faded = |opacity: f32| Style {
+            opacity: Some(opacity),
+            transition_duration: Some(0.2),
+            transition_timing_function: Some(TimingFunction::Linear),
+            ..Default::default()
+        };
+
+        for opacity in [1.0, 0.0] {
+            transitions
+                .compute_style(&mut engine, id, &faded(opacity), &context, &mut driver)
+                .unwrap();
+        }
+        let start = Instant::now();
+        driver.tick(start, &mut node, &mut render_context);
+        driver.tick(
+            start + Duration::from_millis(100),
+            &mut node,
+            &mut render_context,
+        );
+        assert!((opacity(&node).unwrap() - 0.5).abs() < 1e-6);
+
+        // Fading back in halfway through starts at one half, not at zero
+        transitions
+            .compute_style(&mut engine, id, &faded(1.0), &context, &mut driver)
+            .unwrap();
+        driver.tick(
+            start + Duration::from_millis(116),
+            &mut node,
+            &mut render_context,
+        );
+        assert!((opacity(&node).unwrap() - 0.5).abs() < 1e-6);
+        driver.tick(
+            start + Duration::from_millis(216),
+            &mut node,
+            &mut render_context,
+        );
+        assert!((opacity(&node).unwrap() - 0.75).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,473 @@
+//! Shared image cache that decodes images once and hands them to renderers
+
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+
+/// Decoded image pixels, tightly packed as unpremultiplied RGBA8
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedImage {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// RGBA8 pixel data, `width * height * 4` bytes
+    pub pixels: Vec<u8>,
+}
+
+/// Loading state of a cached image
+#[derive(Debug, Clone)]
+pub enum ImageState {
+    /// Decoding has not finished yet; renderers should draw a placeholder
+    Loading,
+    /// The image is decoded and ready to draw
+    Ready(Arc<DecodedImage>),
+    /// The image could not be read or decoded
+    Failed(String),
+}
+
+/// Shared handle to an image in the cache
+///
+/// Handles compare equal when they refer to the same cache entry.
+#[derive(Debug, Clone)]
+pub struct ImageHandle {
+    state: Arc<Mutex<ImageState>>,
+}
+
+impl ImageHandle {
+    fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ImageState::Loading)),
+        }
+    }
+
+    fn set_state(&self, state: ImageState) {
+        if let Ok(mut current) = self.state.lock() {
+            *current = state;
+        }
+    }
+
+    /// Get the current loading state
+    pub fn state(&self) -> ImageState {
+        self.state
+            .lock()
+            .map(|state| state.clone())
+            .unwrap_or_else(|_| ImageState::Failed("image state lock poisoned".to_string()))
+    }
+
+    /// Get the decoded image if it is ready
+    pub fn image(&self) -> Option<Arc<DecodedImage>> {
+        match self.state() {
+            ImageState::Ready(image) => Some(image),
+            _ => None,
+        }
+    }
+
+    /// Whether decoding has finished, successfully or not
+    pub fn is_settled(&self) -> bool {
+        !matches!(self.state(), ImageState::Loading)
+    }
+}
+
+impl PartialEq for ImageHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl Eq for ImageHandle {}
+
+/// Called each time an image in the cache finishes loading
+pub type LoadListener = dyn Fn() + Send + Sync;
+
+/// Decoded bytes kept by a cache created with [`ImageCache::new`]
+pub const DEFAULT_CACHE_BYTES: usize = 256 * 1024 * 1024;
+
+/// Number of threads decoding images for one cache on native targets
+#[cfg(not(target_arch = "wasm32"))]
+const DECODE_THREADS: usize = 2;
+
+type DecodeJob = Box<dyn FnOnce() + Send>;
+
+/// Cache of images keyed by URL or file path
+///
+/// Each source is decoded at most once while it stays cached, off the render
+/// loop, so requesting an image never blocks on I/O or decoding. Native
+/// targets decode on a small pool of worker threads, and the web on the
+/// browser's task queue. Once the decoded images exceed the cache's size,
+/// the least recently requested ones are dropped and decoded again if they
+/// are requested later.
+pub struct ImageCache {
+    shared: Arc<Shared>,
+    /// Queue feeding the decode threads, started on first use
+    #[cfg(not(target_arch = "wasm32"))]
+    decoder: Mutex<Option<mpsc::Sender<DecodeJob>>>,
+}
+
+struct Shared {
+    entries: Mutex<Entries>,
+    /// Most decoded bytes kept
+    max_bytes: usize,
+    listeners: Mutex<Vec<Weak<LoadListener>>>,
+}
+
+#[derive(Default)]
+struct Entries {
+    images: HashMap<String, Entry>,
+    /// Incremented on every request, to find the least recently used entry
+    clock: u64,
+}
+
+struct Entry {
+    handle: ImageHandle,
+    last_used: u64,
+}
+
+impl ImageCache {
+    /// Create an empty cache holding up to [`DEFAULT_CACHE_BYTES`]
+    pub fn new() -> Self {
+        Self::with_max_bytes(DEFAULT_CACHE_BYTES)
+    }
+
+    /// Create an empty cache holding up to `max_bytes` of decoded pixels
+    ///
+    /// An image larger than the whole cache is still kept until another
+    /// image finishes loading.
+    pub fn with_max_bytes(max_bytes: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                entries: Mutex::new(Entries::default()),
+                max_bytes,
+                listeners: Mutex::new(Vec::new()),
+            }),
+            #[cfg(not(target_arch = "wasm32"))]
+            decoder: Mutex::new(None),
+        }
+    }
+
+    /// Get the process-wide cache shared by all renderers
+    pub fn global() -> &'static ImageCache {
+        static CACHE: OnceLock<ImageCache> = OnceLock::new();
+        CACHE.get_or_init(ImageCache::new)
+    }
+
+    /// Call `listener` each time an image finishes loading or fails to
+    ///
+    /// Only a weak reference is kept, so the listener is dropped with the
+    /// caller's `Arc`.
+    pub fn on_load(&self, listener: &Arc<LoadListener>) {
+        if let Ok(mut listeners) = self.shared.listeners.lock() {
+            listeners.push(Arc::downgrade(listener));
+        }
+    }
+
+    /// Request the image at a file path, starting a decode on first use
+    pub fn load(&self, source: &str) -> ImageHandle {
+        let path = source.to_string();
+        self.get_or_decode(source, move || {
+            std::fs::read(&path).map_err(|e| format!("Failed to read {path}: {e}"))
+        })
+    }
+
+    /// Request an image from encoded bytes already in memory
+    ///
+    /// `key` identifies the image in the cache; later requests with the same
+    /// key return the existing handle without decoding the bytes again.
+    pub fn load_bytes(&self, key: &str, bytes: impl Into<Vec<u8>>) -> ImageHandle {
+        let bytes = bytes.into();
+        self.get_or_decode(key, move || Ok(bytes))
+    }
+
+    /// Get a cached handle without starting a load
+    pub fn get(&self, key: &str) -> Option<ImageHandle> {
+        self.shared
+            .entries
+            .lock()
+            .ok()
+            .and_then(|entries| entries.images.get(key).map(|entry| entry.handle.clone()))
+    }
+
+    /// Drop an image from the cache
+    pub fn remove(&self, key: &str) -> Option<ImageHandle> {
+        self.shared
+            .entries
+            .lock()
+            .ok()
+            .and_then(|mut entries| entries.images.remove(key))
+            .map(|entry| entry.handle)
+    }
+
+    /// Number of cached images
+    pub fn len(&self) -> usize {
+        self.shared
+            .entries
+            .lock()
+            .map(|entries| entries.images.len())
+            .unwrap_or(0)
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_or_decode<F>(&self, key: &str, read: F) -> ImageHandle
+    where
+        F: FnOnce() -> Result<Vec<u8>, String> + Send + 'static,
+    {
+        let mut entries = match self.shared.entries.lock() {
+            Ok(entries) => entries,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        entries.clock += 1;
+        let now = entries.clock;
+        if let Some(entry) = entries.images.get_mut(key) {
+            entry.last_used = now;
+            return entry.handle.clone();
+        }
+
+        let handle = ImageHandle::new();
+        entries.images.insert(
+            key.to_string(),
+            Entry {
+                handle: handle.clone(),
+                last_used: now,
+            },
+        );
+        drop(entries);
+
+        let (worker, shared, key) = (handle.clone(), Arc::clone(&self.shared), key.to_string());
+        self.spawn(Box::new(move || {
+            let state = match read().and_then(|bytes| decode(&bytes)) {
+                Ok(image) => ImageState::Ready(Arc::new(image)),
+                Err(message) => ImageState::Failed(message),
+            };
+            if let ImageState::Ready(image) = &state {
+                shared.make_room(&key, image.pixels.len());
+            }
+            worker.set_state(state);
+            shared.notify();
+        }));
+
+        handle
+    }
+
+    /// Run a decode off the render loop
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spawn(&self, job: DecodeJob) {
+        let mut decoder = match self.decoder.lock() {
+            Ok(decoder) => decoder,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let sender = decoder.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel::<DecodeJob>();
+            let receiver = Arc::new(Mutex::new(receiver));
+            for _ in 0..DECODE_THREADS {
+                let receiver = Arc::clone(&receiver);
+                // Workers exit once the cache, and with it the sender, is dropped
+                thread::spawn(move || loop {
+                    let job = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => return,
+                    };
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                });
+            }
+            sender
+        });
+        if let Err(mpsc::SendError(job)) = sender.send(job) {
+            // Every worker has gone, so decode here rather than never
+            job();
+        }
+    }
+
+    /// Run a decode off the render loop
+    #[cfg(target_arch = "wasm32")]
+    fn spawn(&self, job: DecodeJob) {
+        #[cfg(feature = "web")]
+        wasm_bindgen_futures::spawn_local(async move { job() });
+        #[cfg(not(feature = "web"))]
+        job();
+    }
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ImageCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageCache")
+            .field("len", &self.len())
+            .field("max_bytes", &self.shared.max_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Shared {
+    /// Drop the least recently used images until `bytes` more fit
+    ///
+    /// `loaded` is the image about to take them, which is never dropped.
+    fn make_room(&self, loaded: &str, bytes: usize) {
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut total: usize = entries
+            .images
+            .values()
+            .filter_map(|entry| entry.handle.image())
+            .map(|image| image.pixels.len())
+            .sum::<usize>()
+            + bytes;
+        while total > self.max_bytes {
+            let oldest = entries
+                .images
+                .iter()
+                .filter(|(key, entry)| key.as_str() != loaded && entry.handle.image().is_some())
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            let Some(oldest) = oldest else {
+                break;
+            };
+            if let Some(entry) = entries.images.remove(&oldest) {
+                total -= entry.handle.image().map_or(0, |image| image.pixels.len());
+            }
+        }
+    }
+
+    fn notify(&self) {
+        let listeners: Vec<_> = match self.listeners.lock() {
+            Ok(mut listeners) => {
+                listeners.retain(|listener| listener.strong_count() > 0);
+                listeners.iter().filter_map(Weak::upgrade).collect()
+            }
+            Err(_) => return,
+        };
+        for listener in listeners {
+            listener();
+        }
+    }
+}
+
+/// Decode PNG or JPEG bytes into RGBA8 pixels
+#[cfg(any(feature = "skia", feature = "desktop", feature = "desktop-no-skia"))]
+fn decode(bytes: &[u8]) -> Result<DecodedImage, String> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| format!("Failed to decode image: {e}"))?
+        .to_rgba8();
+
+    Ok(DecodedImage {
+        width: image.width(),
+        height: image.height(),
+        pixels: image.into_raw(),
+    })
+}
+
+/// Decode PNG or JPEG bytes into RGBA8 pixels
+#[cfg(not(any(feature = "skia", feature = "desktop", feature = "desktop-no-skia")))]
+fn decode(_bytes: &[u8]) -> Result<DecodedImage, String> {
+    Err("Image decoding requires the skia or desktop feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    /// A 2x1 PNG with one red and one blue pixel
+    const TEST_PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0xf4,
+        0x22, 0x7f, 0x8a, 0x00, 0x00, 0x00, 0x0e, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0xf8,
+        0xcf, 0xc0, 0x00, 0x42, 0xff, 0x01, 0x0f, 0xf9, 0x03, 0xfd, 0x98, 0x79, 0xd7, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    fn wait_until_settled(handle: &ImageHandle) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !handle.is_settled() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_second_request_returns_same_handle() {
+        let cache = ImageCache::new();
+
+        let first = cache.load_bytes("test.png", TEST_PNG);
+        let second = cache.load_bytes("test.png", TEST_PNG);
+        assert_eq!(first, second);
+        assert_eq!(cache.len(), 1);
+
+        let other = cache.load_bytes("other.png", TEST_PNG);
+        assert_ne!(first, other);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[cfg(any(feature = "skia", feature = "desktop", feature = "desktop-no-skia"))]
+    #[test]
+    fn test_embedded_png_decodes() {
+        let cache = ImageCache::new();
+        let handle = cache.load_bytes("test.png", TEST_PNG);
+        wait_until_settled(&handle);
+
+        let image = handle.image().expect("embedded PNG should decode");
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.pixels, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_missing_file_fails() {
+        let cache = ImageCache::new();
+        let handle = cache.load("/nonexistent/orbit-test-image.png");
+        wait_until_settled(&handle);
+
+        assert!(matches!(handle.state(), ImageState::Failed(_)));
+    }
+
+    #[test]
+    fn test_listeners_hear_when_an_image_settles() {
+        let cache = ImageCache::new();
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let listener: Arc<LoadListener> = Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        cache.on_load(&listener);
+
+        let handle = cache.load_bytes("test.png", TEST_PNG);
+        wait_until_settled(&handle);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while loads.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(any(feature = "skia", feature = "desktop", feature = "desktop-no-skia"))]
+    #[test]
+    fn test_least_recently_used_image_is_evicted_past_the_size_cap() {
+        // Each test image is 8 bytes decoded
+        let cache = ImageCache::with_max_bytes(16);
+        for key in ["a.png", "b.png"] {
+            wait_until_settled(&cache.load_bytes(key, TEST_PNG));
+        }
+        // Touch `a` so `b` is the least recently used
+        cache.load_bytes("a.png", TEST_PNG);
+        wait_until_settled(&cache.load_bytes("c.png", TEST_PNG));
+
+        assert!(cache.get("a.png").is_some());
+        assert!(cache.get("b.png").is_none());
+        assert!(cache.get("c.png").is_some());
+    }
+}
@@ -1,16 +1,30 @@
 //! Enhanced renderer module with performance optimizations and component integration
 
 // Renderer modules
+pub mod animation_driver;
+pub mod image_cache;
+pub mod null;
+pub mod paint_order;
 #[cfg(feature = "skia")]
 pub mod skia;
+pub mod text_cache;
 pub mod wgpu;
 
 // Re-export renderer items
+pub use animation_driver::{AnimatedProperty, AnimationDriver, StyleTransitions};
+pub use image_cache::{
+    DecodedImage, ImageCache, ImageHandle, ImageState, LoadListener, DEFAULT_CACHE_BYTES,
+};
+pub use null::{NullRenderer, RenderLog, VisitedNode};
+pub use paint_order::{paint_order, PaintItem};
 #[cfg(feature = "skia")]
-pub use skia::{RendererError, RendererMessage, RendererResult, SkiaRenderer};
+pub use skia::{RendererError, RendererMessage, RendererResult, SkiaRenderer, SkiaTextMeasurer};
+pub use text_cache::{TextMeasureCache, TextMeasureKey};
 
 use crate::component::{ComponentId, Node};
+use crate::layout::TextMeasurer;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Types of renderers available
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,6 +35,8 @@ pub enum RendererType {
     Wgpu,
     /// WebGL-based renderer (for web)
     WebGL,
+    /// Headless renderer that records visits without drawing
+    Null,
     /// Automatic selection based on platform
     Auto,
 }
@@ -98,6 +114,8 @@ pub struct RenderStats {
     pub vertex_count: u32,
     /// Number of components rendered in last frame
     pub component_count: u32,
+    /// Number of components skipped by selective rendering in last frame
+    pub skipped_components: u32,
 }
 
 /// Enhanced renderer interface with performance monitoring
@@ -121,6 +139,22 @@ pub trait Renderer {
         self.render(root, context)
     }
 
+    /// Render a component tree into an in-memory image instead of a surface
+    ///
+    /// Returns tightly packed RGBA8 pixels, `viewport_width * viewport_height`
+    /// of them in row-major order, which suits golden-image tests and
+    /// thumbnails.
+    fn render_to_buffer(
+        &mut self,
+        _root: &Node,
+        _context: &mut RenderContext,
+    ) -> Result<Vec<u8>, crate::Error> {
+        Err(crate::Error::Renderer(format!(
+            "{} does not support rendering to a buffer",
+            self.name()
+        )))
+    }
+
     /// Flush any pending operations
     fn flush(&mut self) -> Result<(), crate::Error> {
         Ok(()) // Default implementation does nothing
@@ -134,6 +168,13 @@ pub trait Renderer {
     /// Get the renderer name
     fn name(&self) -> &str;
 
+    /// Measurer that sizes text the way this renderer draws it
+    ///
+    /// Layout estimates text widths from the font size when this is `None`.
+    fn text_measurer(&self) -> Option<Arc<dyn TextMeasurer>> {
+        None
+    }
+
     /// Get render statistics
     fn get_stats(&self) -> RenderStats {
         RenderStats::default()
@@ -188,6 +229,7 @@ pub fn create_renderer(renderer_type: RendererType) -> Result<Box<dyn Renderer>,
                 ))
             }
         }
+        RendererType::Null => Ok(Box::new(NullRenderer::new())),
         RendererType::WebGL => {
             #[cfg(feature = "web")]
             {
@@ -249,6 +291,9 @@ pub fn create_renderer(renderer_type: RendererType) -> Result<Box<dyn Renderer>,
 }
 
 /// Enhanced renderer composition for hybrid UIs with performance monitoring
+///
+/// Each layer paints the tree in [`paint_order`], so `z_index` stacking holds
+/// within both the 3D and the 2D pass.
 pub struct CompositeRenderer {
     /// The 2D renderer (usually Skia)
     pub renderer_2d: Box<dyn Renderer>,
@@ -0,0 +1,220 @@
+//! Headless renderer that records what it would draw without drawing anything
+
+use std::collections::HashMap;
+
+use crate::component::{ComponentId, Node};
+use crate::layout::Rect;
+use crate::renderer::{paint_order, RenderContext, RenderStats, Renderer};
+
+/// A node visited during a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisitedNode {
+    /// ID of the visited node
+    pub node_id: Option<usize>,
+    /// Component attached to the node, if any
+    pub component_id: Option<ComponentId>,
+    /// Layout rectangle resolved for the node, if layout has run
+    pub rect: Option<Rect>,
+}
+
+/// Everything a [`NullRenderer`] has recorded
+#[derive(Debug, Clone, Default)]
+pub struct RenderLog {
+    /// Nodes visited in the last frame, in draw order
+    pub visited: Vec<VisitedNode>,
+    /// Number of times each component has been rendered across all frames
+    pub render_counts: HashMap<ComponentId, u32>,
+}
+
+impl RenderLog {
+    /// Number of times a component has been rendered
+    pub fn render_count(&self, component_id: ComponentId) -> u32 {
+        self.render_counts.get(&component_id).copied().unwrap_or(0)
+    }
+
+    /// Resolved rectangle of a node visited in the last frame
+    pub fn rect_of(&self, node_id: usize) -> Option<Rect> {
+        self.visited
+            .iter()
+            .find(|visited| visited.node_id == Some(node_id))
+            .and_then(|visited| visited.rect)
+    }
+}
+
+/// Renderer that performs no drawing
+///
+/// Useful for integration tests and server-side layout, where the component
+/// lifecycle should run without a GPU or Skia. Every node it visits is
+/// recorded in a [`RenderLog`].
+#[derive(Debug, Default)]
+pub struct NullRenderer {
+    log: RenderLog,
+    stats: RenderStats,
+}
+
+impl NullRenderer {
+    /// Create a new null renderer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get everything recorded so far
+    pub fn log(&self) -> &RenderLog {
+        &self.log
+    }
+
+    /// Forget all recorded frames and render counts
+    pub fn clear_log(&mut self) {
+        self.log = RenderLog::default();
+    }
+
+    /// Record a node and its descendants in paint order
+    fn visit(&mut self, root: &Node) {
+        for item in paint_order(root) {
+            let node = item.node;
+            let component_id = node.component().map(|component| component.id());
+            if let Some(id) = component_id {
+                *self.log.render_counts.entry(id).or_insert(0) += 1;
+            }
+            self.log.visited.push(VisitedNode {
+                node_id: node.id(),
+                component_id,
+                rect: node.layout_rect(),
+            });
+        }
+    }
+
+    /// Record only the subtrees rooted at dirty components
+    fn visit_dirty(&mut self, node: &Node, dirty_components: &[ComponentId]) {
+        let is_dirty = node
+            .component()
+            .is_some_and(|component| dirty_components.contains(&component.id()));
+        if is_dirty {
+            self.visit(node);
+            return;
+        }
+
+        for child in node.children() {
+            self.visit_dirty(child, dirty_components);
+        }
+    }
+
+    /// Update the stats after a frame that visited `total` nodes in all
+    fn finish_frame(&mut self, total: u32) {
+        let visited = self.log.visited.len() as u32;
+        self.stats.frame_count += 1;
+        self.stats.draw_calls = 0;
+        self.stats.vertex_count = 0;
+        self.stats.component_count = visited;
+        self.stats.skipped_components = total - visited;
+    }
+}
+
+impl Renderer for NullRenderer {
+    fn render(&mut self, root: &Node, _context: &mut RenderContext) -> Result<(), crate::Error> {
+        self.log.visited.clear();
+        self.visit(root);
+        self.finish_frame(count_nodes(root));
+        Ok(())
+    }
+
+    fn render_selective(
+        &mut self,
+        root: &Node,
+        _context: &mut RenderContext,
+        dirty_components: &[ComponentId],
+    ) -> Result<(), crate::Error> {
+        self.log.visited.clear();
+        self.visit_dirty(root, dirty_components);
+        self.finish_frame(count_nodes(root));
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "NullRenderer"
+    }
+
+    fn get_stats(&self) -> RenderStats {
+        self.stats.clone()
+    }
+
+    fn reset_stats(&mut self) {
+        self.stats = RenderStats::default();
+    }
+}
+
+/// Count a node and all of its descendants
+fn count_nodes(node: &Node) -> u32 {
+    1 + node.children().iter().map(count_nodes).sum::<u32>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::ComponentInstance;
+    use crate::kit::components::card::{Card, CardProps};
+    use crate::renderer::{create_renderer, RendererType};
+
+    fn card_node(rect: Rect) -> Node {
+        let props = CardProps {
+            title: None,
+            elevation: None,
+            border_radius: None,
+            bordered: None,
+            padding: None,
+            children: None,
+        };
+        let mut node = Node::new(Some(ComponentInstance::new(Card::default(), props)));
+        node.set_layout_rect(rect);
+        node
+    }
+
+    fn component_id(node: &Node) -> ComponentId {
+        node.component().unwrap().id()
+    }
+
+    #[test]
+    fn test_records_visits_rects_and_render_counts() {
+        let mut root = Node::default();
+        root.set_layout_rect(Rect::new(0.0, 0.0, 200.0, 100.0));
+        root.add_child(card_node(Rect::new(0.0, 0.0, 100.0, 100.0)));
+        root.add_child(card_node(Rect::new(100.0, 0.0, 100.0, 100.0)));
+        let first = component_id(&root.children()[0]);
+        let second = component_id(&root.children()[1]);
+        let second_node_id = root.children()[1].id().unwrap();
+
+        let mut renderer = NullRenderer::new();
+        let mut context = RenderContext::new(200, 100);
+        renderer.render(&root, &mut context).unwrap();
+
+        let log = renderer.log();
+        assert_eq!(log.visited.len(), 3);
+        assert_eq!(log.visited[0].node_id, root.id());
+        assert_eq!(log.visited[2].component_id, Some(second));
+        assert_eq!(
+            log.rect_of(second_node_id),
+            Some(Rect::new(100.0, 0.0, 100.0, 100.0))
+        );
+
+        // A state change in the second card re-renders only that card
+        renderer
+            .render_selective(&root, &mut context, &[second])
+            .unwrap();
+
+        let log = renderer.log();
+        assert_eq!(log.visited.len(), 1);
+        assert_eq!(log.render_count(first), 1);
+        assert_eq!(log.render_count(second), 2);
+
+        let stats = renderer.get_stats();
+        assert_eq!(stats.frame_count, 2);
+        assert_eq!(stats.component_count, 1);
+        assert_eq!(stats.skipped_components, 2);
+    }
+
+    #[test]
+    fn test_create_null_renderer() {
+        let renderer = create_renderer(RendererType::Null).unwrap();
+        assert_eq!(renderer.name(), "NullRenderer");
+    }
+}
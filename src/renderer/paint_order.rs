@@ -0,0 +1,137 @@
+//! Paint ordering that honours `z_index`
+//!
+//! Renderers walk the list built here instead of the node tree, so
+//! overlapping elements stack by `z_index` rather than by tree order alone.
+//!
+//! Any node with a `z_index` establishes a stacking context. Inside a
+//! context, the context root paints first, then its descendants sorted by
+//! `(z_index, tree order)`. Descendants without a `z_index` count as zero and
+//! keep their tree order; a nested context paints as a unit at its own
+//! `z_index`, so nothing inside it can interleave with the outside.
+
+use crate::component::Node;
+use crate::layout::{multiply_matrices, TransformMatrix, IDENTITY_MATRIX};
+
+/// A node to paint, with its transform and those of its ancestors
+#[derive(Debug, Clone, Copy)]
+pub struct PaintItem<'a> {
+    /// Node to paint
+    pub node: &'a Node,
+    /// Viewport transform for the node's own content
+    pub matrix: TransformMatrix,
+}
+
+/// Nodes of a tree in the order they should be painted, back to front
+pub fn paint_order(root: &Node) -> Vec<PaintItem<'_>> {
+    let mut items = Vec::new();
+    collect_context(root, node_matrix(root, IDENTITY_MATRIX), &mut items);
+    items
+}
+
+/// Paint a stacking context root followed by its sorted descendants
+fn collect_context<'a>(node: &'a Node, matrix: TransformMatrix, items: &mut Vec<PaintItem<'a>>) {
+    items.push(PaintItem { node, matrix });
+
+    let mut layers = Vec::new();
+    for child in node.children() {
+        collect_layers(child, matrix, &mut layers);
+    }
+
+    // The sort is stable, so equal z-indices keep their tree order
+    layers.sort_by_key(|(z_index, _)| *z_index);
+    items.extend(layers.into_iter().flat_map(|(_, layer)| layer));
+}
+
+/// Gather a node and its descendants as layers of the enclosing context
+fn collect_layers<'a>(
+    node: &'a Node,
+    parent_matrix: TransformMatrix,
+    layers: &mut Vec<(i32, Vec<PaintItem<'a>>)>,
+) {
+    let matrix = node_matrix(node, parent_matrix);
+
+    if let Some(z_index) = node.style().and_then(|style| style.z_index) {
+        let mut context = Vec::new();
+        collect_context(node, matrix, &mut context);
+        layers.push((z_index, context));
+        return;
+    }
+
+    layers.push((0, vec![PaintItem { node, matrix }]));
+    for child in node.children() {
+        collect_layers(child, matrix, layers);
+    }
+}
+
+/// Combine a node's own transform with its ancestors'
+fn node_matrix(node: &Node, parent_matrix: TransformMatrix) -> TransformMatrix {
+    match node.transform_matrix() {
+        Some(own) => multiply_matrices(&parent_matrix, &own),
+        None => parent_matrix,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Style;
+
+    fn node_with_z(z_index: Option<i32>) -> Node {
+        let mut node = Node::default();
+        node.set_style(Style {
+            z_index,
+            ..Default::default()
+        });
+        node
+    }
+
+    fn order(root: &Node) -> Vec<usize> {
+        paint_order(root)
+            .iter()
+            .map(|item| item.node.id_value())
+            .collect()
+    }
+
+    #[test]
+    fn test_lower_z_index_sibling_paints_behind() {
+        let mut root = Node::default();
+        let high = node_with_z(Some(2));
+        let low = node_with_z(Some(1));
+        let (high_id, low_id) = (high.id_value(), low.id_value());
+        root.add_child(high);
+        root.add_child(low);
+
+        assert_eq!(order(&root), vec![root.id_value(), low_id, high_id]);
+    }
+
+    #[test]
+    fn test_stacking_context_paints_as_a_unit() {
+        // root
+        // ├── context (z 1)
+        // │   └── inner (z 10)
+        // ├── plain
+        // └── above (z 2)
+        let mut root = Node::default();
+        let mut context = node_with_z(Some(1));
+        let inner = node_with_z(Some(10));
+        let plain = node_with_z(None);
+        let above = node_with_z(Some(2));
+        let ids = [
+            context.id_value(),
+            inner.id_value(),
+            plain.id_value(),
+            above.id_value(),
+        ];
+        context.add_child(inner);
+        root.add_child(context);
+        root.add_child(plain);
+        root.add_child(above);
+
+        // inner's z-index only counts within its parent's context
+        let [context, inner, plain, above] = ids;
+        assert_eq!(
+            order(&root),
+            vec![root.id_value(), plain, context, inner, above]
+        );
+    }
+}
@@ -1,14 +1,35 @@
 // Skia renderer implementation for the Orbit UI framework
-use std::{error::Error, fmt, sync::Arc};
+use std::{collections::HashMap, error::Error, fmt, sync::Arc};
 
 use skia_safe::{
+    canvas::SaveLayerRec,
+    color_filters,
+    font_style::{Slant, Weight, Width},
     gpu::gl::FramebufferInfo,
     gpu::{gl::Interface, BackendRenderTarget, DirectContext, Protected, SurfaceOrigin},
-    Color, Color4f, ColorType, Paint, Surface, M44,
+    image_filters, images,
+    region::RegionOp,
+    surfaces, AlphaType, Canvas, Color, Color4f, ColorType, Data, Font, FontMgr,
+    FontStyle as SkFontStyle, IRect, Image, ImageFilter, ImageInfo, Matrix, Paint, Region, Surface,
+    M44,
 };
 
-use crate::component::Node;
-use crate::renderer::RenderContext;
+use crate::component::{ComponentId, Node};
+use crate::layout::{wrap_lines, Rect, Size, TextMeasurer, DEFAULT_LINE_HEIGHT, IDENTITY_MATRIX};
+use crate::renderer::{
+    paint_order, ImageCache, PaintItem, RenderContext, RenderStats, TextMeasureCache,
+    TextMeasureKey,
+};
+use crate::style::{Filter, FontStyle, FontWeight, ObjectFit, Style, TextAlign};
+
+/// Family used when none of the requested font families are available
+const DEFAULT_FONT_FAMILY: &str = "sans-serif";
+
+/// Font size used when a style does not specify one
+const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+/// Fill color drawn in place of images that are still decoding
+const IMAGE_PLACEHOLDER_COLOR: Color = Color::from_rgb(0xe0, 0xe0, 0xe0);
 
 /// A message sent to the renderer thread
 #[derive(Clone)]
@@ -78,6 +99,18 @@ pub(crate) struct SkiaState {
 pub struct SkiaRenderer {
     /// Renderer state
     pub(crate) state: Option<SkiaState>,
+
+    /// Font manager used to resolve typefaces
+    font_mgr: FontMgr,
+
+    /// Skia images uploaded from the shared image cache, keyed by source
+    textures: HashMap<String, Image>,
+
+    /// Text measurements, shared with the layout engine
+    text_cache: Arc<TextMeasureCache>,
+
+    /// Statistics for the last rendered frame
+    stats: RenderStats,
 }
 
 // Explicitly implement Send for SkiaRenderer since we control the access to the state
@@ -86,7 +119,90 @@ unsafe impl Send for SkiaRenderer {}
 impl SkiaRenderer {
     /// Create a new Skia renderer
     pub fn new() -> Self {
-        Self { state: None }
+        Self {
+            state: None,
+            font_mgr: FontMgr::new(),
+            textures: HashMap::new(),
+            text_cache: TextMeasureCache::global(),
+            stats: RenderStats::default(),
+        }
+    }
+
+    /// Use a specific text measurement cache instead of the global one
+    pub fn with_text_cache(mut self, text_cache: Arc<TextMeasureCache>) -> Self {
+        self.text_cache = text_cache;
+        self
+    }
+
+    /// Get the text measurement cache
+    pub fn text_cache(&self) -> &Arc<TextMeasureCache> {
+        &self.text_cache
+    }
+
+    /// Measure the size of `text` when drawn with `style`
+    ///
+    /// The width is the advance of the whole string and the height is the
+    /// font's line spacing, which is what layout needs for intrinsic sizing.
+    /// Results are cached, so measuring the same string again is cheap.
+    pub fn measure_text(&self, text: &str, style: &Style) -> Size {
+        let key = TextMeasureKey::new(text, style, None);
+        self.text_cache.measure(key, || {
+            let font = resolve_font(&self.font_mgr, style);
+            let (width, _) = font.measure_str(text, None);
+            let (line_spacing, _) = font.metrics();
+            Size::new(width, line_spacing)
+        })
+    }
+
+    /// Draw a node tree onto the current surface
+    fn draw_tree(&mut self, root: &Node) -> RendererResult {
+        let Self {
+            state,
+            font_mgr,
+            textures,
+            stats,
+            ..
+        } = self;
+        let Some(state) = state else {
+            return Err(Box::new(RendererError::GeneralError(
+                "Renderer not initialized".into(),
+            )));
+        };
+
+        let canvas = state.surface.canvas();
+        canvas.clear(Color::WHITE);
+        let drawn = draw_nodes(canvas, font_mgr, textures, root);
+
+        stats.frame_count += 1;
+        stats.component_count = drawn;
+        stats.skipped_components = 0;
+
+        Ok(())
+    }
+
+    /// Repaint only the regions covered by dirty components
+    fn draw_dirty(&mut self, root: &Node, dirty_components: &[ComponentId]) -> RendererResult {
+        let Self {
+            state,
+            font_mgr,
+            textures,
+            stats,
+            ..
+        } = self;
+        let Some(state) = state else {
+            return Err(Box::new(RendererError::GeneralError(
+                "Renderer not initialized".into(),
+            )));
+        };
+
+        let canvas = state.surface.canvas();
+        let (drawn, skipped) = repaint_damaged(canvas, font_mgr, textures, root, dirty_components);
+
+        stats.frame_count += 1;
+        stats.component_count = drawn;
+        stats.skipped_components = skipped;
+
+        Ok(())
     }
 
     /// Initialize Skia state
@@ -247,6 +363,43 @@ impl Default for SkiaRenderer {
     }
 }
 
+/// Measures text with the fonts Skia draws it with
+#[derive(Clone)]
+pub struct SkiaTextMeasurer {
+    font_mgr: FontMgr,
+}
+
+// Skia's font managers are reference counted atomically and lock their own
+// state, so one can be used from any thread
+unsafe impl Send for SkiaTextMeasurer {}
+unsafe impl Sync for SkiaTextMeasurer {}
+
+impl SkiaTextMeasurer {
+    /// Measure with the fonts of `font_mgr`
+    pub fn new(font_mgr: FontMgr) -> Self {
+        Self { font_mgr }
+    }
+}
+
+impl Default for SkiaTextMeasurer {
+    fn default() -> Self {
+        Self::new(FontMgr::new())
+    }
+}
+
+impl fmt::Debug for SkiaTextMeasurer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SkiaTextMeasurer").finish_non_exhaustive()
+    }
+}
+
+impl TextMeasurer for SkiaTextMeasurer {
+    fn measure_width(&self, text: &str, style: &Style) -> f32 {
+        let font = resolve_font(&self.font_mgr, style);
+        font.measure_str(text, None).0
+    }
+}
+
 // Implement the Renderer trait for SkiaRenderer
 impl crate::renderer::Renderer for SkiaRenderer {
     fn init(&mut self) -> Result<(), crate::Error> {
@@ -258,7 +411,7 @@ impl crate::renderer::Renderer for SkiaRenderer {
         Ok(())
     }
 
-    fn render(&mut self, _root: &Node, _context: &mut RenderContext) -> Result<(), crate::Error> {
+    fn render(&mut self, root: &Node, _context: &mut RenderContext) -> Result<(), crate::Error> {
         // Initialize if not already done
         if self.state.is_none() {
             // Use default dimensions for now
@@ -266,11 +419,75 @@ impl crate::renderer::Renderer for SkiaRenderer {
                 .map_err(|e| crate::Error::Renderer(format!("{e}")))?;
         }
 
-        // Simple implementation - just draw a test circle for now
-        self.draw_test_circle()
+        self.draw_tree(root)
             .map_err(|e| crate::Error::Renderer(format!("{e}")))
     }
 
+    fn render_selective(
+        &mut self,
+        root: &Node,
+        _context: &mut RenderContext,
+        dirty_components: &[ComponentId],
+    ) -> Result<(), crate::Error> {
+        if self.state.is_none() {
+            // The surface starts out blank, so the first frame must be drawn in full
+            self.init_skia(800, 600)
+                .map_err(|e| crate::Error::Renderer(format!("{e}")))?;
+            return self
+                .draw_tree(root)
+                .map_err(|e| crate::Error::Renderer(format!("{e}")));
+        }
+
+        self.draw_dirty(root, dirty_components)
+            .map_err(|e| crate::Error::Renderer(format!("{e}")))
+    }
+
+    fn render_to_buffer(
+        &mut self,
+        root: &Node,
+        context: &mut RenderContext,
+    ) -> Result<Vec<u8>, crate::Error> {
+        let (width, height) = (
+            context.viewport_width as i32,
+            context.viewport_height as i32,
+        );
+        if width <= 0 || height <= 0 {
+            return Err(crate::Error::Renderer(
+                "Cannot render to a buffer with an empty viewport".to_string(),
+            ));
+        }
+
+        // A CPU raster surface needs no GL context, so this also works headless
+        let surface_info = ImageInfo::new(
+            (width, height),
+            ColorType::RGBA8888,
+            AlphaType::Premul,
+            None,
+        );
+        let mut surface = surfaces::raster(&surface_info, None, None)
+            .ok_or_else(|| crate::Error::Renderer("Failed to create raster surface".to_string()))?;
+
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        draw_nodes(canvas, &self.font_mgr, &mut self.textures, root);
+
+        let output_info = ImageInfo::new(
+            (width, height),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        );
+        let row_bytes = width as usize * 4;
+        let mut pixels = vec![0; row_bytes * height as usize];
+        if !surface.read_pixels(&output_info, &mut pixels, row_bytes, (0, 0)) {
+            return Err(crate::Error::Renderer(
+                "Failed to read pixels from raster surface".to_string(),
+            ));
+        }
+
+        Ok(pixels)
+    }
+
     fn flush(&mut self) -> Result<(), crate::Error> {
         // Skia surface doesn't have a flush method like we were expecting
         // Instead, we'll create a snapshot which will finalize any pending drawing operations
@@ -292,6 +509,426 @@ impl crate::renderer::Renderer for SkiaRenderer {
     fn name(&self) -> &str {
         "SkiaRenderer"
     }
+
+    fn text_measurer(&self) -> Option<Arc<dyn TextMeasurer>> {
+        Some(Arc::new(SkiaTextMeasurer::new(self.font_mgr.clone())))
+    }
+
+    fn get_stats(&self) -> RenderStats {
+        self.stats.clone()
+    }
+
+    fn reset_stats(&mut self) {
+        self.stats = RenderStats::default();
+    }
+}
+
+/// Draw a node tree in paint order, returning how many nodes were drawn
+fn draw_nodes(
+    canvas: &Canvas,
+    font_mgr: &FontMgr,
+    textures: &mut HashMap<String, Image>,
+    root: &Node,
+) -> u32 {
+    let items = paint_order(root);
+    for item in &items {
+        draw_item(canvas, font_mgr, textures, item);
+    }
+    items.len() as u32
+}
+
+/// Draw a node's content under its accumulated transform
+fn draw_item(
+    canvas: &Canvas,
+    font_mgr: &FontMgr,
+    textures: &mut HashMap<String, Image>,
+    item: &PaintItem,
+) {
+    if item.matrix == IDENTITY_MATRIX {
+        draw_filtered(canvas, font_mgr, textures, item.node);
+        return;
+    }
+
+    let [a, b, c, d, e, f] = item.matrix;
+    canvas.save();
+    canvas.concat(&Matrix::new_all(a, c, e, b, d, f, 0.0, 0.0, 1.0));
+    draw_filtered(canvas, font_mgr, textures, item.node);
+    canvas.restore();
+}
+
+/// Draw a node's content through its `backdrop-filter` and `filter` chains
+///
+/// The backdrop filter rewrites what has already been painted inside the
+/// node's bounds, then the content is drawn into a layer that the filter
+/// chain is applied to as it is composited. Descendants are painted as
+/// separate items and are not filtered along with their parent.
+fn draw_filtered(
+    canvas: &Canvas,
+    font_mgr: &FontMgr,
+    textures: &mut HashMap<String, Image>,
+    node: &Node,
+) {
+    let style = node.style();
+    let backdrop = style.and_then(|style| filter_chain(style.backdrop_filter.as_deref()?));
+    if let (Some(backdrop), Some(rect)) = (backdrop, node.layout_rect()) {
+        let bounds = to_sk_rect(rect);
+        canvas.save();
+        canvas.clip_rect(bounds, None, true);
+        canvas.save_layer(&SaveLayerRec::default().bounds(&bounds).backdrop(&backdrop));
+        canvas.restore();
+        canvas.restore();
+    }
+
+    let Some(filter) = style.and_then(|style| filter_chain(style.filter.as_deref()?)) else {
+        draw_node_content(canvas, font_mgr, textures, node);
+        return;
+    };
+    let mut paint = Paint::default();
+    paint.set_image_filter(filter);
+    // No layer bounds, since a blur spreads past the node's rect
+    canvas.save_layer(&SaveLayerRec::default().paint(&paint));
+    draw_node_content(canvas, font_mgr, textures, node);
+    canvas.restore();
+}
+
+/// Build an image filter applying `filters` in order, or `None` for an empty chain
+///
+/// Filter functions the renderer doesn't support yet are skipped.
+fn filter_chain(filters: &[Filter]) -> Option<ImageFilter> {
+    filters.iter().fold(None, |input, filter| {
+        let next = match filter {
+            // CSS blur radii are the standard deviation of the Gaussian
+            Filter::Blur(radius) => {
+                image_filters::blur((*radius, *radius), None, input.clone(), None)
+            }
+            filter => {
+                let Some(matrix) = color_matrix(filter) else {
+                    return input;
+                };
+                let color_filter = color_filters::matrix_row_major(&matrix, None);
+                image_filters::color_filter(color_filter, input.clone(), None)
+            }
+        };
+        next.or(input)
+    })
+}
+
+/// Row-major 4x5 color matrix for a color filter function
+///
+/// Matrices follow the Filter Effects specification.
+fn color_matrix(filter: &Filter) -> Option<[f32; 20]> {
+    match *filter {
+        Filter::Brightness(amount) => Some(scale_matrix(amount, amount, amount, 1.0)),
+        Filter::Opacity(amount) => Some(scale_matrix(1.0, 1.0, 1.0, amount)),
+        Filter::Grayscale(amount) => {
+            let inverse = 1.0 - amount.clamp(0.0, 1.0);
+            #[rustfmt::skip]
+            let matrix = [
+                0.2126 + 0.7874 * inverse, 0.7152 - 0.7152 * inverse, 0.0722 - 0.0722 * inverse, 0.0, 0.0,
+                0.2126 - 0.2126 * inverse, 0.7152 + 0.2848 * inverse, 0.0722 - 0.0722 * inverse, 0.0, 0.0,
+                0.2126 - 0.2126 * inverse, 0.7152 - 0.7152 * inverse, 0.0722 + 0.9278 * inverse, 0.0, 0.0,
+                0.0, 0.0, 0.0, 1.0, 0.0,
+            ];
+            Some(matrix)
+        }
+        _ => None,
+    }
+}
+
+/// Color matrix scaling each channel
+fn scale_matrix(r: f32, g: f32, b: f32, a: f32) -> [f32; 20] {
+    #[rustfmt::skip]
+    let matrix = [
+        r, 0.0, 0.0, 0.0, 0.0,
+        0.0, g, 0.0, 0.0, 0.0,
+        0.0, 0.0, b, 0.0, 0.0,
+        0.0, 0.0, 0.0, a, 0.0,
+    ];
+    matrix
+}
+
+/// Draw a node's own background, image and text
+fn draw_node_content(
+    canvas: &Canvas,
+    font_mgr: &FontMgr,
+    textures: &mut HashMap<String, Image>,
+    node: &Node,
+) {
+    if let (Some(rect), Some(style)) = (node.layout_rect(), node.style()) {
+        if let Some((r, g, b, a)) = style.background_color.as_ref().and_then(|c| c.to_rgba()) {
+            let paint = Paint::new(Color4f::new(r, g, b, a), None);
+            canvas.draw_rect(to_sk_rect(rect), &paint);
+        }
+    }
+
+    if let (Some(source), Some(rect)) = (node.image_source(), node.content_rect()) {
+        let object_fit = node
+            .style()
+            .and_then(|style| style.object_fit)
+            .unwrap_or_default();
+        draw_image(canvas, textures, source, object_fit, rect);
+    }
+
+    if let Some(text) = node.text() {
+        if let Some(rect) = node.content_rect() {
+            let default_style = Style::default();
+            let style = node.style().unwrap_or(&default_style);
+            draw_text(canvas, font_mgr, text, style, rect);
+        }
+    }
+}
+
+/// Clear and redraw the bounds of the dirty components
+///
+/// Every node overlapping a damaged region is redrawn, clipped to that region,
+/// so parents and siblings painted underneath or on top stay correct. Returns
+/// the number of nodes drawn and skipped.
+fn repaint_damaged(
+    canvas: &Canvas,
+    font_mgr: &FontMgr,
+    textures: &mut HashMap<String, Image>,
+    root: &Node,
+    dirty_components: &[ComponentId],
+) -> (u32, u32) {
+    let mut damage = Vec::new();
+    collect_damage(root, dirty_components, &mut damage);
+    if damage.is_empty() {
+        return (0, count_nodes(root));
+    }
+
+    let mut region = Region::new();
+    for rect in &damage {
+        region.op_rect(to_sk_irect(*rect), RegionOp::Union);
+    }
+
+    canvas.save();
+    canvas.clip_region(&region, None);
+    canvas.clear(Color::WHITE);
+    let mut counts = (0, 0);
+    draw_damaged(canvas, font_mgr, textures, root, &damage, &mut counts);
+    canvas.restore();
+
+    counts
+}
+
+/// Draw the nodes overlapping `damage` in paint order, tallying drawn and
+/// skipped nodes
+fn draw_damaged(
+    canvas: &Canvas,
+    font_mgr: &FontMgr,
+    textures: &mut HashMap<String, Image>,
+    root: &Node,
+    damage: &[Rect],
+    counts: &mut (u32, u32),
+) {
+    // Children may overflow their parent, so every node is checked on its own
+    for item in paint_order(root) {
+        let overlaps = item
+            .node
+            .layout_rect()
+            .is_some_and(|rect| damage.iter().any(|region| region.intersects(&rect)));
+        if overlaps {
+            draw_item(canvas, font_mgr, textures, &item);
+            counts.0 += 1;
+        } else {
+            counts.1 += 1;
+        }
+    }
+}
+
+/// Collect the bounds of every subtree rooted at a dirty component
+fn collect_damage(node: &Node, dirty_components: &[ComponentId], damage: &mut Vec<Rect>) {
+    let is_dirty = node
+        .component()
+        .is_some_and(|component| dirty_components.contains(&component.id()));
+    if is_dirty {
+        damage.extend(subtree_bounds(node));
+        return;
+    }
+
+    for child in node.children() {
+        collect_damage(child, dirty_components, damage);
+    }
+}
+
+/// Bounding rectangle of a node and all of its descendants
+fn subtree_bounds(node: &Node) -> Option<Rect> {
+    node.children()
+        .iter()
+        .filter_map(subtree_bounds)
+        .fold(node.layout_rect(), |bounds, rect| match bounds {
+            Some(bounds) => Some(bounds.union(&rect)),
+            None => Some(rect),
+        })
+}
+
+/// Count a node and all of its descendants
+fn count_nodes(node: &Node) -> u32 {
+    1 + node.children().iter().map(count_nodes).sum::<u32>()
+}
+
+/// Draw an image fitted into `rect`, or a placeholder while it decodes
+fn draw_image(
+    canvas: &Canvas,
+    textures: &mut HashMap<String, Image>,
+    source: &str,
+    object_fit: ObjectFit,
+    rect: Rect,
+) {
+    if !textures.contains_key(source) {
+        let Some(decoded) = ImageCache::global().load(source).image() else {
+            let mut placeholder = Paint::default();
+            placeholder.set_color(IMAGE_PLACEHOLDER_COLOR);
+            canvas.draw_rect(to_sk_rect(rect), &placeholder);
+            return;
+        };
+
+        let info = ImageInfo::new(
+            (decoded.width as i32, decoded.height as i32),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        );
+        let row_bytes = decoded.width as usize * 4;
+        match images::raster_from_data(&info, Data::new_copy(&decoded.pixels), row_bytes) {
+            Some(image) => {
+                textures.insert(source.to_string(), image);
+            }
+            None => {
+                crate::trace::warn_event!("Failed to create Skia image for {source}");
+                return;
+            }
+        }
+    }
+
+    let image = &textures[source];
+    let destination =
+        object_fit.destination_rect(image.width() as f32, image.height() as f32, rect);
+
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+
+    canvas.save();
+    canvas.clip_rect(to_sk_rect(rect), None, true);
+    canvas.draw_image_rect(image, None, to_sk_rect(destination), &paint);
+    canvas.restore();
+}
+
+/// Draw text wrapped and aligned within `rect`
+///
+/// Lines wrap at the rect's width and are spaced by the line height, the way
+/// layout sized the node for them.
+fn draw_text(canvas: &Canvas, font_mgr: &FontMgr, text: &str, style: &Style, rect: Rect) {
+    let font = resolve_font(font_mgr, style);
+    let (_, metrics) = font.metrics();
+    // The same measurement SkiaTextMeasurer gives layout
+    let lines = wrap_lines(text, rect.size.width, |line| font.measure_str(line, None).0);
+
+    let layout = style.layout_style.as_ref();
+    let direction = layout
+        .and_then(|layout| layout.direction)
+        .unwrap_or_default();
+    let line_height = layout
+        .and_then(|layout| layout.line_height)
+        .unwrap_or(DEFAULT_LINE_HEIGHT);
+    let advance = font.size() * line_height;
+    // Extra line height is shared above and below the glyphs; ascent is
+    // negative, so this puts the first line's top at the rect's top
+    let leading = (advance - (metrics.descent - metrics.ascent)) / 2.0;
+    let first_baseline = rect.origin.y + leading - metrics.ascent;
+    let align = style.text_align.as_ref().unwrap_or(&TextAlign::Start);
+
+    let (r, g, b, a) = style
+        .color
+        .as_ref()
+        .and_then(|c| c.to_rgba())
+        .unwrap_or((0.0, 0.0, 0.0, 1.0));
+    let mut paint = Paint::new(Color4f::new(r, g, b, a), None);
+    paint.set_anti_alias(true);
+
+    canvas.save();
+    canvas.clip_rect(to_sk_rect(rect), None, true);
+    for (index, line) in lines.iter().enumerate() {
+        let (line_width, _) = font.measure_str(line, None);
+        let x = match align.resolve(direction) {
+            TextAlign::Center => rect.origin.x + (rect.size.width - line_width) / 2.0,
+            TextAlign::Right => rect.origin.x + rect.size.width - line_width,
+            _ => rect.origin.x,
+        };
+        let baseline = first_baseline + index as f32 * advance;
+        canvas.draw_str(line, (x, baseline), &font, &paint);
+    }
+    canvas.restore();
+}
+
+/// Resolve the font for a style, falling back to the default family
+///
+/// Each entry of a comma-separated `font-family` list is tried in order.
+fn resolve_font(font_mgr: &FontMgr, style: &Style) -> Font {
+    let font_style = to_sk_font_style(style);
+    let size = style.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+
+    let typeface = style
+        .font_family
+        .iter()
+        .flat_map(|families| families.split(','))
+        .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+        .filter(|family| !family.is_empty())
+        .find_map(|family| font_mgr.match_family_style(family, font_style))
+        .or_else(|| font_mgr.match_family_style(DEFAULT_FONT_FAMILY, font_style))
+        .or_else(|| font_mgr.legacy_make_typeface(None, font_style));
+
+    match typeface {
+        Some(typeface) => Font::from_typeface(typeface, size),
+        None => {
+            let mut font = Font::default();
+            font.set_size(size);
+            font
+        }
+    }
+}
+
+/// Convert the weight and slant of a style to a Skia font style
+fn to_sk_font_style(style: &Style) -> SkFontStyle {
+    let weight = match style.font_weight {
+        Some(FontWeight::Thin) => Weight::THIN,
+        Some(FontWeight::ExtraLight) => Weight::EXTRA_LIGHT,
+        Some(FontWeight::Light) => Weight::LIGHT,
+        Some(FontWeight::Normal) | None => Weight::NORMAL,
+        Some(FontWeight::Medium) => Weight::MEDIUM,
+        Some(FontWeight::SemiBold) => Weight::SEMI_BOLD,
+        Some(FontWeight::Bold) => Weight::BOLD,
+        Some(FontWeight::ExtraBold) => Weight::EXTRA_BOLD,
+        Some(FontWeight::Black) => Weight::BLACK,
+        Some(FontWeight::Numeric(weight)) => Weight::from(weight as i32),
+    };
+    let slant = match style.font_style {
+        Some(FontStyle::Italic) => Slant::Italic,
+        Some(FontStyle::Oblique(_)) => Slant::Oblique,
+        Some(FontStyle::Normal) | None => Slant::Upright,
+    };
+
+    SkFontStyle::new(weight, Width::NORMAL, slant)
+}
+
+/// Convert a layout rectangle to the smallest enclosing integer rectangle
+fn to_sk_irect(rect: Rect) -> IRect {
+    IRect::from_ltrb(
+        rect.x().floor() as i32,
+        rect.y().floor() as i32,
+        rect.max_x().ceil() as i32,
+        rect.max_y().ceil() as i32,
+    )
+}
+
+/// Convert a layout rectangle to a Skia rectangle
+fn to_sk_rect(rect: Rect) -> skia_safe::Rect {
+    skia_safe::Rect::from_xywh(
+        rect.origin.x,
+        rect.origin.y,
+        rect.size.width,
+        rect.size.height,
+    )
 }
 
 // Implement From for common error conversions to allow ? operator
@@ -311,3 +948,114 @@ impl From<std::io::Error> for RendererError {
 }
 
 // Additional specific conversions can be added as needed
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::ComponentInstance;
+    use crate::kit::components::card::{Card, CardProps};
+    use crate::renderer::Renderer;
+
+    fn card_node(rect: Rect) -> Node {
+        let props = CardProps {
+            title: None,
+            elevation: None,
+            border_radius: None,
+            bordered: None,
+            padding: None,
+            children: None,
+        };
+        let mut node = Node::new(Some(ComponentInstance::new(Card::default(), props)));
+        node.set_layout_rect(rect);
+        node.set_style(Style {
+            background_color: Some(crate::style::Color::Rgba(0.0, 0.0, 1.0, 1.0)),
+            ..Default::default()
+        });
+        node
+    }
+
+    #[test]
+    fn test_dirty_leaf_redraws_fewer_nodes() {
+        let mut root = Node::default();
+        root.set_layout_rect(Rect::new(0.0, 0.0, 400.0, 100.0));
+        for i in 0..10 {
+            root.add_child(card_node(Rect::new(i as f32 * 40.0, 0.0, 40.0, 40.0)));
+        }
+        let dirty = root.children()[3].component().unwrap().id();
+
+        let info = ImageInfo::new((400, 100), ColorType::RGBA8888, AlphaType::Premul, None);
+        let mut surface = surfaces::raster(&info, None, None).unwrap();
+        let canvas = surface.canvas();
+        let font_mgr = FontMgr::new();
+        let mut textures = HashMap::new();
+
+        let full = draw_nodes(canvas, &font_mgr, &mut textures, &root);
+        let (drawn, skipped) = repaint_damaged(canvas, &font_mgr, &mut textures, &root, &[dirty]);
+
+        assert_eq!(full, 11);
+        // Only the root underneath and the dirty card itself overlap the damage
+        assert_eq!(drawn, 2);
+        assert_eq!(skipped, 9);
+
+        // Nothing is drawn when no dirty component is in the tree
+        let (drawn, skipped) = repaint_damaged(
+            canvas,
+            &font_mgr,
+            &mut textures,
+            &root,
+            &[ComponentId::new()],
+        );
+        assert_eq!((drawn, skipped), (0, 11));
+    }
+
+    #[test]
+    fn test_render_red_rect_to_buffer() {
+        let mut root = Node::default();
+        root.set_layout_rect(Rect::new(0.0, 0.0, 8.0, 4.0));
+        root.set_style(Style {
+            background_color: Some(crate::style::Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            ..Default::default()
+        });
+
+        let mut renderer = SkiaRenderer::new();
+        let mut context = RenderContext::new(16, 8);
+        let pixels = renderer.render_to_buffer(&root, &mut context).unwrap();
+
+        assert_eq!(pixels.len(), 16 * 8 * 4);
+        let pixel = |x: usize, y: usize| {
+            let offset = (y * 16 + x) * 4;
+            &pixels[offset..offset + 4]
+        };
+        assert_eq!(pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(7, 3), [255, 0, 0, 255]);
+        // Outside the rect the white clear color shows through
+        assert_eq!(pixel(12, 6), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_grayscale_filter_desaturates() {
+        let render = |filter: Option<Vec<Filter>>| {
+            let mut root = Node::default();
+            root.set_layout_rect(Rect::new(0.0, 0.0, 8.0, 8.0));
+            root.set_style(Style {
+                background_color: Some(crate::style::Color::Rgba(1.0, 0.2, 0.0, 1.0)),
+                filter,
+                ..Default::default()
+            });
+            let mut context = RenderContext::new(8, 8);
+            let pixels = SkiaRenderer::new()
+                .render_to_buffer(&root, &mut context)
+                .unwrap();
+            let pixel = &pixels[(4 * 8 + 4) * 4..(4 * 8 + 4) * 4 + 3];
+            // Saturation as the spread between the strongest and weakest channel
+            pixel.iter().max().unwrap() - pixel.iter().min().unwrap()
+        };
+
+        let plain = render(None);
+        let half = render(Some(vec![Filter::Grayscale(0.5)]));
+        let gray = render(Some(vec![Filter::Grayscale(1.0)]));
+        assert!(plain > 200);
+        assert!(half < plain);
+        assert!(gray <= 1);
+    }
+}
@@ -0,0 +1,221 @@
+//! Shared cache of text measurements
+//!
+//! Shaping a string is far more expensive than looking up its size, and
+//! layout and painting tend to measure the same labels every frame. Both go
+//! through this cache so each string is shaped once per style.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::layout::Size;
+use crate::style::Style;
+
+/// Number of measurements kept by the shared cache
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Everything that affects the measured size of a string
+///
+/// Float values are stored as bits so the key can be hashed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextMeasureKey {
+    text: String,
+    font_family: Option<String>,
+    font_size: Option<u32>,
+    font_weight: Option<u16>,
+    max_width: Option<u32>,
+}
+
+impl TextMeasureKey {
+    /// Create a key for `text` drawn with the font properties of `style`
+    ///
+    /// `max_width` is the width the text wraps at, if any.
+    pub fn new(text: &str, style: &Style, max_width: Option<f32>) -> Self {
+        Self {
+            text: text.to_string(),
+            font_family: style.font_family.clone(),
+            font_size: style.font_size.map(f32::to_bits),
+            font_weight: style.font_weight.as_ref().map(|weight| weight.value()),
+            max_width: max_width.map(f32::to_bits),
+        }
+    }
+}
+
+struct CacheEntry {
+    size: Size,
+    last_used: u64,
+}
+
+/// Least recently used cache of measured text sizes
+///
+/// Hit and miss counters are kept so the cache's effectiveness can be
+/// reported alongside other frame statistics.
+pub struct TextMeasureCache {
+    entries: RwLock<HashMap<TextMeasureKey, CacheEntry>>,
+    max_size: usize,
+    /// Monotonic counter used to order entries by last use
+    clock: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TextMeasureCache {
+    /// Create a cache holding up to `max_size` measurements
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            max_size: max_size.max(1),
+            clock: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the process-wide cache shared by layout and the renderers
+    pub fn global() -> Arc<TextMeasureCache> {
+        static CACHE: OnceLock<Arc<TextMeasureCache>> = OnceLock::new();
+        CACHE
+            .get_or_init(|| Arc::new(TextMeasureCache::new(DEFAULT_CAPACITY)))
+            .clone()
+    }
+
+    /// Get the cached size for `key`, calling `measure` on a miss
+    pub fn measure(&self, key: TextMeasureKey, measure: impl FnOnce() -> Size) -> Size {
+        if let Some(size) = self.get(&key) {
+            return size;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let size = measure();
+        self.set(key, size);
+        size
+    }
+
+    /// Get a cached size without measuring
+    pub fn get(&self, key: &TextMeasureKey) -> Option<Size> {
+        let mut entries = self.entries.write().ok()?;
+        let entry = entries.get_mut(key)?;
+        entry.last_used = self.tick();
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(entry.size)
+    }
+
+    /// Store a measured size, evicting the least recently used one if full
+    pub fn set(&self, key: TextMeasureKey, size: Size) {
+        if let Ok(mut entries) = self.entries.write() {
+            if entries.len() >= self.max_size && !entries.contains_key(&key) {
+                Self::evict_lru(&mut entries);
+            }
+
+            let last_used = self.tick();
+            entries.insert(key, CacheEntry { size, last_used });
+        }
+    }
+
+    fn evict_lru(entries: &mut HashMap<TextMeasureKey, CacheEntry>) {
+        if let Some(key) = entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone())
+        {
+            entries.remove(&key);
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Number of lookups answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that had to measure the text
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Drop all measurements, for example after fonts change
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.clear();
+        }
+    }
+
+    /// Number of cached measurements
+    pub fn size(&self) -> usize {
+        self.entries.read().map(|e| e.len()).unwrap_or(0)
+    }
+}
+
+impl Default for TextMeasureCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl std::fmt::Debug for TextMeasureCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextMeasureCache")
+            .field("size", &self.size())
+            .field("max_size", &self.max_size)
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::FontWeight;
+
+    fn label_style() -> Style {
+        Style {
+            font_family: Some("Inter".to_string()),
+            font_size: Some(14.0),
+            font_weight: Some(FontWeight::Bold),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_second_measurement_is_a_hit() {
+        let cache = TextMeasureCache::new(8);
+        let style = label_style();
+        let mut shaped = 0;
+        let mut measure = |text: &str| {
+            cache.measure(TextMeasureKey::new(text, &style, None), || {
+                shaped += 1;
+                Size::new(40.0, 18.0)
+            })
+        };
+
+        assert_eq!(measure("Save"), Size::new(40.0, 18.0));
+        assert_eq!(measure("Save"), Size::new(40.0, 18.0));
+        assert_eq!(shaped, 1);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // A different weight is a different measurement
+        let bold = TextMeasureKey::new("Save", &style, None);
+        let normal = TextMeasureKey::new("Save", &Style::default(), None);
+        assert_ne!(bold, normal);
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let cache = TextMeasureCache::new(2);
+        let style = label_style();
+        let key = |text: &str| TextMeasureKey::new(text, &style, Some(100.0));
+
+        cache.set(key("a"), Size::new(1.0, 1.0));
+        cache.set(key("b"), Size::new(2.0, 1.0));
+        cache.get(&key("a"));
+        cache.set(key("c"), Size::new(3.0, 1.0));
+
+        assert_eq!(cache.size(), 2);
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("b")).is_none());
+    }
+}
@@ -3,15 +3,163 @@
 #[cfg(feature = "wgpu")]
 use std::sync::Arc;
 #[cfg(feature = "wgpu")]
-use wgpu::{Adapter, Device, Instance, Queue, Surface, SurfaceConfiguration};
+use wgpu::util::DeviceExt;
+#[cfg(feature = "wgpu")]
+use wgpu::{
+    Adapter, Device, Instance, Queue, RenderPipeline, Surface, SurfaceConfiguration, TextureFormat,
+    TextureView,
+};
 
+#[cfg(feature = "wgpu")]
+use crate::component::ComponentId;
 #[cfg(feature = "wgpu")]
 use crate::component::Node;
 #[cfg(feature = "wgpu")]
-use crate::renderer::{RenderContext, Renderer};
+use crate::layout::{transform_point, Point, Rect, TransformMatrix};
+#[cfg(feature = "wgpu")]
+use crate::renderer::{paint_order, PaintItem, QualityLevel, RenderContext, RenderStats, Renderer};
 #[cfg(feature = "wgpu")]
 use crate::Error;
 
+/// Shader for solid-colored quads already in normalized device coordinates
+#[cfg(feature = "wgpu")]
+const QUAD_SHADER: &str = r#"
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@location(0) position: vec2<f32>, @location(1) color: vec4<f32>) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(position, 0.0, 1.0);
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
+"#;
+
+/// Clear color used behind the node tree
+#[cfg(feature = "wgpu")]
+const CLEAR_COLOR: wgpu::Color = wgpu::Color {
+    r: 0.1,
+    g: 0.2,
+    b: 0.3,
+    a: 1.0,
+};
+
+/// A vertex of a solid-colored quad
+#[cfg(feature = "wgpu")]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct QuadVertex {
+    /// Position in normalized device coordinates
+    position: [f32; 2],
+    /// RGBA color
+    color: [f32; 4],
+}
+
+#[cfg(feature = "wgpu")]
+impl QuadVertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+        wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4];
+
+    fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<QuadVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Background quads collected from a node tree, drawn in a single call
+#[cfg(feature = "wgpu")]
+#[derive(Debug, Default)]
+struct QuadBatch {
+    vertices: Vec<QuadVertex>,
+    indices: Vec<u32>,
+}
+
+#[cfg(feature = "wgpu")]
+impl QuadBatch {
+    /// Collect a quad for every node with a layout rect and background color
+    ///
+    /// Nodes are visited in paint order so later quads paint on top. Colors
+    /// are converted to linear space when the target applies sRGB encoding.
+    fn from_tree(root: &Node, width: u32, height: u32, linear: bool) -> Self {
+        let mut batch = Self::default();
+        for item in paint_order(root) {
+            batch.collect(&item, width as f32, height as f32, linear);
+        }
+        batch
+    }
+
+    fn collect(&mut self, item: &PaintItem, width: f32, height: f32, linear: bool) {
+        let background = item
+            .node
+            .style()
+            .and_then(|style| style.background_color.as_ref())
+            .and_then(|color| color.to_rgba());
+
+        if let (Some(rect), Some((r, g, b, a))) = (item.node.layout_rect(), background) {
+            let color = if linear {
+                [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+            } else {
+                [r, g, b, a]
+            };
+            self.push_rect(rect, &item.matrix, color, width, height);
+        }
+    }
+
+    fn push_rect(
+        &mut self,
+        rect: Rect,
+        matrix: &TransformMatrix,
+        color: [f32; 4],
+        width: f32,
+        height: f32,
+    ) {
+        // Transform the corners in pixel space, then map to clip space, where
+        // y points up instead of down
+        let corners = [
+            Point::new(rect.x(), rect.y()),
+            Point::new(rect.max_x(), rect.y()),
+            Point::new(rect.max_x(), rect.max_y()),
+            Point::new(rect.x(), rect.max_y()),
+        ];
+
+        let base = self.vertices.len() as u32;
+        self.vertices.extend(corners.map(|corner| {
+            let corner = transform_point(matrix, corner);
+            QuadVertex {
+                position: [corner.x / width * 2.0 - 1.0, 1.0 - corner.y / height * 2.0],
+                color,
+            }
+        }));
+        self.indices
+            .extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    fn quad_count(&self) -> usize {
+        self.vertices.len() / 4
+    }
+}
+
+/// Convert an sRGB-encoded channel to linear light
+#[cfg(feature = "wgpu")]
+fn srgb_to_linear(channel: f32) -> f32 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
 /// WGPU renderer for 3D rendering
 #[cfg(feature = "wgpu")]
 pub struct WgpuRenderer {
@@ -33,6 +181,15 @@ pub struct WgpuRenderer {
 
     /// WGPU adapter
     adapter: Adapter,
+
+    /// Quad pipeline along with the target format and sample count it was built for
+    quad_pipeline: Option<(TextureFormat, u32, RenderPipeline)>,
+
+    /// Current quality level, which selects the MSAA sample count
+    quality_level: QualityLevel,
+
+    /// Statistics for the last rendered frame
+    stats: RenderStats,
 }
 
 #[cfg(feature = "wgpu")]
@@ -77,6 +234,9 @@ impl WgpuRenderer {
             surface: None,
             surface_config: None,
             adapter,
+            quad_pipeline: None,
+            quality_level: QualityLevel::Balanced,
+            stats: RenderStats::default(),
         })
     }
 
@@ -124,6 +284,261 @@ impl WgpuRenderer {
         &self.queue
     }
 
+    /// Number of MSAA samples used at the current quality level
+    fn sample_count(&self) -> u32 {
+        match self.quality_level {
+            QualityLevel::Performance | QualityLevel::Balanced => 1,
+            QualityLevel::Quality => 4,
+        }
+    }
+
+    /// Get the quad pipeline for `format`, rebuilding it if the target changed
+    fn quad_pipeline(&mut self, format: TextureFormat, sample_count: u32) -> &RenderPipeline {
+        if !matches!(
+            &self.quad_pipeline,
+            Some((cached_format, cached_samples, _))
+                if *cached_format == format && *cached_samples == sample_count
+        ) {
+            let shader = self
+                .device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("Quad Shader"),
+                    source: wgpu::ShaderSource::Wgsl(QUAD_SHADER.into()),
+                });
+            let layout = self
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("Quad Pipeline Layout"),
+                    bind_group_layouts: &[],
+                    push_constant_ranges: &[],
+                });
+            let pipeline = self
+                .device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some("Quad Pipeline"),
+                    layout: Some(&layout),
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: "vs_main",
+                        buffers: &[QuadVertex::layout()],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point: "fs_main",
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format,
+                            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: sample_count,
+                        ..Default::default()
+                    },
+                    multiview: None,
+                });
+            self.quad_pipeline = Some((format, sample_count, pipeline));
+        }
+
+        &self.quad_pipeline.as_ref().expect("pipeline just built").2
+    }
+
+    /// Bring the surface in line with the context's viewport and vsync setting
+    fn sync_surface(&mut self, context: &RenderContext) -> Result<(), Error> {
+        let (Some(surface), Some(config)) = (&self.surface, &mut self.surface_config) else {
+            return Err(Error::Renderer("Surface not initialized".to_string()));
+        };
+
+        let present_mode = if context.vsync_enabled {
+            wgpu::PresentMode::Fifo
+        } else {
+            let supported = surface.get_capabilities(&self.adapter).present_modes;
+            [wgpu::PresentMode::Mailbox, wgpu::PresentMode::Immediate]
+                .into_iter()
+                .find(|mode| supported.contains(mode))
+                .unwrap_or(wgpu::PresentMode::Fifo)
+        };
+
+        // A zero-sized viewport (e.g. a minimized window) keeps the old size
+        let width = if context.viewport_width > 0 {
+            context.viewport_width
+        } else {
+            config.width
+        };
+        let height = if context.viewport_height > 0 {
+            context.viewport_height
+        } else {
+            config.height
+        };
+
+        if config.width != width || config.height != height || config.present_mode != present_mode {
+            config.width = width;
+            config.height = height;
+            config.present_mode = present_mode;
+            surface.configure(&self.device, config);
+        }
+
+        Ok(())
+    }
+
+    /// Draw the node tree into `view`, batching all quads into one draw call
+    fn draw_tree(
+        &mut self,
+        root: &Node,
+        view: &TextureView,
+        format: TextureFormat,
+        width: u32,
+        height: u32,
+    ) {
+        let batch = QuadBatch::from_tree(root, width, height, format.is_srgb());
+
+        let buffers = (!batch.indices.is_empty()).then(|| {
+            let vertices = self
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Quad Vertex Buffer"),
+                    contents: bytemuck::cast_slice(&batch.vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+            let indices = self
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Quad Index Buffer"),
+                    contents: bytemuck::cast_slice(&batch.indices),
+                    usage: wgpu::BufferUsages::INDEX,
+                });
+            (vertices, indices)
+        });
+
+        // With MSAA, draw into a multisampled texture that resolves into `view`
+        let sample_count = self.sample_count();
+        let multisampled = (sample_count > 1).then(|| {
+            self.device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Multisampled Target"),
+                    size: wgpu::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        });
+        let (target, resolve_target) = match &multisampled {
+            Some(msaa_view) => (msaa_view, Some(view)),
+            None => (view, None),
+        };
+
+        let device = Arc::clone(&self.device);
+        let pipeline = self.quad_pipeline(format, sample_count);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Render Encoder"),
+        });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(CLEAR_COLOR),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            if let Some((vertices, indices)) = &buffers {
+                render_pass.set_pipeline(pipeline);
+                render_pass.set_vertex_buffer(0, vertices.slice(..));
+                render_pass.set_index_buffer(indices.slice(..), wgpu::IndexFormat::Uint32);
+                render_pass.draw_indexed(0..batch.indices.len() as u32, 0, 0..1);
+            }
+        }
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        self.stats.frame_count += 1;
+        self.stats.draw_calls = u32::from(buffers.is_some());
+        self.stats.vertex_count = batch.vertices.len() as u32;
+        self.stats.component_count = batch.quad_count() as u32;
+    }
+
+    /// Copy an RGBA8 texture back to the CPU as tightly packed rows
+    fn read_texture(
+        &self,
+        texture: &wgpu::Texture,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<u8>, Error> {
+        // Buffer copies need rows aligned to 256 bytes, so pad and strip afterwards
+        let row_bytes = width * 4;
+        let alignment = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_row_bytes = row_bytes.div_ceil(alignment) * alignment;
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback Buffer"),
+            size: (padded_row_bytes * height) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Readback Encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .map_err(|e| Error::Renderer(format!("Readback was cancelled: {e}")))?
+            .map_err(|e| Error::Renderer(format!("Failed to map readback buffer: {e}")))?;
+
+        let data = slice.get_mapped_range();
+        let mut pixels = Vec::with_capacity((row_bytes * height) as usize);
+        for row in data.chunks(padded_row_bytes as usize) {
+            pixels.extend_from_slice(&row[..row_bytes as usize]);
+        }
+        drop(data);
+        buffer.unmap();
+
+        Ok(pixels)
+    }
+
     /// Resize the renderer
     pub fn resize(&mut self, width: u32, height: u32) -> Result<(), Error> {
         if let Some(surface) = &self.surface {
@@ -148,56 +563,73 @@ impl Renderer for WgpuRenderer {
         Ok(())
     }
 
-    fn render(&mut self, _root: &Node, _context: &mut RenderContext) -> Result<(), Error> {
+    fn render(&mut self, root: &Node, context: &mut RenderContext) -> Result<(), Error> {
+        self.sync_surface(context)?;
+        let (Some(surface), Some(config)) = (&self.surface, &self.surface_config) else {
+            return Err(Error::Renderer("Surface not initialized".to_string()));
+        };
+        let (format, width, height) = (config.format, config.width, config.height);
+
         // Get current surface texture
-        if let Some(surface) = &self.surface {
-            let frame = surface
-                .get_current_texture()
-                .map_err(|e| Error::Renderer(format!("Failed to get next frame: {e}")))?;
+        let frame = surface
+            .get_current_texture()
+            .map_err(|e| Error::Renderer(format!("Failed to get next frame: {e}")))?;
+        let view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
 
-            let view = frame
-                .texture
-                .create_view(&wgpu::TextureViewDescriptor::default());
+        self.draw_tree(root, &view, format, width, height);
+        frame.present();
 
-            let mut encoder = self
-                .device
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Render Encoder"),
-                });
+        Ok(())
+    }
 
-            // Clear the frame
-            {
-                let _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color {
-                                r: 0.1,
-                                g: 0.2,
-                                b: 0.3,
-                                a: 1.0,
-                            }),
-                            store: wgpu::StoreOp::Store,
-                        },
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
+    fn render_to_buffer(
+        &mut self,
+        root: &Node,
+        context: &mut RenderContext,
+    ) -> Result<Vec<u8>, Error> {
+        let (width, height) = (context.viewport_width, context.viewport_height);
+        if width == 0 || height == 0 {
+            return Err(Error::Renderer(
+                "Cannot render to a buffer with an empty viewport".to_string(),
+            ));
+        }
 
-                // Render components from the node tree would happen here
-            }
+        // A linear format keeps the output bytes equal to the style colors
+        let format = TextureFormat::Rgba8Unorm;
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen Target"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-            // Submit commands
-            self.queue.submit(std::iter::once(encoder.finish()));
-            frame.present();
+        self.draw_tree(root, &view, format, width, height);
+        self.read_texture(&texture, width, height)
+    }
 
-            Ok(())
-        } else {
-            Err(Error::Renderer("Surface not initialized".to_string()))
+    fn render_selective(
+        &mut self,
+        root: &Node,
+        context: &mut RenderContext,
+        dirty_components: &[ComponentId],
+    ) -> Result<(), Error> {
+        // Swapchain frames are not retained between presents, so any change
+        // means redrawing the whole tree; with nothing dirty the frame is skipped
+        if dirty_components.is_empty() {
+            return Ok(());
         }
+        self.render(root, context)
     }
 
     fn flush(&mut self) -> Result<(), Error> {
@@ -216,4 +648,168 @@ impl Renderer for WgpuRenderer {
     fn name(&self) -> &str {
         "WGPU Renderer"
     }
+
+    fn get_stats(&self) -> RenderStats {
+        self.stats.clone()
+    }
+
+    fn reset_stats(&mut self) {
+        self.stats = RenderStats::default();
+    }
+
+    fn set_quality_level(&mut self, level: QualityLevel) -> Result<(), Error> {
+        // The pipeline is rebuilt lazily once the sample count changes
+        self.quality_level = level;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "wgpu"))]
+mod tests {
+    use super::*;
+    use crate::style::{Color, Style};
+
+    const SIZE: u32 = 64;
+    const FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+    fn styled_node(rect: Rect, color: Color) -> Node {
+        let mut node = Node::default();
+        node.set_layout_rect(rect);
+        node.set_style(Style {
+            background_color: Some(color),
+            ..Default::default()
+        });
+        node
+    }
+
+    /// Read back a single RGBA pixel from an offscreen texture
+    fn read_pixel(renderer: &WgpuRenderer, texture: &wgpu::Texture, x: u32, y: u32) -> [u8; 4] {
+        let pixels = renderer.read_texture(texture, SIZE, SIZE).unwrap();
+        let offset = ((y * SIZE + x) * 4) as usize;
+        pixels[offset..offset + 4].try_into().unwrap()
+    }
+
+    fn offscreen_texture(renderer: &WgpuRenderer) -> wgpu::Texture {
+        renderer.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen Target"),
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    fn two_quad_tree() -> Node {
+        let mut root = styled_node(
+            Rect::new(0.0, 0.0, 32.0, 32.0),
+            Color::Rgba(1.0, 0.0, 0.0, 1.0),
+        );
+        root.add_child(styled_node(
+            Rect::new(32.0, 32.0, 32.0, 32.0),
+            Color::Hex("#00ff00".to_string()),
+        ));
+        // Nodes without a background are skipped
+        root.add_child(Node::default());
+        root
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_headless_quad_fill() {
+        let mut renderer = futures::executor::block_on(WgpuRenderer::new()).unwrap();
+
+        let texture = offscreen_texture(&renderer);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        renderer.draw_tree(&two_quad_tree(), &view, FORMAT, SIZE, SIZE);
+
+        assert_eq!(read_pixel(&renderer, &texture, 16, 16), [255, 0, 0, 255]);
+        assert_eq!(read_pixel(&renderer, &texture, 48, 48), [0, 255, 0, 255]);
+
+        let stats = renderer.get_stats();
+        assert_eq!(stats.draw_calls, 1);
+        assert_eq!(stats.vertex_count, 8);
+        assert_eq!(stats.component_count, 2);
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_multisampled_quality_level() {
+        let mut renderer = futures::executor::block_on(WgpuRenderer::new()).unwrap();
+        renderer.set_quality_level(QualityLevel::Quality).unwrap();
+        assert_eq!(renderer.sample_count(), 4);
+
+        let texture = offscreen_texture(&renderer);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        renderer.draw_tree(&two_quad_tree(), &view, FORMAT, SIZE, SIZE);
+
+        assert_eq!(read_pixel(&renderer, &texture, 16, 16), [255, 0, 0, 255]);
+        assert_eq!(read_pixel(&renderer, &texture, 48, 48), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_render_with_context_smoke() {
+        let mut renderer = futures::executor::block_on(WgpuRenderer::new()).unwrap();
+        let root = two_quad_tree();
+        let mut context = RenderContext::new(SIZE, SIZE);
+
+        // Without a window there is no surface to present to
+        let err = renderer.render(&root, &mut context).unwrap_err();
+        assert!(err.to_string().contains("Surface not initialized"));
+
+        // Nothing dirty means nothing to draw, even without a surface
+        renderer.render_selective(&root, &mut context, &[]).unwrap();
+        assert!(renderer
+            .render_selective(&root, &mut context, &[ComponentId::new()])
+            .is_err());
+
+        assert_eq!(renderer.get_stats().frame_count, 0);
+        assert_eq!(renderer.name(), "WGPU Renderer");
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_render_red_rect_to_buffer() {
+        let mut renderer = futures::executor::block_on(WgpuRenderer::new()).unwrap();
+        let root = styled_node(
+            Rect::new(0.0, 0.0, 10.0, 5.0),
+            Color::Rgba(1.0, 0.0, 0.0, 1.0),
+        );
+        // An odd width exercises stripping the row padding from the readback
+        let mut context = RenderContext::new(20, 10);
+
+        let pixels = renderer.render_to_buffer(&root, &mut context).unwrap();
+        assert_eq!(pixels.len(), 20 * 10 * 4);
+
+        let pixel = |x: usize, y: usize| {
+            let offset = (y * 20 + x) * 4;
+            &pixels[offset..offset + 4]
+        };
+        assert_eq!(pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(9, 4), [255, 0, 0, 255]);
+        assert_ne!(pixel(15, 8), [255, 0, 0, 255]);
+        assert_ne!(pixel(10, 0), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_quad_batch_positions() {
+        let root = styled_node(
+            Rect::new(0.0, 0.0, 50.0, 100.0),
+            Color::Named("blue".to_string()),
+        );
+
+        let batch = QuadBatch::from_tree(&root, 100, 100, false);
+
+        assert_eq!(batch.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!(batch.vertices[0].position, [-1.0, 1.0]);
+        assert_eq!(batch.vertices[2].position, [0.0, -1.0]);
+        assert_eq!(batch.vertices[0].color, [0.0, 0.0, 1.0, 1.0]);
+    }
 }
@@ -7,25 +7,35 @@
 //! 3. `Computed<T>` - A derived state value that depends on other values
 //! 4. The reactive module - A fine-grained reactive system with signals and effects
 
+#[cfg(feature = "persistence")]
+mod persistence;
 mod reactive;
 
 pub use reactive::{
-    create_computed, create_effect, create_signal, Effect, ReactiveComputed, ReactiveScope, Signal,
+    batch, create_async_effect, create_computed, create_effect, create_memo, create_memo_by,
+    create_signal, untrack, AsyncEffect, Effect, Memo, ReactiveComputed, ReactiveScope, Signal,
     SignalError,
 };
 
 use std::{
-    any::TypeId,
     collections::HashMap,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
 };
 
 // Define a type alias for the complex subscriber type
-type SubscriberCallback = Box<dyn Fn() + Send + Sync>;
-type SubscriberMap = HashMap<TypeId, Vec<SubscriberCallback>>;
+type SubscriberCallback = Arc<dyn Fn() + Send + Sync>;
+// Each subscriber carries an id that is stable within its state's bucket
+type SubscriberMap = HashMap<StateId, Vec<(u64, SubscriberCallback)>>;
 // Define a type for the complex state value container
 type StateValue = Arc<Mutex<Box<dyn std::any::Any + Send + Sync>>>;
-type StateMap = HashMap<TypeId, StateValue>;
+type StateMap = HashMap<StateId, StateValue>;
+
+/// Unique key of a state value within its container
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateId(u64);
 
 /// State management for Orbit applications
 ///
@@ -34,12 +44,19 @@ type StateMap = HashMap<TypeId, StateValue>;
 #[derive(Clone)]
 pub struct StateContainer {
     // Using Arc<Mutex<>> for thread-safe interior mutability
-    // TypeId is used to identify the type of the stored value
+    // Every created value gets its own StateId, so values of the same type never share a slot
     #[allow(clippy::type_complexity)]
     values: Arc<Mutex<StateMap>>,
     // Subscribers are functions that are called when a value changes
     // Using Arc<Mutex<>> for thread-safe interior mutability
     pub(crate) subscribers: Arc<Mutex<SubscriberMap>>,
+    // Source of state ids
+    next_state_id: Arc<AtomicU64>,
+    // Source of subscriber ids
+    next_subscriber_id: Arc<AtomicU64>,
+    // Values registered for snapshots, keyed by their persistence key
+    #[cfg(feature = "persistence")]
+    persisted: Arc<Mutex<HashMap<String, persistence::PersistedSlot>>>,
 }
 
 impl std::fmt::Debug for StateContainer {
@@ -57,66 +74,87 @@ impl StateContainer {
         Self {
             values: Arc::new(Mutex::new(HashMap::new())),
             subscribers: Arc::new(Mutex::new(HashMap::new())),
+            next_state_id: Arc::new(AtomicU64::new(0)),
+            next_subscriber_id: Arc::new(AtomicU64::new(0)),
+            #[cfg(feature = "persistence")]
+            persisted: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    /// Create a new state value
-    pub fn create<T: 'static + Clone + Send + Sync>(&self, initial: T) -> State<T> {
-        let type_id = TypeId::of::<T>();
-
-        // Store initial value with the correct type (Arc<Mutex<Box<dyn Any + Send + Sync>>>)
+    /// Store a new value under a freshly allocated id
+    fn insert_new(&self, value: Box<dyn std::any::Any + Send + Sync>) -> StateId {
+        let id = StateId(self.next_state_id.fetch_add(1, Ordering::Relaxed));
         self.values
             .lock()
             .unwrap()
-            .insert(type_id, Arc::new(Mutex::new(Box::new(initial.clone()))));
+            .insert(id, Arc::new(Mutex::new(value)));
+        id
+    }
 
+    /// Create a new state value
+    ///
+    /// Each call allocates separate storage, even for values of the same type.
+    pub fn create<T: 'static + Clone + Send + Sync>(&self, initial: T) -> State<T> {
         State {
             container: self.clone(),
-            type_id,
+            id: self.insert_new(Box::new(initial)),
             _marker: std::marker::PhantomData,
         }
     }
 
     /// Create computed state dependent on other state
-    pub fn computed<T, F>(&self, compute: F, dependencies: Vec<TypeId>) -> Computed<T>
+    pub fn computed<T, F>(&self, compute: F, dependencies: Vec<StateId>) -> Computed<T>
     where
         T: 'static + Clone + Send + Sync,
         F: Fn() -> T + Send + Sync + 'static,
     {
-        // Create initial value
-        let initial = compute();
-        let type_id = TypeId::of::<T>();
-
-        // Store initial value with correct type
-        self.values
-            .lock()
-            .unwrap()
-            .insert(type_id, Arc::new(Mutex::new(Box::new(initial))));
-
         Computed::new(self.clone(), compute, dependencies)
     }
 
-    /// Subscribe to state changes
-    pub fn subscribe<T: 'static + Send + Sync, F: Fn() + Send + Sync + 'static>(
+    /// Subscribe to changes of the state value with the given id
+    ///
+    /// The callback stays registered until the returned [`Subscription`] is
+    /// dropped or unsubscribed.
+    pub fn subscribe<F: Fn() + Send + Sync + 'static>(
         &self,
+        state_id: StateId,
         callback: F,
-    ) {
-        let type_id = TypeId::of::<T>();
-        let mut subscribers = self.subscribers.lock().unwrap();
-        subscribers
-            .entry(type_id)
+    ) -> Subscription {
+        self.add_subscriber(state_id, Arc::new(callback))
+    }
+
+    /// Register a callback under `state_id`, returning its handle
+    fn add_subscriber(&self, state_id: StateId, callback: SubscriberCallback) -> Subscription {
+        let id = self.next_subscriber_id.fetch_add(1, Ordering::Relaxed);
+        self.subscribers
+            .lock()
+            .unwrap()
+            .entry(state_id)
             .or_default()
-            .push(Box::new(callback));
+            .push((id, callback));
+
+        Subscription {
+            subscribers: Arc::downgrade(&self.subscribers),
+            state_id,
+            id,
+            active: true,
+        }
     }
 
     /// Notify subscribers of a change to a value
-    pub fn notify(&self, type_id: TypeId) {
-        let subscribers = self.subscribers.lock().unwrap();
+    pub fn notify(&self, state_id: StateId) {
+        // Snapshot the callbacks so they can subscribe or unsubscribe while running
+        let callbacks: Vec<SubscriberCallback> =
+            match self.subscribers.lock().unwrap().get(&state_id) {
+                Some(callbacks) => callbacks
+                    .iter()
+                    .map(|(_, callback)| callback.clone())
+                    .collect(),
+                None => return,
+            };
 
-        if let Some(callbacks) = subscribers.get(&type_id) {
-            for callback in callbacks {
-                callback();
-            }
+        for callback in callbacks {
+            callback();
         }
     }
 }
@@ -127,25 +165,84 @@ impl Default for StateContainer {
     }
 }
 
+/// Handle to a registered state subscriber
+///
+/// Dropping the handle removes the callback. It only holds a weak reference
+/// to the container, so it is safe to drop after the container is gone.
+#[must_use = "dropping a Subscription immediately unsubscribes its callback"]
+pub struct Subscription {
+    subscribers: Weak<Mutex<SubscriberMap>>,
+    state_id: StateId,
+    id: u64,
+    active: bool,
+}
+
+impl Subscription {
+    /// Remove the callback now
+    pub fn unsubscribe(self) {
+        // Dropping does the work
+    }
+
+    /// Keep the callback registered for as long as the container lives
+    pub fn detach(mut self) {
+        self.active = false;
+    }
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("state_id", &self.state_id)
+            .field("id", &self.id)
+            .field("active", &self.active)
+            .finish()
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if !self.active {
+            return;
+        }
+        let Some(subscribers) = self.subscribers.upgrade() else {
+            return;
+        };
+        let Ok(mut subscribers) = subscribers.lock() else {
+            return;
+        };
+        if let Some(bucket) = subscribers.get_mut(&self.state_id) {
+            bucket.retain(|(id, _)| *id != self.id);
+            if bucket.is_empty() {
+                subscribers.remove(&self.state_id);
+            }
+        }
+    }
+}
+
 /// Represents a reactive value that can be observed for changes
 pub struct State<T> {
     /// State container
     container: StateContainer,
 
-    /// Type ID for this state
-    type_id: TypeId,
+    /// Key of this state's value in the container
+    id: StateId,
 
     /// Phantom data for type
     _marker: std::marker::PhantomData<T>,
 }
 
 impl<T: 'static + Clone + Send + Sync> State<T> {
+    /// Get the id of this state in its container
+    pub fn id(&self) -> StateId {
+        self.id
+    }
+
     /// Get current value
     pub fn get(&self) -> T {
         let values = self.container.values.lock().unwrap();
 
         values
-            .get(&self.type_id)
+            .get(&self.id)
             .and_then(|value| {
                 let lock = value.lock().unwrap();
                 lock.downcast_ref::<T>().cloned()
@@ -160,10 +257,10 @@ impl<T: 'static + Clone + Send + Sync> State<T> {
             .values
             .lock()
             .unwrap()
-            .insert(self.type_id, Arc::new(Mutex::new(Box::new(value))));
+            .insert(self.id, Arc::new(Mutex::new(Box::new(value))));
 
         // Notify subscribers
-        self.container.notify(self.type_id);
+        self.container.notify(self.id);
     }
     /// Update value with a function
     pub fn update<F>(&self, f: F)
@@ -172,7 +269,7 @@ impl<T: 'static + Clone + Send + Sync> State<T> {
     {
         let values = self.container.values.lock().unwrap();
 
-        if let Some(value_container) = values.get(&self.type_id) {
+        if let Some(value_container) = values.get(&self.id) {
             let mut value_lock = value_container.lock().unwrap();
 
             // Apply the function to get the new value
@@ -185,29 +282,41 @@ impl<T: 'static + Clone + Send + Sync> State<T> {
                 drop(values);
 
                 // Notify subscribers
-                self.container.notify(self.type_id);
+                self.container.notify(self.id);
             }
         }
     }
 
     /// Add a callback that will be called when the state changes
-    pub fn on_change<F>(&self, callback: F)
+    ///
+    /// The callback stays registered until the returned [`Subscription`] is
+    /// dropped or unsubscribed.
+    pub fn on_change<F>(&self, callback: F) -> Subscription
     where
         F: Fn(&T) + Send + Sync + 'static,
     {
         let container = self.container.clone();
-        let type_id = self.type_id;
+        let id = self.id;
 
-        self.container.subscribe::<T, _>(move || {
+        self.container.subscribe(id, move || {
             // Get the current value and call the callback with it
             let values = container.values.lock().unwrap();
-            if let Some(value_container) = values.get(&type_id) {
+            if let Some(value_container) = values.get(&id) {
                 let value_lock = value_container.lock().unwrap();
                 if let Some(value) = value_lock.downcast_ref::<T>() {
                     callback(value);
                 }
             }
-        });
+        })
+    }
+
+    /// Add a callback that will be called when the state changes, without
+    /// reading the new value
+    pub fn subscribe<F>(&self, callback: F) -> Subscription
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.container.subscribe(self.id, callback)
     }
 }
 
@@ -216,25 +325,25 @@ pub struct Computed<T> {
     /// State container
     container: StateContainer,
 
-    /// Type ID for this state
-    type_id: TypeId,
+    /// Key of the computed value in the container
+    id: StateId,
 
     /// Compute function
     #[allow(dead_code)]
     compute: Arc<Box<dyn Fn() -> T + Send + Sync>>,
 
     /// Dependencies
-    dependencies: Vec<TypeId>,
+    dependencies: Vec<StateId>,
 }
 
 impl<T: 'static + Clone + Send + Sync> Computed<T> {
     /// Create new computed state
-    pub fn new<F>(container: StateContainer, compute: F, dependencies: Vec<TypeId>) -> Self
+    pub fn new<F>(container: StateContainer, compute: F, dependencies: Vec<StateId>) -> Self
     where
         F: Fn() -> T + Send + Sync + 'static,
     {
-        // Initial value already stored in container
-        let type_id = TypeId::of::<T>();
+        // Store the initial value under its own id
+        let id = container.insert_new(Box::new(compute()));
 
         // Wrap compute function in Arc for cloning
         let compute_arc = Arc::new(Box::new(compute) as Box<dyn Fn() -> T + Send + Sync>);
@@ -242,7 +351,7 @@ impl<T: 'static + Clone + Send + Sync> Computed<T> {
         // Create computed state
         let computed = Self {
             container: container.clone(),
-            type_id,
+            id,
             compute: compute_arc.clone(),
             dependencies,
         };
@@ -252,7 +361,7 @@ impl<T: 'static + Clone + Send + Sync> Computed<T> {
             // Clone Arc wrapper for each dependency
             let compute_fn = compute_arc.clone();
             let container_clone = container.clone();
-            let type_id_clone = type_id;
+            let id_clone = id;
 
             // Create a closure for this dependency
             let callback = move || {
@@ -262,15 +371,13 @@ impl<T: 'static + Clone + Send + Sync> Computed<T> {
                     .values
                     .lock()
                     .unwrap()
-                    .insert(type_id_clone, Arc::new(Mutex::new(Box::new(new_value))));
+                    .insert(id_clone, Arc::new(Mutex::new(Box::new(new_value))));
             };
 
-            // Add closure to subscribers for this dependency type
-            let mut subscribers = container.subscribers.lock().unwrap();
-            subscribers
-                .entry(dep_id)
-                .or_default()
-                .push(Box::new(callback));
+            // Computed values follow their dependencies for the container's lifetime
+            container
+                .add_subscriber(dep_id, Arc::new(callback))
+                .detach();
         }
 
         computed
@@ -280,7 +387,7 @@ impl<T: 'static + Clone + Send + Sync> Computed<T> {
     pub fn get(&self) -> T {
         let values = self.container.values.lock().unwrap();
         values
-            .get(&self.type_id)
+            .get(&self.id)
             .and_then(|value| {
                 let lock = value.lock().unwrap();
                 lock.downcast_ref::<T>().cloned()
@@ -288,3 +395,112 @@ impl<T: 'static + Clone + Send + Sync> Computed<T> {
             .unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn counting_subscriber(state: &State<i32>) -> (Arc<AtomicUsize>, Subscription) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let subscription = state.on_change(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        (calls, subscription)
+    }
+
+    #[test]
+    fn test_dropped_subscription_stops_notifications() {
+        let container = StateContainer::new();
+        let state = container.create(0);
+        let (calls, subscription) = counting_subscriber(&state);
+
+        state.set(1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        drop(subscription);
+        state.set(2);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_unsubscribe_keeps_other_subscribers() {
+        let container = StateContainer::new();
+        let state = container.create(0);
+        let (first_calls, first) = counting_subscriber(&state);
+        let (second_calls, _second) = counting_subscriber(&state);
+
+        first.unsubscribe();
+        state.set(1);
+
+        assert_eq!(first_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_drop_subscription_after_container() {
+        let container = StateContainer::new();
+        let state = container.create(0);
+        let (_, subscription) = counting_subscriber(&state);
+
+        drop(state);
+        drop(container);
+        drop(subscription);
+    }
+
+    #[test]
+    fn test_states_of_same_type_are_independent() {
+        let container = StateContainer::new();
+        let first = container.create(1);
+        let second = container.create(2);
+        assert_ne!(first.id(), second.id());
+
+        first.set(10);
+        second.update(|value| value + 5);
+
+        assert_eq!(first.get(), 10);
+        assert_eq!(second.get(), 7);
+    }
+
+    #[test]
+    fn test_notifications_are_per_instance() {
+        let container = StateContainer::new();
+        let first = container.create(0);
+        let second = container.create(0);
+        let (first_calls, _first_subscription) = counting_subscriber(&first);
+        let (second_calls, _second_subscription) = counting_subscriber(&second);
+
+        first.set(1);
+        first.set(2);
+        second.set(1);
+
+        assert_eq!(first_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_computed_follows_dependency_instance() {
+        let container = StateContainer::new();
+        let base = container.create(2);
+        let unrelated = container.create(100);
+
+        let source = container.clone();
+        let base_id = base.id();
+        let doubled = container.computed(
+            move || {
+                let values = source.values.lock().unwrap();
+                let value = values[&base_id].lock().unwrap();
+                value.downcast_ref::<i32>().copied().unwrap() * 2
+            },
+            vec![base.id()],
+        );
+        assert_eq!(doubled.get(), 4);
+
+        base.set(5);
+        assert_eq!(doubled.get(), 10);
+        unrelated.set(1);
+        assert_eq!(doubled.get(), 10);
+        assert_eq!(unrelated.get(), 1);
+    }
+}
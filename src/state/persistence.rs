@@ -0,0 +1,151 @@
+//! Snapshot and restore of state values through serde
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{State, StateContainer, StateId};
+
+type SaveFn = Box<dyn Fn(&(dyn Any + Send + Sync)) -> Result<String, String> + Send + Sync>;
+type LoadFn = Box<dyn Fn(&str) -> Result<Box<dyn Any + Send + Sync>, String> + Send + Sync>;
+
+/// A state value registered for snapshots, with its (de)serializers
+pub(super) struct PersistedSlot {
+    id: StateId,
+    save: SaveFn,
+    load: LoadFn,
+}
+
+impl StateContainer {
+    /// Create a state value that takes part in snapshots under `key`
+    ///
+    /// Registering another value with the same key replaces the earlier
+    /// registration.
+    pub fn create_persisted<T>(&self, key: impl Into<String>, initial: T) -> State<T>
+    where
+        T: 'static + Clone + Send + Sync + Serialize + DeserializeOwned,
+    {
+        let state = self.create(initial);
+        let slot = PersistedSlot {
+            id: state.id(),
+            save: Box::new(|value| {
+                let value = value
+                    .downcast_ref::<T>()
+                    .ok_or_else(|| "state value has an unexpected type".to_string())?;
+                serde_json::to_string(value).map_err(|e| e.to_string())
+            }),
+            load: Box::new(|text| {
+                let value: T = serde_json::from_str(text).map_err(|e| e.to_string())?;
+                Ok(Box::new(value))
+            }),
+        };
+        self.persisted.lock().unwrap().insert(key.into(), slot);
+        state
+    }
+
+    /// Serialize every persisted value, keyed by its persistence key
+    ///
+    /// Values created without a key are not included. Values that fail to
+    /// serialize are logged and skipped.
+    pub fn snapshot(&self) -> HashMap<String, String> {
+        let persisted = self.persisted.lock().unwrap();
+        let values = self.values.lock().unwrap();
+
+        let mut snapshot = HashMap::new();
+        for (key, slot) in persisted.iter() {
+            let Some(value) = values.get(&slot.id) else {
+                continue;
+            };
+            match (slot.save)(value.lock().unwrap().as_ref()) {
+                Ok(text) => {
+                    snapshot.insert(key.clone(), text);
+                }
+                Err(e) => crate::trace::warn_event!("Failed to snapshot state '{key}': {e}"),
+            }
+        }
+        snapshot
+    }
+
+    /// Restore persisted values from a snapshot and notify their subscribers
+    ///
+    /// Keys with no registered value are ignored, and registered values
+    /// missing from the snapshot keep their current value. Entries that fail
+    /// to deserialize are logged and skipped.
+    pub fn restore(&self, snapshot: &HashMap<String, String>) {
+        let mut restored = Vec::new();
+        {
+            let persisted = self.persisted.lock().unwrap();
+            let mut values = self.values.lock().unwrap();
+
+            for (key, slot) in persisted.iter() {
+                let Some(text) = snapshot.get(key) else {
+                    continue;
+                };
+                match (slot.load)(text) {
+                    Ok(value) => {
+                        values.insert(slot.id, Arc::new(Mutex::new(value)));
+                        restored.push(slot.id);
+                    }
+                    Err(e) => crate::trace::warn_event!("Failed to restore state '{key}': {e}"),
+                }
+            }
+        }
+
+        // Notify once every value is in place, with no locks held
+        for id in restored {
+            self.notify(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let container = StateContainer::new();
+        let count = container.create_persisted("count", 1);
+        let name = container.create_persisted("name", "orbit".to_string());
+        let scratch = container.create(7);
+
+        count.set(42);
+        name.set("saved".to_string());
+        let snapshot = container.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot["count"], "42");
+
+        count.set(0);
+        name.set(String::new());
+        scratch.set(8);
+
+        let notified = Arc::new(AtomicUsize::new(0));
+        let counter = notified.clone();
+        let _subscription = count.subscribe(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        container.restore(&snapshot);
+        assert_eq!(count.get(), 42);
+        assert_eq!(name.get(), "saved");
+        assert_eq!(scratch.get(), 8);
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_restore_skips_unknown_and_invalid_entries() {
+        let container = StateContainer::new();
+        let count = container.create_persisted("count", 5);
+
+        let snapshot = HashMap::from([
+            ("count".to_string(), "\"not a number\"".to_string()),
+            ("missing".to_string(), "1".to_string()),
+        ]);
+        container.restore(&snapshot);
+        assert_eq!(count.get(), 5);
+    }
+}
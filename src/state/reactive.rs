@@ -1,9 +1,130 @@
-//! New scope-based reactive system for Orbit UI
+//! Fine-grained reactive system for Orbit UI
 //!
-//! This module provides a fine-grained reactive system based on reactive scopes
-//! rather than global registries, eliminating circular dependency issues.
+//! Signals, effects, and computed values. Dependencies are tracked through a
+//! thread-local current observer: every signal or computed read while an
+//! effect or computed is running is recorded, and changing that value later
+//! re-runs the effect or invalidates the computed.
 
-use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
+
+use futures::future::{AbortHandle, Abortable};
+use futures::task::{Spawn, SpawnExt};
+
+/// Callback run when a signal an observer read has changed
+type ObserverFn = dyn Fn() + Send + Sync;
+
+thread_local! {
+    /// Observer that signal reads on this thread are currently attributed to
+    static CURRENT_OBSERVER: RefCell<Option<Weak<ObserverFn>>> = const { RefCell::new(None) };
+    /// Depth of nested `batch` calls on this thread
+    static BATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Observers notified during the current batch, deduplicated
+    static PENDING_OBSERVERS: RefCell<Vec<Arc<ObserverFn>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Record the current observer, if any, as one of `observers`
+fn track_observer(observers: &Mutex<Vec<Weak<ObserverFn>>>) {
+    let Some(observer) = CURRENT_OBSERVER.with(|current| current.borrow().clone()) else {
+        return;
+    };
+
+    let mut observers = observers.lock().unwrap();
+    observers.retain(|existing| existing.strong_count() > 0);
+    if !observers.iter().any(|existing| existing.ptr_eq(&observer)) {
+        observers.push(observer);
+    }
+}
+
+/// Run every live observer in `observers`
+fn notify_observers(observers: &Mutex<Vec<Weak<ObserverFn>>>) {
+    // Collect first so observers can read the changed value while they run
+    let observers: Vec<Arc<ObserverFn>> = {
+        let mut observers = observers.lock().unwrap();
+        observers.retain(|observer| observer.strong_count() > 0);
+        observers.iter().filter_map(Weak::upgrade).collect()
+    };
+
+    for observer in observers {
+        run_observer(observer);
+    }
+}
+
+/// Run `f` with signal reads on this thread tracked as dependencies of `observer`
+fn track_reads<R>(observer: &Arc<ObserverFn>, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_OBSERVER.with(|current| current.replace(Some(Arc::downgrade(observer))));
+    let result = f();
+    CURRENT_OBSERVER.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// Run `f` with signal notifications deferred until it returns
+///
+/// Effects that depend on signals set inside `f` run once after the
+/// outermost batch completes, however many of their dependencies changed.
+/// Batches nest; only the outermost one flushes. Batching is per thread, so
+/// signals set on another thread notify their observers immediately.
+pub fn batch<T>(f: impl FnOnce() -> T) -> T {
+    /// Ends the batch even if `f` panics
+    struct BatchGuard;
+
+    impl Drop for BatchGuard {
+        fn drop(&mut self) {
+            let depth = BATCH_DEPTH.with(|depth| {
+                depth.set(depth.get() - 1);
+                depth.get()
+            });
+            if depth == 0 && !std::thread::panicking() {
+                flush_pending_observers();
+            }
+        }
+    }
+
+    BATCH_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let _guard = BatchGuard;
+    f()
+}
+
+/// Run every observer deferred by a batch, including any queued while flushing
+fn flush_pending_observers() {
+    loop {
+        let pending = PENDING_OBSERVERS.with(|pending| pending.take());
+        if pending.is_empty() {
+            break;
+        }
+        for observer in pending {
+            observer();
+        }
+    }
+}
+
+/// Run an observer now, or queue it once if a batch is open on this thread
+fn run_observer(observer: Arc<ObserverFn>) {
+    if BATCH_DEPTH.with(Cell::get) == 0 {
+        observer();
+        return;
+    }
+
+    PENDING_OBSERVERS.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        if !pending.iter().any(|queued| Arc::ptr_eq(queued, &observer)) {
+            pending.push(observer);
+        }
+    });
+}
+
+/// Run `f` without tracking the signals it reads
+///
+/// Reads inside `f` do not become dependencies of the effect currently
+/// running, so the effect will not re-run when those signals change. The
+/// surrounding tracking is restored when `f` returns.
+pub fn untrack<T>(f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT_OBSERVER.with(|current| current.take());
+    let result = f();
+    CURRENT_OBSERVER.with(|current| *current.borrow_mut() = previous);
+    result
+}
 
 /// Errors that can occur in the reactive system
 #[derive(Debug, Clone)]
@@ -29,9 +150,6 @@ impl std::fmt::Display for SignalError {
 impl std::error::Error for SignalError {}
 
 /// Reactive scope that manages signals, effects, and computed values
-///
-/// This is a simplified implementation focused on basic functionality.
-/// Advanced dependency tracking will be implemented in future versions.
 #[derive(Debug)]
 pub struct ReactiveScope {
     // Reserved for future dependency tracking functionality
@@ -56,6 +174,28 @@ impl Default for ReactiveScope {
 pub struct Signal<T> {
     pub value: Arc<RwLock<T>>,
     dirty: Arc<RwLock<bool>>,
+    /// Observers that read this signal and re-run when it changes
+    observers: Arc<Mutex<Vec<Weak<ObserverFn>>>>,
+}
+
+/// Clones share the same value and observers
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            dirty: self.dirty.clone(),
+            observers: self.observers.clone(),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value.try_read() {
+            Ok(value) => f.debug_tuple("Signal").field(&*value).finish(),
+            Err(_) => f.debug_tuple("Signal").field(&"<locked>").finish(),
+        }
+    }
 }
 
 // Explicit Send + Sync implementations
@@ -67,13 +207,13 @@ where
     T: Send + Sync + 'static,
 {
     /// Get the current value of the signal
-    pub fn get(&self) -> RwLockReadGuard<T> {
-        // TODO: Track this read for reactive dependencies
+    pub fn get(&self) -> RwLockReadGuard<'_, T> {
+        track_observer(&self.observers);
         self.value.read().unwrap()
     }
 
     /// Get a mutable reference to the signal's value
-    pub fn get_mut(&self) -> RwLockWriteGuard<T> {
+    pub fn get_mut(&self) -> RwLockWriteGuard<'_, T> {
         self.value.write().unwrap()
     }
 
@@ -84,11 +224,7 @@ where
             *val = value;
         }
 
-        // Mark as dirty and trigger updates
-        *self.dirty.write().unwrap() = true;
-
-        // TODO: In a full implementation, this would trigger dependent updates
-        Ok(())
+        self.set_dirty()
     }
 
     /// Update the signal's value with a function
@@ -105,14 +241,44 @@ where
 
     fn set_dirty(&self) -> Result<(), SignalError> {
         *self.dirty.write().unwrap() = true;
+        notify_observers(&self.observers);
         Ok(())
     }
 }
 
+impl<T> Signal<T>
+where
+    T: PartialEq + Send + Sync + 'static,
+{
+    /// Set the signal's value, triggering updates only if it changed
+    ///
+    /// Setting a value equal to the current one does nothing, so dependents
+    /// only re-run on real changes.
+    pub fn set_if_changed(&self, value: T) -> Result<(), SignalError> {
+        {
+            let mut val = self.value.write().unwrap();
+            if *val == value {
+                return Ok(());
+            }
+            *val = value;
+        }
+
+        self.set_dirty()
+    }
+}
+
 /// A reactive effect that runs when its dependencies change
+///
+/// Dropping the effect stops it from re-running.
 pub struct Effect<F> {
+    inner: Arc<EffectInner<F>>,
+}
+
+struct EffectInner<F> {
     callback: Mutex<Option<F>>,
-    dirty: Arc<RwLock<bool>>,
+    dirty: RwLock<bool>,
+    /// Observer for the current run; replacing it drops the previous dependencies
+    observer: Mutex<Option<Arc<ObserverFn>>>,
 }
 
 // Explicit Send + Sync implementations
@@ -120,29 +286,55 @@ unsafe impl<F: Send + Sync> Send for Effect<F> {}
 unsafe impl<F: Send + Sync> Sync for Effect<F> {}
 
 impl Effect<Box<dyn FnMut() + Send + Sync + 'static>> {
-    /// Execute the effect
+    /// Execute the effect, tracking the signals it reads
     pub fn run(&self) -> Result<(), SignalError> {
-        // Check if we should run
-        let should_run = {
-            let callback_ref = self.callback.lock().unwrap();
-            callback_ref.is_some()
+        self.inner.run()
+    }
+}
+
+impl EffectInner<Box<dyn FnMut() + Send + Sync + 'static>> {
+    fn run(self: &Arc<Self>) -> Result<(), SignalError> {
+        // The callback is taken while it runs, so a missing one means the
+        // effect changed one of its own dependencies
+        let Some(mut callback) = self.callback.lock().unwrap().take() else {
+            return Err(SignalError::CircularDependency);
         };
 
-        if should_run {
-            let mut callback = self.callback.lock().unwrap().take().unwrap();
-            callback();
-            *self.callback.lock().unwrap() = Some(callback);
-            *self.dirty.write().unwrap() = false;
-        }
+        let weak = Arc::downgrade(self);
+        let observer: Arc<ObserverFn> = Arc::new(move || {
+            if let Some(inner) = weak.upgrade() {
+                if let Err(e) = inner.run() {
+                    crate::trace::warn_event!("Effect did not re-run: {e}");
+                }
+            }
+        });
+
+        track_reads(&observer, &mut callback);
+        *self.observer.lock().unwrap() = Some(observer);
+        *self.callback.lock().unwrap() = Some(callback);
+        *self.dirty.write().unwrap() = false;
         Ok(())
     }
 }
 
+impl<F> Drop for Effect<F> {
+    fn drop(&mut self) {
+        // Dropping the observer detaches the effect from every signal it read
+        self.inner.observer.lock().unwrap().take();
+    }
+}
+
 /// A computed value that derives from other reactive values
+///
+/// The value is recomputed lazily on the next `get` after a dependency changes.
 pub struct ReactiveComputed<T, F> {
     value: Arc<RwLock<Option<T>>>,
     compute_fn: Mutex<Option<F>>,
     dirty: Arc<RwLock<bool>>,
+    /// Observers that read this value and re-run when it is invalidated
+    observers: Arc<Mutex<Vec<Weak<ObserverFn>>>>,
+    /// Observer for the last computation; replacing it drops the old dependencies
+    observer: Mutex<Option<Arc<ObserverFn>>>,
 }
 
 // Explicit Send + Sync implementations
@@ -155,6 +347,7 @@ where
 {
     /// Get the computed value, recalculating if necessary
     pub fn get(&self) -> Result<T, SignalError> {
+        track_observer(&self.observers);
         if *self.dirty.read().unwrap() || self.value.read().unwrap().is_none() {
             self.recompute()?;
         }
@@ -175,7 +368,20 @@ where
 
         if should_compute {
             let mut compute_fn = self.compute_fn.lock().unwrap().take().unwrap();
-            let new_value = compute_fn();
+
+            let dirty = Arc::downgrade(&self.dirty);
+            let observers = Arc::downgrade(&self.observers);
+            let observer: Arc<ObserverFn> = Arc::new(move || {
+                if let Some(dirty) = dirty.upgrade() {
+                    *dirty.write().unwrap() = true;
+                }
+                if let Some(observers) = observers.upgrade() {
+                    notify_observers(&observers);
+                }
+            });
+
+            let new_value = track_reads(&observer, &mut compute_fn);
+            *self.observer.lock().unwrap() = Some(observer);
             *self.value.write().unwrap() = Some(new_value);
             *self.compute_fn.lock().unwrap() = Some(compute_fn);
             *self.dirty.write().unwrap() = false;
@@ -192,6 +398,7 @@ where
     Signal {
         value: Arc::new(RwLock::new(initial_value)),
         dirty: Arc::new(RwLock::new(false)),
+        observers: Arc::new(Mutex::new(Vec::new())),
     }
 }
 
@@ -204,10 +411,13 @@ where
     F: FnMut() + Send + Sync + 'static,
 {
     let effect = Effect {
-        callback: Mutex::new(Some(
-            Box::new(callback) as Box<dyn FnMut() + Send + Sync + 'static>
-        )),
-        dirty: Arc::new(RwLock::new(true)), // Start dirty to run on creation
+        inner: Arc::new(EffectInner {
+            callback: Mutex::new(Some(
+                Box::new(callback) as Box<dyn FnMut() + Send + Sync + 'static>
+            )),
+            dirty: RwLock::new(true), // Start dirty to run on creation
+            observer: Mutex::new(None),
+        }),
     };
 
     // Run initially
@@ -215,6 +425,220 @@ where
     effect
 }
 
+/// A derived value that only notifies dependents when its result changes
+///
+/// Created by [`create_memo`] or [`create_memo_by`]. Unlike
+/// [`ReactiveComputed`], a memo recomputes eagerly when a dependency changes
+/// and compares the new result with the cached one, so dependents do not
+/// re-run when the derived value stays the same.
+pub struct Memo<T> {
+    inner: Arc<MemoInner<T>>,
+}
+
+type MemoCompute<T> = Box<dyn FnMut() -> T + Send + Sync + 'static>;
+type MemoEq<T> = Box<dyn Fn(&T, &T) -> bool + Send + Sync + 'static>;
+
+struct MemoInner<T> {
+    value: RwLock<T>,
+    compute_fn: Mutex<Option<MemoCompute<T>>>,
+    eq: MemoEq<T>,
+    /// Observers that read this memo and re-run when its value changes
+    observers: Mutex<Vec<Weak<ObserverFn>>>,
+    /// Observer for the last computation; replacing it drops the old dependencies
+    observer: Mutex<Option<Arc<ObserverFn>>>,
+}
+
+impl<T> Memo<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Get the cached value
+    pub fn get(&self) -> RwLockReadGuard<'_, T> {
+        track_observer(&self.inner.observers);
+        self.inner.value.read().unwrap()
+    }
+}
+
+impl<T> MemoInner<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Observer that recomputes the memo when a dependency changes
+    fn observer(weak: Weak<Self>) -> Arc<ObserverFn> {
+        Arc::new(move || {
+            if let Some(inner) = weak.upgrade() {
+                inner.recompute();
+            }
+        })
+    }
+
+    /// Recompute and notify dependents if the value changed
+    fn recompute(self: &Arc<Self>) {
+        // The function is taken while it runs, so a missing one means the
+        // memo changed one of its own dependencies
+        let Some(mut compute_fn) = self.compute_fn.lock().unwrap().take() else {
+            crate::trace::warn_event!(
+                "Memo did not recompute: {}",
+                SignalError::CircularDependency
+            );
+            return;
+        };
+        let observer = Self::observer(Arc::downgrade(self));
+        let new_value = track_reads(&observer, &mut compute_fn);
+        *self.observer.lock().unwrap() = Some(observer);
+        *self.compute_fn.lock().unwrap() = Some(compute_fn);
+
+        let changed = {
+            let mut value = self.value.write().unwrap();
+            if (self.eq)(&value, &new_value) {
+                false
+            } else {
+                *value = new_value;
+                true
+            }
+        };
+        if changed {
+            notify_observers(&self.observers);
+        }
+    }
+}
+
+/// Create a memo that notifies dependents only when its value changes
+pub fn create_memo<T, F>(scope: &ReactiveScope, compute_fn: F) -> Memo<T>
+where
+    F: FnMut() -> T + Send + Sync + 'static,
+    T: PartialEq + Send + Sync + 'static,
+{
+    create_memo_by(scope, compute_fn, T::eq)
+}
+
+/// Create a memo that compares values with `eq` to decide whether they changed
+///
+/// Useful for derived objects where only some fields should gate updates,
+/// or where `T` does not implement `PartialEq`.
+pub fn create_memo_by<T, F, E>(_scope: &ReactiveScope, compute_fn: F, eq: E) -> Memo<T>
+where
+    F: FnMut() -> T + Send + Sync + 'static,
+    E: Fn(&T, &T) -> bool + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    let mut compute_fn: MemoCompute<T> = Box::new(compute_fn);
+    let inner = Arc::new_cyclic(|weak| {
+        let observer = MemoInner::observer(weak.clone());
+        let value = track_reads(&observer, &mut compute_fn);
+        MemoInner {
+            value: RwLock::new(value),
+            compute_fn: Mutex::new(Some(compute_fn)),
+            eq: Box::new(eq),
+            observers: Mutex::new(Vec::new()),
+            observer: Mutex::new(Some(observer)),
+        }
+    });
+
+    Memo { inner }
+}
+
+/// An effect that runs a future and restarts it when its dependencies change
+///
+/// Created by [`create_async_effect`]. Dropping it cancels any in-flight
+/// future and stops further re-runs.
+pub struct AsyncEffect {
+    inner: Arc<AsyncEffectInner>,
+}
+
+struct AsyncEffectInner {
+    /// Produces the future for one run
+    run: Box<dyn Fn() -> futures::future::BoxFuture<'static, ()> + Send + Sync>,
+    /// Executor the futures are spawned on
+    spawner: Box<dyn Spawn + Send + Sync>,
+    /// Observer for the current run; replacing it drops the previous dependencies
+    observer: Mutex<Option<Arc<ObserverFn>>>,
+    /// Handle that cancels the in-flight future
+    abort_handle: Mutex<Option<AbortHandle>>,
+}
+
+impl AsyncEffectInner {
+    /// Cancel the in-flight future, then start a new run
+    fn rerun(self: &Arc<Self>) {
+        self.cancel();
+
+        let weak = Arc::downgrade(self);
+        let observer: Arc<ObserverFn> = Arc::new(move || {
+            if let Some(inner) = weak.upgrade() {
+                inner.rerun();
+            }
+        });
+
+        let future = track_reads(&observer, || (self.run)());
+        *self.observer.lock().unwrap() = Some(observer);
+
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        *self.abort_handle.lock().unwrap() = Some(abort_handle);
+        let future = Abortable::new(future, registration);
+        if let Err(e) = self.spawner.spawn(async move {
+            let _ = future.await;
+        }) {
+            crate::trace::warn_event!("Failed to spawn async effect: {e}");
+        }
+    }
+
+    /// Cancel the in-flight future, if any
+    fn cancel(&self) {
+        if let Some(handle) = self.abort_handle.lock().unwrap().take() {
+            handle.abort();
+        }
+    }
+}
+
+impl AsyncEffect {
+    /// Cancel the in-flight future and re-run the effect
+    pub fn rerun(&self) {
+        self.inner.rerun();
+    }
+
+    /// Cancel the in-flight future without re-running
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+}
+
+impl Drop for AsyncEffect {
+    fn drop(&mut self) {
+        self.inner.cancel();
+        // Dropping the observer detaches the effect from every signal it read
+        self.inner.observer.lock().unwrap().take();
+    }
+}
+
+/// Create an effect that awaits a future and re-runs when its signals change
+///
+/// `f` is called immediately and again whenever a signal it read changes.
+/// Only reads made synchronously inside `f`, before it returns its future,
+/// are tracked; reads inside the future itself are not. Read the signals
+/// you depend on first, then move their values into an `async move` block.
+///
+/// Each future is spawned on `spawner`, so the executor must be driven for
+/// the effect to make progress. Futures must be `Send` because a re-run can
+/// be triggered from whichever thread sets a dependency. Before every re-run
+/// the previous future is cancelled: it is dropped the next time the
+/// executor polls it, without being polled to completion.
+pub fn create_async_effect<F, Fut, S>(_scope: &ReactiveScope, spawner: S, f: F) -> AsyncEffect
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+    S: Spawn + Send + Sync + 'static,
+{
+    let inner = Arc::new(AsyncEffectInner {
+        run: Box::new(move || Box::pin(f())),
+        spawner: Box::new(spawner),
+        observer: Mutex::new(None),
+        abort_handle: Mutex::new(None),
+    });
+    inner.rerun();
+
+    AsyncEffect { inner }
+}
+
 /// Create a new computed value
 pub fn create_computed<T, F>(
     _scope: &ReactiveScope,
@@ -230,12 +654,62 @@ where
             Box::new(compute_fn) as Box<dyn FnMut() -> T + Send + Sync + 'static>
         )),
         dirty: Arc::new(RwLock::new(true)), // Start dirty to compute on first access
+        observers: Arc::new(Mutex::new(Vec::new())),
+        observer: Mutex::new(None),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::future::FutureObj;
+    use futures::task::{noop_waker, SpawnError};
+    use futures::FutureExt;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::task::{Context, Poll};
+
+    /// Executor that queues spawned futures until they are polled manually
+    #[derive(Clone, Default)]
+    struct QueueSpawner {
+        queue: Arc<Mutex<Vec<FutureObj<'static, ()>>>>,
+    }
+
+    impl Spawn for QueueSpawner {
+        fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+            self.queue.lock().unwrap().push(future);
+            Ok(())
+        }
+    }
+
+    impl QueueSpawner {
+        /// Poll every queued future once, dropping those that finish
+        fn run_until_stalled(&self) {
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            let futures = std::mem::take(&mut *self.queue.lock().unwrap());
+            let pending = futures
+                .into_iter()
+                .filter_map(|mut future| match future.poll_unpin(&mut cx) {
+                    Poll::Ready(()) => None,
+                    Poll::Pending => Some(future),
+                })
+                .collect::<Vec<_>>();
+            self.queue.lock().unwrap().extend(pending);
+        }
+
+        fn len(&self) -> usize {
+            self.queue.lock().unwrap().len()
+        }
+    }
+
+    /// Sets a flag when dropped, to observe cancellation
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
 
     #[test]
     fn test_signal_creation_and_access() {
@@ -278,4 +752,293 @@ mod tests {
 
         assert_eq!(computed.get().unwrap(), 10);
     }
+
+    #[test]
+    fn test_async_effect_reruns_on_dependency_change() {
+        let scope = ReactiveScope::new();
+        let spawner = QueueSpawner::default();
+        let query = Arc::new(create_signal(&scope, 1));
+        let result = Arc::new(create_signal(&scope, 0));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let (query_dep, result_out, run_count) = (query.clone(), result.clone(), runs.clone());
+        let _effect = create_async_effect(&scope, spawner.clone(), move || {
+            run_count.fetch_add(1, Ordering::SeqCst);
+            let query = *query_dep.get();
+            let result = result_out.clone();
+            async move {
+                result.set(query * 10).unwrap();
+            }
+            .boxed()
+        });
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        spawner.run_until_stalled();
+        assert_eq!(*result.get(), 10);
+
+        query.set(2).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        spawner.run_until_stalled();
+        assert_eq!(*result.get(), 20);
+
+        // Writing a signal the effect never read does not re-run it
+        result.set(0).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_async_effect_cancels_in_flight_future() {
+        let scope = ReactiveScope::new();
+        let spawner = QueueSpawner::default();
+        let trigger = Arc::new(create_signal(&scope, 0));
+        let dropped = Arc::new(Mutex::new(Vec::<Arc<AtomicBool>>::new()));
+
+        let (trigger_dep, drop_flags) = (trigger.clone(), dropped.clone());
+        let effect = create_async_effect(&scope, spawner.clone(), move || {
+            let _ = *trigger_dep.get();
+            let flag = Arc::new(AtomicBool::new(false));
+            drop_flags.lock().unwrap().push(flag.clone());
+            let guard = DropFlag(flag);
+            async move {
+                // Never completes on its own, so only cancellation ends it
+                let _guard = guard;
+                futures::future::pending::<()>().await;
+            }
+        });
+
+        spawner.run_until_stalled();
+        assert_eq!(spawner.len(), 1);
+
+        trigger.set(1).unwrap();
+        spawner.run_until_stalled();
+
+        let flags = dropped.lock().unwrap().clone();
+        assert_eq!(flags.len(), 2);
+        assert!(flags[0].load(Ordering::SeqCst), "first run was not dropped");
+        assert!(!flags[1].load(Ordering::SeqCst));
+        assert_eq!(spawner.len(), 1);
+
+        // Dropping the effect cancels the last run and stops re-runs
+        drop(effect);
+        spawner.run_until_stalled();
+        assert!(flags[1].load(Ordering::SeqCst));
+        assert_eq!(spawner.len(), 0);
+
+        trigger.set(2).unwrap();
+        assert_eq!(dropped.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_untracked_read_does_not_rerun_effect() {
+        let scope = ReactiveScope::new();
+        let spawner = QueueSpawner::default();
+        let data = Arc::new(create_signal(&scope, 1));
+        let mode = Arc::new(create_signal(&scope, "light"));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let (data_dep, mode_read, run_count) = (data.clone(), mode.clone(), runs.clone());
+        let _effect = create_async_effect(&scope, spawner, move || {
+            run_count.fetch_add(1, Ordering::SeqCst);
+            let _data = *data_dep.get();
+            let _mode = untrack(|| *mode_read.get());
+            async {}
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        mode.set("dark").unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        data.set(2).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_batch_runs_effect_once() {
+        let scope = ReactiveScope::new();
+        let spawner = QueueSpawner::default();
+        let a = Arc::new(create_signal(&scope, 0));
+        let b = Arc::new(create_signal(&scope, 0));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let (a_dep, b_dep, run_count) = (a.clone(), b.clone(), runs.clone());
+        let _effect = create_async_effect(&scope, spawner, move || {
+            run_count.fetch_add(1, Ordering::SeqCst);
+            let _sum = *a_dep.get() + *b_dep.get();
+            async {}
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        batch(|| {
+            a.set(1).unwrap();
+            batch(|| b.set(2).unwrap());
+            // The inner batch does not flush while the outer one is open
+            assert_eq!(runs.load(Ordering::SeqCst), 1);
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // Outside a batch every set notifies immediately
+        a.set(3).unwrap();
+        b.set(4).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_basic_computed() {
+        let scope = ReactiveScope::new();
+        let count = Arc::new(create_signal(&scope, 0));
+        let count_for_computed = count.clone();
+        let double = create_computed(&scope, move || *count_for_computed.get() * 2);
+
+        assert_eq!(double.get().unwrap(), 0);
+
+        count.set(5).unwrap();
+        assert_eq!(double.get().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_effect_runs_on_dependency_change() {
+        let scope = ReactiveScope::new();
+        let count = Arc::new(create_signal(&scope, 0));
+        let effect_ran = Arc::new(AtomicUsize::new(0));
+
+        let effect_ran_clone = effect_ran.clone();
+        let count_for_effect = count.clone();
+        let _effect = create_effect(&scope, move || {
+            let _ = *count_for_effect.get(); // Track dependency
+            effect_ran_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Effect should have run once initially
+        assert_eq!(effect_ran.load(Ordering::SeqCst), 1);
+
+        // Effect should run again when signal changes
+        count.set(5).unwrap();
+        assert_eq!(effect_ran.load(Ordering::SeqCst), 2);
+
+        // Effect should not run when value doesn't change
+        count.set_if_changed(5).unwrap();
+        assert_eq!(effect_ran.load(Ordering::SeqCst), 2);
+
+        // Plain set notifies even with an equal value
+        count.set(5).unwrap();
+        assert_eq!(effect_ran.load(Ordering::SeqCst), 3);
+
+        // Effect should run again when value changes
+        count.set_if_changed(10).unwrap();
+        assert_eq!(effect_ran.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_computed_dependency_chain() {
+        let scope = ReactiveScope::new();
+        let count = Arc::new(create_signal(&scope, 0));
+        let count_for_double = count.clone();
+        let double = Arc::new(create_computed(&scope, move || *count_for_double.get() * 2));
+        let double_for_quad = double.clone();
+        let quadruple = create_computed(&scope, move || double_for_quad.get().unwrap() * 2);
+
+        assert_eq!(double.get().unwrap(), 0);
+        assert_eq!(quadruple.get().unwrap(), 0);
+
+        count.set(5).unwrap();
+        assert_eq!(double.get().unwrap(), 10);
+        assert_eq!(quadruple.get().unwrap(), 20);
+    }
+
+    #[test]
+    fn test_multiple_dependencies() {
+        let scope = ReactiveScope::new();
+        let a = Arc::new(create_signal(&scope, 1));
+        let b = Arc::new(create_signal(&scope, 2));
+        let a_for_sum = a.clone();
+        let b_for_sum = b.clone();
+        let sum = create_computed(&scope, move || *a_for_sum.get() + *b_for_sum.get());
+
+        assert_eq!(sum.get().unwrap(), 3);
+
+        a.set(5).unwrap();
+        assert_eq!(sum.get().unwrap(), 7);
+
+        b.set(10).unwrap();
+        assert_eq!(sum.get().unwrap(), 15);
+    }
+
+    #[test]
+    fn test_dropped_effect_stops_running() {
+        let scope = ReactiveScope::new();
+        let count = Arc::new(create_signal(&scope, 0));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let (count_dep, run_count) = (count.clone(), runs.clone());
+        let effect = create_effect(&scope, move || {
+            let _ = *count_dep.get();
+            run_count.fetch_add(1, Ordering::SeqCst);
+        });
+        drop(effect);
+
+        count.set(1).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_unchanged_memo_does_not_rerun_subscribers() {
+        let scope = ReactiveScope::new();
+        let count = Arc::new(create_signal(&scope, 1));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let count_dep = count.clone();
+        let is_odd = Arc::new(create_memo(&scope, move || *count_dep.get() % 2 == 1));
+        let (memo_dep, run_count) = (is_odd.clone(), runs.clone());
+        let _effect = create_effect(&scope, move || {
+            let _ = *memo_dep.get();
+            run_count.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Still odd, so the effect does not re-run
+        count.set(3).unwrap();
+        assert!(*is_odd.get());
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        count.set(4).unwrap();
+        assert!(!*is_odd.get());
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_memo_by_uses_custom_equality() {
+        #[derive(Debug)]
+        struct Profile {
+            id: u32,
+            fetched_at: u64,
+        }
+
+        let scope = ReactiveScope::new();
+        let source = Arc::new(create_signal(&scope, (1, 0)));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let source_dep = source.clone();
+        let profile = Arc::new(create_memo_by(
+            &scope,
+            move || {
+                let (id, fetched_at) = *source_dep.get();
+                Profile { id, fetched_at }
+            },
+            |a, b| a.id == b.id,
+        ));
+        let (memo_dep, run_count) = (profile.clone(), runs.clone());
+        let _effect = create_effect(&scope, move || {
+            let _ = memo_dep.get().id;
+            run_count.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Only the timestamp changed, which the comparison ignores
+        source.set((1, 100)).unwrap();
+        assert_eq!(profile.get().fetched_at, 0);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        source.set((2, 200)).unwrap();
+        assert_eq!(profile.get().id, 2);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
 }
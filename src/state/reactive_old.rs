@@ -1,685 +0,0 @@
-//! Reactive state management for Orbit UI framework
-//!
-//! This module provides a simplified but robust reactive system for state management.
-//! It uses a scope-based approach to manage effects and dependencies, avoiding
-//! the circular dependency issues of the previous implementation.
-
-use std::{
-    cell::{Cell, RefCell},
-    collections::HashSet,
-    fmt,
-    rc::{Rc, Weak},
-};
-
-/// Error type for signal operations
-#[derive(Debug)]
-pub enum SignalError {
-    /// Error accessing signal value
-    AccessError(String),
-    /// Type mismatch in signal operation
-    TypeMismatch {
-        expected: &'static str,
-        actual: &'static str,
-    },
-    /// Other signal error
-    Other(String),
-}
-
-impl fmt::Display for SignalError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::AccessError(msg) => write!(f, "Signal access error: {}", msg),
-            Self::TypeMismatch { expected, actual } => {
-                write!(f, "Type mismatch: expected {}, got {}", expected, actual)
-            }
-            Self::Other(msg) => write!(f, "Signal error: {}", msg),
-        }
-    }
-}
-
-impl std::error::Error for SignalError {}
-
-/// A unique identifier for effects
-type EffectId = u64;
-static NEXT_EFFECT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
-
-/// A unique identifier for signals
-type SignalId = u64;
-static NEXT_SIGNAL_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
-
-/// The reactive scope manages the execution context for effects and computed values
-pub struct ReactiveScope {
-    current_effect: RefCell<Option<EffectId>>,
-    effects: RefCell<Vec<Box<dyn EffectRunner>>>,
-}
-
-impl ReactiveScope {
-    /// Create a new reactive scope
-    pub fn new() -> Self {
-        Self {
-            current_effect: RefCell::new(None),
-            effects: RefCell::new(Vec::new()),
-        }
-    }
-
-    /// Run a closure with effect tracking enabled
-    fn with_effect_tracking<F, R>(&self, effect_id: EffectId, f: F) -> R
-    where
-        F: FnOnce() -> R,
-    {
-        let old_effect = self.current_effect.replace(Some(effect_id));
-        let result = f();
-        self.current_effect.replace(old_effect);
-        result
-    }
-
-    /// Get the currently running effect ID
-    fn current_effect(&self) -> Option<EffectId> {
-        *self.current_effect.borrow()
-    }
-}
-
-impl Default for ReactiveScope {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-/// Trait for objects that can run effects
-trait EffectRunner {
-    fn run(&mut self, scope: &ReactiveScope);
-    fn id(&self) -> EffectId;
-}
-
-/// A reactive signal that holds a value and notifies subscribers when it changes
-pub struct Signal<T> {
-    id: SignalId,
-    value: RefCell<T>,
-    subscribers: RefCell<HashSet<EffectId>>,
-    scope: Weak<ReactiveScope>,
-}
-
-impl<T: Clone + 'static> Signal<T> {
-    /// Create a new signal with an initial value
-    pub fn new(initial: T, scope: &Rc<ReactiveScope>) -> Rc<Self> {
-        let id = NEXT_SIGNAL_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        Rc::new(Self {
-            id,
-            value: RefCell::new(initial),
-            subscribers: RefCell::new(HashSet::new()),
-            scope: Rc::downgrade(scope),
-        })
-    }
-
-    /// Get the current value, tracking this read in the current effect
-    pub fn get(&self) -> T {
-        // Track this read in the current effect if one is active
-        if let Some(scope) = self.scope.upgrade() {
-            if let Some(effect_id) = scope.current_effect() {
-                self.subscribers.borrow_mut().insert(effect_id);
-            }
-        }
-
-        self.value.borrow().clone()
-    }
-
-    /// Set a new value and notify subscribers if changed
-    pub fn set(&self, new_value: T)
-    where
-        T: PartialEq,
-    {
-        let should_notify = {
-            let mut value = self.value.borrow_mut();
-            if *value != new_value {
-                *value = new_value;
-                true
-            } else {
-                false
-            }
-        };
-
-        if should_notify {
-            self.notify();
-        }
-    }
-
-    /// Update the value using a function
-    pub fn update<F>(&self, f: F)
-    where
-        F: FnOnce(&T) -> T,
-        T: PartialEq,
-    {
-        let should_notify = {
-            let value = self.value.borrow();
-            let new_value = f(&value);
-            let changed = *value != new_value;
-            drop(value); // Release borrow before potentially mutating
-
-            if changed {
-                *self.value.borrow_mut() = new_value;
-                true
-            } else {
-                false
-            }
-        };
-
-        if should_notify {
-            self.notify();
-        }
-    }
-
-    /// Notify all subscribers of a change
-    fn notify(&self) {
-        if let Some(scope) = self.scope.upgrade() {
-            let subscribers = self.subscribers.borrow().clone();
-            let mut effects = scope.effects.borrow_mut();
-            
-            for effect_id in subscribers {
-                if let Some(effect) = effects.iter_mut().find(|e| e.id() == effect_id) {
-                    effect.run(&scope);
-                }
-            }
-        }
-    }
-}
-
-impl Effect {
-    /// Create a new effect
-    pub fn new<F>(f: F) -> Self
-    where
-        F: FnMut() + 'static,
-    {
-        let id = NEXT_EFFECT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        Self {
-            id,
-            f: Box::new(f),
-            dependencies: Rc::new(RefCell::new(HashSet::new())),
-            _unsend: PhantomData,
-            _unsync: PhantomData,
-        }
-    }
-
-    /// Run the effect, tracking dependencies
-    pub fn run(&mut self) {
-        // Store current dependencies to compare later
-        let old_dependencies = self.dependencies.borrow().clone();
-
-        // Create a new set for tracking dependencies during this run
-        let dependencies = Rc::new(RefCell::new(HashSet::new()));
-
-        // Set up tracking context
-        CURRENT_EFFECT.with(|current| {
-            *current.borrow_mut() = Some(Rc::clone(&dependencies));
-        });
-
-        // Run the effect
-        (self.f)();
-
-        // Clean up tracking context
-        CURRENT_EFFECT.with(|current| {
-            *current.borrow_mut() = None;
-        });
-
-        // Update dependencies
-        *self.dependencies.borrow_mut() = dependencies.borrow().clone();
-
-        // Unsubscribe from signals that are no longer dependencies
-        for signal_id in old_dependencies.difference(&self.dependencies.borrow()) {
-            SIGNAL_REGISTRY.with(|registry| {
-                if let Some(signal) = registry.borrow().get(signal_id) {
-                    signal.unsubscribe(self.id);
-                }
-            });
-        }
-
-        // Subscribe to signals that are new dependencies
-        for signal_id in self.dependencies.borrow().iter() {
-            SIGNAL_REGISTRY.with(|registry| {
-                if let Some(signal) = registry.borrow().get(signal_id) {
-                    signal.subscribe(self.id);
-                }
-            });
-        }
-    }
-}
-
-impl Drop for Effect {
-    fn drop(&mut self) {
-        // Unsubscribe from all signals when the effect is dropped
-        for signal_id in self.dependencies.borrow().iter() {
-            SIGNAL_REGISTRY.with(|registry| {
-                if let Some(signal) = registry.borrow().get(signal_id) {
-                    signal.unsubscribe(self.id);
-                }
-            });
-        }
-    }
-}
-
-/// Create an effect that tracks dependencies and re-runs when they change
-pub fn create_effect<F>(f: F) -> Effect
-where
-    F: FnMut() + 'static,
-{
-    let mut effect = Effect::new(f);
-    effect.run();
-    effect
-}
-
-/// A unique identifier for a signal
-type SignalId = u64;
-static NEXT_SIGNAL_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
-
-thread_local! {
-    static SIGNAL_REGISTRY: RefCell<HashMap<SignalId, Rc<dyn SignalBase>>> = RefCell::new(HashMap::new());
-}
-
-/// Base trait for all signals
-pub trait SignalBase {
-    /// Subscribe an effect to this signal
-    fn subscribe(&self, effect_id: EffectId);
-
-    /// Unsubscribe an effect from this signal
-    fn unsubscribe(&self, effect_id: EffectId);
-
-    /// Notify all subscribers of a change
-    fn notify(&self);
-
-    /// Get the signal's ID
-    #[allow(dead_code)]
-    fn id(&self) -> SignalId;
-}
-
-/// A reactive value that tracks reads and notifies subscribers on changes
-pub struct Signal<T> {
-    id: SignalId,
-    value: RefCell<T>,
-    subscribers: RefCell<HashSet<EffectId>>,
-    _unsend: PhantomData<Rc<()>>,      // Mark as !Send
-    _unsync: PhantomData<RefCell<()>>, // Mark as !Sync
-}
-
-impl<T: Clone + 'static> Signal<T> {
-    /// Create a new signal with an initial value
-    pub fn new(initial: T) -> Rc<Self> {
-        let id = NEXT_SIGNAL_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        let signal = Rc::new(Self {
-            id,
-            value: RefCell::new(initial),
-            subscribers: RefCell::new(HashSet::new()),
-            _unsend: PhantomData,
-            _unsync: PhantomData,
-        });
-
-        // Register the signal
-        SIGNAL_REGISTRY.with(|registry| {
-            registry
-                .borrow_mut()
-                .insert(id, signal.clone() as Rc<dyn SignalBase>);
-        });
-
-        signal
-    }
-
-    /// Get the current value, tracking this read in the current effect
-    pub fn get(&self) -> T {
-        // Track this read in the current effect
-        CURRENT_EFFECT.with(|current| {
-            if let Some(dependencies) = current.borrow().as_ref() {
-                dependencies.borrow_mut().insert(self.id);
-            }
-        });
-
-        self.value.borrow().clone()
-    }
-
-    /// Set a new value and notify subscribers if changed
-    pub fn set(&self, new_value: T)
-    where
-        T: PartialEq,
-    {
-        let should_notify = {
-            let mut value = self.value.borrow_mut();
-
-            // Check if value changed
-            if *value != new_value {
-                *value = new_value;
-                true
-            } else {
-                false
-            }
-        };
-
-        if should_notify {
-            self.notify();
-        }
-    }
-
-    /// Update the value using a function
-    pub fn update<F>(&self, f: F)
-    where
-        F: FnOnce(&T) -> T,
-        T: PartialEq,
-    {
-        let should_notify = {
-            let value = self.value.borrow();
-            let new_value = f(&value);
-            let changed = *value != new_value;
-            drop(value); // Release borrow before potentially mutating
-
-            if changed {
-                *self.value.borrow_mut() = new_value;
-                true
-            } else {
-                false
-            }
-        };
-
-        if should_notify {
-            self.notify();
-        }
-    }
-}
-
-impl<T: 'static> SignalBase for Signal<T> {
-    fn subscribe(&self, effect_id: EffectId) {
-        self.subscribers.borrow_mut().insert(effect_id);
-    }
-
-    fn unsubscribe(&self, effect_id: EffectId) {
-        self.subscribers.borrow_mut().remove(&effect_id);
-    }
-
-    fn notify(&self) {
-        // Get a copy of subscribers to avoid borrow issues during effect execution
-        let subscribers = self.subscribers.borrow().clone();
-
-        // Run all subscriber effects
-        for effect_id in subscribers {
-            EFFECT_REGISTRY.with(|registry| {
-                if let Some(effect) = registry.borrow_mut().get_mut(&effect_id) {
-                    effect.run();
-                }
-            });
-        }
-    }
-
-    fn id(&self) -> SignalId {
-        self.id
-    }
-}
-
-impl<T> Drop for Signal<T> {
-    fn drop(&mut self) {
-        // Unregister the signal
-        SIGNAL_REGISTRY.with(|registry| {
-            registry.borrow_mut().remove(&self.id);
-        });
-    }
-}
-
-thread_local! {
-    static EFFECT_REGISTRY: RefCell<HashMap<EffectId, Effect>> = RefCell::new(HashMap::new());
-}
-
-/// A derived signal that computes its value from other signals
-pub struct Computed<T> {
-    signal: Rc<Signal<T>>,
-    _effect: Effect,
-}
-
-impl<T: Clone + PartialEq + 'static> Computed<T> {
-    /// Create a new computed signal
-    pub fn new<F>(compute_fn: F) -> Rc<Self>
-    where
-        F: Fn() -> T + 'static,
-    {
-        let initial = compute_fn();
-        let signal = Signal::new(initial);
-        let signal_clone = signal.clone();
-
-        // Create effect to update signal when dependencies change
-        let effect = create_effect(move || {
-            let new_value = compute_fn();
-            signal_clone.set(new_value);
-        });
-
-        Rc::new(Self {
-            signal,
-            _effect: effect,
-        })
-    }
-
-    /// Get the current value
-    pub fn get(&self) -> T {
-        self.signal.get()
-    }
-}
-
-/// Create a new signal with the given initial value
-pub fn create_signal<T: Clone + PartialEq + 'static>(initial: T) -> Rc<Signal<T>> {
-    Signal::new(initial)
-}
-
-/// Create a new computed signal with the given compute function
-pub fn create_computed<T, F>(compute_fn: F) -> Rc<Computed<T>>
-where
-    T: Clone + PartialEq + 'static,
-    F: Fn() -> T + 'static,
-{
-    Computed::new(compute_fn)
-}
-
-/// A thread-safe version of Signal for use across threads
-pub struct ThreadSafeSignal<T> {
-    id: SignalId,
-    value: Arc<RwLock<T>>,
-    subscribers: Arc<Mutex<HashSet<EffectId>>>,
-}
-
-impl<T: Clone + Send + Sync + 'static> ThreadSafeSignal<T> {
-    /// Create a new thread-safe signal with an initial value
-    pub fn new(initial: T) -> Arc<Self> {
-        let id = NEXT_SIGNAL_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        Arc::new(Self {
-            id,
-            value: Arc::new(RwLock::new(initial)),
-            subscribers: Arc::new(Mutex::new(HashSet::new())),
-        })
-    }
-
-    /// Get the current value
-    pub fn get(&self) -> Result<T, SignalError> {
-        match self.value.read() {
-            Ok(guard) => Ok(guard.clone()),
-            Err(_) => Err(SignalError::AccessError(
-                "Failed to read signal value".to_string(),
-            )),
-        }
-    }
-
-    /// Set a new value
-    pub fn set(&self, new_value: T) -> Result<(), SignalError>
-    where
-        T: PartialEq,
-    {
-        let should_notify = {
-            match self.value.write() {
-                Ok(mut guard) => {
-                    *guard = new_value;
-                    true
-                }
-                Err(_) => {
-                    return Err(SignalError::AccessError(
-                        "Failed to write signal value".to_string(),
-                    ))
-                }
-            }
-        };
-
-        if should_notify {
-            self.notify_subscribers()?;
-        }
-
-        Ok(())
-    }
-
-    /// Notify all subscribers of a change
-    fn notify_subscribers(&self) -> Result<(), SignalError> {
-        match self.subscribers.lock() {
-            Ok(guard) => {
-                // In a real implementation, we would have a thread-safe way to run effects
-                // For simplicity, we'll just log that notification happened
-                println!("Signal {} notifying {} subscribers", self.id, guard.len());
-                Ok(())
-            }
-            Err(_) => Err(SignalError::AccessError(
-                "Failed to lock subscribers".to_string(),
-            )),
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::cell::Cell;
-
-    /// Helper to safely run tests with cleanup
-    fn with_clean_state<F, R>(test: F) -> R
-    where
-        F: FnOnce() -> R + std::panic::UnwindSafe,
-    {
-        // Clear any existing state
-        let _ = std::panic::catch_unwind(|| {
-            CURRENT_EFFECT.with(|current| {
-                current.borrow_mut().take();
-            });
-            SIGNAL_REGISTRY.with(|registry| {
-                registry.borrow_mut().clear();
-            });
-            EFFECT_REGISTRY.with(|registry| {
-                registry.borrow_mut().clear();
-            });
-        });
-
-        // Run the test
-        let result = std::panic::catch_unwind(test);
-
-        // Clean up after test
-        let _ = std::panic::catch_unwind(|| {
-            CURRENT_EFFECT.with(|current| {
-                current.borrow_mut().take();
-            });
-            SIGNAL_REGISTRY.with(|registry| {
-                registry.borrow_mut().clear();
-            });
-            EFFECT_REGISTRY.with(|registry| {
-                registry.borrow_mut().clear();
-            });
-        });
-
-        result.unwrap_or_else(|e| std::panic::resume_unwind(e))
-    }
-
-    #[test]
-    fn test_basic_signal() {
-        with_clean_state(|| {
-            let count = create_signal(0);
-            assert_eq!(count.get(), 0);
-
-            count.set(5);
-            assert_eq!(count.get(), 5);
-
-            count.update(|&c| c + 1);
-            assert_eq!(count.get(), 6);
-        });
-    }
-
-    #[test]
-    #[ignore = "Reactive system needs redesign - temporarily disabled for CI"]
-    fn test_basic_computed() {
-        with_clean_state(|| {
-            let count = create_signal(0);
-            let count_for_computed = count.clone();
-            let double = create_computed(move || count_for_computed.get() * 2);
-
-            assert_eq!(double.get(), 0);
-
-            count.set(5);
-            assert_eq!(double.get(), 10);
-        });
-    }
-
-    #[test]
-    #[ignore = "Reactive system needs redesign - temporarily disabled for CI"]
-    fn test_effect_runs_on_dependency_change() {
-        with_clean_state(|| {
-            let count = create_signal(0);
-            let effect_ran = Rc::new(Cell::new(0));
-
-            let effect_ran_clone = effect_ran.clone();
-            let count_for_effect = count.clone();
-            let _effect = create_effect(move || {
-                let _ = count_for_effect.get(); // Track dependency
-                effect_ran_clone.set(effect_ran_clone.get() + 1);
-            });
-
-            // Effect should have run once initially
-            assert_eq!(effect_ran.get(), 1);
-
-            // Effect should run again when signal changes
-            count.set(5);
-            assert_eq!(effect_ran.get(), 2);
-
-            // Effect should not run when value doesn't change
-            count.set(5);
-            assert_eq!(effect_ran.get(), 2);
-
-            // Effect should run again when value changes
-            count.set(10);
-            assert_eq!(effect_ran.get(), 3);
-        });
-    }
-
-    #[test]
-    #[ignore = "Reactive system needs redesign - temporarily disabled for CI"]
-    fn test_computed_dependency_chain() {
-        with_clean_state(|| {
-            let count = create_signal(0);
-            let count_for_double = count.clone();
-            let double = create_computed(move || count_for_double.get() * 2);
-            let double_for_quad = double.clone();
-            let quadruple = create_computed(move || double_for_quad.get() * 2);
-
-            assert_eq!(count.get(), 0);
-            assert_eq!(double.get(), 0);
-            assert_eq!(quadruple.get(), 0);
-
-            count.set(5);
-            assert_eq!(count.get(), 5);
-            assert_eq!(double.get(), 10);
-            assert_eq!(quadruple.get(), 20);
-        });
-    }
-
-    #[test]
-    #[ignore = "Reactive system needs redesign - temporarily disabled for CI"]
-    fn test_multiple_dependencies() {
-        with_clean_state(|| {
-            let a = create_signal(1);
-            let b = create_signal(2);
-            let a_for_sum = a.clone();
-            let b_for_sum = b.clone();
-            let sum = create_computed(move || a_for_sum.get() + b_for_sum.get());
-
-            assert_eq!(sum.get(), 3);
-
-            a.set(5);
-            assert_eq!(sum.get(), 7);
-
-            b.set(10);
-            assert_eq!(sum.get(), 15);
-        });
-    }
-}
@@ -1,12 +1,22 @@
 // Enhanced styling system for the Orbit UI framework with CSS-like properties and layout integration
 
+mod animation;
+mod selector;
 #[cfg(test)]
 mod tests;
 
+pub use animation::{Animation, Keyframe, Spring};
+pub use selector::{ElementInfo, SelectorMatcher};
+
 use crate::component::ComponentId;
-use crate::layout::{Dimension, EdgeValues, LayoutStyle};
+use crate::layout::{
+    multiply_matrices, CalcExpr, Dimension, Direction, EdgeDimensions, EdgeValues, LayoutStyle,
+    Point, Rect, TransformMatrix, DEFAULT_FONT_SIZE, IDENTITY_MATRIX,
+};
 use std::collections::HashMap;
+use std::iter::Peekable;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::vec::IntoIter;
 
 /// CSS selector specificity (a, b, c)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -19,6 +29,8 @@ pub struct CssProperty {
     pub name: String,
     /// Value of the property
     pub value: String,
+    /// Whether the declaration was marked `!important`
+    pub important: bool,
 }
 
 /// CSS selector
@@ -41,6 +53,83 @@ pub struct StyleRule {
     pub specificity: Specificity,
     /// Source order for breaking specificity ties
     pub source_order: usize,
+    /// Viewport condition of an enclosing `@media` block, if any
+    pub media: Option<MediaQuery>,
+}
+
+/// Viewport condition of an `@media` block
+///
+/// Only width and height features are supported. Every condition must hold
+/// for the query to match, as when they are joined by `and`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaQuery {
+    pub conditions: Vec<MediaCondition>,
+}
+
+/// A single `(feature: value)` test within a media query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaCondition {
+    MinWidth(f32),
+    MaxWidth(f32),
+    MinHeight(f32),
+    MaxHeight(f32),
+}
+
+impl MediaQuery {
+    /// Parse the condition of an `@media` rule
+    ///
+    /// Accepts e.g. `(min-width: 768px) and (max-width: 1024px)`, with or
+    /// without the leading `@media` and an optional `screen` or `all` media
+    /// type.
+    pub fn parse(query: &str) -> Result<Self, StyleError> {
+        let invalid = || StyleError::ParseError(format!("Invalid media query: {query}"));
+
+        let query = query.trim();
+        let query = query.strip_prefix("@media").unwrap_or(query);
+        let mut conditions = Vec::new();
+
+        for part in query.split(" and ") {
+            let part = part.trim();
+            if matches!(part, "all" | "screen") {
+                continue;
+            }
+
+            let feature = part
+                .strip_prefix('(')
+                .and_then(|part| part.strip_suffix(')'))
+                .ok_or_else(invalid)?;
+            let (name, value) = feature.split_once(':').ok_or_else(invalid)?;
+            let value = value.trim();
+            let value: f32 = value
+                .strip_suffix("px")
+                .unwrap_or(value)
+                .parse()
+                .map_err(|_| invalid())?;
+
+            conditions.push(match name.trim() {
+                "min-width" => MediaCondition::MinWidth(value),
+                "max-width" => MediaCondition::MaxWidth(value),
+                "min-height" => MediaCondition::MinHeight(value),
+                "max-height" => MediaCondition::MaxHeight(value),
+                _ => return Err(invalid()),
+            });
+        }
+
+        if conditions.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self { conditions })
+    }
+
+    /// Whether a viewport of the given size satisfies every condition
+    pub fn matches(&self, viewport_width: f32, viewport_height: f32) -> bool {
+        self.conditions.iter().all(|condition| match *condition {
+            MediaCondition::MinWidth(width) => viewport_width >= width,
+            MediaCondition::MaxWidth(width) => viewport_width <= width,
+            MediaCondition::MinHeight(height) => viewport_height >= height,
+            MediaCondition::MaxHeight(height) => viewport_height <= height,
+        })
+    }
 }
 
 /// CSS stylesheet
@@ -65,9 +154,16 @@ impl StyleRule {
             scoped,
             specificity,
             source_order,
+            media: None,
         }
     }
 
+    /// Only apply this rule while the viewport matches `media`
+    pub fn with_media(mut self, media: MediaQuery) -> Self {
+        self.media = Some(media);
+        self
+    }
+
     /// Calculate selector specificity (a, b, c)
     /// a = ID selectors
     /// b = Class selectors, attributes, and pseudo-classes
@@ -98,17 +194,49 @@ impl StyleRule {
     }
 
     /// Apply component scoping to selectors
-    pub fn apply_scoping(&mut self, component_id: &str) {
+    ///
+    /// The scope class is added to the subject of each selector, the part
+    /// right of the last combinator, so `.card .title` becomes
+    /// `.card .title.{scope_class}`. Only elements carrying the class match,
+    /// not everything beneath them, which keeps a scoped component's rules
+    /// out of the components it renders.
+    pub fn apply_scoping(&mut self, scope_class: &str) {
         if self.scoped {
             for selector in &mut self.selectors {
-                // Add component scoping class to each selector
-                selector.selector = format!(".{} {}", component_id, selector.selector);
-                self.specificity = Self::calculate_specificity(&selector.selector);
+                selector.selector = scope_selector(&selector.selector, scope_class);
             }
+            self.specificity = self
+                .selectors
+                .iter()
+                .map(|s| Self::calculate_specificity(&s.selector))
+                .max()
+                .unwrap_or(Specificity(0, 0, 0));
         }
     }
 }
 
+/// Class that scopes a component's styles
+///
+/// The style engine gives every component the scope class of its own ID and
+/// of the component that rendered it, so a scoped rule matches the
+/// component and the elements it renders, but nothing nested deeper.
+pub fn scope_class(component_id: ComponentId) -> String {
+    format!("orbit-c{}", component_id.id())
+}
+
+/// Add `.scope_class` to the subject compound of a selector, before any
+/// pseudo-classes
+fn scope_selector(selector: &str, scope_class: &str) -> String {
+    let selector = selector.trim();
+    let subject = selector
+        .rfind([' ', '>', '+', '~'])
+        .map_or(0, |index| index + 1);
+    let end = selector[subject..]
+        .find(':')
+        .map_or(selector.len(), |index| subject + index);
+    format!("{}.{}{}", &selector[..end], scope_class, &selector[end..])
+}
+
 impl Stylesheet {
     /// Create a new empty stylesheet
     pub fn new() -> Self {
@@ -121,10 +249,14 @@ impl Stylesheet {
     }
 
     /// Parse CSS text into a stylesheet
+    ///
+    /// Rules inside an `@media` block keep its condition and only apply
+    /// while the viewport matches it.
     pub fn parse(css: &str, scoped: bool) -> Result<Self, StyleError> {
         let mut stylesheet = Self::new();
         let mut current_selectors = Vec::new();
         let mut current_properties = Vec::new();
+        let mut current_media = None;
         let mut in_rule = false;
         let mut source_order = 0;
 
@@ -136,7 +268,11 @@ impl Stylesheet {
                 continue;
             }
 
-            if line.contains('{') {
+            if line.starts_with("@media") && line.contains('{') {
+                // Start of a media block - rules until its `}` share the query
+                let query = line.split('{').next().unwrap_or("");
+                current_media = Some(MediaQuery::parse(query)?);
+            } else if line.contains('{') {
                 // Start of a rule - parse selectors
                 in_rule = true;
                 current_selectors = line
@@ -149,6 +285,9 @@ impl Stylesheet {
                         properties: Vec::new(),
                     })
                     .collect();
+            } else if line.contains('}') && !in_rule {
+                // End of a media block
+                current_media = None;
             } else if line.contains('}') {
                 // End of a rule - create StyleRule
                 in_rule = false;
@@ -156,7 +295,8 @@ impl Stylesheet {
                     for selector in &mut current_selectors {
                         selector.properties = current_properties.clone();
                     }
-                    let rule = StyleRule::new(current_selectors.clone(), scoped, source_order);
+                    let mut rule = StyleRule::new(current_selectors.clone(), scoped, source_order);
+                    rule.media = current_media.clone();
                     stylesheet.add_rule(rule);
                     source_order += 1;
                 }
@@ -166,9 +306,15 @@ impl Stylesheet {
                 // Property definition
                 let parts: Vec<&str> = line.split(':').collect();
                 if parts.len() == 2 {
+                    let value = parts[1].trim().trim_end_matches(';').trim_end();
+                    let (value, important) = match value.strip_suffix("!important") {
+                        Some(value) => (value.trim_end(), true),
+                        None => (value, false),
+                    };
                     current_properties.push(CssProperty {
                         name: parts[0].trim().to_string(),
-                        value: parts[1].trim().trim_end_matches(';').to_string(),
+                        value: value.to_string(),
+                        important,
                     });
                 }
             }
@@ -195,6 +341,9 @@ impl std::fmt::Display for Stylesheet {
                     result.push_str(&property.name);
                     result.push_str(": ");
                     result.push_str(&property.value);
+                    if property.important {
+                        result.push_str(" !important");
+                    }
                     result.push_str(";\n");
                 }
 
@@ -226,12 +375,19 @@ pub struct Style {
     pub font_size: Option<f32>,
     pub font_weight: Option<FontWeight>,
     pub font_style: Option<FontStyle>,
-    pub line_height: Option<f32>,
+    pub line_height: Option<LineHeight>,
     pub letter_spacing: Option<f32>,
     pub text_align: Option<TextAlign>,
     pub text_decoration: Option<TextDecoration>,
     pub text_transform: Option<TextTransform>,
 
+    // Image properties
+    pub object_fit: Option<ObjectFit>,
+
+    // Spacing properties (written into the layout style)
+    pub margin: Option<EdgeDimensions>,
+    pub padding: Option<EdgeDimensions>,
+
     // Layout integration (connects to layout engine)
     pub layout_style: Option<LayoutStyle>,
 
@@ -240,9 +396,12 @@ pub struct Style {
     pub transform_origin: Option<Point2D>,
 
     // Transition properties (for animation integration)
+    /// CSS names of the properties to transition, or `all`
     pub transition_property: Option<Vec<String>>,
+    /// Transition length in seconds
     pub transition_duration: Option<f32>,
     pub transition_timing_function: Option<TimingFunction>,
+    /// Wait before a transition starts, in seconds
     pub transition_delay: Option<f32>,
 
     // Shadow properties
@@ -306,6 +465,245 @@ impl std::hash::Hash for Color {
     }
 }
 
+impl Color {
+    /// Resolve this color into normalized RGBA channels in [0, 1]
+    ///
+    /// Hex strings (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`) and named CSS colors
+    /// are resolved here so renderers have a single path to concrete channel
+    /// values. Returns `None` for `currentColor`, which depends on context, and
+    /// for values that cannot be resolved.
+    pub fn to_rgba(&self) -> Option<(f32, f32, f32, f32)> {
+        match self {
+            Color::Rgba(r, g, b, a) => Some((*r, *g, *b, *a)),
+            Color::Hex(hex) => Self::hex_to_rgba(hex),
+            Color::Named(name) => {
+                let name = name.trim().to_ascii_lowercase();
+                match name.as_str() {
+                    "transparent" => Some((0.0, 0.0, 0.0, 0.0)),
+                    "currentcolor" => None,
+                    _ => NAMED_COLORS
+                        .binary_search_by(|(candidate, _)| candidate.cmp(&name.as_str()))
+                        .ok()
+                        .map(|index| Self::packed_to_rgba(NAMED_COLORS[index].1)),
+                }
+            }
+            Color::Hsl(h, s, l, a) => {
+                let (r, g, b) = Self::hsl_to_rgb(*h, *s, *l);
+                Some((r, g, b, *a))
+            }
+            Color::CurrentColor => None,
+            Color::Transparent => Some((0.0, 0.0, 0.0, 0.0)),
+        }
+    }
+
+    /// Parse `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`
+    fn hex_to_rgba(hex: &str) -> Option<(f32, f32, f32, f32)> {
+        let digits = hex.trim().strip_prefix('#')?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        // Expand shorthand so every channel has two digits
+        let expanded: String = match digits.len() {
+            3 | 4 => digits.chars().flat_map(|c| [c, c]).collect(),
+            6 | 8 => digits.to_string(),
+            _ => return None,
+        };
+
+        let channel = |index: usize| {
+            u8::from_str_radix(&expanded[index * 2..index * 2 + 2], 16)
+                .ok()
+                .map(|v| v as f32 / 255.0)
+        };
+        let alpha = if expanded.len() == 8 {
+            channel(3)?
+        } else {
+            1.0
+        };
+
+        Some((channel(0)?, channel(1)?, channel(2)?, alpha))
+    }
+
+    /// Unpack a 0xRRGGBB value into opaque RGBA channels
+    fn packed_to_rgba(packed: u32) -> (f32, f32, f32, f32) {
+        (
+            ((packed >> 16) & 0xFF) as f32 / 255.0,
+            ((packed >> 8) & 0xFF) as f32 / 255.0,
+            (packed & 0xFF) as f32 / 255.0,
+            1.0,
+        )
+    }
+
+    /// Convert hue (degrees), saturation and lightness (fractions) to RGB
+    fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (f32, f32, f32) {
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        (r + m, g + m, b + m)
+    }
+}
+
+/// CSS named colors as 0xRRGGBB, sorted by name for binary search
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
 /// Visibility values
 #[derive(Debug, Clone, PartialEq)]
 pub enum Visibility {
@@ -337,6 +735,52 @@ pub struct BorderRadius {
     pub bottom_left: f32,
 }
 
+impl BorderRadius {
+    /// Same radius on every corner
+    pub fn uniform(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    /// Whether a point lies inside `rect` with these corners rounded off
+    ///
+    /// Radii larger than half the shorter side are clamped, so a square with
+    /// a radius of half its size is a circle.
+    pub fn contains_point(&self, rect: Rect, point: Point) -> bool {
+        if !rect.contains_point(point) {
+            return false;
+        }
+
+        let max_radius = rect.width().min(rect.height()) / 2.0;
+        let corners = [
+            (self.top_left, rect.x(), rect.y(), 1.0, 1.0),
+            (self.top_right, rect.max_x(), rect.y(), -1.0, 1.0),
+            (self.bottom_right, rect.max_x(), rect.max_y(), -1.0, -1.0),
+            (self.bottom_left, rect.x(), rect.max_y(), 1.0, -1.0),
+        ];
+
+        corners
+            .into_iter()
+            .all(|(radius, corner_x, corner_y, dir_x, dir_y)| {
+                let radius = radius.clamp(0.0, max_radius);
+                // Center of the arc that rounds this corner
+                let center_x = corner_x + dir_x * radius;
+                let center_y = corner_y + dir_y * radius;
+                let in_corner_x = (point.x - center_x) * dir_x < 0.0;
+                let in_corner_y = (point.y - center_y) * dir_y < 0.0;
+                if !(in_corner_x && in_corner_y) {
+                    return true;
+                }
+                let (dx, dy) = (point.x - center_x, point.y - center_y);
+                dx * dx + dy * dy <= radius * radius
+            })
+    }
+}
+
 /// Edge-specific colors for borders
 #[derive(Debug, Clone, PartialEq)]
 pub struct EdgeColors {
@@ -361,6 +805,24 @@ pub enum FontWeight {
     Numeric(u16),
 }
 
+impl FontWeight {
+    /// Numeric weight on the CSS 100-900 scale
+    pub fn value(&self) -> u16 {
+        match self {
+            FontWeight::Thin => 100,
+            FontWeight::ExtraLight => 200,
+            FontWeight::Light => 300,
+            FontWeight::Normal => 400,
+            FontWeight::Medium => 500,
+            FontWeight::SemiBold => 600,
+            FontWeight::Bold => 700,
+            FontWeight::ExtraBold => 800,
+            FontWeight::Black => 900,
+            FontWeight::Numeric(weight) => *weight,
+        }
+    }
+}
+
 /// Font style values
 #[derive(Debug, Clone, PartialEq)]
 pub enum FontStyle {
@@ -369,6 +831,30 @@ pub enum FontStyle {
     Oblique(f32), // angle in degrees
 }
 
+/// Height of a line of text
+///
+/// A multiple is inherited as a multiple, so each element applies it to
+/// its own font size; a pixel height is inherited as is. Both are resolved
+/// against the font size when the layout style is computed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineHeight {
+    /// A multiple of the font size, from a number, a percentage or `em`
+    Multiple(f32),
+    /// A fixed height in pixels
+    Px(f32),
+}
+
+impl LineHeight {
+    /// The line height as a multiple of `font_size`
+    pub fn multiple_of(self, font_size: f32) -> f32 {
+        match self {
+            LineHeight::Multiple(multiple) => multiple,
+            LineHeight::Px(_) if font_size <= 0.0 => 0.0,
+            LineHeight::Px(px) => px / font_size,
+        }
+    }
+}
+
 /// Text alignment
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextAlign {
@@ -380,6 +866,63 @@ pub enum TextAlign {
     End,
 }
 
+impl TextAlign {
+    /// The physical alignment in a given inline direction
+    ///
+    /// `Start` and `End` become `Left` and `Right`, swapped in RTL.
+    pub fn resolve(&self, direction: Direction) -> TextAlign {
+        match (self, direction) {
+            (TextAlign::Start, Direction::Ltr) | (TextAlign::End, Direction::Rtl) => {
+                TextAlign::Left
+            }
+            (TextAlign::Start, Direction::Rtl) | (TextAlign::End, Direction::Ltr) => {
+                TextAlign::Right
+            }
+            (align, _) => align.clone(),
+        }
+    }
+}
+
+/// How replaced content such as images is fitted into its box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObjectFit {
+    /// Stretch to fill the box, ignoring the aspect ratio
+    #[default]
+    Fill,
+    /// Scale to fit entirely inside the box, preserving the aspect ratio
+    Contain,
+    /// Scale to cover the whole box, preserving the aspect ratio
+    Cover,
+}
+
+impl ObjectFit {
+    /// Compute where content of the given intrinsic size is drawn within `container`
+    ///
+    /// The result is centered in the container. For `Cover` it may extend past
+    /// the container's edges, so callers should clip to the container.
+    pub fn destination_rect(&self, width: f32, height: f32, container: Rect) -> Rect {
+        if *self == ObjectFit::Fill || width <= 0.0 || height <= 0.0 {
+            return container;
+        }
+
+        let scale_x = container.size.width / width;
+        let scale_y = container.size.height / height;
+        let scale = match self {
+            ObjectFit::Contain => scale_x.min(scale_y),
+            _ => scale_x.max(scale_y),
+        };
+
+        let fitted_width = width * scale;
+        let fitted_height = height * scale;
+        Rect::new(
+            container.origin.x + (container.size.width - fitted_width) / 2.0,
+            container.origin.y + (container.size.height - fitted_height) / 2.0,
+            fitted_width,
+            fitted_height,
+        )
+    }
+}
+
 /// Text decoration
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextDecoration {
@@ -423,6 +966,76 @@ pub enum Transform {
     Multiple(Vec<Transform>),
 }
 
+impl Transform {
+    /// Affine matrix for this transform about the coordinate origin
+    ///
+    /// `Multiple` composes like a CSS transform list: the last entry is
+    /// applied to the element first, so `[Translate, Scale]` scales then
+    /// translates.
+    pub fn to_matrix(&self) -> TransformMatrix {
+        match self {
+            Transform::None => IDENTITY_MATRIX,
+            Transform::Matrix(a, b, c, d, e, f) => [*a, *b, *c, *d, *e, *f],
+            Transform::Translate(x, y) => [1.0, 0.0, 0.0, 1.0, *x, *y],
+            Transform::TranslateX(x) => [1.0, 0.0, 0.0, 1.0, *x, 0.0],
+            Transform::TranslateY(y) => [1.0, 0.0, 0.0, 1.0, 0.0, *y],
+            Transform::Scale(x, y) => [*x, 0.0, 0.0, *y, 0.0, 0.0],
+            Transform::ScaleX(x) => [*x, 0.0, 0.0, 1.0, 0.0, 0.0],
+            Transform::ScaleY(y) => [1.0, 0.0, 0.0, *y, 0.0, 0.0],
+            Transform::Rotate(degrees) => {
+                let (sin, cos) = degrees.to_radians().sin_cos();
+                [cos, sin, -sin, cos, 0.0, 0.0]
+            }
+            Transform::SkewX(degrees) => [1.0, 0.0, degrees.to_radians().tan(), 1.0, 0.0, 0.0],
+            Transform::SkewY(degrees) => [1.0, degrees.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+            Transform::Multiple(transforms) => transforms
+                .iter()
+                .fold(IDENTITY_MATRIX, |matrix, transform| {
+                    multiply_matrices(&matrix, &transform.to_matrix())
+                }),
+        }
+    }
+
+    /// Affine matrix for this transform about `origin`
+    pub fn to_matrix_about(&self, origin: Point) -> TransformMatrix {
+        let to_origin = [1.0, 0.0, 0.0, 1.0, -origin.x, -origin.y];
+        let from_origin = [1.0, 0.0, 0.0, 1.0, origin.x, origin.y];
+        multiply_matrices(
+            &from_origin,
+            &multiply_matrices(&self.to_matrix(), &to_origin),
+        )
+    }
+}
+
+/// Parse a number with the given unit, or a bare zero
+fn parse_unit(value: &str, unit: &str) -> Option<f32> {
+    match value.strip_suffix(unit) {
+        Some(number) => number.parse().ok(),
+        None => value.parse().ok().filter(|number: &f32| *number == 0.0),
+    }
+}
+
+/// Split a list of CSS functions such as `translate(10px, 0) rotate(45deg)`
+///
+/// Returns each function's name and its comma-separated arguments, trimmed,
+/// in the order written, or `None` if the list is not well formed.
+fn parse_css_functions(value: &str) -> Option<Vec<(&str, Vec<&str>)>> {
+    let mut functions = Vec::new();
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        let open = rest.find('(')?;
+        let close = rest.find(')')?;
+        if close < open {
+            return None;
+        }
+        let name = rest[..open].trim();
+        let args = rest[open + 1..close].split(',').map(str::trim).collect();
+        functions.push((name, args));
+        rest = rest[close + 1..].trim_start();
+    }
+    Some(functions)
+}
+
 /// Timing functions for animations
 #[derive(Debug, Clone, PartialEq)]
 pub enum TimingFunction {
@@ -525,6 +1138,8 @@ pub struct StyleEngine {
     computed_cache: HashMap<u64, ComputedStyle>,
     /// Style inheritance tree
     inheritance_tree: HashMap<ComponentId, ComponentId>,
+    /// Tag, ID and classes of components, for selector matching
+    elements: HashMap<ComponentId, ElementInfo>,
     /// Global style rules
     global_rules: Vec<StyleRule>,
     /// Component-scoped style rules
@@ -569,6 +1184,93 @@ pub struct StyleStats {
     pub cache_size: usize,
 }
 
+/// Interactive state of an element, matched by pseudo-classes like `:hover`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ElementState(u8);
+
+impl ElementState {
+    /// The pointer is over the element or one of its descendants
+    pub const HOVERED: Self = Self(1);
+    /// The primary button went down on the element and has not been released
+    pub const ACTIVE: Self = Self(1 << 1);
+    /// The element has keyboard focus
+    pub const FOCUSED: Self = Self(1 << 2);
+    /// The element does not accept interaction
+    pub const DISABLED: Self = Self(1 << 3);
+
+    /// No state flags set
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Whether every flag in `other` is set
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Set the flags in `other`
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clear the flags in `other`
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// Set or clear the flags in `other`
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.insert(other);
+        } else {
+            self.remove(other);
+        }
+    }
+
+    /// Flag matched by a pseudo-class name, without the leading `:`
+    pub(crate) fn for_pseudo_class(name: &str) -> Option<Self> {
+        match name {
+            "hover" => Some(Self::HOVERED),
+            "active" => Some(Self::ACTIVE),
+            "focus" => Some(Self::FOCUSED),
+            "disabled" => Some(Self::DISABLED),
+            _ => None,
+        }
+    }
+
+    /// Whether an element in this state can match a selector
+    ///
+    /// Only the state pseudo-classes of the rightmost compound, the one
+    /// naming the element itself, are checked; those on ancestor compounds
+    /// need the ancestors' state, which [`SelectorMatcher`] has. Selectors
+    /// with a pseudo-element such as `::before` never match. Pseudo-classes
+    /// that don't describe element state, such as `:first-child`, are
+    /// ignored.
+    pub fn matches_selector(self, selector: &str) -> bool {
+        if selector.contains("::") {
+            return false;
+        }
+        let subject = selector
+            .rsplit(|c: char| c.is_whitespace() || c == '>')
+            .next()
+            .unwrap_or(selector);
+        subject.split(':').skip(1).all(|pseudo| {
+            let end = pseudo
+                .find(|c: char| !(c.is_alphanumeric() || c == '-'))
+                .unwrap_or(pseudo.len());
+            Self::for_pseudo_class(&pseudo[..end]).is_none_or(|flag| self.contains(flag))
+        })
+    }
+}
+
+impl std::ops::BitOr for ElementState {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
 /// Style resolution context for computing styles
 #[derive(Debug)]
 pub struct StyleContext {
@@ -585,6 +1287,8 @@ pub struct StyleContext {
     pub theme_variables: HashMap<String, String>,
     /// Performance monitoring enabled
     pub performance_monitoring: bool,
+    /// Interactive state matched against pseudo-classes such as `:hover`
+    pub element_state: ElementState,
 }
 
 impl Style {
@@ -610,6 +1314,45 @@ impl Style {
 pub enum StyleError {
     #[error("Error parsing CSS: {0}")]
     ParseError(String),
+    #[error("Invalid animation: {0}")]
+    InvalidAnimation(String),
+}
+
+/// Split a property value on whitespace, keeping parenthesized groups
+/// such as `calc(100% - 20px)` whole
+fn split_values(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+
+    for (index, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    parts.push(&value[start..index]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(index);
+    }
+    if let Some(start) = start {
+        parts.push(&value[start..]);
+    }
+
+    parts
+}
+
+/// Token of a `calc()` expression
+#[derive(Debug, Clone)]
+enum CalcToken {
+    Value(CalcExpr),
+    Operator(char),
+    Open,
+    Close,
 }
 
 impl StyleEngine {
@@ -618,6 +1361,7 @@ impl StyleEngine {
         Self {
             computed_cache: HashMap::new(),
             inheritance_tree: HashMap::new(),
+            elements: HashMap::new(),
             global_rules: Vec::new(),
             component_rules: HashMap::new(),
             stats: StyleStats::default(),
@@ -630,12 +1374,22 @@ impl StyleEngine {
         self.global_rules.extend(rules);
     }
 
-    /// Add component-scoped CSS rules
+    /// Add CSS rules belonging to a component
+    ///
+    /// Scoped rules are rewritten with the component's `scope_class`, so
+    /// they match the component and the components it renders. Unscoped
+    /// rules only apply to the component itself.
     pub fn add_component_rules(&mut self, component_id: ComponentId, rules: Vec<StyleRule>) {
+        let scope = scope_class(component_id);
+        let rules = rules.into_iter().map(|mut rule| {
+            rule.apply_scoping(&scope);
+            rule
+        });
         self.component_rules
             .entry(component_id)
             .or_default()
             .extend(rules);
+        self.clear_cache();
     }
 
     /// Compute the final style for a component with context
@@ -665,12 +1419,21 @@ impl StyleEngine {
             self.apply_inheritance(&mut computed_style, &inherited.style);
         }
 
-        // Apply global CSS rules
-        self.apply_css_rules(&mut computed_style, &self.global_rules.clone(), context)?;
-
-        // Apply component-scoped rules
-        if let Some(component_rules) = self.component_rules.get(&component_id) {
-            self.apply_css_rules(&mut computed_style, component_rules, context)?;
+        // Apply global then component-scoped CSS rules, with important
+        // declarations in a second pass so they beat every normal one
+        let matcher = self.selector_matcher(component_id, context.element_state);
+        let global_rules = self.global_rules.clone();
+        let component_rules = self.component_rules_for(component_id, matcher.is_some());
+        for important in [false, true] {
+            for rules in [&global_rules, &component_rules] {
+                self.apply_css_rules(
+                    &mut computed_style,
+                    rules,
+                    matcher.as_ref(),
+                    important,
+                    context,
+                )?;
+            }
         }
 
         // Convert to layout style
@@ -722,6 +1485,7 @@ impl StyleEngine {
         context.viewport_width.to_bits().hash(&mut hasher);
         context.viewport_height.to_bits().hash(&mut hasher);
         context.device_pixel_ratio.to_bits().hash(&mut hasher);
+        context.element_state.hash(&mut hasher);
 
         hasher.finish()
     }
@@ -750,15 +1514,105 @@ impl StyleEngine {
         if style.text_align.is_none() {
             style.text_align = parent_style.text_align.clone();
         }
+        if style.font_style.is_none() {
+            style.font_style = parent_style.font_style.clone();
+        }
+        if style.text_transform.is_none() {
+            style.text_transform = parent_style.text_transform.clone();
+        }
+        if style.cursor.is_none() {
+            style.cursor = parent_style.cursor.clone();
+        }
+        if style.visibility.is_none() {
+            style.visibility = parent_style.visibility.clone();
+        }
+        let parent_direction = parent_style
+            .layout_style
+            .as_ref()
+            .and_then(|layout| layout.direction);
+        if let Some(direction) = parent_direction {
+            let layout = style.layout_style.get_or_insert_with(LayoutStyle::default);
+            layout.direction.get_or_insert(direction);
+        }
 
         self.stats.inheritance_operations += 1;
     }
 
-    /// Apply CSS rules to a style
+    /// Matcher for a component's selectors, if its element info is known
+    ///
+    /// `state` is added to the state recorded for the component itself.
+    fn selector_matcher(
+        &self,
+        component_id: ComponentId,
+        state: ElementState,
+    ) -> Option<SelectorMatcher> {
+        self.elements.get(&component_id)?;
+        let mut element = self.scoped_element(component_id);
+        element.state.insert(state);
+
+        let mut ancestors = Vec::new();
+        let mut current = component_id;
+        while let Some(parent) = self.inheritance_tree.get(&current) {
+            // Guard against a cycle in the inheritance tree
+            if *parent == component_id || ancestors.len() > self.inheritance_tree.len() {
+                break;
+            }
+            ancestors.push(self.scoped_element(*parent));
+            current = *parent;
+        }
+
+        Some(SelectorMatcher::new(element, ancestors))
+    }
+
+    /// Element info of a component with its own and its parent's scope class
+    fn scoped_element(&self, component_id: ComponentId) -> ElementInfo {
+        let mut element = self
+            .elements
+            .get(&component_id)
+            .cloned()
+            .unwrap_or_default();
+        element.classes.push(scope_class(component_id));
+        if let Some(parent) = self.inheritance_tree.get(&component_id) {
+            element.classes.push(scope_class(*parent));
+        }
+        element
+    }
+
+    /// The component's own rules, then the scoped rules of other components
+    ///
+    /// Other components' scoped rules can only match through their scope
+    /// class, so they're left out for components without element info, which
+    /// match every selector.
+    fn component_rules_for(&self, component_id: ComponentId, with_scoped: bool) -> Vec<StyleRule> {
+        let mut rules = self
+            .component_rules
+            .get(&component_id)
+            .cloned()
+            .unwrap_or_default();
+        if with_scoped {
+            let mut others: Vec<_> = self
+                .component_rules
+                .iter()
+                .filter(|(id, _)| **id != component_id)
+                .collect();
+            others.sort_by_key(|(id, _)| id.id());
+            let scoped = others
+                .into_iter()
+                .flat_map(|(_, rules)| rules.iter().filter(|rule| rule.scoped).cloned());
+            rules.extend(scoped);
+        }
+        rules
+    }
+
+    /// Apply the normal or the `!important` declarations of CSS rules
+    ///
+    /// Without a matcher, every selector is treated as matching the element.
     fn apply_css_rules(
         &self,
         style: &mut Style,
         rules: &[StyleRule],
+        matcher: Option<&SelectorMatcher>,
+        important: bool,
         context: &StyleContext,
     ) -> Result<(), StyleError> {
         // Sort rules by specificity and source order
@@ -769,10 +1623,24 @@ impl StyleEngine {
                 .then(a.source_order.cmp(&b.source_order))
         });
 
-        // Apply rules in order
-        for rule in sorted_rules {
-            for selector in &rule.selectors {
-                for property in &selector.properties {
+        // Apply rules in order, skipping media rules the viewport doesn't match
+        let matching = sorted_rules.into_iter().filter(|rule| {
+            rule.media
+                .as_ref()
+                .is_none_or(|media| media.matches(context.viewport_width, context.viewport_height))
+        });
+        for rule in matching {
+            // Selectors needing a state the element isn't in don't match
+            let selectors = rule.selectors.iter().filter(|selector| match matcher {
+                Some(matcher) => matcher.matches(&selector.selector),
+                None => context.element_state.matches_selector(&selector.selector),
+            });
+            for selector in selectors {
+                let properties = selector
+                    .properties
+                    .iter()
+                    .filter(|property| property.important == important);
+                for property in properties {
                     self.apply_css_property(style, property, context)?;
                 }
             }
@@ -782,12 +1650,26 @@ impl StyleEngine {
     }
 
     /// Apply a single CSS property to a style
+    ///
+    /// `inherit` takes the parent's value, or the initial one without a
+    /// parent, and `initial` unsets the property.
     fn apply_css_property(
         &self,
         style: &mut Style,
         property: &CssProperty,
-        _context: &StyleContext,
+        context: &StyleContext,
     ) -> Result<(), StyleError> {
+        let value = property.value.trim();
+        if value.eq_ignore_ascii_case("inherit") || value.eq_ignore_ascii_case("initial") {
+            let parent = context
+                .inherited_style
+                .as_ref()
+                .map(|inherited| &inherited.style)
+                .filter(|_| value.eq_ignore_ascii_case("inherit"));
+            self.copy_css_property(style, &property.name, parent.unwrap_or(&Style::default()));
+            return Ok(());
+        }
+
         match property.name.as_str() {
             "color" => {
                 style.color = Some(self.parse_color(&property.value)?);
@@ -807,15 +1689,62 @@ impl StyleEngine {
             "font-family" => {
                 style.font_family = Some(property.value.clone());
             }
+            "font-style" => {
+                style.font_style = Some(self.parse_font_style(&property.value)?);
+            }
+            "line-height" => {
+                style.line_height = self.parse_line_height(&property.value)?;
+            }
+            "font" => {
+                self.apply_font_shorthand(style, &property.value)?;
+            }
             "text-align" => {
                 style.text_align = Some(self.parse_text_align(&property.value)?);
             }
+            "object-fit" => {
+                style.object_fit = Some(self.parse_object_fit(&property.value)?);
+            }
             "border-radius" => {
                 style.border_radius = Some(self.parse_border_radius(&property.value)?);
             }
             "z-index" => {
                 style.z_index = property.value.parse().ok();
             }
+            "transform" => {
+                style.transform = Some(self.parse_transform(&property.value)?);
+            }
+            "filter" => {
+                style.filter = Some(self.parse_filters(&property.value)?);
+            }
+            "backdrop-filter" => {
+                style.backdrop_filter = Some(self.parse_filters(&property.value)?);
+            }
+            "width" | "height" | "min-width" | "min-height" | "max-width" | "max-height" => {
+                let dimension = self.parse_dimension(&property.value)?;
+                let layout = style.layout_style.get_or_insert_with(LayoutStyle::default);
+                match property.name.as_str() {
+                    "width" => layout.width = dimension,
+                    "height" => layout.height = dimension,
+                    "min-width" => layout.min_width = dimension,
+                    "min-height" => layout.min_height = dimension,
+                    "max-width" => layout.max_width = dimension,
+                    _ => layout.max_height = dimension,
+                }
+            }
+            "margin" => {
+                style.margin = Some(self.parse_edge_values(&property.value)?);
+            }
+            "padding" => {
+                style.padding = Some(self.parse_edge_values(&property.value)?);
+            }
+            "margin-top" | "margin-right" | "margin-bottom" | "margin-left" => {
+                let edges = style.margin.get_or_insert_with(EdgeDimensions::zero);
+                self.apply_edge_longhand(edges, &property.name, &property.value)?;
+            }
+            "padding-top" | "padding-right" | "padding-bottom" | "padding-left" => {
+                let edges = style.padding.get_or_insert_with(EdgeDimensions::zero);
+                self.apply_edge_longhand(edges, &property.name, &property.value)?;
+            }
             _ => {
                 // Unknown property - could log warning in debug mode
             }
@@ -824,6 +1753,70 @@ impl StyleEngine {
         Ok(())
     }
 
+    /// Set a property to its value in `source`, for `inherit` and `initial`
+    fn copy_css_property(&self, style: &mut Style, name: &str, source: &Style) {
+        match name {
+            "color" => style.color = source.color.clone(),
+            "background-color" => style.background_color = source.background_color.clone(),
+            "opacity" => style.opacity = source.opacity,
+            "visibility" => style.visibility = source.visibility.clone(),
+            "font-size" => style.font_size = source.font_size,
+            "font-weight" => style.font_weight = source.font_weight.clone(),
+            "font-family" => style.font_family = source.font_family.clone(),
+            "font-style" => style.font_style = source.font_style.clone(),
+            "line-height" => style.line_height = source.line_height,
+            "font" => {
+                style.font_style = source.font_style.clone();
+                style.font_weight = source.font_weight.clone();
+                style.font_size = source.font_size;
+                style.line_height = source.line_height;
+                style.font_family = source.font_family.clone();
+            }
+            "letter-spacing" => style.letter_spacing = source.letter_spacing,
+            "text-align" => style.text_align = source.text_align.clone(),
+            "text-decoration" => style.text_decoration = source.text_decoration.clone(),
+            "text-transform" => style.text_transform = source.text_transform.clone(),
+            "cursor" => style.cursor = source.cursor.clone(),
+            "object-fit" => style.object_fit = source.object_fit,
+            "border-radius" => style.border_radius = source.border_radius.clone(),
+            "z-index" => style.z_index = source.z_index,
+            "transform" => style.transform = source.transform.clone(),
+            "filter" => style.filter = source.filter.clone(),
+            "backdrop-filter" => style.backdrop_filter = source.backdrop_filter.clone(),
+            "width" | "height" | "min-width" | "min-height" | "max-width" | "max-height" => {
+                let from = source.layout_style.clone().unwrap_or_default();
+                let layout = style.layout_style.get_or_insert_with(LayoutStyle::default);
+                match name {
+                    "width" => layout.width = from.width,
+                    "height" => layout.height = from.height,
+                    "min-width" => layout.min_width = from.min_width,
+                    "min-height" => layout.min_height = from.min_height,
+                    "max-width" => layout.max_width = from.max_width,
+                    _ => layout.max_height = from.max_height,
+                }
+            }
+            "margin" => style.margin = source.margin.clone(),
+            "padding" => style.padding = source.padding.clone(),
+            "margin-top" | "margin-right" | "margin-bottom" | "margin-left" | "padding-top"
+            | "padding-right" | "padding-bottom" | "padding-left" => {
+                let (edges, from) = if name.starts_with("margin") {
+                    (&mut style.margin, &source.margin)
+                } else {
+                    (&mut style.padding, &source.padding)
+                };
+                let from = from.clone().unwrap_or_else(EdgeDimensions::zero);
+                let edges = edges.get_or_insert_with(EdgeDimensions::zero);
+                match name.rsplit('-').next() {
+                    Some("top") => edges.top = from.top,
+                    Some("right") => edges.right = from.right,
+                    Some("bottom") => edges.bottom = from.bottom,
+                    _ => edges.left = from.left,
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Convert Style to LayoutStyle for layout engine integration
     fn style_to_layout_style(
         &mut self,
@@ -851,12 +1844,43 @@ impl StyleEngine {
             layout_style.border = *border_width;
         }
 
-        // Handle transforms that affect layout bounds
-        if let Some(_transform) = &style.transform {
-            // Transforms can affect layout bounds
-            // Implementation depends on transform type
+        // Rounded corners shape the area that receives pointer events
+        if let Some(border_radius) = &style.border_radius {
+            layout_style.border_radius = Some(border_radius.clone());
         }
 
+        // Font size approximates the baseline for baseline alignment, and
+        // with the line height, family and weight sizes wrapped text
+        if let Some(font_size) = style.font_size {
+            layout_style.font_size = Some(font_size);
+        }
+        if let Some(line_height) = style.line_height {
+            let font_size = style.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+            layout_style.line_height = Some(line_height.multiple_of(font_size));
+        }
+        if let Some(font_family) = &style.font_family {
+            layout_style.font_family = Some(font_family.clone());
+        }
+        if let Some(font_weight) = &style.font_weight {
+            layout_style.font_weight = Some(font_weight.clone());
+        }
+
+        // Spacing set through the stylesheet
+        if let Some(margin) = &style.margin {
+            layout_style.margin = margin.clone();
+        }
+        if let Some(padding) = &style.padding {
+            layout_style.padding = padding.clone();
+        }
+
+        // Transforms are visual only; layout carries them through so the
+        // resulting matrix can be resolved against the final box size
+        layout_style.transform = style.transform.clone();
+        layout_style.transform_origin = style
+            .transform_origin
+            .as_ref()
+            .map(|origin| Point::new(origin.x, origin.y));
+
         // Apply responsive sizing based on viewport
         self.apply_responsive_sizing(&mut layout_style, context);
 
@@ -952,6 +1976,8 @@ impl StyleEngine {
             } else {
                 Err(StyleError::ParseError("Invalid RGBA format".to_string()))
             }
+        } else if value.starts_with("hsl(") || value.starts_with("hsla(") {
+            self.parse_hsl_color(value)
         } else if value == "transparent" {
             Ok(Color::Transparent)
         } else if value == "currentColor" {
@@ -961,6 +1987,67 @@ impl StyleEngine {
         }
     }
 
+    /// Parse hsl(h, s%, l%) or hsla(h, s%, l%, a) format
+    ///
+    /// Hue is stored in degrees, saturation and lightness as fractions in [0, 1].
+    fn parse_hsl_color(&self, value: &str) -> Result<Color, StyleError> {
+        let (expected, inner) = if let Some(inner) = value.strip_prefix("hsla(") {
+            (4, inner)
+        } else {
+            (3, value.trim_start_matches("hsl("))
+        };
+        let inner = inner.trim_end_matches(')');
+        let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+        if parts.len() != expected {
+            return Err(StyleError::ParseError(format!(
+                "Invalid HSL format: expected {expected} values, got {}",
+                parts.len()
+            )));
+        }
+
+        let h = self.parse_hue(parts[0])?;
+        let s = self.parse_percentage(parts[1], "saturation")?;
+        let l = self.parse_percentage(parts[2], "lightness")?;
+        let a = match parts.get(3) {
+            Some(alpha) if alpha.ends_with('%') => self.parse_percentage(alpha, "alpha")?,
+            Some(alpha) => alpha
+                .parse::<f32>()
+                .map_err(|_| StyleError::ParseError("Invalid alpha value".to_string()))?
+                .clamp(0.0, 1.0),
+            None => 1.0,
+        };
+
+        Ok(Color::Hsl(h, s, l, a))
+    }
+
+    /// Parse a CSS hue angle into degrees normalized to [0, 360)
+    fn parse_hue(&self, value: &str) -> Result<f32, StyleError> {
+        let invalid = || StyleError::ParseError(format!("Invalid hue value: {value}"));
+        let degrees = if let Some(deg) = value.strip_suffix("deg") {
+            deg.trim().parse::<f32>().map_err(|_| invalid())?
+        } else if let Some(rad) = value.strip_suffix("rad") {
+            rad.trim()
+                .parse::<f32>()
+                .map_err(|_| invalid())?
+                .to_degrees()
+        } else if let Some(turn) = value.strip_suffix("turn") {
+            turn.trim().parse::<f32>().map_err(|_| invalid())? * 360.0
+        } else {
+            value.parse::<f32>().map_err(|_| invalid())?
+        };
+
+        Ok(degrees.rem_euclid(360.0))
+    }
+
+    /// Parse a percentage (e.g. `50%`) into a fraction clamped to [0, 1]
+    fn parse_percentage(&self, value: &str, channel: &str) -> Result<f32, StyleError> {
+        value
+            .strip_suffix('%')
+            .and_then(|v| v.trim().parse::<f32>().ok())
+            .map(|v| (v / 100.0).clamp(0.0, 1.0))
+            .ok_or_else(|| StyleError::ParseError(format!("Invalid {channel} value: {value}")))
+    }
+
     /// Parse font size from CSS value
     fn parse_font_size(&self, value: &str) -> Option<f32> {
         let value = value.trim();
@@ -983,6 +2070,230 @@ impl StyleEngine {
         }
     }
 
+    /// Parse a `transform` value
+    ///
+    /// A list of functions becomes [`Transform::Multiple`] in the order
+    /// written, and a single function is returned as is. Lengths must be in
+    /// `px` and angles in `deg`; only a bare `0` may leave out the unit.
+    fn parse_transform(&self, value: &str) -> Result<Transform, StyleError> {
+        let value = value.trim();
+        let invalid = |reason: &str| StyleError::ParseError(format!("Invalid transform: {reason}"));
+        if value == "none" {
+            return Ok(Transform::None);
+        }
+
+        let mut transforms = Vec::new();
+        for (name, args) in parse_css_functions(value).ok_or_else(|| invalid(value))? {
+            let length = |arg: &str| {
+                parse_unit(arg, "px").ok_or_else(|| invalid(&format!("{arg} is not a px length")))
+            };
+            let angle = |arg: &str| {
+                parse_unit(arg, "deg").ok_or_else(|| invalid(&format!("{arg} is not a deg angle")))
+            };
+            let number = |arg: &str| {
+                arg.parse::<f32>()
+                    .map_err(|_| invalid(&format!("{arg} is not a number")))
+            };
+
+            let transform = match (name, args.as_slice()) {
+                ("translate", [x]) => Transform::Translate(length(x)?, 0.0),
+                ("translate", [x, y]) => Transform::Translate(length(x)?, length(y)?),
+                ("translateX", [x]) => Transform::TranslateX(length(x)?),
+                ("translateY", [y]) => Transform::TranslateY(length(y)?),
+                ("scale", [s]) => {
+                    let s = number(s)?;
+                    Transform::Scale(s, s)
+                }
+                ("scale", [x, y]) => Transform::Scale(number(x)?, number(y)?),
+                ("scaleX", [x]) => Transform::ScaleX(number(x)?),
+                ("scaleY", [y]) => Transform::ScaleY(number(y)?),
+                ("rotate", [a]) => Transform::Rotate(angle(a)?),
+                ("skewX", [a]) => Transform::SkewX(angle(a)?),
+                ("skewY", [a]) => Transform::SkewY(angle(a)?),
+                ("matrix", [a, b, c, d, e, f]) => Transform::Matrix(
+                    number(a)?,
+                    number(b)?,
+                    number(c)?,
+                    number(d)?,
+                    number(e)?,
+                    number(f)?,
+                ),
+                _ => return Err(invalid(&format!("{name} with {} arguments", args.len()))),
+            };
+            transforms.push(transform);
+        }
+
+        match transforms.len() {
+            0 => Err(invalid(value)),
+            1 => Ok(transforms.remove(0)),
+            _ => Ok(Transform::Multiple(transforms)),
+        }
+    }
+
+    /// Parse a `filter` or `backdrop-filter` value into its chain of functions
+    ///
+    /// Supports `blur`, `brightness`, `grayscale` and `opacity`, applied in
+    /// the order written. `none` is an empty chain.
+    fn parse_filters(&self, value: &str) -> Result<Vec<Filter>, StyleError> {
+        let value = value.trim();
+        let invalid = |reason: &str| StyleError::ParseError(format!("Invalid filter: {reason}"));
+        if value == "none" {
+            return Ok(Vec::new());
+        }
+
+        let mut filters = Vec::new();
+        for (name, args) in parse_css_functions(value).ok_or_else(|| invalid(value))? {
+            let [arg] = args[..] else {
+                return Err(invalid(&format!("{name} with {} arguments", args.len())));
+            };
+
+            // An omitted argument takes the value that fully applies the filter
+            let amount = || match arg {
+                "" => Ok(1.0),
+                _ => match arg.strip_suffix('%') {
+                    Some(percent) => percent.parse::<f32>().map(|p| p / 100.0),
+                    None => arg.parse::<f32>(),
+                }
+                .ok()
+                .filter(|amount| *amount >= 0.0)
+                .ok_or_else(|| invalid(&format!("{arg} is not a non-negative amount"))),
+            };
+
+            let filter = match name {
+                "blur" if arg.is_empty() => Filter::Blur(0.0),
+                "blur" => Filter::Blur(
+                    parse_unit(arg, "px")
+                        .filter(|radius| *radius >= 0.0)
+                        .ok_or_else(|| invalid(&format!("{arg} is not a px length")))?,
+                ),
+                "brightness" => Filter::Brightness(amount()?),
+                // Amounts over 100% are clamped for these two
+                "grayscale" => Filter::Grayscale(amount()?.min(1.0)),
+                "opacity" => Filter::Opacity(amount()?.min(1.0)),
+                _ => return Err(invalid(&format!("unsupported function {name}"))),
+            };
+            filters.push(filter);
+        }
+
+        if filters.is_empty() {
+            return Err(invalid(value));
+        }
+        Ok(filters)
+    }
+
+    /// Parse a `font-style` value
+    fn parse_font_style(&self, value: &str) -> Result<FontStyle, StyleError> {
+        let value = value.trim();
+        match value.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["normal"] => Ok(FontStyle::Normal),
+            ["italic"] => Ok(FontStyle::Italic),
+            ["oblique"] => Ok(FontStyle::Oblique(14.0)),
+            ["oblique", angle] => angle
+                .trim_end_matches("deg")
+                .parse()
+                .map(FontStyle::Oblique)
+                .map_err(|_| StyleError::ParseError(format!("Invalid font style: {value}"))),
+            _ => Err(StyleError::ParseError(format!(
+                "Invalid font style: {value}"
+            ))),
+        }
+    }
+
+    /// Parse a `line-height` value
+    ///
+    /// `normal` leaves the line height to the renderer and returns `None`.
+    fn parse_line_height(&self, value: &str) -> Result<Option<LineHeight>, StyleError> {
+        let value = value.trim();
+        let invalid = || StyleError::ParseError(format!("Invalid line height: {value}"));
+        if value == "normal" {
+            return Ok(None);
+        }
+        let line_height = if let Some(percent) = value.strip_suffix('%') {
+            LineHeight::Multiple(percent.parse::<f32>().map_err(|_| invalid())? / 100.0)
+        } else if let Some(em) = value.strip_suffix("em") {
+            LineHeight::Multiple(em.parse().map_err(|_| invalid())?)
+        } else if let Ok(number) = value.parse::<f32>() {
+            LineHeight::Multiple(number)
+        } else {
+            LineHeight::Px(self.parse_font_size(value).ok_or_else(invalid)?)
+        };
+        Ok(Some(line_height))
+    }
+
+    /// Apply the `font` shorthand
+    ///
+    /// The value is an optional style and weight in any order, a size with
+    /// an optional `/line-height`, then the family list, as in
+    /// `italic bold 14px/1.5 "Helvetica", sans-serif`. Like CSS, the style,
+    /// weight and line height it leaves out are reset to normal.
+    fn apply_font_shorthand(&self, style: &mut Style, value: &str) -> Result<(), StyleError> {
+        let invalid = || StyleError::ParseError(format!("Invalid font shorthand: {value}"));
+        let mut font_style = FontStyle::Normal;
+        let mut font_weight = FontWeight::Normal;
+
+        // Keywords come first. The size is the first token starting with a
+        // digit that isn't a bare number, which would be a numeric weight
+        let mut rest = value.trim_start();
+        let size_token = loop {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (token, after) = rest.split_at(end);
+            if token.is_empty() {
+                return Err(invalid());
+            }
+            rest = after.trim_start();
+            if token.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+                && token.parse::<f32>().is_err()
+            {
+                break token;
+            }
+            match token {
+                "normal" => {}
+                "italic" => font_style = FontStyle::Italic,
+                "oblique" => font_style = FontStyle::Oblique(14.0),
+                // Variant and stretch keywords have no equivalent here
+                "small-caps" | "condensed" | "expanded" | "semi-condensed" | "semi-expanded" => {}
+                weight => font_weight = self.parse_font_weight(weight).map_err(|_| invalid())?,
+            }
+        };
+
+        // The line height may be attached to the size or spaced out from it
+        let (size, mut line_height) = match size_token.split_once('/') {
+            Some((size, "")) => {
+                let end = rest.find(char::is_whitespace).ok_or_else(invalid)?;
+                let (line_height, after) = rest.split_at(end);
+                rest = after.trim_start();
+                (size, Some(line_height))
+            }
+            Some((size, line_height)) => (size, Some(line_height)),
+            None => (size_token, None),
+        };
+        if line_height.is_none() {
+            if let Some(after_slash) = rest.strip_prefix('/') {
+                let after_slash = after_slash.trim_start();
+                let end = after_slash.find(char::is_whitespace).ok_or_else(invalid)?;
+                line_height = Some(&after_slash[..end]);
+                rest = after_slash[end..].trim_start();
+            }
+        }
+
+        let font_size = self.parse_font_size(size).ok_or_else(invalid)?;
+        let line_height = match line_height {
+            Some(line_height) => self.parse_line_height(line_height)?,
+            None => None,
+        };
+        let family = rest.trim();
+        if family.is_empty() {
+            return Err(invalid());
+        }
+
+        style.font_style = Some(font_style);
+        style.font_weight = Some(font_weight);
+        style.font_size = Some(font_size);
+        style.line_height = line_height;
+        style.font_family = Some(family.to_string());
+        Ok(())
+    }
+
     /// Parse font weight from CSS value
     fn parse_font_weight(&self, value: &str) -> Result<FontWeight, StyleError> {
         match value.trim() {
@@ -1022,6 +2333,18 @@ impl StyleEngine {
         }
     }
 
+    /// Parse object fit from CSS value
+    fn parse_object_fit(&self, value: &str) -> Result<ObjectFit, StyleError> {
+        match value.trim() {
+            "fill" => Ok(ObjectFit::Fill),
+            "contain" => Ok(ObjectFit::Contain),
+            "cover" => Ok(ObjectFit::Cover),
+            _ => Err(StyleError::ParseError(format!(
+                "Invalid object fit: {value}"
+            ))),
+        }
+    }
+
     /// Parse border radius from CSS value
     fn parse_border_radius(&self, value: &str) -> Result<BorderRadius, StyleError> {
         let parts: Vec<&str> = value.split_whitespace().collect();
@@ -1057,6 +2380,203 @@ impl StyleEngine {
         }
     }
 
+    /// Parse a margin/padding shorthand using the CSS 1/2/3/4 value forms
+    fn parse_edge_values(&self, value: &str) -> Result<EdgeDimensions, StyleError> {
+        let parts = split_values(value);
+        match parts.len() {
+            1 => Ok(EdgeDimensions::uniform(self.parse_dimension(parts[0])?)),
+            2 => {
+                let vertical = self.parse_dimension(parts[0])?;
+                let horizontal = self.parse_dimension(parts[1])?;
+                Ok(EdgeDimensions::horizontal_vertical(horizontal, vertical))
+            }
+            3 => {
+                let horizontal = self.parse_dimension(parts[1])?;
+                Ok(EdgeDimensions::new(
+                    self.parse_dimension(parts[0])?,
+                    horizontal.clone(),
+                    self.parse_dimension(parts[2])?,
+                    horizontal,
+                ))
+            }
+            4 => Ok(EdgeDimensions::new(
+                self.parse_dimension(parts[0])?,
+                self.parse_dimension(parts[1])?,
+                self.parse_dimension(parts[2])?,
+                self.parse_dimension(parts[3])?,
+            )),
+            _ => Err(StyleError::ParseError(format!(
+                "Invalid edge values: {value}"
+            ))),
+        }
+    }
+
+    /// Apply a single-side longhand such as `margin-top` or `padding-left`
+    fn apply_edge_longhand(
+        &self,
+        edges: &mut EdgeDimensions,
+        name: &str,
+        value: &str,
+    ) -> Result<(), StyleError> {
+        let length = self.parse_dimension(value)?;
+        match name.rsplit('-').next() {
+            Some("top") => edges.top = length,
+            Some("right") => edges.right = length,
+            Some("bottom") => edges.bottom = length,
+            Some("left") => edges.left = length,
+            _ => {
+                return Err(StyleError::ParseError(format!(
+                    "Unknown edge property: {name}"
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a length that may be auto, a percentage or a `calc()` expression
+    fn parse_dimension(&self, value: &str) -> Result<Dimension, StyleError> {
+        let value = value.trim();
+        if value == "auto" {
+            Ok(Dimension::Auto)
+        } else if value.starts_with("calc(") {
+            self.parse_calc(value).map(Dimension::Calc)
+        } else if let Some(percent) = value.strip_suffix('%') {
+            percent
+                .parse()
+                .map(Dimension::Percent)
+                .map_err(|_| StyleError::ParseError("Invalid percentage value".to_string()))
+        } else {
+            self.parse_length(value).map(Dimension::Points)
+        }
+    }
+
+    /// Parse a `calc()` expression into an expression tree
+    ///
+    /// Supports `+ - * /` with the usual precedence, parentheses (including
+    /// nested `calc()`), percentages, lengths and unitless numbers.
+    fn parse_calc(&self, value: &str) -> Result<CalcExpr, StyleError> {
+        let invalid = || StyleError::ParseError(format!("Invalid calc() expression: {value}"));
+
+        let tokens = self.tokenize_calc(value).ok_or_else(invalid)?;
+        let mut tokens = tokens.into_iter().peekable();
+        let expr = Self::parse_calc_sum(&mut tokens).ok_or_else(invalid)?;
+        if tokens.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(expr)
+    }
+
+    /// Split a `calc()` expression into values, operators and parentheses
+    fn tokenize_calc(&self, value: &str) -> Option<Vec<CalcToken>> {
+        let mut tokens = Vec::new();
+        let mut chars = value.char_indices().peekable();
+
+        while let Some(&(start, c)) = chars.peek() {
+            // A sign only starts a number where no operand precedes it
+            let follows_operand = matches!(
+                tokens.last(),
+                Some(CalcToken::Value(_)) | Some(CalcToken::Close)
+            );
+            match c {
+                c if c.is_whitespace() => {
+                    chars.next();
+                }
+                '(' => {
+                    chars.next();
+                    tokens.push(CalcToken::Open);
+                }
+                ')' => {
+                    chars.next();
+                    tokens.push(CalcToken::Close);
+                }
+                '*' | '/' => {
+                    chars.next();
+                    tokens.push(CalcToken::Operator(c));
+                }
+                '+' | '-' if follows_operand => {
+                    chars.next();
+                    tokens.push(CalcToken::Operator(c));
+                }
+                _ => {
+                    chars.next();
+                    let mut end = start + c.len_utf8();
+                    while let Some(&(index, c)) = chars.peek() {
+                        if !(c.is_alphanumeric() || c == '.' || c == '%') {
+                            break;
+                        }
+                        chars.next();
+                        end = index + c.len_utf8();
+                    }
+
+                    let word = &value[start..end];
+                    if word == "calc" {
+                        // `calc(` nested inside calc behaves like a parenthesis
+                        if chars.peek().map(|&(_, c)| c) != Some('(') {
+                            return None;
+                        }
+                        continue;
+                    }
+                    tokens.push(CalcToken::Value(self.parse_calc_value(word)?));
+                }
+            }
+        }
+
+        Some(tokens)
+    }
+
+    /// Parse a single operand of a `calc()` expression
+    fn parse_calc_value(&self, word: &str) -> Option<CalcExpr> {
+        if let Some(percent) = word.strip_suffix('%') {
+            percent.parse().ok().map(CalcExpr::Percent)
+        } else if let Ok(number) = word.parse() {
+            Some(CalcExpr::Number(number))
+        } else {
+            self.parse_length(word).ok().map(CalcExpr::Points)
+        }
+    }
+
+    /// Parse terms joined by `+` and `-`
+    fn parse_calc_sum(tokens: &mut Peekable<IntoIter<CalcToken>>) -> Option<CalcExpr> {
+        let mut expr = Self::parse_calc_product(tokens)?;
+        while let Some(CalcToken::Operator(op @ ('+' | '-'))) = tokens.peek().cloned() {
+            tokens.next();
+            let rhs = Box::new(Self::parse_calc_product(tokens)?);
+            expr = if op == '+' {
+                CalcExpr::Add(Box::new(expr), rhs)
+            } else {
+                CalcExpr::Sub(Box::new(expr), rhs)
+            };
+        }
+        Some(expr)
+    }
+
+    /// Parse factors joined by `*` and `/`
+    fn parse_calc_product(tokens: &mut Peekable<IntoIter<CalcToken>>) -> Option<CalcExpr> {
+        let mut expr = Self::parse_calc_factor(tokens)?;
+        while let Some(CalcToken::Operator(op @ ('*' | '/'))) = tokens.peek().cloned() {
+            tokens.next();
+            let rhs = Box::new(Self::parse_calc_factor(tokens)?);
+            expr = if op == '*' {
+                CalcExpr::Mul(Box::new(expr), rhs)
+            } else {
+                CalcExpr::Div(Box::new(expr), rhs)
+            };
+        }
+        Some(expr)
+    }
+
+    /// Parse an operand or a parenthesized sub-expression
+    fn parse_calc_factor(tokens: &mut Peekable<IntoIter<CalcToken>>) -> Option<CalcExpr> {
+        match tokens.next()? {
+            CalcToken::Value(value) => Some(value),
+            CalcToken::Open => {
+                let expr = Self::parse_calc_sum(tokens)?;
+                matches!(tokens.next(), Some(CalcToken::Close)).then_some(expr)
+            }
+            _ => None,
+        }
+    }
+
     /// Parse length value (px, pt, em, etc.)
     fn parse_length(&self, value: &str) -> Result<f32, StyleError> {
         let value = value.trim();
@@ -1099,8 +2619,37 @@ impl StyleEngine {
     }
 
     /// Set parent-child inheritance relationship
+    ///
+    /// The parent chain is the ancestor chain for descendant and child
+    /// selectors, so cached styles are dropped.
     pub fn set_inheritance(&mut self, child_id: ComponentId, parent_id: ComponentId) {
         self.inheritance_tree.insert(child_id, parent_id);
+        self.clear_cache();
+    }
+
+    /// Set the tag, ID and classes selectors are matched against
+    ///
+    /// Rules only match components with element info when their selector
+    /// does. Components without it receive every rule, as before selector
+    /// matching existed.
+    pub fn set_element_info(&mut self, component_id: ComponentId, info: ElementInfo) {
+        self.elements.insert(component_id, info);
+        self.clear_cache();
+    }
+
+    /// Set the interactive state of a component with element info
+    ///
+    /// Selectors like `.card:hover .title` check it when the component is
+    /// an ancestor of the one being styled; the styled component's own state
+    /// comes from [`StyleContext::element_state`] too.
+    pub fn set_element_state(&mut self, component_id: ComponentId, state: ElementState) {
+        let Some(element) = self.elements.get_mut(&component_id) else {
+            return;
+        };
+        if element.state != state {
+            element.state = state;
+            self.clear_cache();
+        }
     }
 }
 
@@ -1120,6 +2669,7 @@ impl Default for StyleContext {
             component_id: None,
             theme_variables: HashMap::new(),
             performance_monitoring: false,
+            element_state: ElementState::empty(),
         }
     }
 }
@@ -1145,4 +2695,114 @@ impl ComputedStyle {
             _ => None,
         }
     }
+
+    /// Interpolate between this style and `other` at progress `t` in [0, 1]
+    ///
+    /// Animatable properties (opacity, colors, font size, border radius and
+    /// translate/scale/rotate transforms) are blended linearly when both sides
+    /// define them. Everything else snaps to `other` once `t >= 0.5`.
+    pub fn interpolate(&self, other: &ComputedStyle, t: f32) -> ComputedStyle {
+        let t = t.clamp(0.0, 1.0);
+        let mut result = if t < 0.5 { self.clone() } else { other.clone() };
+        let (from, to) = (&self.style, &other.style);
+
+        if let (Some(a), Some(b)) = (from.opacity, to.opacity) {
+            result.style.opacity = Some(lerp(a, b, t));
+        }
+        if let (Some(a), Some(b)) = (from.font_size, to.font_size) {
+            result.style.font_size = Some(lerp(a, b, t));
+        }
+        if let (Some(a), Some(b)) = (&from.color, &to.color) {
+            if let Some(color) = lerp_color(a, b, t) {
+                result.style.color = Some(color);
+            }
+        }
+        if let (Some(a), Some(b)) = (&from.background_color, &to.background_color) {
+            if let Some(color) = lerp_color(a, b, t) {
+                result.style.background_color = Some(color);
+            }
+        }
+        if let (Some(a), Some(b)) = (&from.border_radius, &to.border_radius) {
+            result.style.border_radius = Some(BorderRadius {
+                top_left: lerp(a.top_left, b.top_left, t),
+                top_right: lerp(a.top_right, b.top_right, t),
+                bottom_right: lerp(a.bottom_right, b.bottom_right, t),
+                bottom_left: lerp(a.bottom_left, b.bottom_left, t),
+            });
+        }
+        if let (Some(a), Some(b)) = (&from.transform, &to.transform) {
+            if let Some(transform) = lerp_transform(a, b, t) {
+                result.style.transform = Some(transform);
+            }
+        }
+
+        result.is_animatable = self.is_animatable || other.is_animatable;
+        result.computed_at = std::time::Instant::now();
+        result
+    }
+}
+
+/// Linear interpolation between two values
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Blend two colors channel-wise in RGBA space
+fn lerp_color(a: &Color, b: &Color, t: f32) -> Option<Color> {
+    let (ar, ag, ab, aa) = a.to_rgba()?;
+    let (br, bg, bb, ba) = b.to_rgba()?;
+    Some(Color::Rgba(
+        lerp(ar, br, t),
+        lerp(ag, bg, t),
+        lerp(ab, bb, t),
+        lerp(aa, ba, t),
+    ))
+}
+
+/// Blend two transforms of the same kind, treating `none` as the identity
+fn lerp_transform(a: &Transform, b: &Transform, t: f32) -> Option<Transform> {
+    use Transform::*;
+
+    match (a, b) {
+        (None, None) => Some(None),
+        (None, other) => lerp_transform(&identity_transform(other)?, other, t),
+        (other, None) => lerp_transform(other, &identity_transform(other)?, t),
+        (Translate(ax, ay), Translate(bx, by)) => {
+            Some(Translate(lerp(*ax, *bx, t), lerp(*ay, *by, t)))
+        }
+        (TranslateX(a), TranslateX(b)) => Some(TranslateX(lerp(*a, *b, t))),
+        (TranslateY(a), TranslateY(b)) => Some(TranslateY(lerp(*a, *b, t))),
+        (Scale(ax, ay), Scale(bx, by)) => Some(Scale(lerp(*ax, *bx, t), lerp(*ay, *by, t))),
+        (ScaleX(a), ScaleX(b)) => Some(ScaleX(lerp(*a, *b, t))),
+        (ScaleY(a), ScaleY(b)) => Some(ScaleY(lerp(*a, *b, t))),
+        (Rotate(a), Rotate(b)) => Some(Rotate(lerp(*a, *b, t))),
+        (Multiple(a), Multiple(b)) if a.len() == b.len() => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| lerp_transform(a, b, t))
+            .collect::<Option<Vec<_>>>()
+            .map(Multiple),
+        _ => Option::None,
+    }
+}
+
+/// The identity transform matching the kind of `transform`
+fn identity_transform(transform: &Transform) -> Option<Transform> {
+    use Transform::*;
+
+    match transform {
+        Translate(..) => Some(Translate(0.0, 0.0)),
+        TranslateX(_) => Some(TranslateX(0.0)),
+        TranslateY(_) => Some(TranslateY(0.0)),
+        Scale(..) => Some(Scale(1.0, 1.0)),
+        ScaleX(_) => Some(ScaleX(1.0)),
+        ScaleY(_) => Some(ScaleY(1.0)),
+        Rotate(_) => Some(Rotate(0.0)),
+        Multiple(parts) => parts
+            .iter()
+            .map(identity_transform)
+            .collect::<Option<Vec<_>>>()
+            .map(Multiple),
+        _ => Option::None,
+    }
 }
@@ -0,0 +1,418 @@
+//! Keyframe animations driven by CSS timing functions, and spring physics
+
+use super::{ComputedStyle, StepPosition, StyleError, TimingFunction};
+use std::time::Duration;
+
+/// Number of Newton iterations used when solving cubic-bezier curves
+const NEWTON_ITERATIONS: usize = 8;
+/// Precision at which the cubic-bezier solver stops iterating
+const BEZIER_EPSILON: f32 = 1e-6;
+/// Distance from the target below which a spring counts as settled
+const SPRING_REST_DISPLACEMENT: f32 = 1e-3;
+/// Speed below which a spring counts as settled
+const SPRING_REST_VELOCITY: f32 = 1e-3;
+
+/// A single keyframe within an animation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframe {
+    /// Position of the keyframe as a percentage of the duration (0-100)
+    pub offset: f32,
+    /// Style at this keyframe
+    pub style: ComputedStyle,
+}
+
+impl Keyframe {
+    /// Create a keyframe at the given percentage offset
+    pub fn new(offset: f32, style: ComputedStyle) -> Self {
+        Self { offset, style }
+    }
+}
+
+/// A keyframe animation between computed styles
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    /// Keyframes sorted by offset
+    keyframes: Vec<Keyframe>,
+    /// Total duration of one iteration
+    pub duration: Duration,
+    /// Timing function applied between each pair of keyframes
+    pub timing_function: TimingFunction,
+}
+
+impl Animation {
+    /// Create a new animation
+    ///
+    /// Keyframes may be given in any order, but there must be at least one and
+    /// every offset must lie within 0-100%.
+    pub fn new(
+        mut keyframes: Vec<Keyframe>,
+        duration: Duration,
+        timing_function: TimingFunction,
+    ) -> Result<Self, StyleError> {
+        if keyframes.is_empty() {
+            return Err(StyleError::InvalidAnimation(
+                "an animation needs at least one keyframe".to_string(),
+            ));
+        }
+        if let Some(keyframe) = keyframes
+            .iter()
+            .find(|k| !(0.0..=100.0).contains(&k.offset))
+        {
+            return Err(StyleError::InvalidAnimation(format!(
+                "keyframe offset {}% is outside 0-100%",
+                keyframe.offset
+            )));
+        }
+
+        keyframes.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+        Ok(Self {
+            keyframes,
+            duration,
+            timing_function,
+        })
+    }
+
+    /// Get the keyframes in offset order
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// Sample the animated style after `elapsed` time
+    ///
+    /// Times past the end of the animation hold the final keyframe.
+    pub fn sample(&self, elapsed: Duration) -> ComputedStyle {
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+        let offset = progress * 100.0;
+
+        let first = &self.keyframes[0];
+        let last = &self.keyframes[self.keyframes.len() - 1];
+        if offset <= first.offset {
+            return first.style.clone();
+        }
+        if offset >= last.offset {
+            return last.style.clone();
+        }
+
+        // Find the pair of keyframes surrounding the current offset
+        let next_index = self
+            .keyframes
+            .iter()
+            .position(|k| k.offset > offset)
+            .unwrap_or(self.keyframes.len() - 1);
+        let from = &self.keyframes[next_index - 1];
+        let to = &self.keyframes[next_index];
+
+        let local = (offset - from.offset) / (to.offset - from.offset);
+        let eased = self.timing_function.evaluate(local);
+        from.style.interpolate(&to.style, eased)
+    }
+}
+
+impl TimingFunction {
+    /// Map linear progress in [0, 1] to eased progress
+    pub fn evaluate(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        match self {
+            TimingFunction::Linear => progress,
+            TimingFunction::Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, progress),
+            TimingFunction::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, progress),
+            TimingFunction::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, progress),
+            TimingFunction::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, progress),
+            TimingFunction::CubicBezier(x1, y1, x2, y2) => {
+                cubic_bezier(*x1, *y1, *x2, *y2, progress)
+            }
+            TimingFunction::Steps(0, _) => progress,
+            TimingFunction::Steps(steps, position) => {
+                let steps = *steps as f32;
+                let step = match position {
+                    StepPosition::Start => (progress * steps).floor() + 1.0,
+                    StepPosition::End => (progress * steps).floor(),
+                };
+                (step / steps).min(1.0)
+            }
+        }
+    }
+}
+
+/// A damped spring that moves a value towards a target
+///
+/// Unlike timing functions a spring has no fixed duration; it carries its
+/// velocity between steps, so retargeting mid-flight stays smooth. Any `f32`
+/// style property can be animated by keeping its value and velocity and
+/// calling [`Spring::step`] once per frame until [`Spring::settled`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    /// Force per unit of displacement; higher is snappier
+    pub stiffness: f32,
+    /// Force per unit of velocity; higher overshoots less
+    pub damping: f32,
+    /// Mass of the moving value; higher is slower
+    pub mass: f32,
+}
+
+impl Spring {
+    /// Create a spring
+    pub fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass,
+        }
+    }
+
+    /// Advance the spring by `dt` seconds, returning the new value and velocity
+    ///
+    /// The damped harmonic oscillator is solved exactly rather than stepped
+    /// numerically, so large or uneven frame times stay stable.
+    pub fn step(&self, dt: f32, current: f32, target: f32, velocity: f32) -> (f32, f32) {
+        if dt <= 0.0 {
+            return (current, velocity);
+        }
+
+        let stiffness = self.stiffness.max(f32::EPSILON);
+        let mass = self.mass.max(f32::EPSILON);
+        let omega = (stiffness / mass).sqrt();
+        let zeta = self.damping.max(0.0) / (2.0 * (stiffness * mass).sqrt());
+        let x0 = current - target;
+        let v0 = velocity;
+
+        let (x, v) = if (zeta - 1.0).abs() < 1e-4 {
+            // Critically damped
+            let decay = (-omega * dt).exp();
+            let c = v0 + omega * x0;
+            (decay * (x0 + c * dt), decay * (v0 - omega * c * dt))
+        } else if zeta < 1.0 {
+            // Underdamped: oscillates around the target while decaying
+            let damped = omega * (1.0 - zeta * zeta).sqrt();
+            let decay = (-zeta * omega * dt).exp();
+            let (sin, cos) = (damped * dt).sin_cos();
+            let b = (v0 + zeta * omega * x0) / damped;
+            (
+                decay * (x0 * cos + b * sin),
+                decay * (v0 * cos - (zeta * omega * v0 + omega * omega * x0) / damped * sin),
+            )
+        } else {
+            // Overdamped: approaches the target without crossing it
+            let root = (zeta * zeta - 1.0).sqrt();
+            let (r1, r2) = (-omega * (zeta - root), -omega * (zeta + root));
+            let c2 = (v0 - r1 * x0) / (r2 - r1);
+            let c1 = x0 - c2;
+            let (e1, e2) = ((r1 * dt).exp(), (r2 * dt).exp());
+            (c1 * e1 + c2 * e2, r1 * c1 * e1 + r2 * c2 * e2)
+        };
+
+        (target + x, v)
+    }
+
+    /// Whether a value has come to rest at its target
+    pub fn settled(&self, current: f32, target: f32, velocity: f32) -> bool {
+        (current - target).abs() < SPRING_REST_DISPLACEMENT && velocity.abs() < SPRING_REST_VELOCITY
+    }
+}
+
+impl Default for Spring {
+    /// A responsive spring with a slight overshoot
+    fn default() -> Self {
+        Self::new(170.0, 26.0, 1.0)
+    }
+}
+
+/// Evaluate one coordinate of a cubic bezier with endpoints at 0 and 1
+fn bezier_coordinate(p1: f32, p2: f32, t: f32) -> f32 {
+    let u = 1.0 - t;
+    3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+}
+
+/// Derivative of [`bezier_coordinate`] with respect to `t`
+fn bezier_slope(p1: f32, p2: f32, t: f32) -> f32 {
+    let u = 1.0 - t;
+    3.0 * u * u * p1 + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (1.0 - p2)
+}
+
+/// Evaluate a CSS cubic-bezier easing curve at horizontal position `x`
+///
+/// The curve is parametric, so Newton iteration first solves for the `t`
+/// whose x coordinate is `x`, falling back to bisection where the slope is
+/// too flat to converge.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    if x <= 0.0 || x >= 1.0 {
+        return x;
+    }
+
+    let mut t = x;
+    for _ in 0..NEWTON_ITERATIONS {
+        let error = bezier_coordinate(x1, x2, t) - x;
+        if error.abs() < BEZIER_EPSILON {
+            return bezier_coordinate(y1, y2, t);
+        }
+        let slope = bezier_slope(x1, x2, t);
+        if slope.abs() < BEZIER_EPSILON {
+            break;
+        }
+        t -= error / slope;
+    }
+
+    // Newton did not converge; x(t) is monotonic on [0, 1] so bisect instead
+    let (mut low, mut high) = (0.0, 1.0);
+    t = x;
+    while high - low > BEZIER_EPSILON {
+        let value = bezier_coordinate(x1, x2, t);
+        if (value - x).abs() < BEZIER_EPSILON {
+            break;
+        }
+        if value < x {
+            low = t;
+        } else {
+            high = t;
+        }
+        t = (low + high) / 2.0;
+    }
+
+    bezier_coordinate(y1, y2, t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::LayoutStyle;
+    use crate::style::Style;
+    use std::time::Instant;
+
+    fn opacity_frame(offset: f32, opacity: f32) -> Keyframe {
+        Keyframe::new(
+            offset,
+            ComputedStyle {
+                style: Style {
+                    opacity: Some(opacity),
+                    ..Default::default()
+                },
+                layout_style: LayoutStyle::default(),
+                hash: 0,
+                is_animatable: true,
+                computed_at: Instant::now(),
+            },
+        )
+    }
+
+    fn opacity_at(animation: &Animation, millis: u64) -> f32 {
+        animation
+            .sample(Duration::from_millis(millis))
+            .style
+            .opacity
+            .unwrap()
+    }
+
+    #[test]
+    fn test_linear_sample_at_midpoint() {
+        let animation = Animation::new(
+            vec![opacity_frame(0.0, 0.0), opacity_frame(100.0, 1.0)],
+            Duration::from_millis(1000),
+            TimingFunction::Linear,
+        )
+        .unwrap();
+
+        assert_eq!(opacity_at(&animation, 0), 0.0);
+        assert!((opacity_at(&animation, 500) - 0.5).abs() < 1e-6);
+        assert_eq!(opacity_at(&animation, 1000), 1.0);
+        assert_eq!(opacity_at(&animation, 2000), 1.0);
+    }
+
+    #[test]
+    fn test_ease_in_out_sample() {
+        let animation = Animation::new(
+            vec![opacity_frame(0.0, 0.0), opacity_frame(100.0, 1.0)],
+            Duration::from_millis(1000),
+            TimingFunction::EaseInOut,
+        )
+        .unwrap();
+
+        // ease-in-out is symmetric, so the midpoint stays at half way...
+        assert!((opacity_at(&animation, 500) - 0.5).abs() < 1e-4);
+        // ...while the first quarter lags behind linear progress
+        let quarter = opacity_at(&animation, 250);
+        assert!(quarter < 0.25, "expected slow start, got {quarter}");
+        assert!((quarter - 0.1291).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_sample_between_middle_keyframes() {
+        let animation = Animation::new(
+            vec![
+                opacity_frame(100.0, 0.0),
+                opacity_frame(0.0, 0.0),
+                opacity_frame(50.0, 1.0),
+            ],
+            Duration::from_millis(1000),
+            TimingFunction::Linear,
+        )
+        .unwrap();
+
+        assert!((opacity_at(&animation, 250) - 0.5).abs() < 1e-6);
+        assert!((opacity_at(&animation, 750) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_steps_timing_function() {
+        let end = TimingFunction::Steps(4, StepPosition::End);
+        assert_eq!(end.evaluate(0.0), 0.0);
+        assert_eq!(end.evaluate(0.3), 0.25);
+        assert_eq!(end.evaluate(1.0), 1.0);
+
+        let start = TimingFunction::Steps(4, StepPosition::Start);
+        assert_eq!(start.evaluate(0.0), 0.25);
+        assert_eq!(start.evaluate(0.3), 0.5);
+    }
+
+    /// Run a spring from 0 to 1 at 60fps, returning the peak and final state
+    fn run_spring(spring: Spring, seconds: f32) -> (f32, f32, f32) {
+        let (mut value, mut velocity, mut peak) = (0.0, 0.0, 0.0f32);
+        for _ in 0..(seconds * 60.0) as usize {
+            (value, velocity) = spring.step(1.0 / 60.0, value, 1.0, velocity);
+            peak = peak.max(value);
+        }
+        (peak, value, velocity)
+    }
+
+    #[test]
+    fn test_spring_converges_to_target() {
+        for spring in [
+            Spring::default(),
+            Spring::new(100.0, 20.0, 1.0),
+            Spring::new(100.0, 40.0, 1.0),
+        ] {
+            let (_, value, velocity) = run_spring(spring, 3.0);
+            assert!((value - 1.0).abs() < 1e-3, "{spring:?} ended at {value}");
+            assert!(spring.settled(value, 1.0, velocity));
+        }
+        assert!(!Spring::default().settled(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_spring_damping_reduces_overshoot() {
+        let (loose, _, _) = run_spring(Spring::new(170.0, 8.0, 1.0), 2.0);
+        let (tight, _, _) = run_spring(Spring::new(170.0, 20.0, 1.0), 2.0);
+        assert!(loose > tight, "overshoot {loose} should exceed {tight}");
+        assert!(tight > 1.0);
+
+        // Critical damping never passes the target
+        let (critical, _, _) = run_spring(Spring::new(100.0, 20.0, 1.0), 2.0);
+        assert!(critical <= 1.0);
+    }
+
+    #[test]
+    fn test_invalid_animations() {
+        assert!(Animation::new(vec![], Duration::from_secs(1), TimingFunction::Linear).is_err());
+        assert!(Animation::new(
+            vec![opacity_frame(120.0, 1.0)],
+            Duration::from_secs(1),
+            TimingFunction::Linear
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,297 @@
+//! Selector matching against an element and its ancestors
+
+use super::ElementState;
+
+/// What a selector can match on an element
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ElementInfo {
+    /// Element type, matched by type selectors such as `button`
+    pub tag: Option<String>,
+    /// Element ID, matched by `#id`
+    pub id: Option<String>,
+    /// Class names, matched by `.class`
+    pub classes: Vec<String>,
+    /// Interactive state, matched by pseudo-classes such as `:hover`
+    pub state: ElementState,
+}
+
+impl ElementInfo {
+    /// Create element info with a tag name
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self {
+            tag: Some(tag.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the element ID
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Add a class name
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+
+    /// Set the interactive state
+    pub fn with_state(mut self, state: ElementState) -> Self {
+        self.state = state;
+        self
+    }
+}
+
+/// How a compound selector relates to the one on its right
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    /// `a b`: any ancestor
+    Descendant,
+    /// `a > b`: the direct parent
+    Child,
+}
+
+/// A run of simple selectors with no combinator, such as `button.primary`
+#[derive(Debug, Default)]
+struct Compound<'s> {
+    tag: Option<&'s str>,
+    id: Option<&'s str>,
+    classes: Vec<&'s str>,
+    /// State flags required by pseudo-classes such as `:hover`
+    states: ElementState,
+    /// Whether it names a pseudo-element such as `::before`
+    pseudo_element: bool,
+}
+
+impl Compound<'_> {
+    fn matches(&self, element: &ElementInfo) -> bool {
+        element.state.contains(self.states)
+            && self
+                .tag
+                .is_none_or(|tag| element.tag.as_deref() == Some(tag))
+            && self.id.is_none_or(|id| element.id.as_deref() == Some(id))
+            && self
+                .classes
+                .iter()
+                .all(|class| element.classes.iter().any(|c| c == class))
+    }
+}
+
+/// Matches selectors against an element and its ancestor chain
+///
+/// Type, ID and class selectors are compared with the element info, and
+/// descendant (` `) and child (`>`) combinators walk the ancestors. State
+/// pseudo-classes such as `:hover` are checked against the state of the
+/// element their compound matched. Selectors with a pseudo-element never
+/// match, since a pseudo-element isn't the element itself. Other
+/// pseudo-classes and attribute selectors are ignored.
+#[derive(Debug, Clone)]
+pub struct SelectorMatcher {
+    element: ElementInfo,
+    /// Ancestors, nearest first
+    ancestors: Vec<ElementInfo>,
+}
+
+impl SelectorMatcher {
+    /// Create a matcher for an element with its ancestors, nearest first
+    pub fn new(element: ElementInfo, ancestors: Vec<ElementInfo>) -> Self {
+        Self { element, ancestors }
+    }
+
+    /// Whether the selector matches the element
+    ///
+    /// Selectors that fail to parse never match.
+    pub fn matches(&self, selector: &str) -> bool {
+        let Some((mut compounds, combinators)) = parse_selector(selector) else {
+            return false;
+        };
+        if compounds.iter().any(|compound| compound.pseudo_element) {
+            return false;
+        }
+        let Some(subject) = compounds.pop() else {
+            return false;
+        };
+        subject.matches(&self.element) && self.matches_ancestors(&compounds, &combinators, 0)
+    }
+
+    /// Match the remaining compounds, right to left, from ancestor `from`
+    fn matches_ancestors(
+        &self,
+        compounds: &[Compound],
+        combinators: &[Combinator],
+        from: usize,
+    ) -> bool {
+        let (Some(compound), Some(combinator)) = (compounds.last(), combinators.last()) else {
+            return true;
+        };
+        let rest = &compounds[..compounds.len() - 1];
+        let rest_combinators = &combinators[..combinators.len() - 1];
+
+        match combinator {
+            Combinator::Child => self.ancestors.get(from).is_some_and(|ancestor| {
+                compound.matches(ancestor)
+                    && self.matches_ancestors(rest, rest_combinators, from + 1)
+            }),
+            // Try every ancestor, since a nearer match may fail further up
+            // where a farther one succeeds
+            Combinator::Descendant => (from..self.ancestors.len()).any(|index| {
+                compound.matches(&self.ancestors[index])
+                    && self.matches_ancestors(rest, rest_combinators, index + 1)
+            }),
+        }
+    }
+}
+
+/// Split a selector into compounds and the combinators between them
+fn parse_selector(selector: &str) -> Option<(Vec<Compound<'_>>, Vec<Combinator>)> {
+    let mut compounds = Vec::new();
+    let mut combinators = Vec::new();
+    let mut pending = None;
+
+    let spaced = selector.split('>').enumerate().flat_map(|(index, part)| {
+        let child = (index > 0).then_some(None);
+        child.into_iter().chain(part.split_whitespace().map(Some))
+    });
+    for token in spaced {
+        match token {
+            // `>` between two compounds
+            None => {
+                if compounds.is_empty() || pending == Some(Combinator::Child) {
+                    return None;
+                }
+                pending = Some(Combinator::Child);
+            }
+            Some(word) => {
+                if !compounds.is_empty() {
+                    combinators.push(pending.take().unwrap_or(Combinator::Descendant));
+                }
+                compounds.push(parse_compound(word)?);
+            }
+        }
+    }
+
+    // A selector can't start or end with a combinator
+    if compounds.is_empty() || pending.is_some() {
+        return None;
+    }
+    Some((compounds, combinators))
+}
+
+/// Parse a compound selector such as `button#save.primary:hover`
+fn parse_compound(word: &str) -> Option<Compound<'_>> {
+    let mut compound = Compound::default();
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+
+    let mut rest = word;
+    let tag_end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+    if tag_end > 0 {
+        compound.tag = Some(&rest[..tag_end]);
+    }
+    rest = &rest[tag_end..];
+    rest = rest.strip_prefix('*').unwrap_or(rest);
+
+    while let Some(prefix) = rest.chars().next() {
+        rest = &rest[prefix.len_utf8()..];
+        let end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+        let name = &rest[..end];
+        match prefix {
+            '#' if !name.is_empty() => compound.id = Some(name),
+            '.' if !name.is_empty() => compound.classes.push(name),
+            ':' => {
+                if let Some(after) = rest.strip_prefix(':') {
+                    compound.pseudo_element = true;
+                    rest = after;
+                    continue;
+                }
+                if let Some(state) = ElementState::for_pseudo_class(name) {
+                    compound.states.insert(state);
+                }
+            }
+            '[' => {
+                let close = rest.find(']')?;
+                rest = &rest[close + 1..];
+                continue;
+            }
+            _ => return None,
+        }
+        rest = &rest[end..];
+    }
+
+    Some(compound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card() -> ElementInfo {
+        ElementInfo::new("div").with_class("card")
+    }
+
+    fn title() -> ElementInfo {
+        ElementInfo::new("h2").with_class("title")
+    }
+
+    #[test]
+    fn test_descendant_combinator_matches_any_ancestor() {
+        // card > section > title
+        let nested = SelectorMatcher::new(title(), vec![ElementInfo::new("section"), card()]);
+        assert!(nested.matches(".card .title"));
+        assert!(nested.matches("div.card h2"));
+
+        let elsewhere = SelectorMatcher::new(title(), vec![ElementInfo::new("section")]);
+        assert!(!elsewhere.matches(".card .title"));
+        assert!(elsewhere.matches(".title"));
+    }
+
+    #[test]
+    fn test_child_combinator_requires_direct_parent() {
+        let direct = SelectorMatcher::new(title(), vec![card()]);
+        assert!(direct.matches(".card > .title"));
+        assert!(direct.matches(".card>.title"));
+
+        let nested = SelectorMatcher::new(title(), vec![ElementInfo::new("section"), card()]);
+        assert!(!nested.matches(".card > .title"));
+        assert!(nested.matches(".card > section > .title"));
+        assert!(nested.matches(".card > * .title"));
+    }
+
+    #[test]
+    fn test_compound_parts_and_invalid_selectors() {
+        let button = ElementInfo::new("button")
+            .with_id("save")
+            .with_class("primary")
+            .with_state(ElementState::HOVERED);
+        let matcher = SelectorMatcher::new(button, Vec::new());
+        assert!(matcher.matches("button#save.primary:hover"));
+        assert!(matcher.matches("*"));
+        assert!(matcher.matches("[type=submit].primary"));
+        assert!(!matcher.matches("button.secondary"));
+        assert!(!matcher.matches("> button"));
+        assert!(!matcher.matches("div > > button"));
+    }
+
+    #[test]
+    fn test_state_pseudo_classes_match_their_own_compound() {
+        let hovered_card = card().with_state(ElementState::HOVERED);
+        let matcher = SelectorMatcher::new(title(), vec![hovered_card]);
+        assert!(matcher.matches(".card:hover .title"));
+        assert!(!matcher.matches(".card .title:hover"));
+        assert!(!matcher.matches(".card:focus .title"));
+        // Pseudo-classes that aren't states don't affect matching
+        assert!(matcher.matches(".card:hover .title:first-child"));
+
+        let matcher = SelectorMatcher::new(title().with_state(ElementState::HOVERED), vec![card()]);
+        assert!(matcher.matches(".card .title:hover"));
+        assert!(!matcher.matches(".card:hover .title"));
+    }
+
+    #[test]
+    fn test_pseudo_elements_never_match() {
+        let matcher = SelectorMatcher::new(title(), vec![card()]);
+        assert!(!matcher.matches(".title::before"));
+        assert!(!matcher.matches(".card .title::after"));
+        assert!(!matcher.matches(".card::before .title"));
+    }
+}
@@ -1,7 +1,23 @@
 #[cfg(test)]
 #[allow(clippy::module_inception)]
 mod tests {
-    use crate::style::{CssSelector, Specificity, StyleRule, Stylesheet};
+    use crate::component::ComponentId;
+    use crate::layout::{Dimension, EdgeDimensions, EdgeValues, LayoutStyle, Rect};
+    use crate::style::{
+        scope_class, Color, ComputedStyle, CssProperty, CssSelector, ElementInfo, ElementState,
+        Filter, FontStyle, FontWeight, LineHeight, MediaCondition, MediaQuery, ObjectFit,
+        Specificity, Style, StyleContext, StyleEngine, StyleRule, Stylesheet, Transform,
+    };
+
+    fn computed(style: Style) -> ComputedStyle {
+        ComputedStyle {
+            style,
+            layout_style: LayoutStyle::default(),
+            hash: 0,
+            is_animatable: true,
+            computed_at: std::time::Instant::now(),
+        }
+    }
 
     #[test]
     fn test_specificity_calculation() {
@@ -63,8 +79,23 @@ mod tests {
         );
 
         rule.apply_scoping("component-123");
-        assert_eq!(rule.selectors[0].selector, ".component-123 .button");
+        assert_eq!(rule.selectors[0].selector, ".button.component-123");
         assert_eq!(rule.specificity, Specificity(0, 2, 0));
+
+        // The scope class goes on the subject, ahead of pseudo-classes
+        let mut rule = StyleRule::new(
+            vec![CssSelector {
+                selector: ".list > .item:hover".to_string(),
+                properties: vec![],
+            }],
+            true,
+            0,
+        );
+        rule.apply_scoping("component-123");
+        assert_eq!(
+            rule.selectors[0].selector,
+            ".list > .item.component-123:hover"
+        );
     }
 
     #[test]
@@ -93,4 +124,827 @@ mod tests {
         assert_eq!(second_rule.selectors.len(), 2);
         assert_eq!(second_rule.selectors[0].properties.len(), 2);
     }
+
+    #[test]
+    fn test_parse_hsl_color() {
+        let engine = StyleEngine::new();
+
+        let color = engine.parse_color("hsl(120, 50%, 50%)").unwrap();
+        assert_eq!(color, Color::Hsl(120.0, 0.5, 0.5, 1.0));
+
+        let color = engine.parse_color("hsl(-90deg, 100%, 25%)").unwrap();
+        assert_eq!(color, Color::Hsl(270.0, 1.0, 0.25, 1.0));
+    }
+
+    #[test]
+    fn test_parse_hsla_color() {
+        let engine = StyleEngine::new();
+
+        let color = engine.parse_color("hsla(240, 100%, 50%, 0.25)").unwrap();
+        assert_eq!(color, Color::Hsl(240.0, 1.0, 0.5, 0.25));
+
+        let color = engine.parse_color("hsla(0.5turn, 20%, 80%, 50%)").unwrap();
+        assert_eq!(color, Color::Hsl(180.0, 0.2, 0.8, 0.5));
+    }
+
+    #[test]
+    fn test_parse_hsl_invalid_channel_count() {
+        let engine = StyleEngine::new();
+
+        assert!(engine.parse_color("hsl(120, 50%)").is_err());
+        assert!(engine.parse_color("hsla(120, 50%, 50%)").is_err());
+        assert!(engine.parse_color("hsl(120, 50, 50%)").is_err());
+    }
+
+    #[test]
+    fn test_hex_shorthand_expansion() {
+        let color = Color::Hex("#abc".to_string());
+        let expected = (
+            0xAA as f32 / 255.0,
+            0xBB as f32 / 255.0,
+            0xCC as f32 / 255.0,
+            1.0,
+        );
+        assert_eq!(color.to_rgba(), Some(expected));
+
+        let color = Color::Hex("#f008".to_string());
+        assert_eq!(color.to_rgba(), Some((1.0, 0.0, 0.0, 0x88 as f32 / 255.0)));
+    }
+
+    #[test]
+    fn test_hex_with_alpha() {
+        let color = Color::Hex("#336699cc".to_string());
+        let (r, g, b, a) = color.to_rgba().unwrap();
+        assert_eq!(r, 0x33 as f32 / 255.0);
+        assert_eq!(g, 0x66 as f32 / 255.0);
+        assert_eq!(b, 0x99 as f32 / 255.0);
+        assert_eq!(a, 0xCC as f32 / 255.0);
+
+        assert_eq!(
+            Color::Hex("#ffffff".to_string()).to_rgba(),
+            Some((1.0, 1.0, 1.0, 1.0))
+        );
+        assert_eq!(Color::Hex("#ggg".to_string()).to_rgba(), None);
+        assert_eq!(Color::Hex("#12345".to_string()).to_rgba(), None);
+    }
+
+    #[test]
+    fn test_named_and_special_colors_to_rgba() {
+        assert_eq!(
+            Color::Named("Red".to_string()).to_rgba(),
+            Some((1.0, 0.0, 0.0, 1.0))
+        );
+        assert_eq!(
+            Color::Named("rebeccapurple".to_string()).to_rgba(),
+            Some((
+                0x66 as f32 / 255.0,
+                0x33 as f32 / 255.0,
+                0x99 as f32 / 255.0,
+                1.0
+            ))
+        );
+        assert_eq!(Color::Named("notacolor".to_string()).to_rgba(), None);
+        assert_eq!(Color::CurrentColor.to_rgba(), None);
+        assert_eq!(Color::Transparent.to_rgba(), Some((0.0, 0.0, 0.0, 0.0)));
+
+        let (r, g, b, a) = Color::Hsl(120.0, 1.0, 0.5, 0.5).to_rgba().unwrap();
+        assert_eq!((r, g, b, a), (0.0, 1.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_edge_value_forms() {
+        let engine = StyleEngine::new();
+
+        assert_eq!(
+            engine.parse_edge_values("10px").unwrap().resolve(0.0),
+            EdgeValues::new(10.0, 10.0, 10.0, 10.0)
+        );
+        assert_eq!(
+            engine.parse_edge_values("10px 20px").unwrap().resolve(0.0),
+            EdgeValues::new(10.0, 20.0, 10.0, 20.0)
+        );
+        assert_eq!(
+            engine
+                .parse_edge_values("10px 20px 30px")
+                .unwrap()
+                .resolve(0.0),
+            EdgeValues::new(10.0, 20.0, 30.0, 20.0)
+        );
+        assert_eq!(
+            engine
+                .parse_edge_values("1px 2px 3px 4px")
+                .unwrap()
+                .resolve(0.0),
+            EdgeValues::new(1.0, 2.0, 3.0, 4.0)
+        );
+        assert_eq!(
+            engine.parse_edge_values("5% auto").unwrap(),
+            EdgeDimensions::horizontal_vertical(Dimension::Auto, Dimension::Percent(5.0))
+        );
+        assert!(engine.parse_edge_values("1px 2px 3px 4px 5px").is_err());
+        assert!(engine.parse_edge_values("").is_err());
+    }
+
+    fn apply_font(value: &str) -> Result<Style, crate::style::StyleError> {
+        let property = CssProperty {
+            name: "font".to_string(),
+            value: value.to_string(),
+            important: false,
+        };
+        let mut style = Style {
+            line_height: Some(LineHeight::Multiple(2.0)),
+            ..Default::default()
+        };
+        StyleEngine::new().apply_css_property(&mut style, &property, &StyleContext::default())?;
+        Ok(style)
+    }
+
+    #[test]
+    fn test_font_shorthand_size_and_family() {
+        let style = apply_font("16px Arial").unwrap();
+        assert_eq!(style.font_size, Some(16.0));
+        assert_eq!(style.font_family.as_deref(), Some("Arial"));
+        // Everything left out is reset, including an earlier line height
+        assert_eq!(style.font_weight, Some(FontWeight::Normal));
+        assert_eq!(style.font_style, Some(FontStyle::Normal));
+        assert_eq!(style.line_height, None);
+
+        let style = apply_font("bold 16px/24px Times New Roman").unwrap();
+        assert_eq!(style.font_weight, Some(FontWeight::Bold));
+        assert_eq!(style.line_height, Some(LineHeight::Px(24.0)));
+        assert_eq!(style.font_family.as_deref(), Some("Times New Roman"));
+
+        // A size and a family are both required
+        assert!(apply_font("bold Arial").is_err());
+        assert!(apply_font("16px").is_err());
+    }
+
+    #[test]
+    fn test_font_shorthand_with_style_weight_and_line_height() {
+        let style = apply_font(r#"italic bold 14px/1.5 "Helvetica", sans-serif"#).unwrap();
+        assert_eq!(style.font_style, Some(FontStyle::Italic));
+        assert_eq!(style.font_weight, Some(FontWeight::Bold));
+        assert_eq!(style.font_size, Some(14.0));
+        assert_eq!(style.line_height, Some(LineHeight::Multiple(1.5)));
+        assert_eq!(
+            style.font_family.as_deref(),
+            Some(r#""Helvetica", sans-serif"#)
+        );
+
+        // Weight before style, with the line height spaced out
+        let style = apply_font("600 italic 20px / 150% Inter, system-ui").unwrap();
+        assert_eq!(style.font_style, Some(FontStyle::Italic));
+        assert_eq!(style.font_weight, Some(FontWeight::SemiBold));
+        assert_eq!(style.line_height, Some(LineHeight::Multiple(1.5)));
+        assert_eq!(style.font_family.as_deref(), Some("Inter, system-ui"));
+    }
+
+    #[test]
+    fn test_line_height_resolves_against_the_computed_font_size() {
+        let mut engine = StyleEngine::new();
+        let declare = |style: &mut Style, name: &str, value: &str| {
+            let property = CssProperty {
+                name: name.to_string(),
+                value: value.to_string(),
+                important: false,
+            };
+            StyleEngine::new()
+                .apply_css_property(style, &property, &StyleContext::default())
+                .unwrap();
+        };
+
+        // A pixel line height doesn't depend on the font size declared after it
+        let mut parent = Style::new();
+        declare(&mut parent, "line-height", "24px");
+        declare(&mut parent, "font-size", "12px");
+        assert_eq!(parent.line_height, Some(LineHeight::Px(24.0)));
+        let parent = engine
+            .compute_style(ComponentId::new(), &parent, &StyleContext::default())
+            .unwrap();
+        assert_eq!(parent.layout_style.line_height, Some(2.0));
+
+        // A child with a larger font inherits the pixel height, not the ratio
+        let child = Style {
+            font_size: Some(32.0),
+            ..Default::default()
+        };
+        let context = StyleContext {
+            inherited_style: Some(parent),
+            ..Default::default()
+        };
+        let child = engine
+            .compute_style(ComponentId::new(), &child, &context)
+            .unwrap();
+        assert_eq!(child.layout_style.line_height, Some(0.75));
+
+        // A multiple is inherited as a multiple
+        let mut parent = Style::new();
+        declare(&mut parent, "line-height", "150%");
+        let parent = engine
+            .compute_style(ComponentId::new(), &parent, &StyleContext::default())
+            .unwrap();
+        let child = Style {
+            font_size: Some(32.0),
+            ..Default::default()
+        };
+        let context = StyleContext {
+            inherited_style: Some(parent),
+            ..Default::default()
+        };
+        let child = engine
+            .compute_style(ComponentId::new(), &child, &context)
+            .unwrap();
+        assert_eq!(child.layout_style.line_height, Some(1.5));
+    }
+
+    #[test]
+    fn test_parse_single_transform_function() {
+        let engine = StyleEngine::new();
+        assert_eq!(
+            engine.parse_transform("rotate(45deg)").unwrap(),
+            Transform::Rotate(45.0)
+        );
+        assert_eq!(
+            engine.parse_transform("translate(10px)").unwrap(),
+            Transform::Translate(10.0, 0.0)
+        );
+        assert_eq!(
+            engine.parse_transform("scale(2)").unwrap(),
+            Transform::Scale(2.0, 2.0)
+        );
+        assert_eq!(
+            engine.parse_transform("matrix(1, 0, 0, 1, 5, 0)").unwrap(),
+            Transform::Matrix(1.0, 0.0, 0.0, 1.0, 5.0, 0.0)
+        );
+        assert_eq!(engine.parse_transform("none").unwrap(), Transform::None);
+
+        // Only px lengths and deg angles are understood
+        for value in ["rotate(1rad)", "translateX(2em)", "skewY(45)", "spin(3deg)"] {
+            assert!(engine.parse_transform(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_parse_transform_chain() {
+        let css = r#"
+            .badge {
+                transform: translate(10px, 20px) rotate(45deg) scale(1.5);
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let computed = engine
+            .compute_style(ComponentId::new(), &Style::new(), &StyleContext::default())
+            .unwrap();
+        assert_eq!(
+            computed.style.transform,
+            Some(Transform::Multiple(vec![
+                Transform::Translate(10.0, 20.0),
+                Transform::Rotate(45.0),
+                Transform::Scale(1.5, 1.5),
+            ]))
+        );
+        assert_eq!(
+            engine
+                .parse_transform("translateY(0) skewX(-10deg)")
+                .unwrap(),
+            Transform::Multiple(vec![Transform::TranslateY(0.0), Transform::SkewX(-10.0)])
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_chain() {
+        let css = r#"
+            .frosted {
+                filter: blur(4px) brightness(120%);
+                backdrop-filter: grayscale(1) opacity(0.5);
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let computed = engine
+            .compute_style(ComponentId::new(), &Style::new(), &StyleContext::default())
+            .unwrap();
+        assert_eq!(
+            computed.style.filter,
+            Some(vec![Filter::Blur(4.0), Filter::Brightness(1.2)])
+        );
+        assert_eq!(
+            computed.style.backdrop_filter,
+            Some(vec![Filter::Grayscale(1.0), Filter::Opacity(0.5)])
+        );
+        assert_eq!(engine.parse_filters("none").unwrap(), vec![]);
+        assert!(engine.parse_filters("sepia(1)").is_err());
+        assert!(engine.parse_filters("blur(-2px)").is_err());
+    }
+
+    #[test]
+    fn test_margin_and_padding_applied_to_layout_style() {
+        let css = r#"
+            .card {
+                margin: 8px 16px;
+                padding: 4px;
+                padding-left: 12px;
+                margin-bottom: 0;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let computed = engine
+            .compute_style(ComponentId::new(), &Style::new(), &StyleContext::default())
+            .unwrap();
+
+        assert_eq!(
+            computed.layout_style.margin.resolve(0.0),
+            EdgeValues::new(8.0, 16.0, 0.0, 16.0)
+        );
+        assert_eq!(
+            computed.layout_style.padding.resolve(0.0),
+            EdgeValues::new(4.0, 4.0, 4.0, 12.0)
+        );
+    }
+
+    #[test]
+    fn test_percentage_padding_parsed_as_dimension() {
+        let css = r#"
+            .card {
+                padding: 5%;
+                margin-left: 10%;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let computed = engine
+            .compute_style(ComponentId::new(), &Style::new(), &StyleContext::default())
+            .unwrap();
+
+        assert_eq!(
+            computed.layout_style.padding,
+            EdgeDimensions::uniform(Dimension::Percent(5.0))
+        );
+        assert_eq!(computed.layout_style.margin.left, Dimension::Percent(10.0));
+        assert_eq!(
+            computed.layout_style.padding.resolve(400.0),
+            EdgeValues::uniform(20.0)
+        );
+    }
+
+    #[test]
+    fn test_calc_mixes_percentages_and_lengths() {
+        let engine = StyleEngine::new();
+
+        let width = engine.parse_dimension("calc(100% - 20px)").unwrap();
+        assert_eq!(width.resolve(200.0), 180.0);
+
+        let width = engine.parse_dimension("calc(50% + 10px)").unwrap();
+        assert_eq!(width.resolve(200.0), 110.0);
+    }
+
+    #[test]
+    fn test_calc_precedence_and_nesting() {
+        let engine = StyleEngine::new();
+
+        let resolve = |value: &str| engine.parse_dimension(value).unwrap().resolve(300.0);
+        assert_eq!(resolve("calc(10px + 100% / 3)"), 110.0);
+        assert_eq!(resolve("calc((10px + 100%) / 2)"), 155.0);
+        assert_eq!(resolve("calc(2 * (50% - calc(10px * 2)))"), 260.0);
+        assert_eq!(resolve("calc(-10px + 10%)"), 20.0);
+
+        assert!(engine.parse_dimension("calc(100% -)").is_err());
+        assert!(engine.parse_dimension("calc((100%)").is_err());
+        assert!(engine.parse_dimension("calc(100% 20px)").is_err());
+    }
+
+    #[test]
+    fn test_calc_in_stylesheet() {
+        let css = r#"
+            .panel {
+                width: calc(100% - 20px);
+                margin: calc(1% + 2px) 0;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let computed = engine
+            .compute_style(ComponentId::new(), &Style::new(), &StyleContext::default())
+            .unwrap();
+
+        assert_eq!(computed.layout_style.width.resolve(400.0), 380.0);
+        assert_eq!(
+            computed.layout_style.margin.resolve(400.0),
+            EdgeValues::horizontal_vertical(0.0, 6.0)
+        );
+    }
+
+    #[test]
+    fn test_media_rule_applies_only_when_viewport_matches() {
+        let css = r#"
+            .panel {
+                color: red;
+            }
+            @media (min-width: 800px) {
+                .panel {
+                    color: blue;
+                }
+            }
+            .panel {
+                opacity: 0.5;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+        assert_eq!(stylesheet.rules.len(), 3);
+        assert_eq!(stylesheet.rules[2].media, None);
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let compute = |engine: &mut StyleEngine, viewport_width: f32| {
+            let context = StyleContext {
+                viewport_width,
+                ..Default::default()
+            };
+            engine
+                .compute_style(ComponentId::new(), &Style::new(), &context)
+                .unwrap()
+                .style
+        };
+
+        let wide = compute(&mut engine, 1000.0);
+        assert_eq!(wide.color, Some(Color::Named("blue".to_string())));
+        assert_eq!(wide.opacity, Some(0.5));
+
+        let narrow = compute(&mut engine, 600.0);
+        assert_eq!(narrow.color, Some(Color::Named("red".to_string())));
+        assert_eq!(narrow.opacity, Some(0.5));
+    }
+
+    #[test]
+    fn test_media_query_conditions_combine_with_and() {
+        let query =
+            MediaQuery::parse("screen and (min-width: 768px) and (max-height: 600px)").unwrap();
+        assert_eq!(
+            query.conditions,
+            vec![
+                MediaCondition::MinWidth(768.0),
+                MediaCondition::MaxHeight(600.0)
+            ]
+        );
+        assert!(query.matches(768.0, 600.0));
+        assert!(!query.matches(700.0, 500.0));
+        assert!(!query.matches(1000.0, 800.0));
+
+        assert!(MediaQuery::parse("(orientation: landscape)").is_err());
+        assert!(MediaQuery::parse("min-width: 768px").is_err());
+    }
+
+    #[test]
+    fn test_hover_rule_applies_only_when_hovered() {
+        let css = r#"
+            button {
+                color: black;
+            }
+            button:hover {
+                color: blue;
+            }
+            button::before {
+                opacity: 0.5;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let compute = |engine: &mut StyleEngine, element_state: ElementState| {
+            let context = StyleContext {
+                element_state,
+                ..Default::default()
+            };
+            engine
+                .compute_style(ComponentId::new(), &Style::new(), &context)
+                .unwrap()
+                .style
+        };
+
+        let idle = compute(&mut engine, ElementState::empty());
+        assert_eq!(idle.color, Some(Color::Named("black".to_string())));
+        // Pseudo-element rules style the pseudo-element, not the button
+        assert_eq!(idle.opacity, None);
+
+        let hovered = compute(&mut engine, ElementState::HOVERED);
+        assert_eq!(hovered.color, Some(Color::Named("blue".to_string())));
+
+        let focused = compute(&mut engine, ElementState::FOCUSED);
+        assert_eq!(focused.color, Some(Color::Named("black".to_string())));
+    }
+
+    #[test]
+    fn test_ancestor_hover_is_checked_against_the_ancestor() {
+        let css = r#"
+            .card:hover .title {
+                color: blue;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+        let (card, title) = (ComponentId::new(), ComponentId::new());
+        engine.set_element_info(card, ElementInfo::new("div").with_class("card"));
+        engine.set_element_info(title, ElementInfo::new("h2").with_class("title"));
+        engine.set_inheritance(title, card);
+
+        let compute = |engine: &mut StyleEngine, element_state: ElementState| {
+            let context = StyleContext {
+                element_state,
+                ..Default::default()
+            };
+            engine
+                .compute_style(title, &Style::new(), &context)
+                .unwrap()
+                .style
+                .color
+        };
+
+        // Hovering the title alone doesn't hover the card
+        assert_eq!(compute(&mut engine, ElementState::HOVERED), None);
+
+        engine.set_element_state(card, ElementState::HOVERED);
+        let blue = Some(Color::Named("blue".to_string()));
+        assert_eq!(compute(&mut engine, ElementState::empty()), blue);
+
+        engine.set_element_state(card, ElementState::empty());
+        assert_eq!(compute(&mut engine, ElementState::empty()), None);
+    }
+
+    #[test]
+    fn test_combinators_use_inheritance_tree() {
+        let css = r#"
+            .card .title {
+                color: blue;
+            }
+            .card > .title {
+                opacity: 0.5;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        // card > body > nested title, and a title directly in the card
+        let (card, body, nested, direct) = (
+            ComponentId::new(),
+            ComponentId::new(),
+            ComponentId::new(),
+            ComponentId::new(),
+        );
+        engine.set_element_info(card, ElementInfo::new("div").with_class("card"));
+        engine.set_element_info(body, ElementInfo::new("div"));
+        for title in [nested, direct] {
+            engine.set_element_info(title, ElementInfo::new("h2").with_class("title"));
+        }
+        engine.set_inheritance(body, card);
+        engine.set_inheritance(nested, body);
+        engine.set_inheritance(direct, card);
+
+        let context = StyleContext::default();
+        let nested = engine
+            .compute_style(nested, &Style::new(), &context)
+            .unwrap();
+        assert_eq!(nested.style.color, Some(Color::Named("blue".to_string())));
+        assert_eq!(nested.style.opacity, None);
+
+        let direct = engine
+            .compute_style(direct, &Style::new(), &context)
+            .unwrap();
+        assert_eq!(direct.style.opacity, Some(0.5));
+
+        // The card itself isn't a title
+        let card = engine.compute_style(card, &Style::new(), &context).unwrap();
+        assert_eq!(card.style.color, None);
+    }
+
+    #[test]
+    fn test_scoped_rules_stay_inside_their_component_instance() {
+        let (first, second) = (ComponentId::new(), ComponentId::new());
+        assert_ne!(scope_class(first), scope_class(second));
+
+        // Two instances of the same component, each rendering a label, and a
+        // nested scoped component inside the first that renders one too
+        let nested = ComponentId::new();
+        let (first_label, second_label, nested_label) =
+            (ComponentId::new(), ComponentId::new(), ComponentId::new());
+
+        let mut engine = StyleEngine::new();
+        let scoped = |color: &str| {
+            Stylesheet::parse(&format!(".label {{\n color: {color};\n}}"), true)
+                .unwrap()
+                .rules
+        };
+        engine.add_component_rules(first, scoped("red"));
+        engine.add_component_rules(second, scoped("blue"));
+        engine.add_component_rules(nested, scoped("green"));
+
+        for (label, parent) in [
+            (first_label, first),
+            (second_label, second),
+            (nested_label, nested),
+        ] {
+            engine.set_element_info(label, ElementInfo::new("span").with_class("label"));
+            engine.set_inheritance(label, parent);
+        }
+        for component in [first, second, nested] {
+            engine.set_element_info(component, ElementInfo::new("div"));
+        }
+        engine.set_inheritance(nested, first);
+
+        let context = StyleContext::default();
+        let mut color = |id| {
+            engine
+                .compute_style(id, &Style::new(), &context)
+                .unwrap()
+                .style
+                .color
+        };
+        assert_eq!(color(first_label), Some(Color::Named("red".to_string())));
+        assert_eq!(color(second_label), Some(Color::Named("blue".to_string())));
+        assert_eq!(color(nested_label), Some(Color::Named("green".to_string())));
+        assert_eq!(color(nested), None);
+    }
+
+    #[test]
+    fn test_important_beats_later_more_specific_rule() {
+        let css = r#"
+            .label {
+                color: red !important;
+            }
+            #title.label {
+                color: blue;
+                opacity: 0.5;
+            }
+        "#;
+        let stylesheet = Stylesheet::parse(css, false).unwrap();
+        let important = &stylesheet.rules[0].selectors[0].properties[0];
+        assert_eq!(important.value, "red");
+        assert!(important.important);
+        assert!(!stylesheet.rules[1].selectors[0].properties[0].important);
+
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(stylesheet.rules);
+
+        let computed = engine
+            .compute_style(ComponentId::new(), &Style::new(), &StyleContext::default())
+            .unwrap();
+
+        assert_eq!(computed.style.color, Some(Color::Named("red".to_string())));
+        assert_eq!(computed.style.opacity, Some(0.5));
+    }
+
+    #[test]
+    fn test_inherit_and_initial_keywords() {
+        let parent = Style {
+            color: Some(Color::Named("blue".to_string())),
+            font_size: Some(20.0),
+            ..Default::default()
+        };
+        let context = StyleContext {
+            inherited_style: Some(computed(parent)),
+            ..Default::default()
+        };
+        let compute = |css: &str, base: &Style| {
+            let mut engine = StyleEngine::new();
+            engine.add_global_rules(Stylesheet::parse(css, false).unwrap().rules);
+            engine
+                .compute_style(ComponentId::new(), base, &context)
+                .unwrap()
+                .style
+        };
+
+        // inherit overrides the element's own color with the parent's
+        let own_color = Style {
+            color: Some(Color::Named("red".to_string())),
+            ..Default::default()
+        };
+        let style = compute(".a {\n color: inherit;\n}", &own_color);
+        assert_eq!(style.color, Some(Color::Named("blue".to_string())));
+
+        // initial clears the inherited color but leaves other properties
+        let style = compute(".a {\n color: initial;\n}", &Style::new());
+        assert_eq!(style.color, None);
+        assert_eq!(style.font_size, Some(20.0));
+
+        // Without a parent, inherit falls back to the initial value
+        let mut engine = StyleEngine::new();
+        engine.add_global_rules(
+            Stylesheet::parse(".a {\n color: inherit;\n}", false)
+                .unwrap()
+                .rules,
+        );
+        let style = engine
+            .compute_style(ComponentId::new(), &own_color, &StyleContext::default())
+            .unwrap()
+            .style;
+        assert_eq!(style.color, None);
+    }
+
+    #[test]
+    fn test_interpolate_opacity() {
+        let from = computed(Style {
+            opacity: Some(0.0),
+            ..Default::default()
+        });
+        let to = computed(Style {
+            opacity: Some(1.0),
+            ..Default::default()
+        });
+
+        assert_eq!(from.interpolate(&to, 0.0).style.opacity, Some(0.0));
+        assert_eq!(from.interpolate(&to, 0.5).style.opacity, Some(0.5));
+        assert_eq!(from.interpolate(&to, 1.0).style.opacity, Some(1.0));
+    }
+
+    #[test]
+    fn test_interpolate_rgba_color() {
+        let from = computed(Style {
+            color: Some(Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            background_color: Some(Color::Hex("#ffffff".to_string())),
+            ..Default::default()
+        });
+        let to = computed(Style {
+            color: Some(Color::Rgba(1.0, 0.5, 0.0, 0.0)),
+            background_color: Some(Color::Named("black".to_string())),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            from.interpolate(&to, 0.0).style.color,
+            Some(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+        );
+        assert_eq!(
+            from.interpolate(&to, 0.5).style.color,
+            Some(Color::Rgba(0.5, 0.25, 0.0, 0.5))
+        );
+        assert_eq!(
+            from.interpolate(&to, 1.0).style.color,
+            Some(Color::Rgba(1.0, 0.5, 0.0, 0.0))
+        );
+        assert_eq!(
+            from.interpolate(&to, 0.5).style.background_color,
+            Some(Color::Rgba(0.5, 0.5, 0.5, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_interpolate_transform_and_discrete_properties() {
+        let from = computed(Style {
+            transform: Some(Transform::None),
+            font_weight: Some(FontWeight::Normal),
+            ..Default::default()
+        });
+        let to = computed(Style {
+            transform: Some(Transform::Translate(100.0, 20.0)),
+            font_weight: Some(FontWeight::Bold),
+            ..Default::default()
+        });
+
+        let mid = from.interpolate(&to, 0.25);
+        assert_eq!(mid.style.transform, Some(Transform::Translate(25.0, 5.0)));
+        assert_eq!(mid.style.font_weight, Some(FontWeight::Normal));
+
+        let late = from.interpolate(&to, 0.5);
+        assert_eq!(late.style.font_weight, Some(FontWeight::Bold));
+    }
+
+    #[test]
+    fn test_object_fit_destination_rect() {
+        let container = Rect::new(10.0, 20.0, 200.0, 100.0);
+
+        // A 100x100 image in a 200x100 box
+        assert_eq!(
+            ObjectFit::Fill.destination_rect(100.0, 100.0, container),
+            container
+        );
+        assert_eq!(
+            ObjectFit::Contain.destination_rect(100.0, 100.0, container),
+            Rect::new(60.0, 20.0, 100.0, 100.0)
+        );
+        assert_eq!(
+            ObjectFit::Cover.destination_rect(100.0, 100.0, container),
+            Rect::new(10.0, -30.0, 200.0, 200.0)
+        );
+    }
 }
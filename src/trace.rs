@@ -0,0 +1,163 @@
+//! Structured diagnostics for the framework
+//!
+//! With the `tracing` feature, mounting, updating, unmounting, rendering
+//! and layout each run inside a `tracing` span carrying the component's ID,
+//! and framework messages are `tracing` events, so a subscriber can time the
+//! work and filter it by level. Without the feature, spans compile to
+//! nothing and messages go through `log`.
+
+use crate::component::ComponentId;
+
+/// Framework work that runs inside a span
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Phase {
+    Mount,
+    Update,
+    Unmount,
+    Render,
+    Layout,
+}
+
+/// Keeps a span entered until dropped
+#[cfg(feature = "tracing")]
+pub(crate) type SpanGuard = tracing::span::EnteredSpan;
+
+/// Keeps a span entered until dropped
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct SpanGuard;
+
+/// Enter the span for `phase` of `component`
+#[cfg(feature = "tracing")]
+pub(crate) fn span(phase: Phase, component: ComponentId) -> SpanGuard {
+    let component = component.id();
+    let span = match phase {
+        Phase::Mount => tracing::debug_span!("mount", component),
+        Phase::Update => tracing::debug_span!("update", component),
+        Phase::Unmount => tracing::debug_span!("unmount", component),
+        Phase::Render => tracing::trace_span!("render", component),
+        Phase::Layout => tracing::debug_span!("layout", component),
+    };
+    span.entered()
+}
+
+/// Enter the span for `phase` of `component`
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn span(_phase: Phase, _component: ComponentId) -> SpanGuard {
+    SpanGuard
+}
+
+/// Report a message at `$level` through `tracing`, or `log` without it
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        ::log::$level!($($arg)+);
+    }};
+}
+
+macro_rules! error_event {
+    ($($arg:tt)+) => { $crate::trace::event!(error, $($arg)+) };
+}
+
+macro_rules! warn_event {
+    ($($arg:tt)+) => { $crate::trace::event!(warn, $($arg)+) };
+}
+
+macro_rules! info_event {
+    ($($arg:tt)+) => { $crate::trace::event!(info, $($arg)+) };
+}
+
+macro_rules! debug_event {
+    ($($arg:tt)+) => { $crate::trace::event!(debug, $($arg)+) };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {debug_event, error_event, event, info_event, warn_event};
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::component::{Component, ComponentInstance, Context, LifecycleManager};
+    use crate::kit::components::spinner::{Spinner, SpinnerProps};
+
+    /// An event's message and the names of the spans it was emitted in
+    type RecordedEvent = (Vec<&'static str>, String);
+
+    /// Records span names and event messages with the spans they were in
+    #[derive(Default)]
+    struct Recorder {
+        next_id: AtomicU64,
+        spans: Mutex<Vec<&'static str>>,
+        entered: Mutex<Vec<u64>>,
+        events: Arc<Mutex<Vec<RecordedEvent>>>,
+    }
+
+    struct Message(String);
+
+    impl Visit for Message {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0 = format!("{value:?}");
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            self.spans.lock().unwrap().push(span.metadata().name());
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut message = Message(String::new());
+            event.record(&mut message);
+            let spans = self.spans.lock().unwrap();
+            let open = self.entered.lock().unwrap();
+            let open = open.iter().map(|&id| spans[id as usize - 1]).collect();
+            self.events.lock().unwrap().push((open, message.0));
+        }
+
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _span: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+    }
+
+    #[test]
+    fn test_mount_emits_an_event_inside_its_span() {
+        let recorder = Recorder::default();
+        let events = recorder.events.clone();
+
+        tracing::subscriber::with_default(recorder, || {
+            let context = Context::new();
+            let spinner = Spinner::create(SpinnerProps::default(), context.clone());
+            let instance = ComponentInstance::new(spinner, SpinnerProps::default());
+            let mut manager = LifecycleManager::new(instance, context);
+            manager.mount().unwrap();
+            manager.render().unwrap();
+        });
+
+        let events = events.lock().unwrap();
+        assert!(events
+            .iter()
+            .any(|(spans, message)| spans == &["mount"] && message == "Component mounted"));
+    }
+}